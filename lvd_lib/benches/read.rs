@@ -0,0 +1,62 @@
+use std::{hint::black_box, io::Cursor};
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use lvd_lib::{array::Array, version::Version, LvdFile};
+
+/// The number of copies of each sample object in the benchmarked file.
+const COPIES: usize = 1000;
+
+/// Appends copies of each element of the section until it has `COPIES` times as many.
+fn repeat<T: Version + Clone>(section: Option<&mut Array<T>>) {
+    if let Some(section) = section {
+        let elements = section.elements().to_vec();
+
+        for _ in 1..COPIES {
+            section.elements_mut().extend(elements.iter().cloned());
+        }
+    }
+}
+
+/// Returns the bytes of a large version 13 file, built from many copies of the sample objects.
+fn large_file() -> Vec<u8> {
+    let mut file = LvdFile::sample_v13();
+
+    repeat(file.data.collisions_mut());
+    repeat(file.data.start_positions_mut());
+    repeat(file.data.restart_positions_mut());
+    repeat(file.data.camera_regions_mut());
+    repeat(file.data.death_regions_mut());
+    repeat(file.data.item_popups_mut());
+    repeat(file.data.general_shapes2_mut());
+    repeat(file.data.general_shapes3_mut());
+
+    let mut writer = Cursor::new(Vec::new());
+
+    file.write(&mut writer).unwrap();
+    writer.into_inner()
+}
+
+fn read(c: &mut Criterion) {
+    let bytes = large_file();
+    let mut group = c.benchmark_group("large_file");
+
+    group.throughput(Throughput::Bytes(bytes.len() as u64));
+    group.bench_function("read", |b| {
+        b.iter(|| LvdFile::read(&mut Cursor::new(black_box(&bytes))).unwrap())
+    });
+
+    let file = LvdFile::read(&mut Cursor::new(&bytes)).unwrap();
+
+    group.bench_function("write", |b| {
+        b.iter(|| {
+            let mut writer = Cursor::new(Vec::with_capacity(bytes.len()));
+
+            black_box(&file).write(&mut writer).unwrap();
+            writer
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, read);
+criterion_main!(benches);
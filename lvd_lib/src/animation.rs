@@ -0,0 +1,255 @@
+//! Previews of dynamic collisions moved by an animation of the stage model's joints.
+//!
+//! This module contains the [`JointAnimation`] type, which stores the world transform of each
+//! joint for every frame of an animation, and the [`LvdFile::animate_collisions`] method, which
+//! exports the vertices of every dynamic collision on each frame so that external viewers can
+//! preview moving platforms.
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    array::Array, model::Vector2Data, objects::LvdObject, transform::Transform, LvdFile, ObjectRef,
+    SectionKind,
+};
+
+/// The world transforms of a model's joints, sampled once per frame.
+///
+/// When serialized, the animation is a mapping of each joint's name to its transform on every
+/// frame, each written as the four columns of a matrix which transforms column vectors.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JointAnimation {
+    #[cfg_attr(feature = "serde", serde(with = "columns"))]
+    joints: BTreeMap<String, Vec<Transform>>,
+}
+
+impl JointAnimation {
+    /// Creates a new animation without any joints.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the transform of the joint named `joint_name` on each frame, replacing any previous
+    /// transforms of the joint.
+    pub fn insert(&mut self, joint_name: impl Into<String>, frames: Vec<Transform>) {
+        self.joints.insert(joint_name.into(), frames);
+    }
+
+    /// Returns the number of frames in the animation, which is the number of frames of its
+    /// longest joint.
+    pub fn frame_count(&self) -> usize {
+        self.joints.values().map(Vec::len).max().unwrap_or_default()
+    }
+
+    /// Returns the transform of the joint named `joint_name` on the given frame,
+    /// or `None` if the animation does not contain the joint.
+    ///
+    /// Joints with fewer frames than the animation hold their last transform.
+    pub fn transform(&self, joint_name: &str, frame: usize) -> Option<&Transform> {
+        let frames = self.joints.get(joint_name)?;
+
+        frames.get(frame).or_else(|| frames.last())
+    }
+}
+
+/// The vertices of a dynamic collision on each frame of a [`JointAnimation`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollisionAnimation {
+    /// The animated collision.
+    pub object: ObjectRef,
+
+    /// The name of the joint the collision is bound to.
+    pub joint: String,
+
+    /// The transformed vertices of the collision on each frame.
+    pub frames: Vec<Vec<Vector2Data>>,
+}
+
+impl LvdFile {
+    /// Returns the vertices of every collision classed as dynamic on each frame of the animation,
+    /// moved from the space of the joint it is bound to into world space
+    /// with [`Collision::apply_joint_transform`](crate::objects::Collision::apply_joint_transform).
+    ///
+    /// Collisions bound to joints not contained in the animation are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{animation::JointAnimation, transform::Transform, vector::Vector3, LvdFile};
+    ///
+    /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    /// let mut animation = JointAnimation::new();
+    ///
+    /// animation.insert(
+    ///     "platform_joint",
+    ///     (0..60)
+    ///         .map(|frame| Transform::from_translation(Vector3::new(frame as f32, 0.0, 0.0)))
+    ///         .collect(),
+    /// );
+    ///
+    /// for collision in file.animate_collisions(&animation) {
+    ///     println!("{}: {} frames", collision.joint, collision.frames.len());
+    /// }
+    /// ```
+    pub fn animate_collisions(&self, animation: &JointAnimation) -> Vec<CollisionAnimation> {
+        let frame_count = animation.frame_count();
+
+        self.data
+            .collisions()
+            .into_iter()
+            .flat_map(Array::iter)
+            .enumerate()
+            .filter(|(_, collision)| collision.flags().dynamic())
+            .filter_map(|(index, collision)| {
+                let joint = collision
+                    .base()
+                    .and_then(|base| base.joint_name())
+                    .and_then(|name| name.to_str().ok())
+                    .filter(|name| animation.joints.contains_key(*name))?;
+                let frames = (0..frame_count)
+                    .map(|frame| {
+                        let mut collision = collision.clone();
+
+                        collision.apply_joint_transform(animation.transform(joint, frame).unwrap());
+                        collision
+                            .vertices()
+                            .iter()
+                            .map(|&vertex| vertex.into())
+                            .collect()
+                    })
+                    .collect();
+
+                Some(CollisionAnimation {
+                    object: ObjectRef::new(SectionKind::Collisions, index),
+                    joint: joint.to_string(),
+                    frames,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Serialization of joint transforms as the columns of their matrices.
+#[cfg(feature = "serde")]
+mod columns {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::transform::Transform;
+
+    type Columns = [[f32; 4]; 4];
+
+    pub fn serialize<S: Serializer>(
+        joints: &BTreeMap<String, Vec<Transform>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        joints
+            .iter()
+            .map(|(name, frames)| (name, frames.iter().map(Transform::to_columns).collect()))
+            .collect::<BTreeMap<_, Vec<Columns>>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<String, Vec<Transform>>, D::Error> {
+        let joints = BTreeMap::<String, Vec<Columns>>::deserialize(deserializer)?;
+
+        Ok(joints
+            .into_iter()
+            .map(|(name, frames)| {
+                let frames = frames.into_iter().map(Transform::from_columns).collect();
+
+                (name, frames)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixtures::file_with_collisions,
+        objects::Collision,
+        vector::{Vector2, Vector3},
+    };
+
+    use super::*;
+
+    fn bound_platform(joint: &str, dynamic: bool) -> Collision {
+        let mut platform =
+            Collision::new_soft_platform(Vector2::new(-10.0, 0.0), Vector2::new(10.0, 0.0));
+
+        platform.base_mut().unwrap().bind_to_joint(joint).unwrap();
+        platform.flags_mut().set_dynamic(dynamic);
+        platform
+    }
+
+    #[test]
+    fn animate_dynamic_collision() {
+        let file = file_with_collisions([
+            bound_platform("platform", true),
+            bound_platform("platform", false),
+        ]);
+        let mut animation = JointAnimation::new();
+
+        animation.insert(
+            "platform",
+            vec![
+                Transform::IDENTITY,
+                Transform::from_translation(Vector3::new(0.0, 5.0, 0.0)),
+            ],
+        );
+
+        let animations = file.animate_collisions(&animation);
+
+        assert_eq!(animations.len(), 1);
+        assert_eq!(animations[0].object.index, 0);
+        assert_eq!(
+            animations[0].frames,
+            [
+                [Vector2Data::new(-10.0, 0.0), Vector2Data::new(10.0, 0.0)],
+                [Vector2Data::new(-10.0, 5.0), Vector2Data::new(10.0, 5.0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn unanimated_joints_are_skipped() {
+        let file = file_with_collisions([
+            bound_platform("other", true),
+            bound_platform("platform", true),
+        ]);
+        let mut animation = JointAnimation::new();
+
+        animation.insert("platform", Vec::new());
+
+        let animations = file.animate_collisions(&animation);
+
+        assert_eq!(animations.len(), 1);
+        assert_eq!(animations[0].object.index, 1);
+        assert!(animations[0].frames.is_empty());
+        assert!(file.animate_collisions(&JointAnimation::new()).is_empty());
+    }
+
+    #[test]
+    fn short_joints_hold_last_frame() {
+        let mut animation = JointAnimation::new();
+
+        animation.insert("a", vec![Transform::IDENTITY; 3]);
+        animation.insert("b", vec![Transform::from_rotation_z(1.0)]);
+
+        assert_eq!(animation.frame_count(), 3);
+        assert_eq!(
+            animation.transform("b", 2),
+            Some(&Transform::from_rotation_z(1.0))
+        );
+        assert_eq!(animation.transform("c", 0), None);
+    }
+}
@@ -0,0 +1,316 @@
+//! Byte ranges of the fields of an LVD file.
+//!
+//! This module contains the [`LvdFile::annotations`] method, which lays out the parsed data of a
+//! file as [`Annotation`]s naming the bytes each part was read from, for inspecting the raw file
+//! alongside its decoded contents.
+
+use crate::{
+    objects::base::{Base, MetaInfo},
+    size::BinarySize,
+    string::FixedString,
+    vector::Vector3,
+    version::Version,
+    LvdFile, ObjectRef,
+};
+
+/// A named range of bytes within an LVD file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    /// The position of the first byte of the range.
+    pub offset: u64,
+
+    /// The number of bytes in the range.
+    pub len: u64,
+
+    /// The nesting level of the range, where ranges with a greater depth lie within the
+    /// preceding range with a lesser depth.
+    pub depth: usize,
+
+    /// The name of the range and its decoded value, if any.
+    pub label: String,
+
+    /// The object occupying the range, if the range is a whole object.
+    pub object: Option<ObjectRef>,
+}
+
+impl LvdFile {
+    /// Returns the byte ranges of the file's header, sections, and objects, in file order.
+    ///
+    /// Each object is annotated with its version, the fields of its [`MetaInfo`] and [`Base`],
+    /// and the range of its remaining fields. The ranges are derived from the parsed data,
+    /// so they match the bytes of a file which round trips unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::LvdFile;
+    ///
+    /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    ///
+    /// for annotation in file.annotations() {
+    ///     println!("{:#x}: {}", annotation.offset, annotation.label);
+    /// }
+    /// ```
+    pub fn annotations(&self) -> Vec<Annotation> {
+        let mut annotator = Annotator::default();
+
+        annotator.field(0, 4, "header");
+        annotator.field(0, 1, format!("version = {}", self.data.version()));
+        annotator.field(0, 5, "magic");
+
+        for (kind, _) in self.data.sections() {
+            let len = self.data.section_len(kind).unwrap_or_default();
+            let objects = (0..len)
+                .filter_map(|index| self.data.object(kind, index))
+                .collect::<Vec<_>>();
+            let size = 5 + objects
+                .iter()
+                .map(|object| 1 + object.binary_size())
+                .sum::<u64>();
+
+            annotator.group(0, size, kind.name());
+            annotator.field(1, 1, "version");
+            annotator.field(1, 4, format!("count = {len}"));
+
+            for (index, object) in objects.into_iter().enumerate() {
+                let start = annotator.offset;
+                let size = 1 + object.binary_size();
+
+                annotator.object(1, size, ObjectRef::new(kind, index));
+                annotator.field(2, 1, "version");
+
+                match object.base() {
+                    Some(base) => annotator.base(3, base),
+                    None => annotator.meta_info(3, object.meta_info()),
+                }
+
+                let remaining = start + size - annotator.offset;
+
+                if remaining > 0 {
+                    annotator.field(2, remaining, "fields");
+                }
+            }
+        }
+
+        let unknown = self.data.unknown_sections().bytes.len() as u64;
+
+        if unknown > 0 {
+            annotator.field(0, unknown, "unknown_sections");
+        }
+
+        annotator.annotations
+    }
+}
+
+/// The state of laying out a file as annotations.
+#[derive(Default)]
+struct Annotator {
+    /// The position following the last annotated field.
+    offset: u64,
+
+    /// The annotations so far.
+    annotations: Vec<Annotation>,
+}
+
+impl Annotator {
+    /// Adds an annotation for a range containing other annotations,
+    /// without moving past its bytes.
+    fn group(&mut self, depth: usize, len: u64, label: impl Into<String>) {
+        self.annotations.push(Annotation {
+            offset: self.offset,
+            len,
+            depth,
+            label: label.into(),
+            object: None,
+        });
+    }
+
+    /// Adds an annotation for an object containing other annotations,
+    /// without moving past its bytes.
+    fn object(&mut self, depth: usize, len: u64, object: ObjectRef) {
+        self.annotations.push(Annotation {
+            offset: self.offset,
+            len,
+            depth,
+            label: format!("{}[{}]", object.section.name(), object.index),
+            object: Some(object),
+        });
+    }
+
+    /// Adds an annotation for a field and moves past its bytes.
+    fn field(&mut self, depth: usize, len: u64, label: impl Into<String>) {
+        self.group(depth, len, label);
+        self.offset += len;
+    }
+
+    /// Adds an annotation for a versioned field and moves past its bytes.
+    fn versioned<T: Version>(&mut self, depth: usize, value: &T, label: impl Into<String>) {
+        self.field(depth, 1 + value.binary_size(), label);
+    }
+
+    /// Adds annotations for a versioned [`MetaInfo`] and its fields.
+    fn meta_info(&mut self, depth: usize, meta_info: &MetaInfo) {
+        self.group(depth - 1, 1 + meta_info.binary_size(), "meta_info");
+        self.field(depth, 1, "version");
+
+        match meta_info {
+            MetaInfo::V1 { version_info, name } => {
+                self.versioned(depth, &version_info.inner, "version_info");
+                self.versioned(depth, &name.inner, format!("name = {}", string(name)));
+            }
+        }
+    }
+
+    /// Adds annotations for a versioned [`Base`] and its fields.
+    fn base(&mut self, depth: usize, base: &Base) {
+        self.group(depth - 1, 1 + base.binary_size(), "base");
+        self.field(depth, 1, "version");
+
+        let (meta_info, dynamic_name) = match base {
+            Base::V1 {
+                meta_info,
+                dynamic_name,
+            }
+            | Base::V2 {
+                meta_info,
+                dynamic_name,
+                ..
+            }
+            | Base::V3 {
+                meta_info,
+                dynamic_name,
+                ..
+            }
+            | Base::V4 {
+                meta_info,
+                dynamic_name,
+                ..
+            } => (meta_info, dynamic_name),
+        };
+
+        self.meta_info(depth + 1, meta_info);
+        self.versioned(
+            depth,
+            &dynamic_name.inner,
+            format!("dynamic_name = {}", string(dynamic_name)),
+        );
+
+        if let Base::V2 { dynamic_offset, .. }
+        | Base::V3 { dynamic_offset, .. }
+        | Base::V4 { dynamic_offset, .. } = base
+        {
+            self.versioned(
+                depth,
+                &dynamic_offset.inner,
+                format!("dynamic_offset = {}", vector(dynamic_offset)),
+            );
+        }
+
+        if let Base::V3 {
+            is_dynamic,
+            instance_id,
+            instance_offset,
+            ..
+        }
+        | Base::V4 {
+            is_dynamic,
+            instance_id,
+            instance_offset,
+            ..
+        } = base
+        {
+            self.field(depth, 1, format!("is_dynamic = {is_dynamic}"));
+            self.versioned(
+                depth,
+                &instance_id.inner,
+                format!("instance_id = {}", instance_id.0),
+            );
+            self.versioned(
+                depth,
+                &instance_offset.inner,
+                format!("instance_offset = {}", vector(instance_offset)),
+            );
+        }
+
+        if let Base::V4 {
+            joint_index,
+            joint_name,
+            ..
+        } = base
+        {
+            self.field(depth, 4, format!("joint_index = {joint_index}"));
+            self.versioned(
+                depth,
+                &joint_name.inner,
+                format!("joint_name = {}", string(joint_name)),
+            );
+        }
+    }
+}
+
+/// Returns the quoted contents of a string, or a placeholder if it is not valid UTF-8.
+fn string<const N: usize>(value: &FixedString<N>) -> String {
+    value
+        .to_str()
+        .map_or_else(|_| "<invalid UTF-8>".to_string(), |s| format!("{s:?}"))
+}
+
+/// Returns the components of a vector in parentheses.
+fn vector(value: &Vector3) -> String {
+    format!("({}, {}, {})", value.x(), value.y(), value.z())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{
+        fixtures::{file_with_collisions, platform_file},
+        objects::LvdObject,
+    };
+
+    #[test]
+    fn annotations_cover_the_file() {
+        let mut file = platform_file();
+
+        *file.data.collisions_mut().unwrap().elements_mut()[0]
+            .meta_info_mut()
+            .name_mut() = "COL_Floor".try_into().unwrap();
+
+        let mut writer = Cursor::new(Vec::new());
+
+        file.write(&mut writer).unwrap();
+
+        let annotations = file.annotations();
+        let end = annotations
+            .iter()
+            .filter(|annotation| annotation.depth == 0)
+            .fold(0, |offset, annotation| {
+                assert_eq!(annotation.offset, offset);
+
+                offset + annotation.len
+            });
+
+        assert_eq!(end, writer.into_inner().len() as u64);
+        assert!(annotations
+            .iter()
+            .any(|annotation| annotation.label == "name = \"COL_Floor\""));
+    }
+
+    #[test]
+    fn unknown_sections_follow_the_sections() {
+        let mut file = file_with_collisions([]);
+
+        file.data.unknown_sections_mut().bytes = vec![1, 2, 3];
+
+        let len = file.to_bytes().unwrap().len() as u64;
+        let annotations = file.annotations();
+        let last = annotations.last().unwrap();
+
+        assert_eq!((last.offset, last.len), (len - 3, 3));
+        assert_eq!(last.label, "unknown_sections");
+        assert!(annotations
+            .iter()
+            .all(|annotation| annotation.object.is_none()));
+    }
+}
@@ -2,18 +2,27 @@
 //!
 //! This module contains the [`Array`] type.
 
-use binrw::binrw;
+use std::{
+    fmt,
+    io::{Read, Seek},
+};
+
+use binrw::{binrw, BinRead, BinResult, Endian};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::version::{Version, Versioned};
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+use crate::version::{Upgrade, Version, Versioned};
 
 /// A fixed-size collection of contiguous versioned elements.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum Array<T: Version + 'static> {
     /// The first version of the `Array` type.
     #[br(pre_assert(version == 1))]
@@ -23,11 +32,52 @@ pub enum Array<T: Version + 'static> {
         count: u32,
 
         /// The collection of contiguous versioned elements.
-        #[br(count = count)]
+        #[br(parse_with = read_elements, args(count))]
         elements: Vec<Versioned<T>>,
     },
 }
 
+/// The maximum number of elements to reserve space for before reading them.
+///
+/// Bounds the allocation made for a corrupt or malicious element count.
+const MAX_RESERVED: usize = 0x10000;
+
+/// Reads the given number of elements, reserving space for them up front.
+fn read_elements<T, R>(
+    reader: &mut R,
+    endian: Endian,
+    (count,): (u32,),
+) -> BinResult<Vec<Versioned<T>>>
+where
+    T: Version + 'static,
+    Versioned<T>: for<'a> BinRead<Args<'a> = ()>,
+    R: Read + Seek,
+{
+    let count = count as usize;
+    let mut elements = Vec::with_capacity(count.min(MAX_RESERVED));
+
+    for _index in 0..count {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!(
+            "read_element",
+            index = _index,
+            pos = reader.stream_position()?
+        )
+        .entered();
+
+        let element = Versioned::<T>::read_options(reader, endian, ());
+
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &element {
+            tracing::trace!(%error, "failed to read element");
+        }
+
+        elements.push(element?);
+    }
+
+    Ok(elements)
+}
+
 impl<T> Version for Array<T>
 where
     T: Version,
@@ -38,3 +88,157 @@ where
         }
     }
 }
+
+impl<T: Version> Array<T> {
+    /// Creates a new `Array` from the given elements.
+    pub fn new(elements: Vec<T>) -> Self {
+        Self::V1 {
+            elements: elements.into_iter().map(Versioned::new).collect(),
+        }
+    }
+
+    /// Returns the collection of versioned elements.
+    pub fn elements(&self) -> &[Versioned<T>] {
+        match self {
+            Self::V1 { elements } => elements,
+        }
+    }
+
+    /// Returns the collection of versioned elements mutably.
+    pub fn elements_mut(&mut self) -> &mut Vec<Versioned<T>> {
+        match self {
+            Self::V1 { elements } => elements,
+        }
+    }
+
+    /// Returns the number of elements in the collection.
+    pub fn len(&self) -> usize {
+        self.elements().len()
+    }
+
+    /// Returns `true` if the collection contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elements().is_empty()
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.elements().iter().map(|e| &e.inner)
+    }
+
+    /// Returns an iterator that allows modifying each element.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.elements_mut().iter_mut().map(|e| &mut e.inner)
+    }
+
+    /// Appends an element to the back of the collection.
+    pub fn push(&mut self, element: T) {
+        self.elements_mut().push(Versioned::new(element));
+    }
+
+    /// Appends the default element to the back of the collection, returning it for editing.
+    ///
+    /// The element and every versioned field within it are written with their version,
+    /// so new objects can be added without knowing the version bytes of their type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{array::Array, objects::Point, vector::Vector2};
+    ///
+    /// let mut points = Array::<Point>::default();
+    ///
+    /// *points.push_default().pos_mut() = Vector2::new(-30.0, 10.0);
+    ///
+    /// assert_eq!(points.to_string(), "0: Point \"\": at (-30, 10)");
+    /// ```
+    pub fn push_default(&mut self) -> &mut T
+    where
+        T: Default,
+    {
+        self.push(T::default());
+        &mut self.elements_mut().last_mut().unwrap().inner
+    }
+
+    /// Inserts an element at the given index, shifting the elements after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the number of elements.
+    pub fn insert(&mut self, index: usize, element: T) {
+        self.elements_mut().insert(index, Versioned::new(element));
+    }
+
+    /// Removes and returns the element at the given index, shifting the elements after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        self.elements_mut().remove(index).inner
+    }
+}
+
+impl<T: Version> Default for Array<T> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+/// Writes each element on its own line, preceded by its index.
+///
+/// # Examples
+///
+/// ```
+/// use lvd_lib::{array::Array, objects::Point, vector::Vector2};
+///
+/// let mut point = Point::default();
+///
+/// *point.pos_mut() = Vector2::new(-30.0, 10.0);
+///
+/// let points = Array::new(vec![Point::default(), point]);
+///
+/// assert_eq!(points.to_string(), "0: Point \"\": at (0, 0)\n1: Point \"\": at (-30, 10)");
+/// ```
+impl<T: Version + fmt::Display> fmt::Display for Array<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, element) in self.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{index}: {element}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Upgrade> Upgrade for Array<T> {
+    const LATEST_VERSION: u8 = 1;
+
+    fn into_latest(self) -> Self {
+        match self {
+            Self::V1 { elements } => Self::V1 {
+                elements: elements.into_iter().map(Versioned::into_latest).collect(),
+            },
+        }
+    }
+}
+
+impl<T: Version> FromIterator<T> for Array<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<T: Version> IntoIterator for Array<T> {
+    type Item = T;
+    type IntoIter = std::iter::Map<std::vec::IntoIter<Versioned<T>>, fn(Versioned<T>) -> T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            Self::V1 { elements } => elements.into_iter().map(|element| element.inner),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Asynchronous reading and writing of LVD files with Tokio.
+//!
+//! This module contains the [`LvdFile::from_file_async`] and [`LvdFile::write_to_file_async`]
+//! methods, which access files asynchronously and parse or serialize their data on Tokio's
+//! blocking thread pool, so services converting uploaded files do not stall their other tasks.
+
+use std::{io, panic, path::Path};
+
+use binrw::BinResult;
+use tokio::task::{self, JoinError};
+
+use crate::LvdFile;
+
+impl LvdFile {
+    /// Reads the data from the given file path asynchronously, parsing it on the blocking
+    /// thread pool.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::LvdFile;
+    ///
+    /// # async fn example() {
+    /// let file = LvdFile::from_file_async("battlefield_00.lvd").await.unwrap();
+    /// # }
+    /// ```
+    pub async fn from_file_async<P: AsRef<Path>>(path: P) -> BinResult<Self> {
+        let bytes = tokio::fs::read(path).await?;
+
+        join(task::spawn_blocking(move || Self::from_bytes(&bytes)).await)
+    }
+
+    /// Writes the data to the given file path asynchronously, serializing a copy of it on the
+    /// blocking thread pool.
+    ///
+    /// If serializing fails, the file is not created or modified.
+    pub async fn write_to_file_async<P: AsRef<Path>>(&self, path: P) -> BinResult<()> {
+        let file = self.clone();
+        let bytes = join(task::spawn_blocking(move || file.to_bytes()).await)?;
+
+        tokio::fs::write(path, bytes).await?;
+
+        Ok(())
+    }
+}
+
+/// Returns the result of a blocking task, resuming its panic if it panicked.
+fn join<T>(result: Result<BinResult<T>, JoinError>) -> BinResult<T> {
+    match result {
+        Ok(result) => result,
+        Err(error) if error.is_panic() => panic::resume_unwind(error.into_panic()),
+        Err(error) => Err(io::Error::other(error).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixtures::platform_file,
+        hash::ContentHash,
+        objects::{base::Base, ItemPopup},
+        shape::{Path as ShapePath, Shape2, ShapeArray2},
+        tag::Tag,
+        version::Versioned,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn file_round_trip() {
+        let path = std::env::temp_dir().join(format!("lvd_lib_async_{}.lvd", std::process::id()));
+        let file = platform_file();
+
+        file.write_to_file_async(&path).await.unwrap();
+
+        let read = LvdFile::from_file_async(&path).await;
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read.unwrap().content_hash(), file.content_hash());
+    }
+
+    #[tokio::test]
+    async fn failed_write_creates_no_file() {
+        let path =
+            std::env::temp_dir().join(format!("lvd_lib_async_error_{}.lvd", std::process::id()));
+        let mut file = platform_file();
+        let item_popup = ItemPopup::V1 {
+            base: Versioned::new(Base::default()),
+            tag: Versioned::new(Tag::default()),
+            shapes: Versioned::new(ShapeArray2::new([Shape2::Unknown {
+                ty: 1,
+                data: [0; 4],
+                path: Versioned::new(ShapePath::default()),
+            }])),
+        };
+
+        file.data.item_popups_mut().unwrap().push(item_popup);
+
+        assert!(file.write_to_file_async(&path).await.is_err());
+        assert!(!path.exists());
+        assert!(matches!(
+            LvdFile::from_file_async(&path).await,
+            Err(binrw::Error::Io(error)) if error.kind() == io::ErrorKind::NotFound
+        ));
+    }
+}
@@ -0,0 +1,252 @@
+//! Fitting of camera and death regions to the collisions of a stage.
+//!
+//! This module contains the [`LvdFile::autofit_regions`] method, which replaces the camera and
+//! death regions of a file, and their shrunken versions, with rectangles around its collisions,
+//! and the [`RegionMargins`] and [`Margins`] types setting how far each region extends beyond
+//! them.
+
+use crate::{array::Array, objects::Region, shape::Rect, LvdFile};
+
+/// The distances from the collisions of a stage to each edge of a fitted region.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Margins {
+    /// The distance to the left edge.
+    pub left: f32,
+
+    /// The distance to the right edge.
+    pub right: f32,
+
+    /// The distance to the top edge.
+    pub top: f32,
+
+    /// The distance to the bottom edge.
+    pub bottom: f32,
+}
+
+impl Margins {
+    /// Creates a new `Margins` with the given distances to each edge.
+    pub const fn new(left: f32, right: f32, top: f32, bottom: f32) -> Self {
+        Self {
+            left,
+            right,
+            top,
+            bottom,
+        }
+    }
+
+    /// Creates a new `Margins` with the same distance to every edge.
+    pub const fn uniform(margin: f32) -> Self {
+        Self::new(margin, margin, margin, margin)
+    }
+
+    /// Returns the rectangle with each edge moved outwards by its margin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{autofit::Margins, shape::Rect};
+    ///
+    /// let bounds = Rect::new(-80.0, 80.0, 40.0, -20.0);
+    ///
+    /// assert_eq!(
+    ///     Margins::new(90.0, 90.0, 100.0, 50.0).apply(&bounds),
+    ///     Rect::new(-170.0, 170.0, 140.0, -70.0)
+    /// );
+    /// ```
+    pub fn apply(&self, rect: &Rect) -> Rect {
+        Rect::new(
+            rect.left() - self.left,
+            rect.right() + self.right,
+            rect.top() + self.top,
+            rect.bottom() - self.bottom,
+        )
+    }
+}
+
+/// The margins of each kind of region fitted by [`LvdFile::autofit_regions`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct RegionMargins {
+    /// The margins of the camera region.
+    pub camera: Margins,
+
+    /// The margins of the death region.
+    pub death: Margins,
+
+    /// The margins of the shrunken camera region.
+    pub shrinked_camera: Margins,
+
+    /// The margins of the shrunken death region.
+    pub shrinked_death: Margins,
+}
+
+impl RegionMargins {
+    /// Margins typical of the game's stages, giving the camera room to frame recovering
+    /// characters and placing the blast zone well beyond the ledges.
+    pub const STANDARD: Self = Self {
+        camera: Margins::new(90.0, 90.0, 100.0, 50.0),
+        death: Margins::new(160.0, 160.0, 150.0, 120.0),
+        shrinked_camera: Margins::new(70.0, 70.0, 80.0, 40.0),
+        shrinked_death: Margins::new(130.0, 130.0, 120.0, 100.0),
+    };
+}
+
+impl LvdFile {
+    /// Returns the smallest rectangle containing every vertex of every collision,
+    /// or `None` if the file has no collision vertices.
+    pub fn collision_bounds(&self) -> Option<Rect> {
+        self.data
+            .collisions()
+            .into_iter()
+            .flat_map(Array::iter)
+            .flat_map(|collision| collision.vertices().iter())
+            .map(|v| Rect::new(v.x(), v.x(), v.y(), v.y()))
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// Replaces the camera and death regions, and their shrunken versions, with a single region
+    /// each, extending beyond the bounds of the collisions by the margins,
+    /// returning the bounds of the collisions.
+    ///
+    /// Each fitted region keeps the name and other common data of the first region it replaces.
+    /// Sections not supported by the file's version are skipped, and nothing is changed if the
+    /// file has no collision vertices.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{
+    ///     autofit::{Margins, RegionMargins},
+    ///     LvdFile,
+    /// };
+    ///
+    /// let mut file = LvdFile::from_file("custom_00.lvd").unwrap();
+    ///
+    /// file.autofit_regions(&RegionMargins {
+    ///     camera: Margins::new(90.0, 90.0, 100.0, 50.0),
+    ///     death: Margins::new(160.0, 160.0, 150.0, 120.0),
+    ///     shrinked_camera: Margins::new(70.0, 70.0, 80.0, 40.0),
+    ///     shrinked_death: Margins::new(130.0, 130.0, 120.0, 100.0),
+    /// });
+    /// ```
+    pub fn autofit_regions(&mut self, margins: &RegionMargins) -> Option<Rect> {
+        let bounds = self.collision_bounds()?;
+
+        fit_section(
+            self.data.camera_regions_mut(),
+            margins.camera.apply(&bounds),
+        );
+        fit_section(self.data.death_regions_mut(), margins.death.apply(&bounds));
+        fit_section(
+            self.data.shrinked_camera_regions_mut(),
+            margins.shrinked_camera.apply(&bounds),
+        );
+        fit_section(
+            self.data.shrinked_death_regions_mut(),
+            margins.shrinked_death.apply(&bounds),
+        );
+
+        Some(bounds)
+    }
+}
+
+/// Replaces the regions of a section with a single region covering the rectangle.
+fn fit_section(regions: Option<&mut Array<Region>>, rect: Rect) {
+    let Some(regions) = regions else {
+        return;
+    };
+    let mut region = regions.iter().next().cloned().unwrap_or_default();
+
+    *region.rect_mut() = rect;
+    *regions = Array::new(vec![region]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        fixtures::{file_with_collisions, platform_file},
+        objects::{Collision, LvdObject},
+        vector::Vector2,
+    };
+
+    #[test]
+    fn autofit_regions_around_collisions() {
+        let mut file = file_with_collisions([
+            Collision::new_soft_platform(Vector2::new(-80.0, 0.0), Vector2::new(80.0, 0.0)),
+            Collision::new_soft_platform(Vector2::new(-20.0, 40.0), Vector2::new(20.0, 40.0)),
+        ]);
+
+        file.data
+            .camera_regions_mut()
+            .unwrap()
+            .push(Region::default());
+        file.data
+            .camera_regions_mut()
+            .unwrap()
+            .push(Region::default());
+
+        let margins = RegionMargins {
+            camera: Margins::uniform(50.0),
+            death: Margins::uniform(100.0),
+            shrinked_camera: Margins::uniform(40.0),
+            shrinked_death: Margins::uniform(80.0),
+        };
+
+        assert_eq!(
+            file.autofit_regions(&margins),
+            Some(Rect::new(-80.0, 80.0, 40.0, 0.0))
+        );
+
+        let rects = |regions: Option<&Array<Region>>| {
+            regions
+                .unwrap()
+                .iter()
+                .map(Region::rect)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            rects(file.data.camera_regions()),
+            [Rect::new(-130.0, 130.0, 90.0, -50.0)]
+        );
+        assert_eq!(
+            rects(file.data.death_regions()),
+            [Rect::new(-180.0, 180.0, 140.0, -100.0)]
+        );
+        assert_eq!(
+            rects(file.data.shrinked_death_regions()),
+            [Rect::new(-160.0, 160.0, 120.0, -80.0)]
+        );
+    }
+
+    #[test]
+    fn autofit_keeps_region_data() {
+        let mut empty = file_with_collisions([]);
+        let mut file = platform_file();
+        let mut region = Region::default();
+
+        *region.meta_info_mut().name_mut() = "CAMERA_00".try_into().unwrap();
+        empty
+            .data
+            .camera_regions_mut()
+            .unwrap()
+            .push(region.clone());
+        file.data.camera_regions_mut().unwrap().push(region.clone());
+
+        assert_eq!(empty.autofit_regions(&RegionMargins::STANDARD), None);
+        assert_eq!(
+            empty.data.camera_regions().unwrap().elements()[0].rect(),
+            region.rect(),
+            "nothing is changed without collisions"
+        );
+
+        let bounds = file.autofit_regions(&RegionMargins::STANDARD).unwrap();
+        let camera = &file.data.camera_regions().unwrap().elements()[0];
+
+        assert_eq!(bounds, Rect::new(-10.0, 10.0, 0.0, 0.0));
+        assert_eq!(camera.name().to_str(), Ok("CAMERA_00"));
+        assert_eq!(camera.rect(), RegionMargins::STANDARD.camera.apply(&bounds));
+        assert_eq!(file.data.death_regions().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,250 @@
+//! Audits of the names binding LVD objects to stage models and their joints.
+//!
+//! This module contains the [`LvdFile::bindings`] method, which lists every dynamic name, joint
+//! name, and parent model and joint name referenced by the objects of a file, and the
+//! [`LvdFile::unresolved_bindings`] method, which finds the joint names missing from a list of
+//! the stage model's bone names, such as one exported from its `.numdlb` file.
+
+use std::fmt;
+
+use crate::{string::FixedString64, LvdFile, ObjectRef, SectionKind};
+
+/// The field of an object holding the name of a binding.
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum BindingKind {
+    /// The name of the object as seen by the game when classed as dynamic.
+    DynamicName,
+
+    /// The name of the joint the object is parented to.
+    JointName,
+
+    /// The name of the model a Pokémon Trainer range inherits transformations from.
+    ParentModelName,
+
+    /// The name of the joint a Pokémon Trainer range inherits transformations from.
+    ParentJointName,
+}
+
+impl BindingKind {
+    /// Returns the name of the field holding the binding.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::DynamicName => "dynamic_name",
+            Self::JointName => "joint_name",
+            Self::ParentModelName => "parent_model_name",
+            Self::ParentJointName => "parent_joint_name",
+        }
+    }
+
+    /// Returns `true` if the binding names a joint of a model.
+    pub const fn is_joint(self) -> bool {
+        matches!(self, Self::JointName | Self::ParentJointName)
+    }
+}
+
+impl fmt::Display for BindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A name referenced by an object to bind it to a model or joint.
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+pub struct Binding {
+    /// The object referencing the name.
+    pub object: ObjectRef,
+
+    /// The field holding the name.
+    pub kind: BindingKind,
+
+    /// The referenced name.
+    pub name: String,
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "object {} in section `{}`: {} `{}`",
+            self.object.index,
+            self.object.section.name(),
+            self.kind,
+            self.name
+        )
+    }
+}
+
+impl LvdFile {
+    /// Returns every nonempty dynamic name, joint name, and parent model and joint name referenced
+    /// by the objects of the file, in section order.
+    ///
+    /// Invalid UTF-8 sequences in names are replaced with
+    /// [`U+FFFD REPLACEMENT CHARACTER`](char::REPLACEMENT_CHARACTER).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::LvdFile;
+    ///
+    /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    ///
+    /// for binding in file.bindings() {
+    ///     println!("{binding}");
+    /// }
+    /// ```
+    pub fn bindings(&self) -> Vec<Binding> {
+        let mut bindings = Vec::new();
+
+        for &section in SectionKind::ALL {
+            for index in 0..self.data.section_len(section).unwrap_or_default() {
+                let object = ObjectRef::new(section, index);
+                let mut push = |kind, name: &FixedString64| {
+                    if !name.is_empty() {
+                        bindings.push(Binding {
+                            object,
+                            kind,
+                            name: name.to_string_lossy().into_owned(),
+                        });
+                    }
+                };
+
+                if let Some(base) = self.data.object(section, index).and_then(|o| o.base()) {
+                    push(BindingKind::DynamicName, base.dynamic_name());
+
+                    if let Some(joint_name) = base.joint_name() {
+                        push(BindingKind::JointName, joint_name);
+                    }
+                }
+
+                if section == SectionKind::PTrainerRanges {
+                    let range = &self.data.ptrainer_ranges().unwrap().elements()[index];
+
+                    if let Some(model_name) = range.parent_model_name() {
+                        push(BindingKind::ParentModelName, model_name);
+                    }
+
+                    if let Some(joint_name) = range.parent_joint_name() {
+                        push(BindingKind::ParentJointName, joint_name);
+                    }
+                }
+            }
+        }
+
+        bindings
+    }
+
+    /// Returns every joint name or parent joint name referenced by the objects of the file which
+    /// is not one of the given bone names, and so will not resolve in game.
+    ///
+    /// Dynamic names and parent model names do not name bones and are never returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::LvdFile;
+    ///
+    /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    ///
+    /// for binding in file.unresolved_bindings(&["Root", "platform_joint"]) {
+    ///     println!("{binding}");
+    /// }
+    /// ```
+    pub fn unresolved_bindings<S: AsRef<str>>(&self, bone_names: &[S]) -> Vec<Binding> {
+        self.bindings()
+            .into_iter()
+            .filter(|binding| {
+                binding.kind.is_joint()
+                    && !bone_names.iter().any(|bone| bone.as_ref() == binding.name)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixtures::platform_file,
+        objects::{LvdObject, PTrainerRange},
+    };
+
+    use super::*;
+
+    fn file() -> LvdFile {
+        let mut file = platform_file();
+        let mut range = PTrainerRange::default();
+
+        file.data.collisions_mut().unwrap().elements_mut()[0]
+            .base_mut()
+            .unwrap()
+            .bind_to_joint("platform")
+            .unwrap();
+        *range.parent_model_name_mut().unwrap() = "model".try_into().unwrap();
+        *range.parent_joint_name_mut().unwrap() = "trainer".try_into().unwrap();
+        file.data.ptrainer_ranges_mut().unwrap().push(range);
+
+        file
+    }
+
+    #[test]
+    fn bindings() {
+        let kinds = file()
+            .bindings()
+            .into_iter()
+            .map(|binding| (binding.object.section, binding.kind, binding.name))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            kinds,
+            [
+                (
+                    SectionKind::Collisions,
+                    BindingKind::JointName,
+                    "platform".to_string()
+                ),
+                (
+                    SectionKind::PTrainerRanges,
+                    BindingKind::ParentModelName,
+                    "model".to_string()
+                ),
+                (
+                    SectionKind::PTrainerRanges,
+                    BindingKind::ParentJointName,
+                    "trainer".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn unresolved_bindings() {
+        let unresolved = file().unresolved_bindings(&["platform", "model"]);
+
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(
+            unresolved[0].to_string(),
+            "object 0 in section `ptrainer_ranges`: parent_joint_name `trainer`"
+        );
+    }
+
+    #[test]
+    fn empty_and_invalid_names() {
+        let mut file = platform_file();
+
+        assert!(file.bindings().is_empty(), "empty names are not bindings");
+
+        *file.data.collisions_mut().unwrap().elements_mut()[0]
+            .base_mut()
+            .unwrap()
+            .dynamic_name_mut() = FixedString64::from_bytes(b"dyn\xFF").unwrap();
+
+        let bindings = file.bindings();
+
+        assert_eq!(bindings.len(), 1);
+        assert_eq!(bindings[0].kind, BindingKind::DynamicName);
+        assert_eq!(bindings[0].name, "dyn\u{FFFD}");
+        assert!(
+            file.unresolved_bindings::<&str>(&[]).is_empty(),
+            "dynamic names do not name bones"
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! Conversion of LVD data to a canonical form.
+//!
+//! Files which hold the same objects can still differ in the order of the objects within each
+//! section, the sign of zero values, and the bit patterns of NaN values, which makes converted
+//! output differ between tools and clutters diffs. This module contains the
+//! [`LvdFile::canonicalize`] method, which removes these differences, and the [`Canonical`]
+//! coordinate mapping for applying the same normalization to individual objects.
+
+use crate::{
+    transform::CoordinateMap,
+    vector::{Vector2, Vector3},
+    LvdFile,
+};
+
+/// The handling of the order of the objects within each section when canonicalizing a file.
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum ObjectOrder {
+    /// Sort the objects by name, then by tag.
+    Sorted,
+
+    /// Keep the objects in their current order, such as when the game relies on their indices.
+    Preserved,
+}
+
+/// A coordinate mapping which replaces negative zero with zero, and every NaN with
+/// [`f32::NAN`], leaving every other value unchanged.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Canonical;
+
+impl Canonical {
+    /// Returns the canonical form of the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::canonical::Canonical;
+    ///
+    /// assert!(Canonical::normalize(-0.0).is_sign_positive());
+    /// assert_eq!(Canonical::normalize(-1.5), -1.5);
+    /// ```
+    pub fn normalize(value: f32) -> f32 {
+        if value.is_nan() {
+            f32::NAN
+        } else if value == 0.0 {
+            0.0
+        } else {
+            value
+        }
+    }
+}
+
+impl CoordinateMap for Canonical {
+    fn map_point2(&self, point: Vector2) -> Vector2 {
+        Vector2::new(Self::normalize(point.x()), Self::normalize(point.y()))
+    }
+
+    fn map_point3(&self, point: Vector3) -> Vector3 {
+        Vector3::new(
+            Self::normalize(point.x()),
+            Self::normalize(point.y()),
+            Self::normalize(point.z()),
+        )
+    }
+
+    fn map_vector3(&self, vector: Vector3) -> Vector3 {
+        self.map_point3(vector)
+    }
+
+    fn map_normal2(&self, normal: Vector2) -> Vector2 {
+        self.map_point2(normal)
+    }
+
+    fn map_length2(&self, length: f32) -> f32 {
+        Self::normalize(length)
+    }
+
+    fn map_length3(&self, length: f32) -> f32 {
+        Self::normalize(length)
+    }
+}
+
+impl LvdFile {
+    /// Converts the file to a canonical form, so files holding the same objects are written
+    /// identically.
+    ///
+    /// Negative zeros and NaN values in the positions, normals, and sizes of every object are
+    /// normalized with [`Canonical`], and the objects within each section are sorted by name and
+    /// tag if `order` is [`ObjectOrder::Sorted`]. The sort is stable, so objects with the same
+    /// name and tag keep their relative order. As with [`LvdFile::quantize`], the values of
+    /// objects exclusive to Smash Run are left unchanged, though they are still sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{canonical::ObjectOrder, LvdFile};
+    ///
+    /// let mut file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    ///
+    /// file.canonicalize(ObjectOrder::Sorted);
+    /// ```
+    pub fn canonicalize(&mut self, order: ObjectOrder) {
+        self.data
+            .for_each_object_mut(|object| object.transform(&Canonical));
+
+        if order == ObjectOrder::Sorted {
+            self.data.sort_objects_by_key(|object| {
+                (
+                    object.name().to_string_lossy().into_owned(),
+                    object.tag().copied(),
+                )
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        fixtures::file_with_collisions,
+        objects::{Collision, LvdObject},
+    };
+
+    fn named_platforms(platforms: &[(&str, f32)]) -> LvdFile {
+        file_with_collisions(platforms.iter().map(|&(name, y)| {
+            let mut collision =
+                Collision::new_soft_platform(Vector2::new(-10.0, y), Vector2::new(10.0, y));
+
+            *collision.meta_info_mut().name_mut() = name.try_into().unwrap();
+            collision
+        }))
+    }
+
+    fn heights(file: &LvdFile) -> Vec<f32> {
+        file.data
+            .collisions()
+            .unwrap()
+            .iter()
+            .map(|collision| collision.vertices().iter().next().unwrap().y())
+            .collect()
+    }
+
+    #[test]
+    fn canonicalize_sorts_and_normalizes() {
+        let mut file = named_platforms(&[("COL_B", -0.0), ("COL_A", 1.0), ("COL_B", 2.0)]);
+
+        file.canonicalize(ObjectOrder::Sorted);
+
+        let heights = heights(&file);
+
+        assert_eq!(heights, [1.0, 0.0, 2.0]);
+        assert!(heights[1].is_sign_positive());
+    }
+
+    #[test]
+    fn canonicalize_preserving_order() {
+        let nan = f32::from_bits(0x7fc0_0001);
+        let mut file = named_platforms(&[("COL_B", -0.0), ("COL_A", nan)]);
+
+        file.canonicalize(ObjectOrder::Preserved);
+
+        let heights = heights(&file);
+
+        assert!(heights[0] == 0.0 && heights[0].is_sign_positive());
+        assert_eq!(heights[1].to_bits(), f32::NAN.to_bits());
+        assert_eq!(
+            file.to_bytes().unwrap(),
+            {
+                let mut copy = file.clone();
+
+                copy.canonicalize(ObjectOrder::Preserved);
+                copy.to_bytes().unwrap()
+            },
+            "canonicalizing is idempotent"
+        );
+    }
+}
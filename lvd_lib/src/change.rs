@@ -0,0 +1,797 @@
+//! Recording, replaying, and inverting edits to LVD files.
+//!
+//! This module contains the [`ChangeSet`] type, which applies edits to an LVD file while
+//! recording each of them as a [`Change`], and an error type that may result when applying one.
+//!
+//! Each change stores the values it replaces, so a change set can be inverted to undo its
+//! edits, and serialized as an edit script which reproduces them on another copy of the file.
+//! Objects are addressed by section and index, and fields are addressed by a path relative to
+//! the object without the versions of nested types, such as `rect.left` or `vertices[2].x`.
+//!
+//! ```yaml
+//! - set_field:
+//!     section: collisions
+//!     index: 0
+//!     path: flags.throughable
+//!     old: false
+//!     new: true
+//! - remove_object:
+//!     section: start_positions
+//!     index: 3
+//!     object: { ... }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::{transform::CoordinateMap, version::Versioned, Lvd, LvdFile, ObjectRef, SectionKind};
+
+/// A single recorded edit to an LVD file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Change {
+    /// A field of an object was set.
+    SetField {
+        /// The section containing the object.
+        section: SectionKind,
+
+        /// The index of the object within the section.
+        index: usize,
+
+        /// The path of the field relative to the object, such as `rect.left`.
+        path: String,
+
+        /// The value of the field before the change.
+        old: Value,
+
+        /// The value of the field after the change.
+        new: Value,
+    },
+
+    /// An object was inserted into a section.
+    AddObject {
+        /// The section the object was inserted into.
+        section: SectionKind,
+
+        /// The index of the object within the section.
+        index: usize,
+
+        /// The inserted object.
+        object: Value,
+    },
+
+    /// An object was removed from a section.
+    RemoveObject {
+        /// The section the object was removed from.
+        section: SectionKind,
+
+        /// The index the object had within the section.
+        index: usize,
+
+        /// The removed object.
+        object: Value,
+    },
+
+    /// An object was replaced as a whole, such as by a transformation.
+    ReplaceObject {
+        /// The section containing the object.
+        section: SectionKind,
+
+        /// The index of the object within the section.
+        index: usize,
+
+        /// The object before the change.
+        old: Value,
+
+        /// The object after the change.
+        new: Value,
+    },
+}
+
+impl Change {
+    /// Returns the object the change applies to.
+    pub fn object(&self) -> ObjectRef {
+        match *self {
+            Self::SetField { section, index, .. }
+            | Self::AddObject { section, index, .. }
+            | Self::RemoveObject { section, index, .. }
+            | Self::ReplaceObject { section, index, .. } => ObjectRef::new(section, index),
+        }
+    }
+
+    /// Returns the change which undoes this change.
+    pub fn inverse(&self) -> Self {
+        match self.clone() {
+            Self::SetField {
+                section,
+                index,
+                path,
+                old,
+                new,
+            } => Self::SetField {
+                section,
+                index,
+                path,
+                old: new,
+                new: old,
+            },
+            Self::AddObject {
+                section,
+                index,
+                object,
+            } => Self::RemoveObject {
+                section,
+                index,
+                object,
+            },
+            Self::RemoveObject {
+                section,
+                index,
+                object,
+            } => Self::AddObject {
+                section,
+                index,
+                object,
+            },
+            Self::ReplaceObject {
+                section,
+                index,
+                old,
+                new,
+            } => Self::ReplaceObject {
+                section,
+                index,
+                old: new,
+                new: old,
+            },
+        }
+    }
+
+    /// Applies the change to the serialized form of an LVD file's data.
+    fn apply_to(&self, data: &mut Value) -> Result<(), ChangeError> {
+        match self {
+            Self::SetField {
+                section,
+                index,
+                path,
+                new,
+                ..
+            } => *field_mut(object_mut(data, *section, *index)?, path)? = new.clone(),
+            Self::AddObject {
+                section,
+                index,
+                object,
+            } => {
+                let elements = elements_mut(data, *section)?;
+
+                if *index > elements.len() {
+                    return Err(ChangeError::ObjectNotFound {
+                        section: *section,
+                        index: *index,
+                    });
+                }
+
+                elements.insert(*index, object.clone());
+            }
+            Self::RemoveObject { section, index, .. } => {
+                object_mut(data, *section, *index)?;
+                elements_mut(data, *section)?.remove(*index);
+            }
+            Self::ReplaceObject {
+                section,
+                index,
+                new,
+                ..
+            } => *object_mut(data, *section, *index)? = new.clone(),
+        }
+
+        Ok(())
+    }
+}
+
+/// A sequence of edits to an LVD file, recorded as they are made.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lvd_lib::{change::ChangeSet, LvdFile, SectionKind};
+///
+/// let mut file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+/// let mut changes = ChangeSet::new();
+///
+/// changes
+///     .set_field(&mut file, SectionKind::DeathRegions, 0, "rect.top", 260.0.into())
+///     .unwrap();
+///
+/// // Reverts the death region's top edge.
+/// changes.undo(&mut file).unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ChangeSet {
+    /// The recorded changes, in the order they were made.
+    pub changes: Vec<Change>,
+}
+
+impl ChangeSet {
+    /// Creates an empty `ChangeSet`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a field of an object, recording the change.
+    ///
+    /// The file is left unchanged if an error is returned.
+    pub fn set_field(
+        &mut self,
+        file: &mut LvdFile,
+        section: SectionKind,
+        index: usize,
+        path: &str,
+        value: Value,
+    ) -> Result<(), ChangeError> {
+        let mut data = serde_json::to_value(&file.data)?;
+        let old = field_mut(object_mut(&mut data, section, index)?, path)?.clone();
+
+        self.record(
+            file,
+            Change::SetField {
+                section,
+                index,
+                path: path.to_string(),
+                old,
+                new: value,
+            },
+        )
+    }
+
+    /// Inserts an object into a section at the given index, recording the change.
+    ///
+    /// The file is left unchanged if an error is returned.
+    pub fn add_object(
+        &mut self,
+        file: &mut LvdFile,
+        section: SectionKind,
+        index: usize,
+        object: Value,
+    ) -> Result<(), ChangeError> {
+        self.record(
+            file,
+            Change::AddObject {
+                section,
+                index,
+                object,
+            },
+        )
+    }
+
+    /// Removes the object at the given index from a section, recording the change.
+    ///
+    /// The file is left unchanged if an error is returned.
+    pub fn remove_object(
+        &mut self,
+        file: &mut LvdFile,
+        section: SectionKind,
+        index: usize,
+    ) -> Result<(), ChangeError> {
+        let mut data = serde_json::to_value(&file.data)?;
+        let object = object_mut(&mut data, section, index)?.clone();
+
+        self.record(
+            file,
+            Change::RemoveObject {
+                section,
+                index,
+                object,
+            },
+        )
+    }
+
+    /// Applies the coordinate mapping to every object of the file, recording a change for
+    /// each object the mapping changes.
+    ///
+    /// As with [`LvdObject::transform`](crate::objects::LvdObject::transform), objects exclusive
+    /// to Smash Run are left unchanged.
+    pub fn transform(
+        &mut self,
+        file: &mut LvdFile,
+        map: &dyn CoordinateMap,
+    ) -> Result<(), ChangeError> {
+        let mut old_data = serde_json::to_value(&file.data)?;
+
+        file.data
+            .for_each_object_mut(|object| object.transform(map));
+
+        let mut new_data = serde_json::to_value(&file.data)?;
+
+        for &section in SectionKind::ALL {
+            let Some(len) = file.data.section_len(section) else {
+                continue;
+            };
+
+            for index in 0..len {
+                let old = object_mut(&mut old_data, section, index)?;
+                let new = object_mut(&mut new_data, section, index)?;
+
+                if old != new {
+                    self.changes.push(Change::ReplaceObject {
+                        section,
+                        index,
+                        old: old.clone(),
+                        new: new.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the changes which turn the objects of `old` into those of `new`.
+    ///
+    /// Objects at the same index in both files are compared field by field, recording a
+    /// [`Change::SetField`] for each differing field, or a [`Change::ReplaceObject`] if the objects
+    /// have different versions. Objects beyond the end of a section in either file are recorded as
+    /// added or removed. Sections missing from either file's version are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{change::ChangeSet, LvdFile};
+    ///
+    /// let old = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    /// let new = LvdFile::from_file("battlefield_00_edited.lvd").unwrap();
+    ///
+    /// for change in ChangeSet::diff(&old, &new).unwrap().changes {
+    ///     println!("{change:?}");
+    /// }
+    /// ```
+    pub fn diff(old: &LvdFile, new: &LvdFile) -> Result<Self, ChangeError> {
+        let mut old_data = serde_json::to_value(&old.data)?;
+        let mut new_data = serde_json::to_value(&new.data)?;
+        let mut changes = Vec::new();
+
+        for &section in SectionKind::ALL {
+            let (Some(old_len), Some(new_len)) =
+                (old.data.section_len(section), new.data.section_len(section))
+            else {
+                continue;
+            };
+
+            for index in 0..old_len.min(new_len) {
+                let old = object_mut(&mut old_data, section, index)?;
+                let new = object_mut(&mut new_data, section, index)?;
+
+                if old == new {
+                    continue;
+                }
+
+                if !same_variant(old, new) {
+                    changes.push(Change::ReplaceObject {
+                        section,
+                        index,
+                        old: old.clone(),
+                        new: new.clone(),
+                    });
+                    continue;
+                }
+
+                diff_fields(old, new, "", &mut |path, old, new| {
+                    changes.push(Change::SetField {
+                        section,
+                        index,
+                        path,
+                        old: old.clone(),
+                        new: new.clone(),
+                    })
+                });
+            }
+
+            for index in old_len..new_len {
+                changes.push(Change::AddObject {
+                    section,
+                    index,
+                    object: object_mut(&mut new_data, section, index)?.clone(),
+                });
+            }
+
+            // Objects are removed from the end, so the remaining indices stay valid.
+            for index in (new_len..old_len).rev() {
+                changes.push(Change::RemoveObject {
+                    section,
+                    index,
+                    object: object_mut(&mut old_data, section, index)?.clone(),
+                });
+            }
+        }
+
+        Ok(Self { changes })
+    }
+
+    /// Applies every change to the file in order, such as to replay an edit script.
+    ///
+    /// The file is left unchanged if an error is returned.
+    pub fn apply(&self, file: &mut LvdFile) -> Result<(), ChangeError> {
+        apply_changes(file, &self.changes)
+    }
+
+    /// Returns the change set which undoes this change set's edits.
+    pub fn inverse(&self) -> Self {
+        Self {
+            changes: self.changes.iter().rev().map(Change::inverse).collect(),
+        }
+    }
+
+    /// Undoes every change to the file in reverse order, leaving the change set unchanged.
+    ///
+    /// The file is left unchanged if an error is returned.
+    pub fn undo(&self, file: &mut LvdFile) -> Result<(), ChangeError> {
+        self.inverse().apply(file)
+    }
+
+    /// Removes the last change and undoes it, returning the undone change.
+    ///
+    /// The file and change set are left unchanged if an error is returned.
+    pub fn undo_last(&mut self, file: &mut LvdFile) -> Result<Option<Change>, ChangeError> {
+        let Some(change) = self.changes.last() else {
+            return Ok(None);
+        };
+
+        apply_changes(file, &[change.inverse()])?;
+
+        Ok(self.changes.pop())
+    }
+
+    /// Applies the change to the file and records it if it succeeds.
+    fn record(&mut self, file: &mut LvdFile, change: Change) -> Result<(), ChangeError> {
+        apply_changes(file, std::slice::from_ref(&change))?;
+        self.changes.push(change);
+
+        Ok(())
+    }
+}
+
+/// Applies the changes to the file in order, leaving it unchanged if any change fails.
+fn apply_changes(file: &mut LvdFile, changes: &[Change]) -> Result<(), ChangeError> {
+    let mut data = serde_json::to_value(&file.data)?;
+
+    for change in changes {
+        change.apply_to(&mut data)?;
+    }
+
+    file.data = serde_json::from_value::<Versioned<Lvd>>(data)?;
+
+    Ok(())
+}
+
+/// Returns the objects of a section in the serialized form of an LVD file's data.
+fn elements_mut(data: &mut Value, section: SectionKind) -> Result<&mut Vec<Value>, ChangeError> {
+    variant_mut(data)
+        .get_mut(section.name())
+        .map(variant_mut)
+        .and_then(|array| array.get_mut("elements"))
+        .and_then(Value::as_array_mut)
+        .ok_or(ChangeError::SectionNotFound(section))
+}
+
+/// Returns an object in the serialized form of an LVD file's data.
+fn object_mut(
+    data: &mut Value,
+    section: SectionKind,
+    index: usize,
+) -> Result<&mut Value, ChangeError> {
+    elements_mut(data, section)?
+        .get_mut(index)
+        .ok_or(ChangeError::ObjectNotFound { section, index })
+}
+
+/// Returns the field at the path relative to the serialized form of an object,
+/// looking through the versions of nested types.
+fn field_mut<'a>(object: &'a mut Value, path: &str) -> Result<&'a mut Value, ChangeError> {
+    let not_found = || ChangeError::FieldNotFound(path.to_string());
+    let mut value = object;
+
+    for segment in path.split('.') {
+        let (key, indices) = segment.split_once('[').unwrap_or((segment, ""));
+
+        value = variant_mut(value).get_mut(key).ok_or_else(not_found)?;
+
+        for index in indices.split('[').filter(|index| !index.is_empty()) {
+            let index = index
+                .strip_suffix(']')
+                .and_then(|index| index.parse::<usize>().ok())
+                .ok_or_else(not_found)?;
+            let elements = match variant_mut(value) {
+                Value::Array(elements) => elements,
+                value => value
+                    .get_mut("elements")
+                    .and_then(Value::as_array_mut)
+                    .ok_or_else(not_found)?,
+            };
+
+            value = elements.get_mut(index).ok_or_else(not_found)?;
+        }
+    }
+
+    Ok(value)
+}
+
+/// Returns the value wrapped by a versioned type's variant, or the value itself if it is not wrapped.
+fn variant(value: &Value) -> &Value {
+    if is_variant(value) {
+        return value.as_object().unwrap().values().next().unwrap();
+    }
+
+    value
+}
+
+/// Returns the value wrapped by a versioned type's variant, or the value itself if it is not wrapped.
+fn variant_mut(value: &mut Value) -> &mut Value {
+    if is_variant(value) {
+        return value.as_object_mut().unwrap().values_mut().next().unwrap();
+    }
+
+    value
+}
+
+/// Returns `true` if the value is a single variant of a versioned type, such as `{"V1": {...}}`.
+fn is_variant(value: &Value) -> bool {
+    match value.as_object() {
+        Some(map) if map.len() == 1 => map.keys().all(|key| {
+            key.strip_prefix('V')
+                .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        }),
+        _ => false,
+    }
+}
+
+/// Returns `true` if the values are the same variant of a versioned type, or have the same fields.
+fn same_variant(old: &Value, new: &Value) -> bool {
+    match (old.as_object(), new.as_object()) {
+        (Some(old), Some(new)) => old.keys().eq(new.keys()),
+        _ => true,
+    }
+}
+
+/// Calls `record` with the path and values of each field which differs between two values,
+/// looking through the versions of nested types.
+///
+/// Fields holding different variants or sequences of different lengths are recorded as a whole.
+fn diff_fields(
+    old: &Value,
+    new: &Value,
+    path: &str,
+    record: &mut dyn FnMut(String, &Value, &Value),
+) {
+    if old == new {
+        return;
+    }
+
+    if same_variant(old, new) {
+        match (variant(old), variant(new)) {
+            (Value::Array(old_elements), Value::Array(new_elements))
+                if old_elements.len() == new_elements.len() =>
+            {
+                for (index, (old, new)) in old_elements.iter().zip(new_elements).enumerate() {
+                    diff_fields(old, new, &format!("{path}[{index}]"), record);
+                }
+
+                return;
+            }
+            // Arrays of different lengths are recorded as a whole, including their version.
+            (Value::Object(old_fields), Value::Object(new_fields))
+                if old_fields.keys().eq(new_fields.keys())
+                    && elements_len(old_fields) == elements_len(new_fields) =>
+            {
+                for (key, old) in old_fields {
+                    // The elements of an array are addressed by index alone.
+                    let path = match (path, key.as_str()) {
+                        (path, "elements") if old_fields.len() == 1 => path.to_string(),
+                        ("", key) => key.to_string(),
+                        (path, key) => format!("{path}.{key}"),
+                    };
+
+                    diff_fields(old, &new_fields[key], &path, record);
+                }
+
+                return;
+            }
+            _ => (),
+        }
+    }
+
+    record(path.to_string(), old, new);
+}
+
+/// Returns the number of elements in the serialized form of an [`Array`](crate::array::Array),
+/// or `None` if the fields are not an array's.
+fn elements_len(fields: &Map<String, Value>) -> Option<usize> {
+    match fields.len() {
+        1 => fields
+            .get("elements")
+            .and_then(Value::as_array)
+            .map(Vec::len),
+        _ => None,
+    }
+}
+
+/// The error type used when applying a [`Change`].
+#[derive(Debug, Error)]
+pub enum ChangeError {
+    /// The section is not supported by the file's version.
+    #[error("section `{}` is not supported by the file's version", .0.name())]
+    SectionNotFound(SectionKind),
+
+    /// No object exists at the given index in the section.
+    #[error("no object exists at index {index} in section `{}`", .section.name())]
+    ObjectNotFound {
+        /// The section searched for the object.
+        section: SectionKind,
+
+        /// The index of the object.
+        index: usize,
+    },
+
+    /// The field does not exist on the object.
+    #[error("field `{0}` does not exist")]
+    FieldNotFound(String),
+
+    /// The changed data could not be converted.
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        fixtures::platform_file,
+        transform::Transform,
+        vector::{Vector2, Vector3},
+    };
+
+    #[test]
+    fn record_replay_and_undo() {
+        let original = platform_file();
+        let mut file = original.clone();
+        let mut changes = ChangeSet::new();
+        let collision =
+            serde_json::to_value(&file.data.collisions().unwrap().elements()[0]).unwrap();
+
+        changes
+            .set_field(
+                &mut file,
+                SectionKind::Collisions,
+                0,
+                "vertices[1].x",
+                20.0.into(),
+            )
+            .unwrap();
+        changes
+            .add_object(&mut file, SectionKind::Collisions, 1, collision)
+            .unwrap();
+        changes
+            .transform(
+                &mut file,
+                &Transform::from_translation(Vector3::new(0.0, 5.0, 0.0)),
+            )
+            .unwrap();
+        changes
+            .remove_object(&mut file, SectionKind::Collisions, 1)
+            .unwrap();
+
+        assert!(changes
+            .set_field(
+                &mut file,
+                SectionKind::Collisions,
+                0,
+                "flags.missing",
+                true.into()
+            )
+            .is_err());
+        assert_eq!(changes.changes.len(), 5);
+        assert_eq!(
+            file.data.collisions().unwrap().elements()[0]
+                .vertices()
+                .iter()
+                .copied()
+                .collect::<Vec<_>>(),
+            [Vector2::new(-10.0, 5.0), Vector2::new(20.0, 5.0)]
+        );
+
+        let script = serde_json::to_string(&changes).unwrap();
+        let mut replayed = original.clone();
+
+        serde_json::from_str::<ChangeSet>(&script)
+            .unwrap()
+            .apply(&mut replayed)
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&replayed.data).unwrap(),
+            serde_json::to_value(&file.data).unwrap()
+        );
+
+        changes.undo(&mut file).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&file.data).unwrap(),
+            serde_json::to_value(&original.data).unwrap()
+        );
+    }
+
+    #[test]
+    fn diff_and_apply() {
+        let original = platform_file();
+        let mut file = original.clone();
+        let collisions = file.data.collisions_mut().unwrap();
+        let mut collision = collisions.elements()[0].inner.clone();
+
+        collision.vertices_mut().push(Vector2::new(20.0, 0.0));
+        collisions.push(collision);
+        collisions.elements_mut()[0].vertices_mut().elements_mut()[1] =
+            Versioned::new(Vector2::new(20.0, 0.0));
+
+        let changes = ChangeSet::diff(&original, &file).unwrap();
+
+        assert_eq!(changes.changes.len(), 2);
+        assert!(matches!(
+            &changes.changes[0],
+            Change::SetField { path, new, .. } if path == "vertices[1].x" && *new == 20.0
+        ));
+        assert!(matches!(
+            changes.changes[1],
+            Change::AddObject { index: 1, .. }
+        ));
+
+        let mut replayed = original.clone();
+
+        changes.apply(&mut replayed).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&replayed.data).unwrap(),
+            serde_json::to_value(&file.data).unwrap()
+        );
+
+        changes.undo(&mut replayed).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&replayed.data).unwrap(),
+            serde_json::to_value(&original.data).unwrap()
+        );
+    }
+
+    #[test]
+    fn failed_changes_are_not_recorded() {
+        let original = platform_file();
+        let mut file = original.clone();
+        let mut changes = ChangeSet::new();
+
+        assert!(matches!(changes.undo_last(&mut file), Ok(None)));
+        assert!(matches!(
+            changes.remove_object(&mut file, SectionKind::Collisions, 1),
+            Err(ChangeError::ObjectNotFound { index: 1, .. })
+        ));
+        assert!(matches!(
+            changes.set_field(
+                &mut file,
+                SectionKind::Collisions,
+                0,
+                "vertices[2].x",
+                1.0.into()
+            ),
+            Err(ChangeError::FieldNotFound(path)) if path == "vertices[2].x"
+        ));
+        assert!(matches!(
+            changes.add_object(&mut file, SectionKind::Collisions, 5, Value::Null),
+            Err(ChangeError::ObjectNotFound { index: 5, .. })
+        ));
+        assert!(changes.changes.is_empty());
+        assert_eq!(
+            serde_json::to_value(&file.data).unwrap(),
+            serde_json::to_value(&original.data).unwrap()
+        );
+    }
+}
@@ -0,0 +1,406 @@
+//! Conversion of the positions of point-like sections to and from CSV.
+//!
+//! Sections of objects which are little more than a named position, such as spawn points, can
+//! be written as CSV with a `name,x,y` or `name,x,y,z` header for bulk editing in a spreadsheet.
+//! This module contains the [`CsvPoint`] trait for those object types, the [`Array::to_csv`]
+//! and [`Array::from_csv`] methods, the [`Lvd::section_to_csv`] and [`Lvd::section_from_csv`]
+//! methods, and an error type that may result when reading CSV.
+
+use std::mem;
+
+use thiserror::Error;
+
+use crate::{
+    array::Array,
+    objects::{FsStartPoint, LvdObject, PTrainerFloatingFloor, Point},
+    string::FixedString56,
+    vector::{Vector2, Vector3},
+    version::{Version, Versioned},
+    Lvd, SectionKind,
+};
+
+/// The sections which can be converted to and from CSV.
+pub const SECTIONS: &[SectionKind] = &[
+    SectionKind::StartPositions,
+    SectionKind::RestartPositions,
+    SectionKind::FsStartPoints,
+    SectionKind::PTrainerFloatingFloors,
+];
+
+/// A trait for object types whose position can be converted to and from CSV.
+pub trait CsvPoint: LvdObject + Version + Default {
+    /// The names of the columns of the position, such as `["x", "y"]`.
+    const COORDINATES: &'static [&'static str];
+
+    /// Returns the coordinates of the position, in the order of [`COORDINATES`](Self::COORDINATES).
+    fn coordinates(&self) -> Vec<f32>;
+
+    /// Sets the position from coordinates in the order of [`COORDINATES`](Self::COORDINATES).
+    fn set_coordinates(&mut self, coordinates: &[f32]);
+}
+
+impl CsvPoint for Point {
+    const COORDINATES: &'static [&'static str] = &["x", "y"];
+
+    fn coordinates(&self) -> Vec<f32> {
+        vec![self.pos().x(), self.pos().y()]
+    }
+
+    fn set_coordinates(&mut self, coordinates: &[f32]) {
+        *self.pos_mut() = Vector2::new(coordinates[0], coordinates[1]);
+    }
+}
+
+impl CsvPoint for FsStartPoint {
+    const COORDINATES: &'static [&'static str] = &["x", "y"];
+
+    fn coordinates(&self) -> Vec<f32> {
+        vec![self.pos().x(), self.pos().y()]
+    }
+
+    fn set_coordinates(&mut self, coordinates: &[f32]) {
+        *self.pos_mut() = Vector2::new(coordinates[0], coordinates[1]);
+    }
+}
+
+impl CsvPoint for PTrainerFloatingFloor {
+    const COORDINATES: &'static [&'static str] = &["x", "y", "z"];
+
+    fn coordinates(&self) -> Vec<f32> {
+        vec![self.pos().x(), self.pos().y(), self.pos().z()]
+    }
+
+    fn set_coordinates(&mut self, coordinates: &[f32]) {
+        *self.pos_mut() = Vector3::new(coordinates[0], coordinates[1], coordinates[2]);
+    }
+}
+
+impl<T: CsvPoint + 'static> Array<T> {
+    /// Returns the name and position of each object as CSV, with a header row.
+    ///
+    /// Names which are not valid UTF-8 are written as empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{array::Array, objects::Point};
+    ///
+    /// let points = Array::new(vec![Point::default()]);
+    ///
+    /// assert_eq!(points.to_csv(), "name,x,y\n,0,0\n");
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let mut csv = header::<T>() + "\n";
+
+        for object in self.iter() {
+            let name = object.name().to_string_lossy();
+            let mut fields = vec![quote(&name)];
+
+            fields.extend(object.coordinates().iter().map(f32::to_string));
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    /// Replaces the objects with one for each row of CSV written by [`Array::to_csv`],
+    /// in the order of the rows.
+    ///
+    /// Each row takes the first existing object with the same name not taken by an earlier row,
+    /// keeping every field other than its position, or a new object with default values if
+    /// there is none. Objects without a row are removed. Blank lines are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the header does not match the object type, a row has the wrong
+    /// number of fields, a coordinate is not a number, or a name exceeds its capacity,
+    /// in which case the objects are left unchanged.
+    pub fn from_csv(&mut self, csv: &str) -> Result<(), CsvError> {
+        let mut lines = csv
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line))
+            .filter(|(_, line)| !line.trim().is_empty());
+        let expected = header::<T>();
+        let found = match lines.next() {
+            Some((line, text)) => split(line, text)?.join(","),
+            None => String::new(),
+        };
+
+        if found != expected {
+            return Err(CsvError::Header { expected, found });
+        }
+
+        let rows = lines
+            .map(|(line, text)| row::<T>(line, text))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut objects = mem::take(self.elements_mut())
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+
+        for (name, coordinates) in rows {
+            let existing = objects
+                .iter_mut()
+                .find(|object| object.as_ref().is_some_and(|object| *object.name() == name))
+                .and_then(Option::take);
+            let mut object = existing.unwrap_or_else(|| {
+                let mut object = T::default();
+
+                *object.meta_info_mut().name_mut() = name;
+
+                Versioned::new(object)
+            });
+
+            object.set_coordinates(&coordinates);
+            self.elements_mut().push(object);
+        }
+
+        Ok(())
+    }
+}
+
+impl Lvd {
+    /// Returns the name and position of each object in the section as CSV, with a header row.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the section is not one of [`SECTIONS`], or is not supported by the
+    /// file's version.
+    pub fn section_to_csv(&self, kind: SectionKind) -> Result<String, CsvError> {
+        let csv = match kind {
+            SectionKind::StartPositions => self.start_positions().map(Array::to_csv),
+            SectionKind::RestartPositions => self.restart_positions().map(Array::to_csv),
+            SectionKind::FsStartPoints => self.fs_start_points().map(Array::to_csv),
+            SectionKind::PTrainerFloatingFloors => {
+                self.ptrainer_floating_floors().map(Array::to_csv)
+            }
+            _ => return Err(CsvError::UnsupportedSection(kind)),
+        };
+
+        csv.ok_or(CsvError::MissingSection(kind))
+    }
+
+    /// Replaces the objects in the section with one for each row of CSV, as with
+    /// [`Array::from_csv`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the section is not one of [`SECTIONS`], is not supported by the
+    /// file's version, or the CSV cannot be read, in which case the section is left unchanged.
+    pub fn section_from_csv(&mut self, kind: SectionKind, csv: &str) -> Result<(), CsvError> {
+        let result = match kind {
+            SectionKind::StartPositions => self.start_positions_mut().map(|s| s.from_csv(csv)),
+            SectionKind::RestartPositions => self.restart_positions_mut().map(|s| s.from_csv(csv)),
+            SectionKind::FsStartPoints => self.fs_start_points_mut().map(|s| s.from_csv(csv)),
+            SectionKind::PTrainerFloatingFloors => {
+                self.ptrainer_floating_floors_mut().map(|s| s.from_csv(csv))
+            }
+            _ => return Err(CsvError::UnsupportedSection(kind)),
+        };
+
+        result.unwrap_or(Err(CsvError::MissingSection(kind)))
+    }
+}
+
+/// The error type used when converting a section to or from CSV.
+#[derive(Debug, PartialEq, Error)]
+pub enum CsvError {
+    /// The section does not store objects which can be converted to CSV.
+    #[error("section `{}` cannot be converted to CSV", .0.name())]
+    UnsupportedSection(SectionKind),
+
+    /// The section is not supported by the file's version.
+    #[error("section `{}` is not supported by the file's version", .0.name())]
+    MissingSection(SectionKind),
+
+    /// The header row does not name the columns of the section.
+    #[error("expected header `{expected}`, found `{found}`")]
+    Header {
+        /// The expected header row.
+        expected: String,
+
+        /// The header row found.
+        found: String,
+    },
+
+    /// A row has the wrong number of fields.
+    #[error("line {line}: expected {expected} fields, found {found}")]
+    FieldCount {
+        /// The line number of the row, starting from one.
+        line: usize,
+
+        /// The number of columns in the header.
+        expected: usize,
+
+        /// The number of fields in the row.
+        found: usize,
+    },
+
+    /// A coordinate is not a number.
+    #[error("line {line}: `{value}` is not a number")]
+    InvalidNumber {
+        /// The line number of the row, starting from one.
+        line: usize,
+
+        /// The text of the coordinate.
+        value: String,
+    },
+
+    /// A name exceeds the capacity of its buffer.
+    #[error(
+        "line {line}: name `{name}` exceeds buffer capacity of {} bytes",
+        FixedString56::CAPACITY
+    )]
+    NameTooLong {
+        /// The line number of the row, starting from one.
+        line: usize,
+
+        /// The name.
+        name: String,
+    },
+
+    /// A quoted field is missing its closing quote.
+    #[error("line {line}: quoted field is missing its closing quote")]
+    UnterminatedQuote {
+        /// The line number of the row, starting from one.
+        line: usize,
+    },
+}
+
+/// Returns the header row for an object type.
+fn header<T: CsvPoint>() -> String {
+    ["name"]
+        .iter()
+        .chain(T::COORDINATES)
+        .copied()
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Returns the field quoted if it contains a comma, quote, or surrounding whitespace.
+fn quote(field: &str) -> String {
+    if field.contains([',', '"']) || field.trim() != field {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Splits a line into its fields, removing the quotes from quoted fields.
+fn split(line: usize, text: &str) -> Result<Vec<String>, CsvError> {
+    let mut fields = vec![String::new()];
+    let mut chars = text.chars().peekable();
+    let mut quoted = false;
+
+    while let Some(c) = chars.next() {
+        let field = fields.last_mut().unwrap();
+
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(String::new()),
+            _ => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(CsvError::UnterminatedQuote { line });
+    }
+
+    Ok(fields)
+}
+
+/// Returns the name and coordinates of a row.
+fn row<T: CsvPoint>(line: usize, text: &str) -> Result<(FixedString56, Vec<f32>), CsvError> {
+    let fields = split(line, text)?;
+    let expected = 1 + T::COORDINATES.len();
+
+    if fields.len() != expected {
+        return Err(CsvError::FieldCount {
+            line,
+            expected,
+            found: fields.len(),
+        });
+    }
+
+    let name = fields[0]
+        .as_str()
+        .try_into()
+        .map_err(|_| CsvError::NameTooLong {
+            line,
+            name: fields[0].clone(),
+        })?;
+    let coordinates = fields[1..]
+        .iter()
+        .map(|field| {
+            field.trim().parse().map_err(|_| CsvError::InvalidNumber {
+                line,
+                value: field.clone(),
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((name, coordinates))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_round_trip_matches_by_name() {
+        let mut points = Array::new(Vec::new());
+
+        for (name, x) in [("START_00", -10.0), ("START, \"01\"", 10.0)] {
+            let mut point = Point::default();
+
+            *point.meta_info_mut().name_mut() = name.try_into().unwrap();
+            *point.pos_mut() = Vector2::new(x, 5.5);
+            points.push(point);
+        }
+
+        let csv = points.to_csv();
+
+        assert_eq!(
+            csv,
+            "name,x,y\nSTART_00,-10,5.5\n\"START, \"\"01\"\"\",10,5.5\n"
+        );
+
+        points
+            .from_csv("name,x,y\r\n\"START, \"\"01\"\"\",1,2\r\nSTART_02,3,4\r\n\r\n")
+            .unwrap();
+
+        let rows = points
+            .iter()
+            .map(|point| (point.name().to_string().unwrap(), point.pos()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            rows,
+            [
+                ("START, \"01\"".to_string(), Vector2::new(1.0, 2.0)),
+                ("START_02".to_string(), Vector2::new(3.0, 4.0)),
+            ]
+        );
+        assert_eq!(
+            points.from_csv("name,x,y,z\n"),
+            Err(CsvError::Header {
+                expected: "name,x,y".to_string(),
+                found: "name,x,y,z".to_string(),
+            })
+        );
+        assert_eq!(
+            points.from_csv("name,x,y\nSTART_00,1,up\n"),
+            Err(CsvError::InvalidNumber {
+                line: 2,
+                value: "up".to_string(),
+            })
+        );
+        assert_eq!(points.len(), 2);
+    }
+}
@@ -0,0 +1,382 @@
+//! Duplication of LVD objects.
+//!
+//! This module contains the [`LvdFile::duplicate_object`] method, the [`DuplicateOptions`] type,
+//! and an error type that may result when duplicating an object.
+
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::{
+    id::{Id, IdAllocator},
+    string::FixedString56,
+    tag::Tag,
+    LvdFile, ObjectRef, SectionKind,
+};
+
+/// The options for duplicating an LVD object.
+#[derive(Debug, Clone)]
+pub struct DuplicateOptions {
+    /// Determines if a numbered suffix is appended to the name of the copy.
+    pub rename: bool,
+
+    /// The separator inserted between the name and the numbered suffix.
+    pub separator: String,
+
+    /// Determines if the copy is assigned the next unused tag with the same letters.
+    pub allocate_tag: bool,
+
+    /// Determines if the copy is assigned the next unused numeric identifier
+    /// when the object belongs to a section with unique identifiers.
+    pub allocate_id: bool,
+}
+
+impl Default for DuplicateOptions {
+    fn default() -> Self {
+        Self {
+            rename: true,
+            separator: "_".to_string(),
+            allocate_tag: true,
+            allocate_id: true,
+        }
+    }
+}
+
+impl LvdFile {
+    /// Appends a deep copy of the referenced object to the end of its section.
+    ///
+    /// Depending on the given options, the copy is renamed with a numbered suffix
+    /// unused by the other objects in the section, and assigned a fresh tag and identifier.
+    /// Identifiers are handed out by an [`IdAllocator`].
+    /// The file is left unchanged if an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{duplicate::DuplicateOptions, LvdFile, ObjectRef, SectionKind};
+    ///
+    /// let mut file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    /// let object = ObjectRef::new(SectionKind::Collisions, 0);
+    /// let copy = file
+    ///     .duplicate_object(object, &DuplicateOptions::default())
+    ///     .unwrap();
+    /// ```
+    pub fn duplicate_object(
+        &mut self,
+        object: ObjectRef,
+        options: &DuplicateOptions,
+    ) -> Result<ObjectRef, DuplicateError> {
+        let ObjectRef { section, index } = object;
+        let original = self
+            .data
+            .object(section, index)
+            .ok_or(DuplicateError::ObjectNotFound(object))?;
+
+        let name = if options.rename {
+            Some(self.unused_name(section, original.name(), &options.separator)?)
+        } else {
+            None
+        };
+
+        let tag = match original.tag() {
+            Some(tag) if options.allocate_tag => {
+                let number = self
+                    .tags()
+                    .into_iter()
+                    .filter(|t| t.with_number(0) == tag.with_number(0))
+                    .map(|t| t.number())
+                    .max()
+                    .unwrap_or_default();
+
+                Some(
+                    tag.with_number(number + 1)
+                        .ok_or(DuplicateError::TagsExhausted(*tag))?,
+                )
+            }
+            _ => None,
+        };
+
+        let id = if options.allocate_id && IdAllocator::SECTIONS.contains(&section) {
+            Some(
+                IdAllocator::new(&self.data)
+                    .allocate(section)
+                    .ok_or(DuplicateError::IdsExhausted(section))?,
+            )
+        } else {
+            None
+        };
+
+        let index = self
+            .data
+            .clone_object(section, index)
+            .ok_or(DuplicateError::ObjectNotFound(object))?;
+        let copy = self.data.object_mut(section, index).unwrap();
+
+        if let Some(name) = name {
+            *copy.meta_info_mut().name_mut() = name;
+        }
+
+        if let (Some(tag), Some(copy_tag)) = (tag, copy.tag_mut()) {
+            *copy_tag = tag;
+        }
+
+        if let Some(id) = id {
+            self.set_id(section, index, id);
+        }
+
+        Ok(ObjectRef::new(section, index))
+    }
+
+    /// Returns the name formed by appending the smallest numbered suffix unused within the section.
+    fn unused_name(
+        &self,
+        section: SectionKind,
+        name: &FixedString56,
+        separator: &str,
+    ) -> Result<FixedString56, DuplicateError> {
+        let name = name
+            .to_str()
+            .map_err(|_| DuplicateError::InvalidName)?
+            .to_string();
+        let mut names = Vec::new();
+
+        for index in 0..self.data.section_len(section).unwrap_or_default() {
+            if let Some(object) = self.data.object(section, index) {
+                names.push(object.name().clone());
+            }
+        }
+
+        (1..)
+            .map(|number| format!("{name}{separator}{number}"))
+            .map(|name| {
+                FixedString56::from_str(&name).map_err(|_| DuplicateError::NameTooLong(name))
+            })
+            .find(|result| !matches!(result, Ok(name) if names.contains(name)))
+            .unwrap()
+    }
+
+    /// Returns the tags of every top-level object.
+    fn tags(&self) -> Vec<Tag> {
+        let mut tags = Vec::new();
+
+        self.data
+            .for_each_object(|object| tags.extend(object.tag().copied()));
+
+        tags
+    }
+
+    /// Assigns the numeric identifier to the object at the given index,
+    /// if the section requires unique identifiers.
+    fn set_id(&mut self, section: SectionKind, index: usize, id: Id) {
+        match section {
+            SectionKind::FsStartPoints => {
+                if let Some(points) = self.data.fs_start_points_mut() {
+                    *points.iter_mut().nth(index).unwrap().id_mut() = id;
+                }
+            }
+            SectionKind::DamageShapes => {
+                if let Some(shapes) = self.data.damage_shapes_mut() {
+                    *shapes.iter_mut().nth(index).unwrap().id_mut() = id.into();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The error type used when duplicating an LVD object.
+#[derive(Debug, PartialEq, Error)]
+pub enum DuplicateError {
+    /// The referenced object does not exist.
+    #[error("object {} in section `{}` does not exist", .0.index, .0.section.name())]
+    ObjectNotFound(ObjectRef),
+
+    /// The name of the object is not valid UTF-8.
+    #[error("object name is not valid UTF-8")]
+    InvalidName,
+
+    /// The name with the numbered suffix exceeds the buffer's capacity.
+    #[error("name `{0}` exceeds buffer capacity of {capacity} bytes", capacity = FixedString56::CAPACITY)]
+    NameTooLong(String),
+
+    /// Every number for the tag's letters is already in use.
+    #[error("no unused number remains for tag {0}")]
+    TagsExhausted(Tag),
+
+    /// Every numeric identifier of the section is already in use.
+    #[error("no unused identifier remains in section `{}`", .0.name())]
+    IdsExhausted(SectionKind),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        field_smash::start_points,
+        fixtures::platform_file,
+        objects::{DamageShape, ItemPopup, LvdObject},
+        vector::Vector2,
+    };
+
+    use super::*;
+
+    const PLATFORM: ObjectRef = ObjectRef::new(SectionKind::Collisions, 0);
+
+    fn name(file: &LvdFile, object: ObjectRef) -> String {
+        let object = file.data.object(object.section, object.index).unwrap();
+
+        object.name().to_string_lossy().into_owned()
+    }
+
+    fn set_name(file: &mut LvdFile, object: ObjectRef, name: &str) {
+        let object = file.data.object_mut(object.section, object.index).unwrap();
+
+        *object.meta_info_mut().name_mut() = name.try_into().unwrap();
+    }
+
+    #[test]
+    fn duplicate_renames_with_unused_suffix() {
+        let mut file = platform_file();
+
+        set_name(&mut file, PLATFORM, "COL_Floor");
+
+        let first = file
+            .duplicate_object(PLATFORM, &DuplicateOptions::default())
+            .unwrap();
+
+        set_name(&mut file, first, "COL_Floor_2");
+
+        let second = file
+            .duplicate_object(PLATFORM, &DuplicateOptions::default())
+            .unwrap();
+        let options = DuplicateOptions {
+            rename: false,
+            ..Default::default()
+        };
+        let third = file.duplicate_object(PLATFORM, &options).unwrap();
+
+        assert_eq!(first, ObjectRef::new(SectionKind::Collisions, 1));
+        assert_eq!(name(&file, second), "COL_Floor_1");
+        assert_eq!(name(&file, third), "COL_Floor");
+        assert_eq!(file.data.collisions().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn duplicate_allocates_tags_and_ids() {
+        let mut file = platform_file();
+        let mut popup = ItemPopup::default();
+
+        *popup.tag_mut().unwrap() = Tag::try_from("IPP0003").unwrap();
+        file.data.item_popups_mut().unwrap().push(popup);
+
+        for point in start_points([Vector2::new(0.0, 0.0)], 7) {
+            file.data.fs_start_points_mut().unwrap().push(point);
+        }
+
+        file.data
+            .damage_shapes_mut()
+            .unwrap()
+            .push(DamageShape::default());
+
+        let popup = ObjectRef::new(SectionKind::ItemPopups, 0);
+        let copy = file
+            .duplicate_object(popup, &DuplicateOptions::default())
+            .unwrap();
+        let object = |file: &LvdFile, object: ObjectRef| {
+            file.data
+                .object(object.section, object.index)
+                .and_then(|object| object.tag().copied())
+        };
+
+        assert_eq!(object(&file, copy), Tag::try_from("IPP0004").ok());
+
+        let options = DuplicateOptions {
+            allocate_tag: false,
+            ..Default::default()
+        };
+        let copy = file.duplicate_object(popup, &options).unwrap();
+
+        assert_eq!(object(&file, copy), Tag::try_from("IPP0003").ok());
+
+        file.duplicate_object(
+            ObjectRef::new(SectionKind::FsStartPoints, 0),
+            &DuplicateOptions::default(),
+        )
+        .unwrap();
+        file.duplicate_object(
+            ObjectRef::new(SectionKind::DamageShapes, 0),
+            &DuplicateOptions::default(),
+        )
+        .unwrap();
+
+        let point_ids = file
+            .data
+            .fs_start_points()
+            .unwrap()
+            .iter()
+            .map(|point| point.id().0)
+            .collect::<Vec<_>>();
+        let shape_ids = file
+            .data
+            .damage_shapes()
+            .unwrap()
+            .iter()
+            .map(DamageShape::id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(point_ids, [7, 8]);
+        assert_eq!(shape_ids, [0, 1]);
+    }
+
+    #[test]
+    fn duplicate_errors_leave_file_unchanged() {
+        let mut file = platform_file();
+
+        assert_eq!(
+            file.duplicate_object(
+                ObjectRef::new(SectionKind::Collisions, 1),
+                &DuplicateOptions::default()
+            ),
+            Err(DuplicateError::ObjectNotFound(ObjectRef::new(
+                SectionKind::Collisions,
+                1
+            )))
+        );
+
+        set_name(&mut file, PLATFORM, &"a".repeat(54));
+
+        assert_eq!(
+            file.duplicate_object(PLATFORM, &DuplicateOptions::default()),
+            Err(DuplicateError::NameTooLong(format!("{}_1", "a".repeat(54))))
+        );
+
+        let mut popup = ItemPopup::default();
+
+        *popup.tag_mut().unwrap() = Tag::try_from("IPP9999").unwrap();
+        file.data.item_popups_mut().unwrap().push(popup);
+
+        for mut point in start_points([Vector2::new(0.0, 0.0)], 1) {
+            *point.id_mut() = Id(u32::MAX);
+            file.data.fs_start_points_mut().unwrap().push(point);
+        }
+
+        let bytes = file.to_bytes().unwrap();
+
+        assert_eq!(
+            file.duplicate_object(
+                ObjectRef::new(SectionKind::ItemPopups, 0),
+                &DuplicateOptions::default()
+            ),
+            Err(DuplicateError::TagsExhausted(
+                Tag::try_from("IPP9999").unwrap()
+            ))
+        );
+        assert_eq!(
+            file.duplicate_object(
+                ObjectRef::new(SectionKind::FsStartPoints, 0),
+                &DuplicateOptions::default()
+            ),
+            Err(DuplicateError::IdsExhausted(SectionKind::FsStartPoints))
+        );
+        assert_eq!(file.to_bytes().unwrap(), bytes);
+    }
+}
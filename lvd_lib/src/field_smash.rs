@@ -0,0 +1,126 @@
+//! Helpers for laying out the Smash Run objects of a stage.
+//!
+//! The objects in [`objects::field_smash`](crate::objects::field_smash) mirror the file format
+//! one to one, which makes placing many of them by hand tedious. This module contains functions
+//! for building common arrangements of them: grids of [`FsItem`]s, numbered [`FsStartPoint`]s,
+//! and [`FsAreaLock`]s derived from a single rectangle.
+
+use crate::{
+    id::Id,
+    objects::{
+        base::Base,
+        field_smash::{FsAreaLock, FsItem, FsStartPoint},
+    },
+    shape::{Rect, Shape2},
+    tag::Tag,
+    vector::Vector2,
+    version::Versioned,
+};
+
+/// Returns items with point shapes at the centers of the cells of a grid dividing `area`
+/// into `columns` columns and `rows` rows, all with the identifier `tag`.
+///
+/// The items are ordered by row from top to bottom, then by column from left to right.
+///
+/// # Examples
+///
+/// ```
+/// use lvd_lib::{field_smash::item_grid, shape::Rect, tag::Tag};
+///
+/// let items = item_grid(Rect::new(0.0, 40.0, 20.0, 0.0), 4, 2, Tag::try_from("FSI0000").unwrap());
+///
+/// assert_eq!(items.len(), 8);
+/// ```
+pub fn item_grid(area: Rect, columns: usize, rows: usize, tag: Tag) -> Vec<FsItem> {
+    let cell_width = area.width() / columns as f32;
+    let cell_height = area.height() / rows as f32;
+
+    (0..rows)
+        .flat_map(|row| (0..columns).map(move |column| (row, column)))
+        .map(|(row, column)| FsItem::V1 {
+            base: Versioned::new(Base::default()),
+            shape: Versioned::new(Shape2::point(
+                area.left() + cell_width * (column as f32 + 0.5),
+                area.top() - cell_height * (row as f32 + 0.5),
+            )),
+            tag: Versioned::new(tag),
+        })
+        .collect()
+}
+
+/// Returns start points at the given positions with sequential identifiers beginning at
+/// `first_id`.
+///
+/// # Examples
+///
+/// ```
+/// use lvd_lib::{field_smash::start_points, vector::Vector2};
+///
+/// let points = start_points([Vector2::new(-20.0, 0.0), Vector2::new(20.0, 0.0)], 1);
+///
+/// assert_eq!(points[1].id().0, 2);
+/// ```
+pub fn start_points<I: IntoIterator<Item = Vector2>>(
+    positions: I,
+    first_id: u32,
+) -> Vec<FsStartPoint> {
+    positions
+        .into_iter()
+        .zip(first_id..)
+        .map(|(pos, id)| FsStartPoint::V1 {
+            base: Versioned::new(Base::default()),
+            pos: Versioned::new(pos),
+            id: Versioned::new(Id(id)),
+        })
+        .collect()
+}
+
+/// Returns an area lock whose camera region is `area` with each edge moved outwards by
+/// `camera_margin`, and whose trigger region is `area` with each edge moved inwards by
+/// `trigger_margin`.
+///
+/// Keeping the trigger region within the camera region ensures fighters are fully inside
+/// the area before the camera is restricted to it.
+///
+/// # Examples
+///
+/// ```
+/// use lvd_lib::{field_smash::area_lock, shape::Rect};
+///
+/// let area_lock = area_lock(Rect::new(-100.0, 100.0, 80.0, -20.0), 20.0, 10.0);
+///
+/// assert!(area_lock.camera_region().contains_rect(area_lock.trigger_region()));
+/// ```
+pub fn area_lock(area: Rect, camera_margin: f32, trigger_margin: f32) -> FsAreaLock {
+    FsAreaLock::V2 {
+        base: Versioned::new(Base::default()),
+        camera_region: Versioned::new(area.expand(camera_margin)),
+        trigger_region: Versioned::new(area.expand(-trigger_margin)),
+        unk1: 0,
+        unk2: Versioned::new(Vector2::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn item_grid_cell_centers() {
+        let items = item_grid(Rect::new(0.0, 40.0, 20.0, 0.0), 2, 2, Tag::default());
+        let centers = items
+            .iter()
+            .map(|item| match item {
+                FsItem::V1 { shape, .. } => match **shape {
+                    Shape2::Point { pos_x, pos_y, .. } => (pos_x, pos_y),
+                    _ => unreachable!(),
+                },
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            centers,
+            [(10.0, 15.0), (30.0, 15.0), (10.0, 5.0), (30.0, 5.0)]
+        );
+    }
+}
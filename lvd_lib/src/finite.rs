@@ -0,0 +1,468 @@
+//! Detection and handling of NaN and infinite values.
+//!
+//! Values which are not finite, such as `.nan` and `.inf` written in YAML files, are read and
+//! written unchanged but can crash the game. This module contains the
+//! [`LvdFile::non_finite_objects`] and [`LvdFile::clamp_non_finite`] methods, the
+//! [`LvdFile::write_checked`] and [`LvdFile::write_to_file_checked`] methods applying a
+//! [`NonFinitePolicy`] when writing, and the [`ClampNonFinite`] coordinate mapping for applying
+//! the same replacement to individual objects.
+//!
+//! Only the values an object exposes to a [`CoordinateMap`] are checked: the positions and
+//! normals of its vertices and shapes, the corners of its regions, and the sizes of its shapes.
+//! Other floating point fields, such as the `lr` value of cliffs, the unknown values of spirits
+//! floors, and every value of the objects exclusive to Smash Run, are read and written unchanged
+//! by every policy.
+
+use std::{
+    cell::Cell,
+    fs,
+    io::{Cursor, Seek, Write},
+    path::Path,
+};
+
+use thiserror::Error;
+
+use crate::{
+    transform::CoordinateMap,
+    vector::{Vector2, Vector3},
+    LvdFile, ObjectRef, SectionKind,
+};
+
+/// The handling of NaN and infinite values when writing a file with
+/// [`LvdFile::write_checked`].
+///
+/// Every policy applies to the positions, normals, and sizes of objects only, as described in
+/// the [module documentation](self).
+#[derive(Debug, Default, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum NonFinitePolicy {
+    /// Fail without writing if any object contains a value which is not finite.
+    #[default]
+    Reject,
+
+    /// Write every value which is not finite as zero.
+    ClampToZero,
+
+    /// Write every value unchanged.
+    Allow,
+}
+
+impl NonFinitePolicy {
+    /// Every policy.
+    pub const ALL: &'static [Self] = &[Self::Reject, Self::ClampToZero, Self::Allow];
+
+    /// Returns the name of the policy, such as `clamp-to-zero`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Reject => "reject",
+            Self::ClampToZero => "clamp-to-zero",
+            Self::Allow => "allow",
+        }
+    }
+
+    /// Returns the policy with the given name, or `None` if no policy has that name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::finite::NonFinitePolicy;
+    ///
+    /// assert_eq!(NonFinitePolicy::from_name("allow"), Some(NonFinitePolicy::Allow));
+    /// assert_eq!(NonFinitePolicy::from_name("ignore"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|policy| policy.name() == name)
+    }
+}
+
+/// The error type used when writing a file with [`LvdFile::write_checked`].
+#[derive(Debug, Error)]
+pub enum CheckedWriteError {
+    /// An object contains values which are not finite and the policy rejects them.
+    #[error(
+        "object {} in section `{}` contains {count} NaN or infinite values",
+        object.index,
+        object.section.name()
+    )]
+    NonFinite {
+        /// The first object containing such values.
+        object: ObjectRef,
+
+        /// The number of such values in the object.
+        count: usize,
+    },
+
+    /// An object contains a shape of an unknown type whose type value belongs to a known
+    /// shape type, which would be read back as a different shape.
+    #[error(
+        "object {} (`{name}`) in section `{}` contains a shape with unsupported type {ty}",
+        object.index,
+        object.section.name()
+    )]
+    UnsupportedVariant {
+        /// The first object containing such a shape.
+        object: ObjectRef,
+
+        /// The name of the object.
+        name: String,
+
+        /// The type value of the shape.
+        ty: u32,
+    },
+
+    /// The file could not be written.
+    #[error(transparent)]
+    Write(#[from] binrw::Error),
+}
+
+/// A coordinate mapping which replaces every value which is not finite with zero,
+/// leaving every other value unchanged.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ClampNonFinite;
+
+impl ClampNonFinite {
+    /// Returns the value, or zero if it is not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::finite::ClampNonFinite;
+    ///
+    /// assert_eq!(ClampNonFinite::clamp(f32::NAN), 0.0);
+    /// assert_eq!(ClampNonFinite::clamp(-2.5), -2.5);
+    /// ```
+    pub fn clamp(value: f32) -> f32 {
+        if value.is_finite() {
+            value
+        } else {
+            0.0
+        }
+    }
+}
+
+impl CoordinateMap for ClampNonFinite {
+    fn map_point2(&self, point: Vector2) -> Vector2 {
+        Vector2::new(Self::clamp(point.x()), Self::clamp(point.y()))
+    }
+
+    fn map_point3(&self, point: Vector3) -> Vector3 {
+        Vector3::new(
+            Self::clamp(point.x()),
+            Self::clamp(point.y()),
+            Self::clamp(point.z()),
+        )
+    }
+
+    fn map_vector3(&self, vector: Vector3) -> Vector3 {
+        self.map_point3(vector)
+    }
+
+    fn map_normal2(&self, normal: Vector2) -> Vector2 {
+        self.map_point2(normal)
+    }
+
+    fn map_length2(&self, length: f32) -> f32 {
+        Self::clamp(length)
+    }
+
+    fn map_length3(&self, length: f32) -> f32 {
+        Self::clamp(length)
+    }
+}
+
+/// A coordinate mapping which counts the values which are not finite,
+/// leaving every value unchanged.
+#[derive(Default)]
+struct CountNonFinite(Cell<usize>);
+
+impl CountNonFinite {
+    /// Adds the number of values which are not finite to the count.
+    fn count(&self, values: &[f32]) {
+        let count = values.iter().filter(|value| !value.is_finite()).count();
+
+        self.0.set(self.0.get() + count);
+    }
+}
+
+impl CoordinateMap for CountNonFinite {
+    fn map_point2(&self, point: Vector2) -> Vector2 {
+        self.count(&[point.x(), point.y()]);
+
+        point
+    }
+
+    fn map_point3(&self, point: Vector3) -> Vector3 {
+        self.count(&[point.x(), point.y(), point.z()]);
+
+        point
+    }
+
+    fn map_vector3(&self, vector: Vector3) -> Vector3 {
+        self.map_point3(vector)
+    }
+
+    fn map_normal2(&self, normal: Vector2) -> Vector2 {
+        self.map_point2(normal)
+    }
+
+    fn map_length2(&self, length: f32) -> f32 {
+        self.count(&[length]);
+
+        length
+    }
+
+    fn map_length3(&self, length: f32) -> f32 {
+        self.map_length2(length)
+    }
+}
+
+impl LvdFile {
+    /// Returns each object containing NaN or infinite values in its positions, normals,
+    /// or sizes, in file order, with the number of such values.
+    ///
+    /// As with [`LvdObject::transform`](crate::objects::LvdObject::transform), objects exclusive
+    /// to Smash Run are not checked.
+    pub fn non_finite_objects(&self) -> Vec<(ObjectRef, usize)> {
+        // Objects are only reachable mutably through the coordinate mapping,
+        // so a copy is checked with a mapping which leaves it unchanged.
+        let mut copy = self.clone();
+        let mut objects = Vec::new();
+
+        for &section in SectionKind::ALL {
+            for index in 0..copy.data.section_len(section).unwrap_or_default() {
+                let counter = CountNonFinite::default();
+
+                if let Some(object) = copy.data.object_mut(section, index) {
+                    object.transform(&counter);
+                }
+
+                if counter.0.get() > 0 {
+                    objects.push((ObjectRef::new(section, index), counter.0.get()));
+                }
+            }
+        }
+
+        objects
+    }
+
+    /// Replaces every NaN or infinite value in the positions, normals, and sizes of every
+    /// object with zero.
+    ///
+    /// As with [`LvdFile::non_finite_objects`], objects exclusive to Smash Run are left unchanged.
+    pub fn clamp_non_finite(&mut self) {
+        self.data
+            .for_each_object_mut(|object| object.transform(&ClampNonFinite));
+    }
+
+    /// Returns the first object containing a shape which cannot be written, as described by
+    /// [`Shape2::unsupported_type`](crate::shape::Shape2::unsupported_type), with its name and
+    /// the type value of the shape.
+    pub fn unsupported_shape(&self) -> Option<(ObjectRef, String, u32)> {
+        SectionKind::ALL.iter().find_map(|&section| {
+            (0..self.data.section_len(section).unwrap_or_default()).find_map(|index| {
+                let object = self.data.object(section, index)?;
+                let ty = object
+                    .shapes2()
+                    .into_iter()
+                    .find_map(|shape| shape.unsupported_type())
+                    .or_else(|| {
+                        object
+                            .shapes3()
+                            .into_iter()
+                            .find_map(|shape| shape.unsupported_type())
+                    })?;
+
+                Some((
+                    ObjectRef::new(section, index),
+                    object.name().to_string_lossy().into_owned(),
+                    ty,
+                ))
+            })
+        })
+    }
+
+    /// Writes the data to the given writer, handling NaN and infinite values with the policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an object contains a shape which cannot be written, or if the
+    /// policy is [`NonFinitePolicy::Reject`] and an object contains values which are not
+    /// finite, in which case nothing is written, or if writing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io::Cursor;
+    ///
+    /// use lvd_lib::{finite::NonFinitePolicy, LvdFile};
+    ///
+    /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    /// let mut writer = Cursor::new(Vec::new());
+    ///
+    /// file.write_checked(&mut writer, NonFinitePolicy::Reject).unwrap();
+    /// ```
+    pub fn write_checked<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        policy: NonFinitePolicy,
+    ) -> Result<(), CheckedWriteError> {
+        if let Some((object, name, ty)) = self.unsupported_shape() {
+            return Err(CheckedWriteError::UnsupportedVariant { object, name, ty });
+        }
+
+        match policy {
+            NonFinitePolicy::Reject => {
+                if let Some(&(object, count)) = self.non_finite_objects().first() {
+                    return Err(CheckedWriteError::NonFinite { object, count });
+                }
+
+                self.write(writer)?;
+            }
+            NonFinitePolicy::ClampToZero => {
+                let mut file = self.clone();
+
+                file.clamp_non_finite();
+                file.write(writer)?;
+            }
+            NonFinitePolicy::Allow => self.write(writer)?,
+        }
+
+        Ok(())
+    }
+
+    /// Writes the data to the given file path, handling NaN and infinite values with the policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`LvdFile::write_checked`],
+    /// in which case the file is not created or modified.
+    pub fn write_to_file_checked<P: AsRef<Path>>(
+        &self,
+        path: P,
+        policy: NonFinitePolicy,
+    ) -> Result<(), CheckedWriteError> {
+        let mut cursor = Cursor::new(Vec::new());
+
+        self.write_checked(&mut cursor, policy)?;
+        fs::write(path, cursor.get_mut()).map_err(binrw::Error::from)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        fixtures::{file_with_collisions, platform_file},
+        objects::{base::Base, ItemPopup, LvdObject},
+        shape::{Path, Shape2, ShapeArray2},
+        tag::Tag,
+        version::Versioned,
+    };
+
+    #[test]
+    fn write_checked_applies_policy() {
+        let mut file = platform_file();
+        let edge = file.data.collisions_mut().unwrap().elements_mut()[0]
+            .edge_mut(0)
+            .unwrap();
+
+        *edge.start = Vector2::new(f32::NEG_INFINITY, 0.0);
+        *edge.end = Vector2::new(10.0, f32::NAN);
+
+        let object = ObjectRef::new(SectionKind::Collisions, 0);
+
+        assert_eq!(file.non_finite_objects(), [(object, 2)]);
+
+        let mut writer = Cursor::new(Vec::new());
+
+        assert!(matches!(
+            file.write_checked(&mut writer, NonFinitePolicy::Reject),
+            Err(CheckedWriteError::NonFinite { count: 2, .. })
+        ));
+        assert!(writer.get_ref().is_empty());
+
+        file.write_checked(&mut writer, NonFinitePolicy::ClampToZero)
+            .unwrap();
+        writer.set_position(0);
+
+        let clamped = LvdFile::read(&mut writer).unwrap();
+
+        assert!(clamped.non_finite_objects().is_empty());
+    }
+
+    #[test]
+    fn write_checked_rejects_unsupported_shapes() {
+        let mut item_popup = ItemPopup::V1 {
+            base: Versioned::new(Base::default()),
+            tag: Versioned::new(Tag::default()),
+            shapes: Versioned::new(ShapeArray2::new([Shape2::Unknown {
+                ty: 1,
+                data: [0; 4],
+                path: Versioned::new(Path::default()),
+            }])),
+        };
+
+        *item_popup.meta_info_mut().name_mut() = "ItemPopup".try_into().unwrap();
+
+        let mut file = file_with_collisions([]);
+
+        file.data.item_popups_mut().unwrap().push(item_popup);
+
+        let mut writer = Cursor::new(Vec::new());
+        let error = file
+            .write_checked(&mut writer, NonFinitePolicy::Allow)
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "object 0 (`ItemPopup`) in section `item_popups` contains a shape with unsupported type 1"
+        );
+        assert!(writer.get_ref().is_empty());
+        assert!(file.write(&mut writer).is_err());
+    }
+
+    #[test]
+    fn only_coordinates_and_sizes_are_checked() {
+        let mut file = platform_file();
+        let item_popup = ItemPopup::V1 {
+            base: Versioned::new(Base::default()),
+            tag: Versioned::new(Tag::default()),
+            shapes: Versioned::new(ShapeArray2::new([Shape2::Circle {
+                pos_x: 0.0,
+                pos_y: 0.0,
+                radius: f32::INFINITY,
+                path: Versioned::new(Path::default()),
+            }])),
+        };
+
+        *file.data.collisions_mut().unwrap().elements_mut()[0]
+            .cliffs_mut()
+            .elements_mut()[0]
+            .lr_mut() = f32::NAN;
+        file.data.item_popups_mut().unwrap().push(item_popup);
+
+        assert_eq!(
+            file.non_finite_objects(),
+            [(ObjectRef::new(SectionKind::ItemPopups, 0), 1)],
+            "the `lr` value of cliffs is not checked"
+        );
+
+        file.clamp_non_finite();
+
+        assert!(file.non_finite_objects().is_empty());
+        assert!(matches!(
+            file.data.item_popups().unwrap().elements()[0].shapes2()[..],
+            [Shape2::Circle { radius, .. }] if *radius == 0.0
+        ));
+        assert!(file.data.collisions().unwrap().elements()[0]
+            .cliffs()
+            .elements()[0]
+            .lr()
+            .is_nan());
+    }
+}
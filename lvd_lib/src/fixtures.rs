@@ -0,0 +1,26 @@
+//! Files shared by the unit tests of the crate.
+
+use crate::{objects::Collision, vector::Vector2, version::Versioned, Lvd, LvdFile};
+
+/// Returns a file of the latest version holding the given collisions and no other objects.
+pub(crate) fn file_with_collisions(collisions: impl IntoIterator<Item = Collision>) -> LvdFile {
+    let mut data = Lvd::empty(13).unwrap();
+    let section = data.collisions_mut().unwrap();
+
+    for collision in collisions {
+        section.push(collision);
+    }
+
+    LvdFile {
+        data: Versioned::new(data),
+    }
+}
+
+/// Returns a file of the latest version holding a single soft platform from `(-10, 0)` to
+/// `(10, 0)`.
+pub(crate) fn platform_file() -> LvdFile {
+    file_with_collisions([Collision::new_soft_platform(
+        Vector2::new(-10.0, 0.0),
+        Vector2::new(10.0, 0.0),
+    )])
+}
@@ -0,0 +1,326 @@
+//! Writing LVD files loadable by a particular game.
+//!
+//! Each game only loads LVD files of the file format version its own stages use, holding objects
+//! of the versions its own stages use. This module contains the [`Game`] type naming the supported
+//! games, the [`LvdFile::to_game`] and [`LvdFile::write_for_game`] methods for converting
+//! a file and its objects to a game's versions, and the [`LvdFile::to_version`] method for
+//! converting a file to any file format version.
+
+use std::io::{Seek, Write};
+
+use thiserror::Error;
+
+use crate::{
+    model::{LvdData, ModelError, ModelVersions},
+    version::Versioned,
+    LvdFile, SectionKind,
+};
+
+/// A game which loads LVD files.
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum Game {
+    /// Super Smash Bros. for Wii U.
+    Smash4Wiiu,
+
+    /// Super Smash Bros. Ultimate.
+    Ultimate,
+}
+
+impl Game {
+    /// Returns the LVD file format version used by the game's stages.
+    pub const fn version(self) -> u8 {
+        self.versions().lvd
+    }
+
+    /// Returns the versions of the file format and of each object type used by the game's stages.
+    ///
+    /// For [`Game::Smash4Wiiu`], collisions use the version preceding the spirits floors added
+    /// for Super Smash Bros. Ultimate. Every other type uses its latest version.
+    pub const fn versions(self) -> ModelVersions {
+        let latest = ModelVersions::latest();
+
+        match self {
+            Self::Smash4Wiiu => ModelVersions {
+                lvd: 11,
+                collision: 3,
+                ..latest
+            },
+            Self::Ultimate => latest,
+        }
+    }
+}
+
+/// The handling of sections with objects which the target version does not support.
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum UnsupportedSections {
+    /// Drop the sections along with their objects.
+    Strip,
+
+    /// Fail the conversion.
+    Error,
+}
+
+/// The error type used when converting an [`LvdFile`] for a [`Game`].
+#[derive(Debug, Error)]
+pub enum GameError {
+    /// A section with objects is not supported by the game's file format version.
+    #[error("section `{}` is not supported by version {version} used by {game:?}", section.name())]
+    UnsupportedSection {
+        /// The unsupported section.
+        section: SectionKind,
+
+        /// The target game.
+        game: Game,
+
+        /// The file format version used by the game.
+        version: u8,
+    },
+
+    /// The objects could not be converted to the versions used by the game.
+    #[error(transparent)]
+    Model(#[from] ModelError),
+
+    /// The file could not be written.
+    #[error(transparent)]
+    Write(#[from] binrw::Error),
+}
+
+impl LvdFile {
+    /// Returns a copy of the file converted to the file format version used by the game.
+    ///
+    /// Sections added by the game's version are left empty. Sections with objects which the
+    /// version does not support are dropped or cause an error depending on `unsupported`.
+    /// Every object is converted to the version of its type given by [`Game::versions`],
+    /// dropping the fields which that version does not have, such as the spirits floors of
+    /// collisions for [`Game::Smash4Wiiu`]. Unknown sections are only kept if the file is
+    /// already of the game's file format version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `unsupported` is [`UnsupportedSections::Error`] and a section with
+    /// objects is not supported by the game's version, or if the objects cannot be converted to
+    /// the game's versions.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{
+    ///     game::{Game, UnsupportedSections},
+    ///     LvdFile,
+    /// };
+    ///
+    /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    /// let smash4 = file.to_game(Game::Smash4Wiiu, UnsupportedSections::Strip).unwrap();
+    /// ```
+    pub fn to_game(&self, game: Game, unsupported: UnsupportedSections) -> Result<Self, GameError> {
+        let file = self
+            .to_version(game.version(), unsupported)
+            .map_err(|error| match error {
+                ModelError::UnsupportedSection { section, version } => {
+                    GameError::UnsupportedSection {
+                        section,
+                        game,
+                        version,
+                    }
+                }
+                error => GameError::Model(error),
+            })?;
+
+        Ok(LvdData::from(file.data.inner).into_file(&game.versions())?)
+    }
+
+    /// Returns a copy of the file converted to the given file format version.
+    ///
+    /// As with [`LvdFile::to_game`], sections added by the version are left empty, and sections
+    /// with objects which the version does not support are dropped or cause an error depending
+    /// on `unsupported`. Unlike [`LvdFile::to_game`], objects keep their own versions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version is not known to this library, or if `unsupported` is
+    /// [`UnsupportedSections::Error`] and a section with objects is not supported by the version.
+    pub fn to_version(
+        &self,
+        version: u8,
+        unsupported: UnsupportedSections,
+    ) -> Result<Self, ModelError> {
+        let data = self
+            .data
+            .to_version(version)
+            .ok_or(ModelError::version("Lvd", version))?;
+
+        if unsupported == UnsupportedSections::Error {
+            if let Some(&section) = SectionKind::ALL.iter().find(|&&section| {
+                !section.is_supported_by(version)
+                    && self.data.section_len(section).is_some_and(|len| len > 0)
+            }) {
+                return Err(ModelError::UnsupportedSection { section, version });
+            }
+        }
+
+        Ok(Self {
+            data: Versioned::new(data),
+        })
+    }
+
+    /// Writes the file to the given writer in the versions used by the game.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be converted for the game as described by
+    /// [`LvdFile::to_game`], or if writing fails.
+    pub fn write_for_game<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        game: Game,
+        unsupported: UnsupportedSections,
+    ) -> Result<(), GameError> {
+        let file = self.to_game(game, unsupported)?;
+
+        file.write(writer)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    use crate::{
+        fixtures::{file_with_collisions, platform_file},
+        objects::{base::MetaInfo, PTrainerRange, Point},
+        vector::Vector2,
+        version::{Version, Versioned},
+    };
+
+    #[test]
+    fn unsupported_sections() {
+        let mut file = file_with_collisions([]);
+
+        file.data
+            .ptrainer_ranges_mut()
+            .unwrap()
+            .push(PTrainerRange::default());
+
+        assert!(matches!(
+            file.to_game(Game::Smash4Wiiu, UnsupportedSections::Error),
+            Err(GameError::UnsupportedSection {
+                section: SectionKind::PTrainerRanges,
+                ..
+            })
+        ));
+
+        let file = file
+            .to_game(Game::Smash4Wiiu, UnsupportedSections::Strip)
+            .unwrap();
+
+        assert_eq!(file.data.version(), 11);
+    }
+
+    #[test]
+    fn empty_unsupported_sections_are_dropped() {
+        let file = platform_file();
+        let smash4 = file
+            .to_game(Game::Smash4Wiiu, UnsupportedSections::Error)
+            .unwrap();
+
+        assert_eq!(smash4.data.version(), 11);
+        assert_eq!(
+            smash4.data.collisions().map(|collisions| collisions.len()),
+            Some(1)
+        );
+
+        let mut writer = Cursor::new(Vec::new());
+
+        file.write_for_game(&mut writer, Game::Smash4Wiiu, UnsupportedSections::Error)
+            .unwrap();
+
+        let read = LvdFile::from_bytes(writer.get_ref()).unwrap();
+        let collision = &read.data.collisions().unwrap().elements()[0];
+
+        assert_eq!(read.data.version(), 11);
+        assert_eq!(collision.version(), 3);
+        assert!(collision.spirits_floors().is_none());
+        assert!(collision.attributes().is_some());
+        assert_eq!(
+            file.to_game(Game::Ultimate, UnsupportedSections::Error)
+                .unwrap()
+                .to_bytes()
+                .unwrap(),
+            file.to_bytes().unwrap()
+        );
+    }
+
+    #[test]
+    fn objects_converted_to_game_versions() {
+        let mut file = platform_file();
+        let old_point = Point::V1 {
+            meta_info: Versioned::new(MetaInfo::default()),
+            pos: Versioned::new(Vector2::new(0.0, 10.0)),
+        };
+
+        file.data.start_positions_mut().unwrap().push(old_point);
+        let floors = file.data.collisions_mut().unwrap().elements_mut()[0]
+            .generate_spirits_floors("main")
+            .unwrap();
+
+        assert_eq!(floors, 1);
+
+        let smash4 = file
+            .to_game(Game::Smash4Wiiu, UnsupportedSections::Error)
+            .unwrap();
+        let collision = &smash4.data.collisions().unwrap().elements()[0];
+        let point = &smash4.data.start_positions().unwrap().elements()[0];
+
+        assert_eq!(collision.version(), Game::Smash4Wiiu.versions().collision);
+        assert!(collision.spirits_floors().is_none());
+        assert_eq!(point.version(), Game::Smash4Wiiu.versions().point);
+        assert_eq!(point.pos(), Vector2::new(0.0, 10.0));
+
+        let ultimate = smash4
+            .to_game(Game::Ultimate, UnsupportedSections::Error)
+            .unwrap();
+        let collision = &ultimate.data.collisions().unwrap().elements()[0];
+
+        assert_eq!(ultimate.data.version(), 13);
+        assert_eq!(collision.version(), 4);
+        assert_eq!(
+            collision.spirits_floors().map(|floors| floors.len()),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn to_version_keeps_object_versions() {
+        let mut file = platform_file();
+
+        assert!(matches!(
+            file.to_version(99, UnsupportedSections::Strip),
+            Err(ModelError::UnsupportedVersion {
+                type_name: "Lvd",
+                version: 99
+            })
+        ));
+
+        file.data
+            .ptrainer_ranges_mut()
+            .unwrap()
+            .push(PTrainerRange::default());
+
+        assert!(matches!(
+            file.to_version(11, UnsupportedSections::Error),
+            Err(ModelError::UnsupportedSection {
+                section: SectionKind::PTrainerRanges,
+                version: 11
+            })
+        ));
+
+        let file = file.to_version(11, UnsupportedSections::Strip).unwrap();
+
+        assert_eq!(file.data.version(), 11);
+        assert_eq!(file.data.collisions().unwrap().elements()[0].version(), 4);
+    }
+}
@@ -0,0 +1,137 @@
+//! Stable hashes of the contents of LVD data.
+//!
+//! This module contains the [`ContentHash`] trait, which is implemented for
+//! [`LvdFile`](crate::LvdFile) and every object type, so build pipelines can skip unchanged
+//! files and modpack tools can find duplicate files and objects, and the [`fnv1a`] function
+//! the hashes are calculated with.
+
+use std::io::Cursor;
+
+use binrw::{BinWrite, Endian};
+
+/// A trait for calculating a hash of the contents of a value which is stable across
+/// platforms, runs, and versions of this library.
+pub trait ContentHash {
+    /// Returns the 64-bit hash of `self` as written by this library.
+    ///
+    /// Values are hashed in their big-endian binary form, regardless of the platform.
+    /// Bytes which are discarded when reading, such as those following the nul terminator
+    /// of a string, do not affect the hash, so a file read and hashed has the same hash
+    /// as the file written back out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{hash::ContentHash, vector::Vector2};
+    ///
+    /// assert_eq!(
+    ///     Vector2::new(1.0, 2.0).content_hash(),
+    ///     Vector2::new(1.0, 2.0).content_hash()
+    /// );
+    /// assert_ne!(
+    ///     Vector2::new(1.0, 2.0).content_hash(),
+    ///     Vector2::new(2.0, 1.0).content_hash()
+    /// );
+    /// ```
+    fn content_hash(&self) -> u64;
+}
+
+impl<T: for<'a> BinWrite<Args<'a> = ()>> ContentHash for T {
+    fn content_hash(&self) -> u64 {
+        let mut writer = Cursor::new(Vec::new());
+
+        self.write_options(&mut writer, Endian::Big, ())
+            .expect("writing to a buffer should not fail");
+
+        fnv1a(writer.get_ref())
+    }
+}
+
+/// Returns the 64-bit FNV-1a hash of the bytes.
+///
+/// # Examples
+///
+/// ```
+/// use lvd_lib::hash::fnv1a;
+///
+/// assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+/// assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+/// ```
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    use crate::{
+        fixtures::platform_file,
+        objects::{Collision, LvdObject},
+        vector::Vector2,
+        version::Versioned,
+        Lvd, LvdFile,
+    };
+
+    #[test]
+    fn content_hash_ignores_string_padding() {
+        let mut file = platform_file();
+
+        *file.data.collisions_mut().unwrap().elements_mut()[0]
+            .meta_info_mut()
+            .name_mut() = "COL_Floor".try_into().unwrap();
+
+        let mut bytes = file.to_bytes().unwrap();
+        let name = bytes
+            .windows(10)
+            .position(|window| window == b"COL_Floor\0")
+            .unwrap();
+
+        // Fill a byte following the nul terminator, which is discarded when reading.
+        bytes[name + 10] = b'x';
+
+        let read = LvdFile::read(&mut Cursor::new(&bytes)).unwrap();
+
+        assert_eq!(read.content_hash(), file.content_hash());
+        assert_ne!(fnv1a(&bytes), file.content_hash());
+    }
+
+    #[test]
+    fn content_hash_depends_on_version_and_order() {
+        let file = platform_file();
+        let mut reordered = platform_file();
+        let collisions = reordered.data.collisions_mut().unwrap();
+
+        collisions.push(Collision::new_soft_platform(
+            Vector2::new(0.0, 10.0),
+            Vector2::new(10.0, 10.0),
+        ));
+
+        let hash = reordered.content_hash();
+
+        reordered
+            .data
+            .collisions_mut()
+            .unwrap()
+            .elements_mut()
+            .reverse();
+
+        assert_ne!(reordered.content_hash(), hash);
+        assert_eq!(file.content_hash(), file.clone().content_hash());
+        assert_eq!(file.content_hash(), fnv1a(&file.to_bytes().unwrap()));
+        assert_ne!(
+            LvdFile {
+                data: Versioned::new(Lvd::empty(12).unwrap())
+            }
+            .content_hash(),
+            LvdFile {
+                data: Versioned::new(Lvd::empty(13).unwrap())
+            }
+            .content_hash()
+        );
+    }
+}
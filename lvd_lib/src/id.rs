@@ -1,19 +1,26 @@
 //! A numeric identifier for matching and filtering LVD objects.
 //!
-//! This module contains the [`Id`] type.
+//! This module contains the [`Id`] type and the [`IdAllocator`] type, which hands out
+//! identifiers not yet used by the objects of a section.
+
+use std::{collections::HashMap, fmt};
 
 use binrw::binrw;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::version::Version;
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+use crate::{version::Version, Lvd, SectionKind};
 
 /// A numeric identifier for matching and filtering LVD objects.
 #[binrw]
 #[br(import(_version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Id(pub u32);
 
 impl Version for Id {
@@ -21,3 +28,152 @@ impl Version for Id {
         1
     }
 }
+
+impl fmt::Display for Id {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u32> for Id {
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Id> for u32 {
+    fn from(value: Id) -> Self {
+        value.0
+    }
+}
+
+/// Hands out the numeric identifiers of new objects in the sections whose objects
+/// require unique identifiers.
+///
+/// Each identifier handed out is one greater than the greatest identifier already used
+/// in its section, or handed out before, starting from `1` in an empty section.
+///
+/// # Examples
+///
+/// ```
+/// use lvd_lib::{
+///     field_smash::start_points,
+///     id::{Id, IdAllocator},
+///     vector::Vector2,
+///     Lvd, SectionKind,
+/// };
+///
+/// let mut lvd = Lvd::empty(13).unwrap();
+///
+/// for point in start_points([Vector2::new(-20.0, 0.0), Vector2::new(20.0, 0.0)], 1) {
+///     lvd.fs_start_points_mut().unwrap().push(point);
+/// }
+///
+/// let mut ids = IdAllocator::new(&lvd);
+///
+/// assert_eq!(ids.allocate(SectionKind::FsStartPoints), Some(Id(3)));
+/// assert_eq!(ids.allocate(SectionKind::FsStartPoints), Some(Id(4)));
+/// assert_eq!(ids.allocate(SectionKind::DamageShapes), Some(Id(1)));
+/// assert_eq!(ids.allocate(SectionKind::Collisions), None);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IdAllocator {
+    next: HashMap<SectionKind, u64>,
+}
+
+impl IdAllocator {
+    /// The sections whose objects require unique identifiers.
+    pub const SECTIONS: [SectionKind; 2] = [SectionKind::FsStartPoints, SectionKind::DamageShapes];
+
+    /// Creates a new `IdAllocator` handing out the identifiers not used by the objects in the data.
+    pub fn new(data: &Lvd) -> Self {
+        let start_points = data
+            .fs_start_points()
+            .and_then(|points| points.iter().map(|point| point.id().0).max());
+        let damage_shapes = data
+            .damage_shapes()
+            .and_then(|shapes| shapes.iter().map(|shape| shape.id()).max());
+        let next = |max: Option<u32>| max.map_or(1, |max| u64::from(max) + 1);
+
+        Self {
+            next: HashMap::from([
+                (SectionKind::FsStartPoints, next(start_points)),
+                (SectionKind::DamageShapes, next(damage_shapes)),
+            ]),
+        }
+    }
+
+    /// Returns the next unused identifier of the section, or `None` if its objects do not
+    /// have identifiers or every identifier has been used.
+    pub fn allocate(&mut self, section: SectionKind) -> Option<Id> {
+        let next = self.next.get_mut(&section)?;
+        let id = u32::try_from(*next).ok()?;
+
+        *next += 1;
+
+        Some(Id(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::objects::{DamageShape, FsStartPoint};
+
+    use super::*;
+
+    fn lvd_with_ids(start_points: &[u32], damage_shapes: &[u32]) -> Lvd {
+        let mut lvd = Lvd::empty(13).unwrap();
+
+        for &id in start_points {
+            let mut point = FsStartPoint::default();
+
+            *point.id_mut() = Id(id);
+            lvd.fs_start_points_mut().unwrap().push(point);
+        }
+
+        for &id in damage_shapes {
+            let mut shape = DamageShape::default();
+
+            *shape.id_mut() = id;
+            lvd.damage_shapes_mut().unwrap().push(shape);
+        }
+
+        lvd
+    }
+
+    #[test]
+    fn allocate_after_greatest_id() {
+        let mut ids = IdAllocator::new(&lvd_with_ids(&[5, 1], &[0]));
+
+        assert_eq!(
+            ids.allocate(SectionKind::FsStartPoints),
+            Some(Id(6)),
+            "gaps below the greatest identifier are not reused"
+        );
+        assert_eq!(ids.allocate(SectionKind::FsStartPoints), Some(Id(7)));
+        assert_eq!(ids.allocate(SectionKind::DamageShapes), Some(Id(1)));
+        assert_eq!(ids.allocate(SectionKind::DamageShapes), Some(Id(2)));
+    }
+
+    #[test]
+    fn allocate_until_exhausted() {
+        let mut ids = IdAllocator::new(&lvd_with_ids(&[u32::MAX - 1], &[u32::MAX]));
+
+        assert_eq!(ids.allocate(SectionKind::FsStartPoints), Some(Id(u32::MAX)));
+        assert_eq!(ids.allocate(SectionKind::FsStartPoints), None);
+        assert_eq!(ids.allocate(SectionKind::FsStartPoints), None);
+        assert_eq!(ids.allocate(SectionKind::DamageShapes), None);
+    }
+
+    #[test]
+    fn allocate_without_sections() {
+        let mut old = IdAllocator::new(&Lvd::empty(1).unwrap());
+
+        assert_eq!(old.allocate(SectionKind::FsStartPoints), Some(Id(1)));
+        assert_eq!(
+            IdAllocator::default().allocate(SectionKind::DamageShapes),
+            None
+        );
+        assert_eq!(old.allocate(SectionKind::StartPositions), None);
+    }
+}
@@ -0,0 +1,128 @@
+//! Lookup of LVD objects by name and tag.
+//!
+//! This module contains the [`LvdIndex`] type, which maps the names and tags of the objects in an
+//! [`Lvd`] to their positions, so repeated lookups, such as those made by an editor, do not scan
+//! every section.
+
+use std::collections::HashMap;
+
+use crate::{string::FixedString56, tag::Tag, Lvd, ObjectRef, SectionKind};
+
+/// The positions of the objects in an [`Lvd`] by name and tag.
+///
+/// The index describes the data it was built from. It is not updated when the data changes,
+/// so it must be built again after objects are added, removed, reordered, or renamed.
+///
+/// # Examples
+///
+/// ```
+/// use lvd_lib::{
+///     index::LvdIndex,
+///     objects::{Collision, LvdObject},
+///     vector::Vector2,
+///     Lvd, ObjectRef, SectionKind,
+/// };
+///
+/// let mut lvd = Lvd::empty(13).unwrap();
+/// let mut platform = Collision::new_soft_platform(Vector2::new(-10.0, 0.0), Vector2::new(10.0, 0.0));
+///
+/// *platform.meta_info_mut().name_mut() = "COL_00_Floor01".try_into().unwrap();
+/// lvd.collisions_mut().unwrap().push(platform);
+///
+/// let index = LvdIndex::new(&lvd);
+///
+/// assert_eq!(
+///     index.named("COL_00_Floor01"),
+///     [ObjectRef::new(SectionKind::Collisions, 0)]
+/// );
+/// assert!(index.named("COL_00_Floor02").is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LvdIndex {
+    names: HashMap<FixedString56, Vec<ObjectRef>>,
+    tags: HashMap<Tag, Vec<ObjectRef>>,
+}
+
+impl LvdIndex {
+    /// Builds the index of the top-level objects in every section of the data.
+    pub fn new(data: &Lvd) -> Self {
+        let mut index = Self::default();
+
+        for &section in SectionKind::ALL {
+            for i in 0..data.section_len(section).unwrap_or_default() {
+                let Some(object) = data.object(section, i) else {
+                    continue;
+                };
+                let object_ref = ObjectRef::new(section, i);
+
+                index
+                    .names
+                    .entry(object.name().clone())
+                    .or_default()
+                    .push(object_ref);
+
+                if let Some(&tag) = object.tag() {
+                    index.tags.entry(tag).or_default().push(object_ref);
+                }
+            }
+        }
+
+        index
+    }
+
+    /// Returns the objects with the given name, in file order.
+    pub fn named(&self, name: &str) -> &[ObjectRef] {
+        self.names.get(name.as_bytes()).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the objects with the given tag, in file order.
+    pub fn tagged(&self, tag: Tag) -> &[ObjectRef] {
+        self.tags.get(&tag).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns an iterator over the distinct names of the objects and the objects with each name.
+    pub fn names(&self) -> impl Iterator<Item = (&FixedString56, &[ObjectRef])> {
+        self.names
+            .iter()
+            .map(|(name, objects)| (name, objects.as_slice()))
+    }
+
+    /// Returns an iterator over the distinct tags of the objects and the objects with each tag.
+    pub fn tags(&self) -> impl Iterator<Item = (Tag, &[ObjectRef])> {
+        self.tags
+            .iter()
+            .map(|(&tag, objects)| (tag, objects.as_slice()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{objects::general_shape::GeneralShape2, objects::LvdObject};
+
+    use super::*;
+
+    #[test]
+    fn index_names_and_tags() {
+        let mut lvd = Lvd::empty(13).unwrap();
+        let tag = Tag::from_str("IPP0001").unwrap();
+
+        for name in ["shape", "shape", "other"] {
+            let mut shape = GeneralShape2::default();
+
+            *shape.meta_info_mut().name_mut() = name.try_into().unwrap();
+            *shape.tag_mut().unwrap() = tag;
+            lvd.general_shapes2_mut().unwrap().push(shape);
+        }
+
+        let index = LvdIndex::new(&lvd);
+        let shape = |i| ObjectRef::new(SectionKind::GeneralShapes2, i);
+
+        assert_eq!(index.named("shape"), [shape(0), shape(1)]);
+        assert_eq!(index.named("other"), [shape(2)]);
+        assert_eq!(index.tagged(tag), [shape(0), shape(1), shape(2)]);
+        assert!(index.tagged(Tag::default()).is_empty());
+        assert_eq!(index.names().count(), 2);
+    }
+}
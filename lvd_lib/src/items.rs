@@ -0,0 +1,148 @@
+//! Generation of the areas items appear from.
+//!
+//! This module contains the [`LvdFile::floor_item_paths`] method, which traces paths hovering
+//! above the floors of a stage, and the [`LvdFile::floor_item_popup`] method, which builds an
+//! [`ItemPopup`] from them in place of drawing its paths by hand.
+
+use crate::{
+    array::Array,
+    objects::{base::Base, ItemPopup},
+    shape::{Path, Shape2, ShapeArray2},
+    tag::Tag,
+    vector::Vector2,
+    version::Versioned,
+    LvdFile,
+};
+
+impl LvdFile {
+    /// Returns paths running `height` above every floor edge of every collision, from left to
+    /// right, simplified with [`Path::simplify`] and the given tolerance.
+    ///
+    /// Floor edges meeting within `tolerance` of each other, whether in the same collision or
+    /// not, are merged into a single path. Paths are ordered by their leftmost point.
+    pub fn floor_item_paths(&self, height: f32, tolerance: f32) -> Vec<Path> {
+        let offset = Vector2::new(0.0, height);
+        let mut segments = self
+            .data
+            .collisions()
+            .into_iter()
+            .flat_map(Array::iter)
+            .flat_map(|collision| {
+                collision
+                    .edges()
+                    .filter(|edge| collision.is_floor_edge(edge.index))
+                    .map(|edge| {
+                        if edge.start.x() <= edge.end.x() {
+                            (edge.start + offset, edge.end + offset)
+                        } else {
+                            (edge.end + offset, edge.start + offset)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let mut paths = Vec::new();
+
+        segments.sort_by(|(a, _), (b, _)| a.x().total_cmp(&b.x()));
+        segments.reverse();
+
+        while let Some((start, end)) = segments.pop() {
+            let mut points = vec![start, end];
+
+            while let Some(next) = segments.iter().rposition(|(next_start, _)| {
+                (*next_start - points[points.len() - 1]).length() <= tolerance
+            }) {
+                points.push(segments.remove(next).1);
+            }
+
+            let mut path = Path::new(points);
+
+            path.simplify(tolerance);
+            paths.push(path);
+        }
+
+        paths
+    }
+
+    /// Returns an item popup whose shapes are the paths returned by
+    /// [`LvdFile::floor_item_paths`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::LvdFile;
+    ///
+    /// let mut file = LvdFile::from_file("custom_00.lvd").unwrap();
+    /// let item_popup = file.floor_item_popup(10.0, 1.0);
+    ///
+    /// file.data.item_popups_mut().unwrap().push(item_popup);
+    /// ```
+    pub fn floor_item_popup(&self, height: f32, tolerance: f32) -> ItemPopup {
+        let shapes = self
+            .floor_item_paths(height, tolerance)
+            .into_iter()
+            .map(|path| Shape2::Path {
+                path: Versioned::new(path),
+            });
+
+        ItemPopup::V1 {
+            base: Versioned::new(Base::default()),
+            tag: Versioned::new(Tag::default()),
+            shapes: Versioned::new(ShapeArray2::new(shapes)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{fixtures::file_with_collisions, objects::Collision};
+
+    #[test]
+    fn floor_item_paths_merge_adjacent_floors() {
+        let file = file_with_collisions([
+            Collision::new_soft_platform(Vector2::new(0.0, 0.0), Vector2::new(20.0, 0.0)),
+            Collision::new_soft_platform(Vector2::new(-40.0, 0.0), Vector2::new(-20.0, 0.0)),
+            Collision::new_soft_platform(Vector2::new(-20.0, 0.0), Vector2::new(0.0, 0.05)),
+            Collision::new_soft_platform(Vector2::new(-10.0, 30.0), Vector2::new(10.0, 30.0)),
+        ]);
+        let paths = file
+            .floor_item_paths(5.0, 0.1)
+            .iter()
+            .map(|path| path.points().iter().copied().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            paths,
+            [
+                vec![Vector2::new(-40.0, 5.0), Vector2::new(20.0, 5.0)],
+                vec![Vector2::new(-10.0, 35.0), Vector2::new(10.0, 35.0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn floor_item_popup_without_floors() {
+        let file = file_with_collisions([]);
+        let popup = file.floor_item_popup(5.0, 0.1);
+
+        assert!(file.floor_item_paths(5.0, 0.1).is_empty());
+        assert!(matches!(
+            popup,
+            ItemPopup::V1 { ref tag, ref shapes, .. }
+                if **tag == Tag::default() && shapes.is_empty()
+        ));
+
+        let gap = file_with_collisions([
+            Collision::new_soft_platform(Vector2::new(-40.0, 0.0), Vector2::new(-20.0, 0.0)),
+            Collision::new_soft_platform(Vector2::new(-19.0, 0.0), Vector2::new(0.0, 0.0)),
+        ]);
+
+        assert_eq!(
+            gap.floor_item_paths(5.0, 0.5).len(),
+            2,
+            "floors further apart than the tolerance stay separate"
+        );
+    }
+}
@@ -0,0 +1,421 @@
+//! Checks of LVD files against common competitive legality rules.
+//!
+//! This module contains the [`LvdFile::check_legality`] method, the [`Rule`] type naming each
+//! check, and the [`LegalityReport`] type listing the rules a file breaks and where. The report's
+//! [`Display`](fmt::Display) implementation is suitable for sharing alongside a stage release.
+//!
+//! Each rule compares positions against their mirror image across the stage's center at
+//! `x = 0`, within a caller-supplied tolerance, or checks for the absence of objects.
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+use crate::{array::Array, shape::Rect, vector::Vector2, LvdFile, ObjectRef, SectionKind};
+
+/// A competitive legality rule.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Rule {
+    /// The left and right edges of every blast zone are equally far from the center.
+    BlastZoneSymmetry,
+
+    /// Every spawn point has a counterpart mirrored across the center.
+    SpawnMirroring,
+
+    /// The stage has no damage shapes.
+    NoHazards,
+
+    /// No solid floor reaches the left or right edge of the camera bounds.
+    NoWalkOffs,
+
+    /// Every ledge has a counterpart mirrored across the center, facing the opposite direction.
+    LedgeParity,
+}
+
+impl Rule {
+    /// The collection of every rule.
+    pub const ALL: &'static [Self] = &[
+        Self::BlastZoneSymmetry,
+        Self::SpawnMirroring,
+        Self::NoHazards,
+        Self::NoWalkOffs,
+        Self::LedgeParity,
+    ];
+
+    /// Returns the name of the rule, such as `blast-zone-symmetry`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::BlastZoneSymmetry => "blast-zone-symmetry",
+            Self::SpawnMirroring => "spawn-mirroring",
+            Self::NoHazards => "no-hazards",
+            Self::NoWalkOffs => "no-walk-offs",
+            Self::LedgeParity => "ledge-parity",
+        }
+    }
+
+    /// Returns the rule with the given name, or `None` if no rule has the name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::legality::Rule;
+    ///
+    /// assert_eq!(Rule::from_name("no-hazards"), Some(Rule::NoHazards));
+    /// assert_eq!(Rule::from_name("no-items"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|rule| rule.name() == name)
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BlastZoneSymmetry => write!(f, "blast zones are symmetric"),
+            Self::SpawnMirroring => write!(f, "spawn points are mirrored"),
+            Self::NoHazards => write!(f, "no hazards"),
+            Self::NoWalkOffs => write!(f, "no walk-offs"),
+            Self::LedgeParity => write!(f, "ledges are mirrored"),
+        }
+    }
+}
+
+/// A breach of a legality rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// The object breaking the rule.
+    pub object: ObjectRef,
+
+    /// The description of the breach.
+    pub kind: ViolationKind,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "object {} in section `{}`: {}",
+            self.object.index,
+            self.object.section.name(),
+            self.kind
+        )
+    }
+}
+
+/// The kinds of breaches of legality rules.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ViolationKind {
+    /// The left and right edges of a death region are not equally far from the center.
+    AsymmetricBlastZone {
+        /// The coordinate of the left edge.
+        left: f32,
+
+        /// The coordinate of the right edge.
+        right: f32,
+    },
+
+    /// A spawn point has no counterpart mirrored across the center.
+    UnmirroredSpawn(Vector2),
+
+    /// A damage shape is present.
+    Hazard,
+
+    /// A floor edge of a solid collision reaches the edge of the camera bounds.
+    WalkOff(usize),
+
+    /// A ledge of a collision has no counterpart mirrored across the center.
+    UnmirroredLedge {
+        /// The position of the ledge.
+        pos: Vector2,
+
+        /// The facing direction of the ledge.
+        lr: f32,
+    },
+}
+
+impl ViolationKind {
+    /// Returns the rule broken by the kind of violation.
+    pub fn rule(&self) -> Rule {
+        match self {
+            Self::AsymmetricBlastZone { .. } => Rule::BlastZoneSymmetry,
+            Self::UnmirroredSpawn(_) => Rule::SpawnMirroring,
+            Self::Hazard => Rule::NoHazards,
+            Self::WalkOff(_) => Rule::NoWalkOffs,
+            Self::UnmirroredLedge { .. } => Rule::LedgeParity,
+        }
+    }
+}
+
+impl fmt::Display for ViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AsymmetricBlastZone { left, right } => {
+                write!(
+                    f,
+                    "left edge {left} and right edge {right} are not equally far from the center"
+                )
+            }
+            Self::UnmirroredSpawn(pos) => write!(
+                f,
+                "spawn at ({}, {}) has no counterpart at ({}, {})",
+                pos.x(),
+                pos.y(),
+                -pos.x(),
+                pos.y()
+            ),
+            Self::Hazard => write!(f, "damage shape is a hazard"),
+            Self::WalkOff(edge) => {
+                write!(f, "floor edge {edge} reaches the edge of the camera bounds")
+            }
+            Self::UnmirroredLedge { pos, lr } => write!(
+                f,
+                "ledge at ({}, {}) facing {} has no counterpart at ({}, {}) facing {}",
+                pos.x(),
+                pos.y(),
+                facing(*lr),
+                -pos.x(),
+                pos.y(),
+                facing(-*lr)
+            ),
+        }
+    }
+}
+
+/// Returns the name of a facing direction.
+fn facing(lr: f32) -> &'static str {
+    if lr < 0.0 {
+        "left"
+    } else {
+        "right"
+    }
+}
+
+/// The result of checking an LVD file against a set of legality rules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegalityReport {
+    /// The rules the file was checked against.
+    pub rules: Vec<Rule>,
+
+    /// The breaches of the rules found in the file.
+    pub violations: Vec<Violation>,
+}
+
+impl LegalityReport {
+    /// Returns `true` if the file breaks none of the checked rules.
+    pub fn is_legal(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Returns the breaches of the given rule.
+    pub fn violations_of(&self, rule: Rule) -> impl Iterator<Item = &Violation> {
+        self.violations
+            .iter()
+            .filter(move |violation| violation.kind.rule() == rule)
+    }
+}
+
+impl fmt::Display for LegalityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &rule in &self.rules {
+            let mut violations = self.violations_of(rule).peekable();
+            let status = if violations.peek().is_none() {
+                "PASS"
+            } else {
+                "FAIL"
+            };
+
+            writeln!(f, "{status} {rule}")?;
+
+            for violation in violations {
+                writeln!(f, "  {violation}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl LvdFile {
+    /// Checks the file against the given legality rules, treating positions within
+    /// `tolerance` of each other as equal.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{legality::Rule, LvdFile};
+    ///
+    /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    /// let report = file.check_legality(Rule::ALL, 0.01);
+    ///
+    /// print!("{report}");
+    /// ```
+    pub fn check_legality(&self, rules: &[Rule], tolerance: f32) -> LegalityReport {
+        let mut violations = Vec::new();
+
+        for &rule in rules {
+            match rule {
+                Rule::BlastZoneSymmetry => self.check_blast_zones(tolerance, &mut violations),
+                Rule::SpawnMirroring => self.check_spawns(tolerance, &mut violations),
+                Rule::NoHazards => self.check_hazards(&mut violations),
+                Rule::NoWalkOffs => self.check_walk_offs(tolerance, &mut violations),
+                Rule::LedgeParity => self.check_ledges(tolerance, &mut violations),
+            }
+        }
+
+        LegalityReport {
+            rules: rules.to_vec(),
+            violations,
+        }
+    }
+
+    fn check_blast_zones(&self, tolerance: f32, violations: &mut Vec<Violation>) {
+        for (index, region) in self
+            .data
+            .death_regions()
+            .into_iter()
+            .flat_map(Array::iter)
+            .enumerate()
+        {
+            let Rect::V1 { left, right, .. } = region.rect();
+
+            if (left + right).abs() > tolerance {
+                violations.push(Violation {
+                    object: ObjectRef::new(SectionKind::DeathRegions, index),
+                    kind: ViolationKind::AsymmetricBlastZone { left, right },
+                });
+            }
+        }
+    }
+
+    fn check_spawns(&self, tolerance: f32, violations: &mut Vec<Violation>) {
+        let spawns = self
+            .data
+            .start_positions()
+            .into_iter()
+            .flat_map(Array::iter)
+            .map(|point| point.pos())
+            .collect::<Vec<_>>();
+
+        for (index, &pos) in spawns.iter().enumerate() {
+            if !spawns
+                .iter()
+                .any(|&other| is_mirrored(pos, other, tolerance))
+            {
+                violations.push(Violation {
+                    object: ObjectRef::new(SectionKind::StartPositions, index),
+                    kind: ViolationKind::UnmirroredSpawn(pos),
+                });
+            }
+        }
+    }
+
+    fn check_hazards(&self, violations: &mut Vec<Violation>) {
+        let count = self.data.section_len(SectionKind::DamageShapes);
+
+        violations.extend((0..count.unwrap_or_default()).map(|index| Violation {
+            object: ObjectRef::new(SectionKind::DamageShapes, index),
+            kind: ViolationKind::Hazard,
+        }));
+    }
+
+    fn check_walk_offs(&self, tolerance: f32, violations: &mut Vec<Violation>) {
+        let Some((left, right)) = self
+            .data
+            .camera_regions()
+            .into_iter()
+            .flat_map(Array::iter)
+            .map(|region| {
+                let Rect::V1 { left, right, .. } = region.rect();
+
+                (left, right)
+            })
+            .reduce(|(l1, r1), (l2, r2)| (l1.min(l2), r1.max(r2)))
+        else {
+            return;
+        };
+
+        for (index, collision) in self
+            .data
+            .collisions()
+            .into_iter()
+            .flat_map(Array::iter)
+            .enumerate()
+        {
+            if collision.flags().throughable() {
+                continue;
+            }
+
+            let vertices = collision.vertices().elements();
+
+            for edge in 0..collision.edge_count() {
+                let reaches_bounds = vertices[edge..]
+                    .iter()
+                    .take(2)
+                    .any(|v| v.x() <= left + tolerance || v.x() >= right - tolerance);
+
+                if collision.is_floor_edge(edge) && reaches_bounds {
+                    violations.push(Violation {
+                        object: ObjectRef::new(SectionKind::Collisions, index),
+                        kind: ViolationKind::WalkOff(edge),
+                    });
+                }
+            }
+        }
+    }
+
+    fn check_ledges(&self, tolerance: f32, violations: &mut Vec<Violation>) {
+        let ledges = self
+            .data
+            .collisions()
+            .into_iter()
+            .flat_map(Array::iter)
+            .enumerate()
+            .flat_map(|(index, collision)| {
+                collision
+                    .cliffs()
+                    .iter()
+                    .map(move |cliff| (index, cliff.pos(), cliff.lr()))
+            })
+            .collect::<Vec<_>>();
+
+        for &(index, pos, lr) in &ledges {
+            let is_paired = ledges.iter().any(|&(_, other, other_lr)| {
+                is_mirrored(pos, other, tolerance) && lr.signum() == -other_lr.signum()
+            });
+
+            if !is_paired {
+                violations.push(Violation {
+                    object: ObjectRef::new(SectionKind::Collisions, index),
+                    kind: ViolationKind::UnmirroredLedge { pos, lr },
+                });
+            }
+        }
+    }
+}
+
+/// Returns `true` if the two positions are mirror images across `x = 0` within `tolerance`.
+fn is_mirrored(a: Vector2, b: Vector2, tolerance: f32) -> bool {
+    (a.x() + b.x()).abs() <= tolerance && (a.y() - b.y()).abs() <= tolerance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mirrored_positions() {
+        let v = Vector2::new;
+
+        assert!(is_mirrored(v(10.0, 5.0), v(-10.0, 5.0), 0.01));
+        assert!(is_mirrored(v(0.0, 5.0), v(0.0, 5.0), 0.01));
+        assert!(!is_mirrored(v(10.0, 5.0), v(-9.0, 5.0), 0.01));
+        assert!(!is_mirrored(v(10.0, 5.0), v(-10.0, 6.0), 0.01));
+    }
+}
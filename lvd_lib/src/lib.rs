@@ -3,392 +3,931 @@
 //! lvd_lib is a library for reading and writing LVD files from Super Smash Bros. for Nintendo 3DS / Wii U and Super Smash Bros. Ultimate.
 
 use std::{
-    fs,
-    io::{Cursor, Read, Seek, Write},
+    any::Any,
+    fmt, fs,
+    io::{BufReader, BufWriter, Cursor, Read, Seek, SeekFrom, Write},
     path::Path,
 };
 
-use binrw::{binrw, BinReaderExt, BinResult, BinWrite};
+use binrw::{
+    binrw, error::ContextExt, io::TakeSeekExt, BinRead, BinReaderExt, BinResult, BinWrite, Endian,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+pub mod animation;
+pub mod annotate;
 pub mod array;
+#[cfg(feature = "tokio")]
+pub mod async_io;
+pub mod autofit;
+pub mod bindings;
+pub mod canonical;
+#[cfg(feature = "serde")]
+pub mod change;
+pub mod csv;
+pub mod duplicate;
+pub mod field_smash;
+pub mod finite;
+#[cfg(test)]
+mod fixtures;
+pub mod game;
+pub mod hash;
 pub mod id;
+pub mod index;
+pub mod items;
+pub mod legality;
+pub mod limits;
+pub mod materials;
+pub mod model;
 pub mod objects;
+#[cfg(feature = "param")]
+pub mod param;
+#[cfg(feature = "serde")]
+pub mod patch;
+pub mod process;
+#[cfg(feature = "project")]
+pub mod project;
+pub mod quantize;
+pub mod raw;
+pub mod rename;
+#[cfg(feature = "render")]
+pub mod render;
+pub mod rescale;
+#[cfg(feature = "schemars")]
+pub mod schema;
 pub mod shape;
+pub mod size;
+pub mod spawn;
+pub mod stage;
 pub mod string;
+pub mod summary;
+pub mod symmetry;
 pub mod tag;
+pub mod tag_family;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+pub mod transform;
+pub mod validation;
+#[cfg(feature = "vanilla")]
+pub mod vanilla;
 pub mod vector;
+#[cfg(feature = "serde")]
+pub mod verify;
 pub mod version;
+#[cfg(feature = "serde")]
+pub mod version_keys;
+pub mod visit;
+pub mod walk_off;
 
 use array::Array;
 use objects::*;
+use raw::UnknownSection;
 use version::{Version, Versioned};
+use visit::{LvdVisitor, LvdVisitorMut, Visit};
 
 /// The container type for the various LVD file format versions.
 #[binrw]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
 #[cfg_attr(feature = "serde", serde(transparent))]
-#[derive(Debug)]
+#[br(import(newer_as_latest: bool))]
+#[derive(Debug, Clone)]
 pub struct LvdFile {
     #[br(temp)]
     #[bw(calc = 1u32)]
     _unk: u32,
 
     /// The associated data for each LVD file format version.
+    #[br(parse_with = read_data, args(newer_as_latest))]
     pub data: Versioned<Lvd>,
 }
 
 impl LvdFile {
-    /// Reads the data from the given file path.
+    /// Reads the data from the given file path, streaming the file through a buffer.
     pub fn from_file<P: AsRef<Path>>(path: P) -> BinResult<Self> {
-        let mut cursor = Cursor::new(fs::read(path)?);
+        Self::read_from(fs::File::open(path)?)
+    }
 
-        Self::read(&mut cursor)
+    /// Reads the data from the given file path by memory mapping the file
+    /// instead of copying it into a buffer.
+    ///
+    /// # Safety
+    ///
+    /// The file is mapped for the duration of the call. It must not be modified or truncated,
+    /// by this process or another, before the call returns.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::LvdFile;
+    ///
+    /// let file = unsafe { LvdFile::read_be_mmap("battlefield_00.lvd") }.unwrap();
+    /// ```
+    #[cfg(feature = "mmap")]
+    pub unsafe fn read_be_mmap<P: AsRef<Path>>(path: P) -> BinResult<Self> {
+        let file = fs::File::open(path)?;
+        let map = memmap2::Mmap::map(&file)?;
+
+        Self::from_bytes(&map)
     }
 
-    /// Reads the data from the given reader.
-    pub fn read<R: Read + Seek>(reader: &mut R) -> BinResult<Self> {
-        reader.read_be()
+    /// Reads the data from the given bytes, such as those of a file piped to the standard input.
+    pub fn from_bytes(bytes: &[u8]) -> BinResult<Self> {
+        Self::read(&mut Cursor::new(bytes))
     }
 
-    /// Writes the data to the given writer.
-    pub fn write<W: Write + Seek>(&self, writer: &mut W) -> BinResult<()> {
-        self.write_be(writer)
+    /// Reads the data from the given bytes, reading a file format version newer than
+    /// [`ModelVersions::LATEST_LVD_VERSION`] as the latest known version.
+    ///
+    /// Newer versions are assumed to only append sections to the latest known version, so the
+    /// bytes following its sections are kept as [unknown sections](raw::UnknownSection). The
+    /// data is written back as the latest known version followed by those bytes, which the game
+    /// will not read as the original file, so it should only be written to inspect or convert it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{version::{Version, Versioned}, Lvd, LvdFile};
+    ///
+    /// let mut bytes = LvdFile { data: Versioned::new(Lvd::empty(13).unwrap()) }
+    ///     .to_bytes()
+    ///     .unwrap();
+    ///
+    /// bytes[4] = 14;
+    ///
+    /// assert!(LvdFile::from_bytes(&bytes).is_err());
+    /// assert_eq!(LvdFile::from_bytes_as_latest(&bytes).unwrap().data.version(), 13);
+    /// ```
+    ///
+    /// [`ModelVersions::LATEST_LVD_VERSION`]: model::ModelVersions::LATEST_LVD_VERSION
+    pub fn from_bytes_as_latest(bytes: &[u8]) -> BinResult<Self> {
+        Cursor::new(bytes).read_be_args((true,))
     }
 
-    /// Writes the data to the given file path.
-    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> BinResult<()> {
+    /// Writes the data to a new buffer and returns its bytes.
+    pub fn to_bytes(&self) -> BinResult<Vec<u8>> {
         let mut cursor = Cursor::new(Vec::new());
 
         self.write(&mut cursor)?;
-        fs::write(path, cursor.get_mut())?;
 
-        Ok(())
+        Ok(cursor.into_inner())
     }
-}
 
-/// The associated data for each LVD file format version.
-#[binrw]
-#[br(import(version: u8))]
-#[brw(magic = b"\x01LVD1")]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
-pub enum Lvd {
-    /// The first version of the `Lvd` type.
+    /// Returns `true` if the bytes start with the header of an LVD file, as opposed to text
+    /// such as YAML.
     ///
-    /// This version is not known to be used.
-    #[br(pre_assert(version == 1))]
-    V1 {
-        collisions: Versioned<Array<Collision>>,
-        start_positions: Versioned<Array<Point>>,
-        restart_positions: Versioned<Array<Point>>,
-        camera_regions: Versioned<Array<Region>>,
-        death_regions: Versioned<Array<Region>>,
-        enemy_generators: Versioned<Array<EnemyGenerator>>,
-    },
-
-    /// The second version of the `Lvd` type.
+    /// # Examples
     ///
-    /// Adds [`fs_items`](#variant.V2.field.fs_items).
-    /// This version is not known to be used.
-    #[br(pre_assert(version == 2))]
-    V2 {
-        collisions: Versioned<Array<Collision>>,
-        start_positions: Versioned<Array<Point>>,
-        restart_positions: Versioned<Array<Point>>,
-        camera_regions: Versioned<Array<Region>>,
-        death_regions: Versioned<Array<Region>>,
-        enemy_generators: Versioned<Array<EnemyGenerator>>,
-        fs_items: Versioned<Array<FsItem>>,
-    },
-
-    /// The third version of the `Lvd` type.
+    /// ```
+    /// use lvd_lib::LvdFile;
     ///
-    /// Adds [`fs_unknown`](#variant.V3.field.fs_unknown), [`fs_area_cams`](#variant.V3.field.fs_area_cams), [`fs_area_locks`](#variant.V3.field.fs_area_locks), and [`fs_cam_limits`](#variant.V3.field.fs_cam_limits).
-    /// This version is not known to be used.
-    #[br(pre_assert(version == 3))]
-    V3 {
-        collisions: Versioned<Array<Collision>>,
-        start_positions: Versioned<Array<Point>>,
-        restart_positions: Versioned<Array<Point>>,
-        camera_regions: Versioned<Array<Region>>,
-        death_regions: Versioned<Array<Region>>,
-        enemy_generators: Versioned<Array<EnemyGenerator>>,
-        fs_items: Versioned<Array<FsItem>>,
-        fs_unknown: Versioned<Array<FsUnknown>>,
-        fs_area_cams: Versioned<Array<FsAreaCam>>,
-        fs_area_locks: Versioned<Array<FsAreaLock>>,
-        fs_cam_limits: Versioned<Array<FsCamLimit>>,
-    },
-
-    /// The fourth version of the `Lvd` type.
+    /// assert!(LvdFile::is_lvd(b"\0\0\0\x01\x0d\x01LVD1"));
+    /// assert!(!LvdFile::is_lvd(b"collisions: []"));
+    /// ```
+    pub fn is_lvd(bytes: &[u8]) -> bool {
+        bytes.len() >= 10 && bytes[..4] == [0, 0, 0, 1] && &bytes[6..10] == b"LVD1"
+    }
+
+    /// Returns the file format version of the LVD file starting with the given bytes,
+    /// or `None` if they do not start with the header of an LVD file.
     ///
-    /// Adds [`damage_shapes`](#variant.V4.field.damage_shapes).
-    #[br(pre_assert(version == 4))]
-    V4 {
-        collisions: Versioned<Array<Collision>>,
-        start_positions: Versioned<Array<Point>>,
-        restart_positions: Versioned<Array<Point>>,
-        camera_regions: Versioned<Array<Region>>,
-        death_regions: Versioned<Array<Region>>,
-        enemy_generators: Versioned<Array<EnemyGenerator>>,
-        fs_items: Versioned<Array<FsItem>>,
-        fs_unknown: Versioned<Array<FsUnknown>>,
-        fs_area_cams: Versioned<Array<FsAreaCam>>,
-        fs_area_locks: Versioned<Array<FsAreaLock>>,
-        fs_cam_limits: Versioned<Array<FsCamLimit>>,
-        damage_shapes: Versioned<Array<DamageShape>>,
-    },
-
-    /// The fifth version of the `Lvd` type.
+    /// # Examples
     ///
-    /// Adds [`item_popups`](#variant.V5.field.item_popups).
-    /// This version is not known to be used.
-    #[br(pre_assert(version == 5))]
-    V5 {
-        collisions: Versioned<Array<Collision>>,
-        start_positions: Versioned<Array<Point>>,
-        restart_positions: Versioned<Array<Point>>,
-        camera_regions: Versioned<Array<Region>>,
-        death_regions: Versioned<Array<Region>>,
-        enemy_generators: Versioned<Array<EnemyGenerator>>,
-        fs_items: Versioned<Array<FsItem>>,
-        fs_unknown: Versioned<Array<FsUnknown>>,
-        fs_area_cams: Versioned<Array<FsAreaCam>>,
-        fs_area_locks: Versioned<Array<FsAreaLock>>,
-        fs_cam_limits: Versioned<Array<FsCamLimit>>,
-        damage_shapes: Versioned<Array<DamageShape>>,
-        item_popups: Versioned<Array<ItemPopup>>,
-    },
-
-    /// The sixth version of the `Lvd` type.
+    /// ```
+    /// use lvd_lib::LvdFile;
     ///
-    /// Adds [`general_shapes2`](#variant.V6.field.general_shapes2) and [`general_shapes3`](#variant.V6.field.general_shapes3).
-    #[br(pre_assert(version == 6))]
-    V6 {
-        collisions: Versioned<Array<Collision>>,
-        start_positions: Versioned<Array<Point>>,
-        restart_positions: Versioned<Array<Point>>,
-        camera_regions: Versioned<Array<Region>>,
-        death_regions: Versioned<Array<Region>>,
-        enemy_generators: Versioned<Array<EnemyGenerator>>,
-        fs_items: Versioned<Array<FsItem>>,
-        fs_unknown: Versioned<Array<FsUnknown>>,
-        fs_area_cams: Versioned<Array<FsAreaCam>>,
-        fs_area_locks: Versioned<Array<FsAreaLock>>,
-        fs_cam_limits: Versioned<Array<FsCamLimit>>,
-        damage_shapes: Versioned<Array<DamageShape>>,
-        item_popups: Versioned<Array<ItemPopup>>,
-        general_shapes2: Versioned<Array<GeneralShape2>>,
-        general_shapes3: Versioned<Array<GeneralShape3>>,
-    },
-
-    /// The seventh version of the `Lvd` type.
+    /// assert_eq!(LvdFile::version_of(b"\0\0\0\x01\x0d\x01LVD1"), Some(13));
+    /// assert_eq!(LvdFile::version_of(b"collisions: []"), None);
+    /// ```
+    pub fn version_of(bytes: &[u8]) -> Option<u8> {
+        Self::is_lvd(bytes).then(|| bytes[4])
+    }
+
+    /// Returns `true` if the bytes start with the header of an LVD file written in little-endian
+    /// byte order, which is not supported.
+    pub fn is_little_endian_lvd(bytes: &[u8]) -> bool {
+        bytes.len() >= 10 && bytes[..4] == [1, 0, 0, 0] && &bytes[6..10] == b"LVD1"
+    }
+
+    /// Reads the data from the given reader.
     ///
-    /// Adds [`area_lights`](#variant.V7.field.area_lights).
-    /// This version is not known to be used.
-    #[br(pre_assert(version == 7))]
-    V7 {
-        collisions: Versioned<Array<Collision>>,
-        start_positions: Versioned<Array<Point>>,
-        restart_positions: Versioned<Array<Point>>,
-        camera_regions: Versioned<Array<Region>>,
-        death_regions: Versioned<Array<Region>>,
-        enemy_generators: Versioned<Array<EnemyGenerator>>,
-        fs_items: Versioned<Array<FsItem>>,
-        fs_unknown: Versioned<Array<FsUnknown>>,
-        fs_area_cams: Versioned<Array<FsAreaCam>>,
-        fs_area_locks: Versioned<Array<FsAreaLock>>,
-        fs_cam_limits: Versioned<Array<FsCamLimit>>,
-        damage_shapes: Versioned<Array<DamageShape>>,
-        item_popups: Versioned<Array<ItemPopup>>,
-        general_shapes2: Versioned<Array<GeneralShape2>>,
-        general_shapes3: Versioned<Array<GeneralShape3>>,
-        area_lights: Versioned<Array<AreaLight>>,
-    },
-
-    /// The eighth version of the `Lvd` type.
+    /// # Errors
     ///
-    /// Adds [`fs_start_points`](#variant.V8.field.fs_start_points).
-    #[br(pre_assert(version == 8))]
-    V8 {
-        collisions: Versioned<Array<Collision>>,
-        start_positions: Versioned<Array<Point>>,
-        restart_positions: Versioned<Array<Point>>,
-        camera_regions: Versioned<Array<Region>>,
-        death_regions: Versioned<Array<Region>>,
-        enemy_generators: Versioned<Array<EnemyGenerator>>,
-        fs_items: Versioned<Array<FsItem>>,
-        fs_unknown: Versioned<Array<FsUnknown>>,
-        fs_area_cams: Versioned<Array<FsAreaCam>>,
-        fs_area_locks: Versioned<Array<FsAreaLock>>,
-        fs_cam_limits: Versioned<Array<FsCamLimit>>,
-        damage_shapes: Versioned<Array<DamageShape>>,
-        item_popups: Versioned<Array<ItemPopup>>,
-        general_shapes2: Versioned<Array<GeneralShape2>>,
-        general_shapes3: Versioned<Array<GeneralShape3>>,
-        area_lights: Versioned<Array<AreaLight>>,
-        fs_start_points: Versioned<Array<FsStartPoint>>,
-    },
-
-    /// The ninth version of the `Lvd` type.
+    /// Returns an error if the file format version is newer than
+    /// [`ModelVersions::LATEST_LVD_VERSION`], since writing the data back would produce a file
+    /// of a different version. [`LvdFile::from_bytes_as_latest`] reads such files instead.
     ///
-    /// Adds [`area_hints`](#variant.V9.field.area_hints).
-    /// This version is not known to be used.
-    #[br(pre_assert(version == 9))]
-    V9 {
-        collisions: Versioned<Array<Collision>>,
-        start_positions: Versioned<Array<Point>>,
-        restart_positions: Versioned<Array<Point>>,
-        camera_regions: Versioned<Array<Region>>,
-        death_regions: Versioned<Array<Region>>,
-        enemy_generators: Versioned<Array<EnemyGenerator>>,
-        fs_items: Versioned<Array<FsItem>>,
-        fs_unknown: Versioned<Array<FsUnknown>>,
-        fs_area_cams: Versioned<Array<FsAreaCam>>,
-        fs_area_locks: Versioned<Array<FsAreaLock>>,
-        fs_cam_limits: Versioned<Array<FsCamLimit>>,
-        damage_shapes: Versioned<Array<DamageShape>>,
-        item_popups: Versioned<Array<ItemPopup>>,
-        general_shapes2: Versioned<Array<GeneralShape2>>,
-        general_shapes3: Versioned<Array<GeneralShape3>>,
-        area_lights: Versioned<Array<AreaLight>>,
-        fs_start_points: Versioned<Array<FsStartPoint>>,
-        area_hints: Versioned<Array<AreaHint>>,
-    },
-
-    /// The tenth version of the `Lvd` type.
+    /// [`ModelVersions::LATEST_LVD_VERSION`]: model::ModelVersions::LATEST_LVD_VERSION
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, err(level = "debug"))
+    )]
+    pub fn read<R: Read + Seek>(reader: &mut R) -> BinResult<Self> {
+        reader.read_be()
+    }
+
+    /// Writes the data to the given writer.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, err(level = "debug"))
+    )]
+    pub fn write<W: Write + Seek>(&self, writer: &mut W) -> BinResult<()> {
+        self.write_be(writer)
+    }
+
+    /// Reads the data from the given reader through a buffer.
     ///
-    /// Adds [`split_areas`](#variant.V10.field.split_areas).
-    #[br(pre_assert(version == 10))]
-    V10 {
-        collisions: Versioned<Array<Collision>>,
-        start_positions: Versioned<Array<Point>>,
-        restart_positions: Versioned<Array<Point>>,
-        camera_regions: Versioned<Array<Region>>,
-        death_regions: Versioned<Array<Region>>,
-        enemy_generators: Versioned<Array<EnemyGenerator>>,
-        fs_items: Versioned<Array<FsItem>>,
-        fs_unknown: Versioned<Array<FsUnknown>>,
-        fs_area_cams: Versioned<Array<FsAreaCam>>,
-        fs_area_locks: Versioned<Array<FsAreaLock>>,
-        fs_cam_limits: Versioned<Array<FsCamLimit>>,
-        damage_shapes: Versioned<Array<DamageShape>>,
-        item_popups: Versioned<Array<ItemPopup>>,
-        general_shapes2: Versioned<Array<GeneralShape2>>,
-        general_shapes3: Versioned<Array<GeneralShape3>>,
-        area_lights: Versioned<Array<AreaLight>>,
-        fs_start_points: Versioned<Array<FsStartPoint>>,
-        area_hints: Versioned<Array<AreaHint>>,
-        split_areas: Versioned<Array<SplitArea>>,
-    },
-
-    /// The eleventh version of the `Lvd` type.
+    /// Unlike [`LvdFile::read`], the reader is not required to buffer its reads, so unbuffered
+    /// sources such as files can be given directly. The data may start at any position of the
+    /// reader and extends to its end, as any bytes following the known sections are kept as
+    /// [unknown sections](raw::UnknownSection). For data followed by unrelated bytes, such as an
+    /// entry of an archive, use [`LvdFile::read_from_range`] instead.
+    pub fn read_from<R: Read + Seek>(reader: R) -> BinResult<Self> {
+        Self::read(&mut BufReader::new(reader))
+    }
+
+    /// Reads the data occupying `len` bytes from `offset` of the given reader through a buffer.
+    ///
+    /// As with [`LvdFile::read_from`], the reader is not required to buffer its reads. Only the
+    /// given range is read, so the bytes following it, such as the remaining entries of an
+    /// archive, are not kept as [unknown sections](raw::UnknownSection).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data extends beyond the range, or if reading fails.
+    ///
+    /// # Examples
     ///
-    /// Adds [`shrinked_camera_regions`](#variant.V11.field.shrinked_camera_regions) and [`shrinked_death_regions`](#variant.V11.field.shrinked_death_regions).
-    #[br(pre_assert(version == 11))]
-    V11 {
-        collisions: Versioned<Array<Collision>>,
-        start_positions: Versioned<Array<Point>>,
-        restart_positions: Versioned<Array<Point>>,
-        camera_regions: Versioned<Array<Region>>,
-        death_regions: Versioned<Array<Region>>,
-        enemy_generators: Versioned<Array<EnemyGenerator>>,
-        fs_items: Versioned<Array<FsItem>>,
-        fs_unknown: Versioned<Array<FsUnknown>>,
-        fs_area_cams: Versioned<Array<FsAreaCam>>,
-        fs_area_locks: Versioned<Array<FsAreaLock>>,
-        fs_cam_limits: Versioned<Array<FsCamLimit>>,
-        damage_shapes: Versioned<Array<DamageShape>>,
-        item_popups: Versioned<Array<ItemPopup>>,
-        general_shapes2: Versioned<Array<GeneralShape2>>,
-        general_shapes3: Versioned<Array<GeneralShape3>>,
-        area_lights: Versioned<Array<AreaLight>>,
-        fs_start_points: Versioned<Array<FsStartPoint>>,
-        area_hints: Versioned<Array<AreaHint>>,
-        split_areas: Versioned<Array<SplitArea>>,
-        shrinked_camera_regions: Versioned<Array<Region>>,
-        shrinked_death_regions: Versioned<Array<Region>>,
-    },
-
-    /// The twelfth version of the `Lvd` type.
+    /// ```no_run
+    /// use std::fs::File;
     ///
-    /// Adds [`ptrainer_ranges`](#variant.V12.field.ptrainer_ranges).
-    #[br(pre_assert(version == 12))]
-    V12 {
-        collisions: Versioned<Array<Collision>>,
-        start_positions: Versioned<Array<Point>>,
-        restart_positions: Versioned<Array<Point>>,
-        camera_regions: Versioned<Array<Region>>,
-        death_regions: Versioned<Array<Region>>,
-        enemy_generators: Versioned<Array<EnemyGenerator>>,
-        fs_items: Versioned<Array<FsItem>>,
-        fs_unknown: Versioned<Array<FsUnknown>>,
-        fs_area_cams: Versioned<Array<FsAreaCam>>,
-        fs_area_locks: Versioned<Array<FsAreaLock>>,
-        fs_cam_limits: Versioned<Array<FsCamLimit>>,
-        damage_shapes: Versioned<Array<DamageShape>>,
-        item_popups: Versioned<Array<ItemPopup>>,
-        ptrainer_ranges: Versioned<Array<PTrainerRange>>,
-        general_shapes2: Versioned<Array<GeneralShape2>>,
-        general_shapes3: Versioned<Array<GeneralShape3>>,
-        area_lights: Versioned<Array<AreaLight>>,
-        fs_start_points: Versioned<Array<FsStartPoint>>,
-        area_hints: Versioned<Array<AreaHint>>,
-        split_areas: Versioned<Array<SplitArea>>,
-        shrinked_camera_regions: Versioned<Array<Region>>,
-        shrinked_death_regions: Versioned<Array<Region>>,
-    },
-
-    /// The thirteenth version of the `Lvd` type.
+    /// use lvd_lib::LvdFile;
+    ///
+    /// let archive = File::open("battlefield_00.bin").unwrap();
+    /// let file = LvdFile::read_from_range(archive, 0x40, 0x1200).unwrap();
+    /// ```
+    pub fn read_from_range<R: Read + Seek>(
+        mut reader: R,
+        offset: u64,
+        len: u64,
+    ) -> BinResult<Self> {
+        reader.seek(SeekFrom::Start(offset))?;
+
+        Self::read_from(reader.take_seek(len))
+    }
+
+    /// Writes the data to the given writer through a buffer, leaving the writer positioned
+    /// at the end of the data.
     ///
-    /// Adds [`ptrainer_floating_floors`](#variant.V13.field.ptrainer_floating_floors).
-    #[br(pre_assert(version == 13))]
-    V13 {
-        collisions: Versioned<Array<Collision>>,
-        start_positions: Versioned<Array<Point>>,
-        restart_positions: Versioned<Array<Point>>,
-        camera_regions: Versioned<Array<Region>>,
-        death_regions: Versioned<Array<Region>>,
-        enemy_generators: Versioned<Array<EnemyGenerator>>,
-        fs_items: Versioned<Array<FsItem>>,
-        fs_unknown: Versioned<Array<FsUnknown>>,
-        fs_area_cams: Versioned<Array<FsAreaCam>>,
-        fs_area_locks: Versioned<Array<FsAreaLock>>,
-        fs_cam_limits: Versioned<Array<FsCamLimit>>,
-        damage_shapes: Versioned<Array<DamageShape>>,
-        item_popups: Versioned<Array<ItemPopup>>,
-        ptrainer_ranges: Versioned<Array<PTrainerRange>>,
-        ptrainer_floating_floors: Versioned<Array<PTrainerFloatingFloor>>,
-        general_shapes2: Versioned<Array<GeneralShape2>>,
-        general_shapes3: Versioned<Array<GeneralShape3>>,
-        area_lights: Versioned<Array<AreaLight>>,
-        fs_start_points: Versioned<Array<FsStartPoint>>,
-        area_hints: Versioned<Array<AreaHint>>,
-        split_areas: Versioned<Array<SplitArea>>,
-        shrinked_camera_regions: Versioned<Array<Region>>,
-        shrinked_death_regions: Versioned<Array<Region>>,
-    },
+    /// Unlike [`LvdFile::write`], the writer is not required to buffer its writes, so unbuffered
+    /// destinations such as files and entries of archives can be given directly.
+    pub fn write_to<W: Write + Seek>(&self, writer: W) -> BinResult<()> {
+        let mut writer = BufWriter::new(writer);
+
+        self.write(&mut writer)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Writes the data to the given file path, streaming the file through a buffer.
+    ///
+    /// If writing fails, the file may be left partially written.
+    /// [`LvdFile::write_to_file_checked`] leaves the file unchanged instead.
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> BinResult<()> {
+        self.write_to(fs::File::create(path)?)
+    }
+}
+
+/// Reads the data of an LVD file, reading file format versions newer than the latest known
+/// version as the latest known version if `newer_as_latest` is `true`, or failing otherwise.
+#[binrw::parser(reader, endian)]
+fn read_data(newer_as_latest: bool) -> BinResult<Versioned<Lvd>> {
+    let pos = reader.stream_position()?;
+    let version = u8::read_options(reader, endian, ())?;
+    let latest = model::ModelVersions::LATEST_LVD_VERSION;
+
+    if version > latest && !newer_as_latest {
+        return Err(binrw::Error::AssertFail {
+            pos,
+            message: format!(
+                "file format version {version} is newer than the latest known version {latest}"
+            ),
+        });
+    }
+
+    if version > latest {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            version,
+            latest,
+            "reading newer file format version as the latest known version"
+        );
+
+        return Lvd::read_options(reader, endian, (latest,)).map(Versioned::new);
+    }
+
+    Lvd::read_options(reader, endian, (version,)).map(Versioned::new)
+}
+
+/// Reads a section, recording its position and number of objects when tracing is enabled.
+fn read_section<T, R>(
+    reader: &mut R,
+    endian: Endian,
+    (kind,): (SectionKind,),
+) -> BinResult<Versioned<Array<T>>>
+where
+    T: Version + 'static,
+    Versioned<T>: for<'a> BinRead<Args<'a> = ()>,
+    R: Read + Seek,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!(
+        "read_section",
+        section = kind.name(),
+        pos = reader.stream_position()?
+    )
+    .entered();
+    #[cfg(not(feature = "tracing"))]
+    let _ = kind;
+
+    let section = Versioned::<Array<T>>::read_options(reader, endian, ())?;
+
+    #[cfg(feature = "tracing")]
+    tracing::trace!(objects = section.len(), "read section");
+
+    Ok(section)
+}
+
+/// Writes a section, recording its position and number of objects when tracing is enabled,
+/// and naming the section in any error.
+fn write_section<T, W>(
+    section: &Versioned<Array<T>>,
+    writer: &mut W,
+    endian: Endian,
+    (kind,): (SectionKind,),
+) -> BinResult<()>
+where
+    T: Version + 'static,
+    W: Write + Seek,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!(
+        "write_section",
+        section = kind.name(),
+        pos = writer.stream_position()?,
+        objects = section.len()
+    )
+    .entered();
+
+    section
+        .write_options(writer, endian, ())
+        .map_err(|error| error.with_message(format!("While writing section `{}`", kind.name())))
+}
+
+/// A reference to an object by its section and index.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub struct ObjectRef {
+    /// The section containing the object.
+    pub section: SectionKind,
+
+    /// The index of the object within the section.
+    pub index: usize,
 }
 
-impl Version for Lvd {
-    fn version(&self) -> u8 {
-        match self {
-            Self::V1 { .. } => 1,
-            Self::V2 { .. } => 2,
-            Self::V3 { .. } => 3,
-            Self::V4 { .. } => 4,
-            Self::V5 { .. } => 5,
-            Self::V6 { .. } => 6,
-            Self::V7 { .. } => 7,
-            Self::V8 { .. } => 8,
-            Self::V9 { .. } => 9,
-            Self::V10 { .. } => 10,
-            Self::V11 { .. } => 11,
-            Self::V12 { .. } => 12,
-            Self::V13 { .. } => 13,
+impl ObjectRef {
+    /// Creates a new `ObjectRef` from the given section and index.
+    pub const fn new(section: SectionKind, index: usize) -> Self {
+        Self { section, index }
+    }
+}
+
+/// Generates the [`Lvd`] type, the [`SectionKind`] type, and the section accessors of [`Lvd`]
+/// from a table of each section's kind, name, and object type, and a table of each version's
+/// number and sections in file order.
+///
+/// The accessors for every section and the methods running over every object, such as
+/// [`Lvd::for_each_object_mut`], are generated from the same tables as the type, so they cover
+/// each section a version adds without being updated by hand.
+macro_rules! lvd_sections {
+    (
+        sections {
+            $($kind:ident: $name:ident, $name_mut:ident: $ty:ty;)+
+        }
+
+        versions {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident = $number:literal: [$($field:ident),+ $(,)?];
+            )+
+        }
+    ) => {
+        /// The object type and kind of each section, named after the section.
+        #[allow(non_camel_case_types, non_upper_case_globals)]
+        mod section_table {
+            use super::*;
+
+            $(
+                pub type $name = $ty;
+                pub const $name: SectionKind = SectionKind::$kind;
+            )+
+        }
+
+        /// The associated data for each LVD file format version.
+        #[binrw]
+        #[br(import(version: u8))]
+        #[brw(magic = b"\x01LVD1")]
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        #[cfg_attr(feature = "schemars", derive(JsonSchema))]
+        #[derive(Debug, Clone)]
+        pub enum Lvd {
+            $(
+                $(#[$variant_meta])*
+                #[br(pre_assert(version == $number))]
+                $variant {
+                    $(
+                        #[br(parse_with = read_section, args(section_table::$field))]
+                        #[bw(write_with = write_section, args(section_table::$field))]
+                        $field: Versioned<Array<section_table::$field>>,
+                    )+
+                    #[cfg_attr(
+                        feature = "serde",
+                        serde(default, skip_serializing_if = "UnknownSection::is_empty")
+                    )]
+                    unknown_sections: UnknownSection,
+                },
+            )+
         }
+
+        impl Version for Lvd {
+            fn version(&self) -> u8 {
+                match self {
+                    $(Self::$variant { .. } => $number,)+
+                }
+            }
+        }
+
+        /// The kinds of sections in an LVD file.
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        #[cfg_attr(feature = "schemars", derive(JsonSchema))]
+        #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+        #[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        pub enum SectionKind {
+            $(
+                #[doc = concat!("The `", stringify!($name), "` section.")]
+                $kind,
+            )+
+        }
+
+        impl SectionKind {
+            /// The collection of every section kind in file order.
+            pub const ALL: &'static [Self] = &[$(Self::$kind),+];
+
+            /// Returns the name of the section as used by [`Lvd`]'s fields.
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Self::$kind => stringify!($name),)+
+                }
+            }
+
+            /// Returns the name of the type of the section's objects, such as `Collision`.
+            pub const fn type_name(self) -> &'static str {
+                match self {
+                    $(Self::$kind => stringify!($ty),)+
+                }
+            }
+
+            /// Returns the section kind with the given name as used by [`Lvd`]'s fields,
+            /// or `None` if no section has the name.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use lvd_lib::SectionKind;
+            ///
+            /// assert_eq!(SectionKind::from_name("collisions"), Some(SectionKind::Collisions));
+            /// assert_eq!(SectionKind::from_name("spawns"), None);
+            /// ```
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $(stringify!($name) => Some(Self::$kind),)+
+                    _ => None,
+                }
+            }
+
+            /// Returns `true` if the given LVD file format version supports the section.
+            pub fn is_supported_by(self, version: u8) -> bool {
+                let sections: &[Self] = match version {
+                    $($number => &[$(section_table::$field),+],)+
+                    _ => &[],
+                };
+
+                sections.contains(&self)
+            }
+
+            /// Returns the serialized form of an object of the section's type with default values.
+            #[cfg(feature = "serde")]
+            pub(crate) fn default_object_value(self) -> serde_json::Value {
+                match self {
+                    $(Self::$kind => serde_json::to_value(<$ty>::default()).unwrap(),)+
+                }
+            }
+        }
+
+        impl fmt::Display for SectionKind {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+
+        impl Lvd {
+            /// Returns the data of the given file format version with every section empty,
+            /// or `None` if the version is unknown.
+            pub fn empty(version: u8) -> Option<Self> {
+                match version {
+                    $($number => Some(Self::$variant {
+                        $($field: Versioned::new(Array::default()),)+
+                        unknown_sections: UnknownSection::default(),
+                    }),)+
+                    _ => None,
+                }
+            }
+
+            /// Returns a copy of the data converted to the given file format version,
+            /// or `None` if the version is unknown.
+            ///
+            /// Sections not supported by the version are dropped, and sections not present in
+            /// the data are left empty. The raw bytes following the known sections are only kept
+            /// if the version is unchanged, as their meaning depends on the version.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use lvd_lib::{version::Version, Lvd, SectionKind};
+            ///
+            /// let data = Lvd::empty(13).unwrap().to_version(10).unwrap();
+            ///
+            /// assert_eq!(data.version(), 10);
+            /// assert!(!data.has_section(SectionKind::PTrainerRanges));
+            /// ```
+            pub fn to_version(&self, version: u8) -> Option<Self> {
+                if version == self.version() {
+                    return Some(self.clone());
+                }
+
+                let mut data = Self::empty(version)?;
+
+                $(
+                    if let (Some(section), Some(source)) = (data.$name_mut(), self.$name()) {
+                        *section = source.clone();
+                    }
+                )+
+
+                Some(data)
+            }
+
+            /// Returns the raw bytes following the known sections.
+            pub fn unknown_sections(&self) -> &UnknownSection {
+                match self {
+                    $(Self::$variant { unknown_sections, .. })|+ => unknown_sections,
+                }
+            }
+
+            /// Returns the raw bytes following the known sections mutably.
+            pub fn unknown_sections_mut(&mut self) -> &mut UnknownSection {
+                match self {
+                    $(Self::$variant { unknown_sections, .. })|+ => unknown_sections,
+                }
+            }
+
+            /// Returns an iterator over the kind and contents of each section supported by the
+            /// version, in file order.
+            ///
+            /// Each section's contents are an [`Array`] of the section's object type,
+            /// which can be recovered with the `downcast_ref` method of [`dyn Any`](Any).
+            ///
+            /// # Examples
+            ///
+            /// ```no_run
+            /// use lvd_lib::{array::Array, objects::Collision, LvdFile, SectionKind};
+            ///
+            /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+            ///
+            /// for (kind, section) in file.data.sections() {
+            ///     if kind == SectionKind::Collisions {
+            ///         assert!(section.downcast_ref::<Array<Collision>>().is_some());
+            ///     }
+            /// }
+            /// ```
+            pub fn sections(&self) -> impl Iterator<Item = (SectionKind, &dyn Any)> {
+                let sections: Vec<(SectionKind, &dyn Any)> = match self {
+                    $(Self::$variant { $($field,)+ .. } => vec![
+                        $((section_table::$field, &$field.inner as &dyn Any),)+
+                    ],)+
+                };
+
+                sections.into_iter()
+            }
+
+            /// Returns the contents of the given section, if supported by the version.
+            fn section(&self, kind: SectionKind) -> Option<&dyn Any> {
+                match self {
+                    $(Self::$variant { $($field,)+ .. } => match kind {
+                        $(section_table::$field => Some(&$field.inner as &dyn Any),)+
+                        #[allow(unreachable_patterns)]
+                        _ => None,
+                    },)+
+                }
+            }
+
+            /// Returns the contents of the given section mutably, if supported by the version.
+            fn section_mut(&mut self, kind: SectionKind) -> Option<&mut dyn Any> {
+                match self {
+                    $(Self::$variant { $($field,)+ .. } => match kind {
+                        $(section_table::$field => Some(&mut $field.inner as &mut dyn Any),)+
+                        #[allow(unreachable_patterns)]
+                        _ => None,
+                    },)+
+                }
+            }
+
+            /// Returns `true` if the version supports the given section.
+            pub fn has_section(&self, kind: SectionKind) -> bool {
+                self.section(kind).is_some()
+            }
+
+            /// Returns the number of objects in the given section, or `None` if the section is not supported by the version.
+            pub fn section_len(&self, kind: SectionKind) -> Option<usize> {
+                match kind {
+                    $(SectionKind::$kind => self.$name().map(Array::len),)+
+                }
+            }
+
+            $(
+                #[doc = concat!("Returns the `", stringify!($name), "` section, if supported by the version.")]
+                pub fn $name(&self) -> Option<&Array<$ty>> {
+                    self.section(SectionKind::$kind)?.downcast_ref()
+                }
+
+                #[doc = concat!("Returns the `", stringify!($name), "` section mutably, if supported by the version.")]
+                pub fn $name_mut(&mut self) -> Option<&mut Array<$ty>> {
+                    self.section_mut(SectionKind::$kind)?.downcast_mut()
+                }
+            )+
+
+            /// Appends a copy of the object at the given index to the end of its section,
+            /// returning the index of the copy.
+            pub fn clone_object(&mut self, kind: SectionKind, index: usize) -> Option<usize> {
+                match kind {
+                    $(SectionKind::$kind => {
+                        let section = self.$name_mut()?;
+                        let object = section.elements().get(index)?.clone();
+
+                        section.elements_mut().push(object);
+
+                        Some(section.len() - 1)
+                    })+
+                }
+            }
+
+            /// Returns the object at the given index in the given section.
+            pub fn object(&self, kind: SectionKind, index: usize) -> Option<&dyn LvdObject> {
+                match kind {
+                    $(SectionKind::$kind => self
+                        .$name()
+                        .and_then(|section| section.elements().get(index))
+                        .map(|object| &object.inner as &dyn LvdObject),)+
+                }
+            }
+
+            /// Returns the object at the given index in the given section mutably.
+            pub fn object_mut(&mut self, kind: SectionKind, index: usize) -> Option<&mut dyn LvdObject> {
+                match kind {
+                    $(SectionKind::$kind => self
+                        .$name_mut()
+                        .and_then(|section| section.elements_mut().get_mut(index))
+                        .map(|object| &mut object.inner as &mut dyn LvdObject),)+
+                }
+            }
+
+            /// Calls a closure on each top-level object in every section.
+            pub fn for_each_object<F: FnMut(&dyn LvdObject)>(&self, mut f: F) {
+                $(
+                    if let Some(section) = self.$name() {
+                        section.iter().for_each(|object| f(object));
+                    }
+                )+
+            }
+
+            /// Calls a closure on each top-level object in every section mutably.
+            pub fn for_each_object_mut<F: FnMut(&mut dyn LvdObject)>(&mut self, mut f: F) {
+                $(
+                    if let Some(section) = self.$name_mut() {
+                        section.iter_mut().for_each(|object| f(object));
+                    }
+                )+
+            }
+
+            /// Sorts the objects within each section by the key returned by a closure.
+            ///
+            /// The sort is stable, so objects with equal keys keep their relative order.
+            pub fn sort_objects_by_key<K: Ord, F: FnMut(&dyn LvdObject) -> K>(&mut self, mut f: F) {
+                $(
+                    if let Some(section) = self.$name_mut() {
+                        section
+                            .elements_mut()
+                            .sort_by_cached_key(|object| f(&object.inner));
+                    }
+                )+
+            }
+
+            /// Calls the visitor's callback for each top-level object in every section, in file order.
+            pub fn visit<V: LvdVisitor + ?Sized>(&self, visitor: &mut V) {
+                $(
+                    if let Some(section) = self.$name() {
+                        for (index, object) in section.iter().enumerate() {
+                            object.accept(ObjectRef::new(SectionKind::$kind, index), visitor);
+                        }
+                    }
+                )+
+            }
+
+            /// Calls the mutable visitor's callback for each top-level object in every section,
+            /// in file order.
+            pub fn visit_mut<V: LvdVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+                $(
+                    if let Some(section) = self.$name_mut() {
+                        for (index, object) in section.iter_mut().enumerate() {
+                            object.accept_mut(ObjectRef::new(SectionKind::$kind, index), visitor);
+                        }
+                    }
+                )+
+            }
+        }
+    };
+}
+
+lvd_sections! {
+    sections {
+        Collisions: collisions, collisions_mut: Collision;
+        StartPositions: start_positions, start_positions_mut: Point;
+        RestartPositions: restart_positions, restart_positions_mut: Point;
+        CameraRegions: camera_regions, camera_regions_mut: Region;
+        DeathRegions: death_regions, death_regions_mut: Region;
+        EnemyGenerators: enemy_generators, enemy_generators_mut: EnemyGenerator;
+        FsItems: fs_items, fs_items_mut: FsItem;
+        FsUnknown: fs_unknown, fs_unknown_mut: FsUnknown;
+        FsAreaCams: fs_area_cams, fs_area_cams_mut: FsAreaCam;
+        FsAreaLocks: fs_area_locks, fs_area_locks_mut: FsAreaLock;
+        FsCamLimits: fs_cam_limits, fs_cam_limits_mut: FsCamLimit;
+        DamageShapes: damage_shapes, damage_shapes_mut: DamageShape;
+        ItemPopups: item_popups, item_popups_mut: ItemPopup;
+        PTrainerRanges: ptrainer_ranges, ptrainer_ranges_mut: PTrainerRange;
+        PTrainerFloatingFloors: ptrainer_floating_floors, ptrainer_floating_floors_mut: PTrainerFloatingFloor;
+        GeneralShapes2: general_shapes2, general_shapes2_mut: GeneralShape2;
+        GeneralShapes3: general_shapes3, general_shapes3_mut: GeneralShape3;
+        AreaLights: area_lights, area_lights_mut: AreaLight;
+        FsStartPoints: fs_start_points, fs_start_points_mut: FsStartPoint;
+        AreaHints: area_hints, area_hints_mut: AreaHint;
+        SplitAreas: split_areas, split_areas_mut: SplitArea;
+        ShrinkedCameraRegions: shrinked_camera_regions, shrinked_camera_regions_mut: Region;
+        ShrinkedDeathRegions: shrinked_death_regions, shrinked_death_regions_mut: Region;
+    }
+
+    versions {
+        /// The first version of the `Lvd` type.
+        ///
+        /// This version is not known to be used.
+        V1 = 1: [
+            collisions, start_positions, restart_positions, camera_regions, death_regions,
+            enemy_generators,
+        ];
+
+        /// The second version of the `Lvd` type.
+        ///
+        /// Adds [`fs_items`](#variant.V2.field.fs_items).
+        /// This version is not known to be used.
+        V2 = 2: [
+            collisions, start_positions, restart_positions, camera_regions, death_regions,
+            enemy_generators, fs_items,
+        ];
+
+        /// The third version of the `Lvd` type.
+        ///
+        /// Adds [`fs_unknown`](#variant.V3.field.fs_unknown), [`fs_area_cams`](#variant.V3.field.fs_area_cams), [`fs_area_locks`](#variant.V3.field.fs_area_locks), and [`fs_cam_limits`](#variant.V3.field.fs_cam_limits).
+        /// This version is not known to be used.
+        V3 = 3: [
+            collisions, start_positions, restart_positions, camera_regions, death_regions,
+            enemy_generators, fs_items, fs_unknown, fs_area_cams, fs_area_locks, fs_cam_limits,
+        ];
+
+        /// The fourth version of the `Lvd` type.
+        ///
+        /// Adds [`damage_shapes`](#variant.V4.field.damage_shapes).
+        V4 = 4: [
+            collisions, start_positions, restart_positions, camera_regions, death_regions,
+            enemy_generators, fs_items, fs_unknown, fs_area_cams, fs_area_locks, fs_cam_limits,
+            damage_shapes,
+        ];
+
+        /// The fifth version of the `Lvd` type.
+        ///
+        /// Adds [`item_popups`](#variant.V5.field.item_popups).
+        /// This version is not known to be used.
+        V5 = 5: [
+            collisions, start_positions, restart_positions, camera_regions, death_regions,
+            enemy_generators, fs_items, fs_unknown, fs_area_cams, fs_area_locks, fs_cam_limits,
+            damage_shapes, item_popups,
+        ];
+
+        /// The sixth version of the `Lvd` type.
+        ///
+        /// Adds [`general_shapes2`](#variant.V6.field.general_shapes2) and [`general_shapes3`](#variant.V6.field.general_shapes3).
+        V6 = 6: [
+            collisions, start_positions, restart_positions, camera_regions, death_regions,
+            enemy_generators, fs_items, fs_unknown, fs_area_cams, fs_area_locks, fs_cam_limits,
+            damage_shapes, item_popups, general_shapes2, general_shapes3,
+        ];
+
+        /// The seventh version of the `Lvd` type.
+        ///
+        /// Adds [`area_lights`](#variant.V7.field.area_lights).
+        /// This version is not known to be used.
+        V7 = 7: [
+            collisions, start_positions, restart_positions, camera_regions, death_regions,
+            enemy_generators, fs_items, fs_unknown, fs_area_cams, fs_area_locks, fs_cam_limits,
+            damage_shapes, item_popups, general_shapes2, general_shapes3, area_lights,
+        ];
+
+        /// The eighth version of the `Lvd` type.
+        ///
+        /// Adds [`fs_start_points`](#variant.V8.field.fs_start_points).
+        V8 = 8: [
+            collisions, start_positions, restart_positions, camera_regions, death_regions,
+            enemy_generators, fs_items, fs_unknown, fs_area_cams, fs_area_locks, fs_cam_limits,
+            damage_shapes, item_popups, general_shapes2, general_shapes3, area_lights,
+            fs_start_points,
+        ];
+
+        /// The ninth version of the `Lvd` type.
+        ///
+        /// Adds [`area_hints`](#variant.V9.field.area_hints).
+        /// This version is not known to be used.
+        V9 = 9: [
+            collisions, start_positions, restart_positions, camera_regions, death_regions,
+            enemy_generators, fs_items, fs_unknown, fs_area_cams, fs_area_locks, fs_cam_limits,
+            damage_shapes, item_popups, general_shapes2, general_shapes3, area_lights,
+            fs_start_points, area_hints,
+        ];
+
+        /// The tenth version of the `Lvd` type.
+        ///
+        /// Adds [`split_areas`](#variant.V10.field.split_areas).
+        V10 = 10: [
+            collisions, start_positions, restart_positions, camera_regions, death_regions,
+            enemy_generators, fs_items, fs_unknown, fs_area_cams, fs_area_locks, fs_cam_limits,
+            damage_shapes, item_popups, general_shapes2, general_shapes3, area_lights,
+            fs_start_points, area_hints, split_areas,
+        ];
+
+        /// The eleventh version of the `Lvd` type.
+        ///
+        /// Adds [`shrinked_camera_regions`](#variant.V11.field.shrinked_camera_regions) and [`shrinked_death_regions`](#variant.V11.field.shrinked_death_regions).
+        V11 = 11: [
+            collisions, start_positions, restart_positions, camera_regions, death_regions,
+            enemy_generators, fs_items, fs_unknown, fs_area_cams, fs_area_locks, fs_cam_limits,
+            damage_shapes, item_popups, general_shapes2, general_shapes3, area_lights,
+            fs_start_points, area_hints, split_areas, shrinked_camera_regions,
+            shrinked_death_regions,
+        ];
+
+        /// The twelfth version of the `Lvd` type.
+        ///
+        /// Adds [`ptrainer_ranges`](#variant.V12.field.ptrainer_ranges).
+        V12 = 12: [
+            collisions, start_positions, restart_positions, camera_regions, death_regions,
+            enemy_generators, fs_items, fs_unknown, fs_area_cams, fs_area_locks, fs_cam_limits,
+            damage_shapes, item_popups, ptrainer_ranges, general_shapes2, general_shapes3,
+            area_lights, fs_start_points, area_hints, split_areas, shrinked_camera_regions,
+            shrinked_death_regions,
+        ];
+
+        /// The thirteenth version of the `Lvd` type.
+        ///
+        /// Adds [`ptrainer_floating_floors`](#variant.V13.field.ptrainer_floating_floors).
+        V13 = 13: [
+            collisions, start_positions, restart_positions, camera_regions, death_regions,
+            enemy_generators, fs_items, fs_unknown, fs_area_cams, fs_area_locks, fs_cam_limits,
+            damage_shapes, item_popups, ptrainer_ranges, ptrainer_floating_floors,
+            general_shapes2, general_shapes3, area_lights, fs_start_points, area_hints,
+            split_areas, shrinked_camera_regions, shrinked_death_regions,
+        ];
     }
 }
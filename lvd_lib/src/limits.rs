@@ -0,0 +1,332 @@
+//! Detection and clamping of coordinates beyond the range the game handles stably.
+//!
+//! Positions far from the origin, such as those of a model exported at the wrong scale, are read
+//! and written unchanged but lose precision in the game's physics, causing characters to jitter or
+//! fall through floors. This module contains the [`CoordinateLimits`] type describing the stable
+//! range, the [`LvdFile::out_of_range_objects`] and [`LvdFile::clamp_coordinates`] methods, and
+//! the [`ClampCoordinates`] coordinate mapping for applying the same clamping to individual
+//! objects.
+
+use std::cell::Cell;
+
+use crate::{
+    transform::CoordinateMap,
+    vector::{Vector2, Vector3},
+    LvdFile, ObjectRef, SectionKind,
+};
+
+/// The largest distance from the origin along each axis at which positions are considered stable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoordinateLimits {
+    /// The largest absolute x coordinate.
+    pub x: f32,
+
+    /// The largest absolute y coordinate.
+    pub y: f32,
+
+    /// The largest absolute z coordinate.
+    pub z: f32,
+}
+
+impl CoordinateLimits {
+    /// The range beyond which the game's physics is known to become unstable.
+    pub const STABLE: Self = Self::uniform(10000.0);
+
+    /// Returns limits with the same distance along every axis.
+    pub const fn uniform(limit: f32) -> Self {
+        Self {
+            x: limit,
+            y: limit,
+            z: limit,
+        }
+    }
+
+    /// Returns `true` if the two-dimensional position is within the limits.
+    ///
+    /// Values which are not finite are considered within the limits,
+    /// as they are handled by the [`finite`](crate::finite) module instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{limits::CoordinateLimits, vector::Vector2};
+    ///
+    /// let limits = CoordinateLimits::uniform(100.0);
+    ///
+    /// assert!(limits.contains2(Vector2::new(-100.0, 50.0)));
+    /// assert!(!limits.contains2(Vector2::new(0.0, 150.0)));
+    /// ```
+    pub fn contains2(&self, point: Vector2) -> bool {
+        self.exceeded2(point) == 0
+    }
+
+    /// Returns `true` if the three-dimensional position is within the limits.
+    ///
+    /// As with [`CoordinateLimits::contains2`], values which are not finite are considered
+    /// within the limits.
+    pub fn contains3(&self, point: Vector3) -> bool {
+        self.exceeded3(point) == 0
+    }
+
+    /// Returns the number of coordinates of the two-dimensional position beyond the limits.
+    fn exceeded2(&self, point: Vector2) -> usize {
+        [(point.x(), self.x), (point.y(), self.y)]
+            .into_iter()
+            .filter(|&(value, limit)| is_beyond(value, limit))
+            .count()
+    }
+
+    /// Returns the number of coordinates of the three-dimensional position beyond the limits.
+    fn exceeded3(&self, point: Vector3) -> usize {
+        [
+            (point.x(), self.x),
+            (point.y(), self.y),
+            (point.z(), self.z),
+        ]
+        .into_iter()
+        .filter(|&(value, limit)| is_beyond(value, limit))
+        .count()
+    }
+}
+
+impl Default for CoordinateLimits {
+    fn default() -> Self {
+        Self::STABLE
+    }
+}
+
+/// A coordinate mapping which moves every position beyond the limits onto them,
+/// leaving directions, normals, sizes, and values which are not finite unchanged.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ClampCoordinates(pub CoordinateLimits);
+
+impl ClampCoordinates {
+    /// Returns the value limited to the range from `-limit` to `limit`,
+    /// or the value unchanged if it is not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::limits::ClampCoordinates;
+    ///
+    /// assert_eq!(ClampCoordinates::clamp(-12000.0, 10000.0), -10000.0);
+    /// assert_eq!(ClampCoordinates::clamp(25.0, 10000.0), 25.0);
+    /// assert!(ClampCoordinates::clamp(f32::NAN, 10000.0).is_nan());
+    /// ```
+    pub fn clamp(value: f32, limit: f32) -> f32 {
+        if is_beyond(value, limit) {
+            limit.copysign(value)
+        } else {
+            value
+        }
+    }
+}
+
+impl CoordinateMap for ClampCoordinates {
+    fn map_point2(&self, point: Vector2) -> Vector2 {
+        Vector2::new(
+            Self::clamp(point.x(), self.0.x),
+            Self::clamp(point.y(), self.0.y),
+        )
+    }
+
+    fn map_point3(&self, point: Vector3) -> Vector3 {
+        Vector3::new(
+            Self::clamp(point.x(), self.0.x),
+            Self::clamp(point.y(), self.0.y),
+            Self::clamp(point.z(), self.0.z),
+        )
+    }
+
+    fn map_vector3(&self, vector: Vector3) -> Vector3 {
+        vector
+    }
+
+    fn map_normal2(&self, normal: Vector2) -> Vector2 {
+        normal
+    }
+
+    fn map_length2(&self, length: f32) -> f32 {
+        length
+    }
+
+    fn map_length3(&self, length: f32) -> f32 {
+        length
+    }
+}
+
+/// A coordinate mapping which counts the coordinates of positions beyond the limits,
+/// leaving every value unchanged.
+struct CountOutOfRange {
+    limits: CoordinateLimits,
+    count: Cell<usize>,
+}
+
+impl CoordinateMap for CountOutOfRange {
+    fn map_point2(&self, point: Vector2) -> Vector2 {
+        self.count
+            .set(self.count.get() + self.limits.exceeded2(point));
+
+        point
+    }
+
+    fn map_point3(&self, point: Vector3) -> Vector3 {
+        self.count
+            .set(self.count.get() + self.limits.exceeded3(point));
+
+        point
+    }
+
+    fn map_vector3(&self, vector: Vector3) -> Vector3 {
+        vector
+    }
+
+    fn map_normal2(&self, normal: Vector2) -> Vector2 {
+        normal
+    }
+
+    fn map_length2(&self, length: f32) -> f32 {
+        length
+    }
+
+    fn map_length3(&self, length: f32) -> f32 {
+        length
+    }
+}
+
+impl LvdFile {
+    /// Returns each object with positions beyond the limits, in file order,
+    /// with the number of coordinates beyond them.
+    ///
+    /// As with [`LvdObject::transform`](crate::objects::LvdObject::transform), objects exclusive
+    /// to Smash Run are not checked.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{limits::CoordinateLimits, LvdFile};
+    ///
+    /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    ///
+    /// for (object, count) in file.out_of_range_objects(CoordinateLimits::STABLE) {
+    ///     println!("object {} in `{}`: {count}", object.index, object.section.name());
+    /// }
+    /// ```
+    pub fn out_of_range_objects(&self, limits: CoordinateLimits) -> Vec<(ObjectRef, usize)> {
+        // Objects are only reachable mutably through the coordinate mapping,
+        // so a copy is checked with a mapping which leaves it unchanged.
+        let mut copy = self.clone();
+        let mut objects = Vec::new();
+
+        for &section in SectionKind::ALL {
+            for index in 0..copy.data.section_len(section).unwrap_or_default() {
+                let counter = CountOutOfRange {
+                    limits,
+                    count: Cell::new(0),
+                };
+
+                if let Some(object) = copy.data.object_mut(section, index) {
+                    object.transform(&counter);
+                }
+
+                if counter.count.get() > 0 {
+                    objects.push((ObjectRef::new(section, index), counter.count.get()));
+                }
+            }
+        }
+
+        objects
+    }
+
+    /// Moves every position beyond the limits onto them, one coordinate at a time.
+    ///
+    /// As with [`LvdFile::out_of_range_objects`], objects exclusive to Smash Run are left
+    /// unchanged.
+    pub fn clamp_coordinates(&mut self, limits: CoordinateLimits) {
+        let clamp = ClampCoordinates(limits);
+
+        self.data
+            .for_each_object_mut(|object| object.transform(&clamp));
+    }
+}
+
+/// Returns `true` if the value is finite and further from zero than the limit.
+fn is_beyond(value: f32, limit: f32) -> bool {
+    value.is_finite() && value.abs() > limit
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures::file_with_collisions, objects::Collision};
+
+    use super::*;
+
+    #[test]
+    fn clamp_out_of_range_coordinates() {
+        let mut file = file_with_collisions([
+            Collision::new_soft_platform(Vector2::new(-12000.0, 0.0), Vector2::new(10.0, 20000.0)),
+            Collision::new_soft_platform(
+                Vector2::new(-10.0, f32::INFINITY),
+                Vector2::new(10.0, 0.0),
+            ),
+        ]);
+        let limits = CoordinateLimits::STABLE;
+
+        assert_eq!(
+            file.out_of_range_objects(limits),
+            [(ObjectRef::new(SectionKind::Collisions, 0), 4)]
+        );
+
+        file.clamp_coordinates(limits);
+
+        let vertices = file.data.collisions().unwrap().elements()[0]
+            .vertices()
+            .iter()
+            .copied()
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vertices,
+            [Vector2::new(-10000.0, 0.0), Vector2::new(10.0, 10000.0)]
+        );
+        assert!(file.out_of_range_objects(limits).is_empty());
+        assert_eq!(file.non_finite_objects().len(), 1);
+    }
+
+    #[test]
+    fn limits_are_inclusive_per_axis() {
+        let limits = CoordinateLimits {
+            x: 100.0,
+            y: 50.0,
+            z: 10.0,
+        };
+
+        assert!(limits.contains2(Vector2::new(100.0, -50.0)));
+        assert!(!limits.contains2(Vector2::new(100.0, 50.5)));
+        assert!(limits.contains3(Vector3::new(-100.0, 50.0, -10.0)));
+        assert!(!limits.contains3(Vector3::new(0.0, 0.0, 10.5)));
+        assert!(limits.contains3(Vector3::new(f32::NEG_INFINITY, f32::NAN, 0.0)));
+        assert_eq!(limits.exceeded3(Vector3::new(101.0, 51.0, 11.0)), 3);
+
+        let mut file = file_with_collisions([Collision::new_soft_platform(
+            Vector2::new(-100.0, 60.0),
+            Vector2::new(100.0, 50.0),
+        )]);
+
+        // The cliff at the end of the platform shares the position of its vertex.
+        assert_eq!(
+            file.out_of_range_objects(limits),
+            [(ObjectRef::new(SectionKind::Collisions, 0), 2)]
+        );
+
+        file.clamp_coordinates(limits);
+
+        assert_eq!(
+            file.data.collisions().unwrap().elements()[0]
+                .vertices()
+                .iter()
+                .next(),
+            Some(&Vector2::new(-100.0, 50.0))
+        );
+    }
+}
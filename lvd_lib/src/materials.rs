@@ -0,0 +1,221 @@
+//! Queries and statistics of the materials and attributes of collision edges.
+//!
+//! This module contains the [`LvdFile::edges_with_material`] method, which finds every edge of a
+//! given material, and the [`LvdFile::material_stats`] method, which counts the edges using each
+//! material and attribute, such as to check that no damaging floors remain after cloning a stage
+//! with hazards.
+
+use std::fmt;
+
+use crate::{
+    array::Array,
+    objects::collision::attribute::{AttributeFlags, MaterialType},
+    LvdFile, ObjectRef, SectionKind,
+};
+
+/// A reference to an edge of a collision.
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub struct EdgeRef {
+    /// The collision containing the edge.
+    pub object: ObjectRef,
+
+    /// The index of the edge in the collision.
+    pub edge: usize,
+}
+
+impl fmt::Display for EdgeRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "object {} in section `{}`: edge {}",
+            self.object.index,
+            self.object.section.name(),
+            self.edge
+        )
+    }
+}
+
+/// The number of collision edges using each material and attribute.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MaterialStats {
+    /// The number of edges with properties, which excludes the edges of collisions whose version
+    /// does not store them.
+    pub edges: usize,
+
+    /// The number of edges using each material, in order of the material's numeric value.
+    ///
+    /// Materials not used by any edge are omitted.
+    pub materials: Vec<(MaterialType, usize)>,
+
+    /// The number of edges with each attribute set, in the order of [`AttributeFlags::NAMES`].
+    ///
+    /// Attributes not set on any edge are omitted.
+    pub flags: Vec<(&'static str, usize)>,
+}
+
+impl fmt::Display for MaterialStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} edges", self.edges)?;
+        writeln!(f, "materials:")?;
+
+        for (material, count) in &self.materials {
+            match material.alias() {
+                Some(alias) => writeln!(f, "  {alias}: {count}")?,
+                None => writeln!(f, "  {}: {count}", u32::from(*material))?,
+            }
+        }
+
+        writeln!(f, "attributes:")?;
+
+        for (name, count) in &self.flags {
+            writeln!(f, "  {name}: {count}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl LvdFile {
+    /// Returns every collision edge using the given material.
+    ///
+    /// Edges of collisions whose version does not store edge properties are never returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{objects::collision::attribute::MaterialType, LvdFile};
+    ///
+    /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    ///
+    /// for edge in file.edges_with_material(MaterialType::Damage1) {
+    ///     println!("{edge}");
+    /// }
+    /// ```
+    pub fn edges_with_material(&self, material: MaterialType) -> Vec<EdgeRef> {
+        self.edge_materials()
+            .filter(|&(_, edge_material, _)| edge_material == material)
+            .map(|(edge, _, _)| edge)
+            .collect()
+    }
+
+    /// Returns the number of collision edges using each material and attribute.
+    pub fn material_stats(&self) -> MaterialStats {
+        let mut stats = MaterialStats::default();
+        let mut flag_counts = [0; AttributeFlags::NAMES.len()];
+
+        for (_, material, flags) in self.edge_materials() {
+            stats.edges += 1;
+
+            match stats.materials.iter_mut().find(|(m, _)| *m == material) {
+                Some((_, count)) => *count += 1,
+                None => stats.materials.push((material, 1)),
+            }
+
+            for flag in flags.iter() {
+                flag_counts[flag as usize] += 1;
+            }
+        }
+
+        stats
+            .materials
+            .sort_by_key(|&(material, _)| u32::from(material));
+        stats.flags = AttributeFlags::NAMES
+            .into_iter()
+            .zip(flag_counts)
+            .filter(|&(_, count)| count > 0)
+            .collect();
+
+        stats
+    }
+
+    /// Returns an iterator over the material and attributes of every collision edge which has
+    /// properties.
+    fn edge_materials(&self) -> impl Iterator<Item = (EdgeRef, MaterialType, AttributeFlags)> + '_ {
+        self.data
+            .collisions()
+            .into_iter()
+            .flat_map(Array::iter)
+            .enumerate()
+            .flat_map(|(index, collision)| {
+                collision.edges().filter_map(move |edge| {
+                    let attribute = edge.attribute?;
+
+                    Some((
+                        EdgeRef {
+                            object: ObjectRef::new(SectionKind::Collisions, index),
+                            edge: edge.index,
+                        },
+                        attribute.material(),
+                        *attribute.flags(),
+                    ))
+                })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures::file_with_collisions, objects::Collision, vector::Vector2};
+
+    use super::*;
+
+    fn file_with_material(material: MaterialType) -> LvdFile {
+        let mut hazard =
+            Collision::new_soft_platform(Vector2::new(-10.0, 0.0), Vector2::new(10.0, 0.0));
+
+        *hazard
+            .edge_mut(0)
+            .unwrap()
+            .attribute
+            .unwrap()
+            .material_mut() = material;
+
+        file_with_collisions([
+            Collision::new_soft_platform(Vector2::new(-30.0, 10.0), Vector2::new(-20.0, 10.0)),
+            hazard,
+        ])
+    }
+
+    #[test]
+    fn edges_with_material() {
+        let file = file_with_material(MaterialType::Damage1);
+
+        assert_eq!(
+            file.edges_with_material(MaterialType::Damage1),
+            [EdgeRef {
+                object: ObjectRef::new(SectionKind::Collisions, 1),
+                edge: 0
+            }]
+        );
+        assert!(file.edges_with_material(MaterialType::Ice).is_empty());
+    }
+
+    #[test]
+    fn material_stats() {
+        let stats = file_with_material(MaterialType::Damage1).material_stats();
+
+        assert_eq!(stats.edges, 2);
+        assert_eq!(
+            stats.materials,
+            [(MaterialType::Rock, 1), (MaterialType::Damage1, 1)]
+        );
+        assert_eq!(stats.flags, [("upper", 2), ("throughable", 2)]);
+    }
+
+    #[test]
+    fn material_stats_of_unknown_materials() {
+        let file = file_with_material(MaterialType::Unknown(99));
+        let stats = file.material_stats();
+
+        assert_eq!(file.edges_with_material(MaterialType::Unknown(99)).len(), 1);
+        assert_eq!(
+            stats.materials,
+            [(MaterialType::Rock, 1), (MaterialType::Unknown(99), 1)]
+        );
+        assert!(stats.to_string().contains("\n  99: 1\n"));
+        assert_eq!(
+            file_with_collisions([]).material_stats(),
+            MaterialStats::default()
+        );
+    }
+}
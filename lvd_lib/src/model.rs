@@ -0,0 +1,644 @@
+//! A flattened data model of LVD files, decoupled from the versions of the binary format.
+//!
+//! The types in [`objects`](crate::objects) mirror the binary format, with an enum variant for
+//! each version of each type and a [`Versioned`] wrapper around each versioned field. The types in
+//! this module are plain structs holding the fields of the latest version of each type, such as
+//! [`CollisionData`] for [`Collision`], so code consuming LVD data only handles one representation.
+//!
+//! Data is converted into the model with [`From`], upgrading older versions as described by
+//! [`Upgrade::into_latest`]. It is converted back with [`LvdData::into_lvd`] or
+//! [`ModelObject::into_object`] for the versions chosen by a [`ModelVersions`], dropping the
+//! fields which the chosen versions do not have. Converting data back with the
+//! [`ModelVersions::of`] the data it came from reproduces the data exactly, as long as each type
+//! is stored in a single version throughout the file, as it is in the game's files.
+//! [`ModelVersions::of_uniform`] returns an error for data where this is not the case.
+//!
+//! # Examples
+//!
+//! ```
+//! use lvd_lib::{
+//!     model::{LvdData, ModelVersions, Vector2Data},
+//!     objects::Collision,
+//!     vector::Vector2,
+//!     version::Version,
+//!     Lvd,
+//! };
+//!
+//! let mut lvd = Lvd::empty(13).unwrap();
+//!
+//! lvd.collisions_mut()
+//!     .unwrap()
+//!     .push(Collision::new_soft_platform(Vector2::new(-10.0, 0.0), Vector2::new(10.0, 0.0)));
+//!
+//! let versions = ModelVersions::of(&lvd);
+//! let mut data = LvdData::from(lvd);
+//!
+//! data.collisions[0].vertices[0] = Vector2Data::new(-20.0, 0.0);
+//!
+//! let lvd = data.into_lvd(&versions).unwrap();
+//! let collision = &lvd.collisions().unwrap().elements()[0];
+//!
+//! assert_eq!(lvd.version(), 13);
+//! assert_eq!(collision.vertices().elements()[0].inner, Vector2::new(-20.0, 0.0));
+//! ```
+
+use std::convert::Infallible;
+
+use thiserror::Error;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+use crate::{
+    array::Array,
+    objects::{
+        base::Base,
+        collision::{CollisionCliff, CollisionSpiritsFloor},
+        *,
+    },
+    raw::UnknownSection,
+    tag::Tag,
+    version::{Upgrade, Version, Versioned},
+    Lvd, LvdFile, SectionKind,
+};
+
+pub mod base;
+pub mod collision;
+pub mod damage_shape;
+pub mod enemy_generator;
+pub mod field_smash;
+pub mod general_shape;
+pub mod item_popup;
+pub mod point;
+pub mod ptrainer;
+pub mod region;
+pub mod shape;
+pub mod vector;
+
+pub use base::BaseData;
+pub use collision::{
+    CollisionAttributeData, CollisionCliffData, CollisionData, CollisionSpiritsFloorData,
+};
+pub use damage_shape::DamageShapeData;
+pub use enemy_generator::EnemyGeneratorData;
+pub use field_smash::{
+    AreaHintData, AreaLightData, FsAreaCamData, FsAreaLockData, FsCamLimitData, FsItemData,
+    FsStartPointData, FsUnknownData, SplitAreaData,
+};
+pub use general_shape::{GeneralShape2Data, GeneralShape3Data};
+pub use item_popup::ItemPopupData;
+pub use point::PointData;
+pub use ptrainer::{PTrainerFloatingFloorData, PTrainerRangeData};
+pub use region::RegionData;
+pub use shape::{RectData, Shape2Data};
+pub use vector::{Vector2Data, Vector3Data};
+
+/// The error type used when converting the model into the binary format's types.
+#[derive(Debug, Error)]
+pub enum ModelError {
+    /// The chosen version of a type is not known to this library.
+    #[error("version {version} of `{type_name}` is not supported")]
+    UnsupportedVersion {
+        /// The name of the type.
+        type_name: &'static str,
+
+        /// The chosen version.
+        version: u8,
+    },
+
+    /// A section with objects is not supported by the chosen file format version.
+    #[error("section `{}` is not supported by version {version}", section.name())]
+    UnsupportedSection {
+        /// The unsupported section.
+        section: SectionKind,
+
+        /// The chosen file format version.
+        version: u8,
+    },
+}
+
+impl ModelError {
+    /// Returns an error for a chosen version of the named type.
+    pub(crate) const fn version(type_name: &'static str, version: u8) -> Self {
+        Self::UnsupportedVersion { type_name, version }
+    }
+}
+
+/// The error type used when a type is stored in more than one version throughout an LVD file.
+#[derive(Debug, Error)]
+#[error("`{type_name}` is stored in both version {version} and version {other}")]
+pub struct MixedVersionsError {
+    /// The name of the type.
+    pub type_name: &'static str,
+
+    /// The version of the type's first instance.
+    pub version: u8,
+
+    /// The first version of the type differing from the version of its first instance.
+    pub other: u8,
+}
+
+/// The versions of the binary format's types to convert the model into.
+///
+/// Only the types with more than one known version are listed. Every other type has a single
+/// version, which is always used.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub struct ModelVersions {
+    /// The file format version, used by [`Lvd`].
+    pub lvd: u8,
+
+    /// The version of [`Base`].
+    pub base: u8,
+
+    /// The version of [`Collision`].
+    pub collision: u8,
+
+    /// The version of [`CollisionCliff`].
+    pub collision_cliff: u8,
+
+    /// The version of [`CollisionSpiritsFloor`].
+    pub collision_spirits_floor: u8,
+
+    /// The version of [`Point`].
+    pub point: u8,
+
+    /// The version of [`Region`].
+    pub region: u8,
+
+    /// The version of [`EnemyGenerator`].
+    pub enemy_generator: u8,
+
+    /// The version of [`PTrainerRange`].
+    pub ptrainer_range: u8,
+
+    /// The version of [`FsUnknown`].
+    pub fs_unknown: u8,
+
+    /// The version of [`FsAreaLock`].
+    pub fs_area_lock: u8,
+
+    /// The version of [`AreaLight`].
+    pub area_light: u8,
+
+    /// The version of [`AreaHint`].
+    pub area_hint: u8,
+}
+
+impl ModelVersions {
+    /// The latest file format version.
+    pub const LATEST_LVD_VERSION: u8 = 13;
+
+    /// Returns the latest version of every type.
+    pub const fn latest() -> Self {
+        Self {
+            lvd: Self::LATEST_LVD_VERSION,
+            base: Base::LATEST_VERSION,
+            collision: Collision::LATEST_VERSION,
+            collision_cliff: CollisionCliff::LATEST_VERSION,
+            collision_spirits_floor: CollisionSpiritsFloor::LATEST_VERSION,
+            point: Point::LATEST_VERSION,
+            region: Region::LATEST_VERSION,
+            enemy_generator: EnemyGenerator::LATEST_VERSION,
+            ptrainer_range: PTrainerRange::LATEST_VERSION,
+            fs_unknown: FsUnknown::LATEST_VERSION,
+            fs_area_lock: FsAreaLock::LATEST_VERSION,
+            area_light: AreaLight::LATEST_VERSION,
+            area_hint: AreaHint::LATEST_VERSION,
+        }
+    }
+
+    /// Returns the versions used by the data, taking the version of the first instance of each type.
+    ///
+    /// Types without any instances in the data use their latest version.
+    pub fn of(lvd: &Lvd) -> Self {
+        let versions = Self::choose(lvd, |_, versions, latest| {
+            Ok::<_, Infallible>(versions.next().unwrap_or(latest))
+        });
+
+        match versions {
+            Ok(versions) => versions,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Returns the versions used by the data, as with [`ModelVersions::of`],
+    /// if each type is stored in a single version throughout the data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first type stored in more than one version,
+    /// as converting the data back with any [`ModelVersions`] would change some of its objects.
+    pub fn of_uniform(lvd: &Lvd) -> Result<Self, MixedVersionsError> {
+        Self::choose(lvd, |type_name, mut versions, latest| {
+            let Some(version) = versions.next() else {
+                return Ok(latest);
+            };
+
+            match Iterator::find(&mut versions, |&other| other != version) {
+                Some(other) => Err(MixedVersionsError {
+                    type_name,
+                    version,
+                    other,
+                }),
+                None => Ok(version),
+            }
+        })
+    }
+
+    /// Returns the versions chosen by a closure given the name of each type, the versions of its
+    /// instances in the data in file order, and its latest version.
+    fn choose<E>(
+        lvd: &Lvd,
+        mut choose: impl FnMut(&'static str, &mut dyn Iterator<Item = u8>, u8) -> Result<u8, E>,
+    ) -> Result<Self, E> {
+        fn versions<'a, T: Version + 'a>(
+            objects: impl Iterator<Item = &'a T> + 'a,
+        ) -> impl Iterator<Item = u8> + 'a {
+            objects.map(Version::version)
+        }
+
+        let latest = Self::latest();
+        let collisions = || lvd.collisions().into_iter().flat_map(Array::iter);
+        let mut bases = Vec::new();
+
+        lvd.for_each_object(|object| bases.extend(object.base().map(Version::version)));
+
+        // Subobjects of collisions have their own common data.
+        for collision in collisions() {
+            let cliffs = collision.cliffs().iter().filter_map(CollisionCliff::base);
+            let spirits_floors = collision
+                .spirits_floors()
+                .into_iter()
+                .flat_map(Array::iter)
+                .filter_map(LvdObject::base);
+
+            bases.extend(cliffs.chain(spirits_floors).map(Version::version));
+        }
+
+        let regions = [
+            lvd.camera_regions(),
+            lvd.death_regions(),
+            lvd.shrinked_camera_regions(),
+            lvd.shrinked_death_regions(),
+        ];
+        let area_cam_regions =
+            lvd.fs_area_cams()
+                .into_iter()
+                .flat_map(Array::iter)
+                .map(|area_cam| match area_cam {
+                    FsAreaCam::V1 { region, .. } => &region.inner,
+                });
+
+        Ok(Self {
+            lvd: lvd.version(),
+            base: choose("Base", &mut bases.into_iter(), latest.base)?,
+            collision: choose("Collision", &mut versions(collisions()), latest.collision)?,
+            collision_cliff: choose(
+                "CollisionCliff",
+                &mut versions(collisions().flat_map(|collision| collision.cliffs().iter())),
+                latest.collision_cliff,
+            )?,
+            collision_spirits_floor: choose(
+                "CollisionSpiritsFloor",
+                &mut versions(collisions().flat_map(|collision| {
+                    collision.spirits_floors().into_iter().flat_map(Array::iter)
+                })),
+                latest.collision_spirits_floor,
+            )?,
+            point: choose(
+                "Point",
+                &mut versions(
+                    [lvd.start_positions(), lvd.restart_positions()]
+                        .into_iter()
+                        .flatten()
+                        .flat_map(Array::iter),
+                ),
+                latest.point,
+            )?,
+            region: choose(
+                "Region",
+                &mut versions(
+                    regions
+                        .into_iter()
+                        .flatten()
+                        .flat_map(Array::iter)
+                        .chain(area_cam_regions),
+                ),
+                latest.region,
+            )?,
+            enemy_generator: choose(
+                "EnemyGenerator",
+                &mut versions(lvd.enemy_generators().into_iter().flat_map(Array::iter)),
+                latest.enemy_generator,
+            )?,
+            ptrainer_range: choose(
+                "PTrainerRange",
+                &mut versions(lvd.ptrainer_ranges().into_iter().flat_map(Array::iter)),
+                latest.ptrainer_range,
+            )?,
+            fs_unknown: choose(
+                "FsUnknown",
+                &mut versions(lvd.fs_unknown().into_iter().flat_map(Array::iter)),
+                latest.fs_unknown,
+            )?,
+            fs_area_lock: choose(
+                "FsAreaLock",
+                &mut versions(lvd.fs_area_locks().into_iter().flat_map(Array::iter)),
+                latest.fs_area_lock,
+            )?,
+            area_light: choose(
+                "AreaLight",
+                &mut versions(lvd.area_lights().into_iter().flat_map(Array::iter)),
+                latest.area_light,
+            )?,
+            area_hint: choose(
+                "AreaHint",
+                &mut versions(lvd.area_hints().into_iter().flat_map(Array::iter)),
+                latest.area_hint,
+            )?,
+        })
+    }
+}
+
+impl Default for ModelVersions {
+    fn default() -> Self {
+        Self::latest()
+    }
+}
+
+/// A trait for converting the model's types into the binary format's types.
+pub trait ModelObject: From<Self::Object> {
+    /// The binary format's type.
+    type Object: Version;
+
+    /// Converts `self` into the binary format's type, in the version chosen by `versions`.
+    ///
+    /// Fields which the chosen version does not have are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version chosen for the type, or for any type it contains,
+    /// is not known to this library.
+    fn into_object(self, versions: &ModelVersions) -> Result<Self::Object, ModelError>;
+}
+
+impl ModelObject for Tag {
+    type Object = Self;
+
+    fn into_object(self, _versions: &ModelVersions) -> Result<Self, ModelError> {
+        Ok(self)
+    }
+}
+
+/// Converts a versioned array into a collection of the model's types.
+pub(crate) fn vec_from<T: ModelObject>(array: Versioned<Array<T::Object>>) -> Vec<T> {
+    array.inner.into_iter().map(T::from).collect()
+}
+
+/// Converts a collection of the model's types into a versioned array.
+pub(crate) fn array_from<T: ModelObject>(
+    data: Vec<T>,
+    versions: &ModelVersions,
+) -> Result<Versioned<Array<T::Object>>, ModelError> {
+    data.into_iter()
+        .map(|data| data.into_object(versions))
+        .collect::<Result<_, _>>()
+        .map(Versioned::new)
+}
+
+/// Generates the [`LvdData`] type and its conversions from a table of each section's name and
+/// object type.
+macro_rules! lvd_data {
+    ($($kind:ident: $name:ident, $name_mut:ident: $ty:ty;)+) => {
+        /// The flattened data of an LVD file, with a collection of objects for every section.
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        #[cfg_attr(feature = "schemars", derive(JsonSchema))]
+        #[cfg_attr(feature = "serde", serde(default))]
+        #[derive(Debug, Clone, Default)]
+        pub struct LvdData {
+            $(
+                #[doc = concat!("The objects of the `", stringify!($name), "` section.")]
+                #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+                pub $name: Vec<$ty>,
+            )+
+
+            /// The raw bytes following the known sections.
+            ///
+            /// Their meaning depends on the file format version, so they should only be kept
+            /// when converting the data back to the version it came from.
+            #[cfg_attr(feature = "serde", serde(skip_serializing_if = "UnknownSection::is_empty"))]
+            pub unknown_sections: UnknownSection,
+        }
+
+        impl From<Lvd> for LvdData {
+            fn from(mut lvd: Lvd) -> Self {
+                Self {
+                    $(
+                        $name: lvd
+                            .$name_mut()
+                            .map(|section| vec_from(Versioned::new(std::mem::take(section))))
+                            .unwrap_or_default(),
+                    )+
+                    unknown_sections: std::mem::take(lvd.unknown_sections_mut()),
+                }
+            }
+        }
+
+        impl LvdData {
+            /// Converts the data into the binary format's types, in the versions chosen by `versions`.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if a section with objects is not supported by the chosen file
+            /// format version, or if any chosen version is not known to this library.
+            pub fn into_lvd(self, versions: &ModelVersions) -> Result<Lvd, ModelError> {
+                let mut lvd =
+                    Lvd::empty(versions.lvd).ok_or(ModelError::version("Lvd", versions.lvd))?;
+
+                $(
+                    match lvd.$name_mut() {
+                        Some(section) => *section = array_from(self.$name, versions)?.inner,
+                        None if self.$name.is_empty() => (),
+                        None => {
+                            return Err(ModelError::UnsupportedSection {
+                                section: SectionKind::$kind,
+                                version: versions.lvd,
+                            })
+                        }
+                    }
+                )+
+
+                *lvd.unknown_sections_mut() = self.unknown_sections;
+
+                Ok(lvd)
+            }
+
+            /// Converts the data into a file, in the versions chosen by `versions`.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error under the same conditions as [`into_lvd`](Self::into_lvd).
+            pub fn into_file(self, versions: &ModelVersions) -> Result<LvdFile, ModelError> {
+                Ok(LvdFile {
+                    data: Versioned::new(self.into_lvd(versions)?),
+                })
+            }
+        }
+    };
+}
+
+lvd_data! {
+    Collisions: collisions, collisions_mut: CollisionData;
+    StartPositions: start_positions, start_positions_mut: PointData;
+    RestartPositions: restart_positions, restart_positions_mut: PointData;
+    CameraRegions: camera_regions, camera_regions_mut: RegionData;
+    DeathRegions: death_regions, death_regions_mut: RegionData;
+    EnemyGenerators: enemy_generators, enemy_generators_mut: EnemyGeneratorData;
+    FsItems: fs_items, fs_items_mut: FsItemData;
+    FsUnknown: fs_unknown, fs_unknown_mut: FsUnknownData;
+    FsAreaCams: fs_area_cams, fs_area_cams_mut: FsAreaCamData;
+    FsAreaLocks: fs_area_locks, fs_area_locks_mut: FsAreaLockData;
+    FsCamLimits: fs_cam_limits, fs_cam_limits_mut: FsCamLimitData;
+    DamageShapes: damage_shapes, damage_shapes_mut: DamageShapeData;
+    ItemPopups: item_popups, item_popups_mut: ItemPopupData;
+    PTrainerRanges: ptrainer_ranges, ptrainer_ranges_mut: PTrainerRangeData;
+    PTrainerFloatingFloors: ptrainer_floating_floors, ptrainer_floating_floors_mut: PTrainerFloatingFloorData;
+    GeneralShapes2: general_shapes2, general_shapes2_mut: GeneralShape2Data;
+    GeneralShapes3: general_shapes3, general_shapes3_mut: GeneralShape3Data;
+    AreaLights: area_lights, area_lights_mut: AreaLightData;
+    FsStartPoints: fs_start_points, fs_start_points_mut: FsStartPointData;
+    AreaHints: area_hints, area_hints_mut: AreaHintData;
+    SplitAreas: split_areas, split_areas_mut: SplitAreaData;
+    ShrinkedCameraRegions: shrinked_camera_regions, shrinked_camera_regions_mut: RegionData;
+    ShrinkedDeathRegions: shrinked_death_regions, shrinked_death_regions_mut: RegionData;
+}
+
+impl From<LvdFile> for LvdData {
+    fn from(file: LvdFile) -> Self {
+        file.data.inner.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{
+        objects::{base::MetaInfo, collision::CollisionFlags},
+        vector::Vector2,
+    };
+
+    use super::*;
+
+    fn to_bytes(lvd: Lvd) -> Vec<u8> {
+        let mut writer = Cursor::new(Vec::new());
+
+        LvdFile {
+            data: Versioned::new(lvd),
+        }
+        .write(&mut writer)
+        .unwrap();
+
+        writer.into_inner()
+    }
+
+    #[test]
+    fn older_versions_round_trip() {
+        let mut lvd = Lvd::empty(4).unwrap();
+
+        lvd.collisions_mut().unwrap().push(Collision::V1 {
+            meta_info: Versioned::new(MetaInfo::default()),
+            flags: CollisionFlags::new().with_throughable(true),
+            vertices: Versioned::new(Array::new(vec![
+                Vector2::new(-10.0, 0.0),
+                Vector2::new(10.0, 0.0),
+            ])),
+            normals: Versioned::new(Array::new(vec![Vector2::new(0.0, 1.0)])),
+            cliffs: Versioned::new(Array::new(vec![CollisionCliff::V2 {
+                base: Versioned::new(Base::V2 {
+                    meta_info: Versioned::new(MetaInfo::default()),
+                    dynamic_name: Versioned::new("dyn".try_into().unwrap()),
+                    dynamic_offset: Versioned::new(Default::default()),
+                }),
+                pos: Versioned::new(Vector2::new(-10.0, 0.0)),
+                lr: -1.0,
+            }])),
+        });
+        lvd.start_positions_mut().unwrap().push(Point::V1 {
+            meta_info: Versioned::new(MetaInfo::default()),
+            pos: Versioned::new(Vector2::new(0.0, 10.0)),
+        });
+
+        let versions = ModelVersions::of(&lvd);
+        let bytes = to_bytes(lvd.clone());
+        let data = LvdData::from(lvd);
+
+        assert_eq!(versions.collision, 1);
+        assert_eq!(versions.collision_cliff, 2);
+        assert_eq!(versions.base, 2);
+        assert_eq!(data.collisions[0].attributes.len(), 1);
+        assert_eq!(data.collisions[0].cliffs[0].base.dynamic_name, "dyn");
+        assert_eq!(to_bytes(data.into_lvd(&versions).unwrap()), bytes);
+    }
+
+    #[test]
+    fn mixed_versions() {
+        let mut lvd = Lvd::empty(13).unwrap();
+        let old = Point::V1 {
+            meta_info: Versioned::new(MetaInfo::default()),
+            pos: Versioned::new(Vector2::new(0.0, 10.0)),
+        };
+
+        lvd.start_positions_mut().unwrap().push(old.clone());
+
+        assert_eq!(
+            ModelVersions::of_uniform(&lvd).unwrap(),
+            ModelVersions::of(&lvd)
+        );
+
+        lvd.restart_positions_mut().unwrap().push(Point::default());
+
+        let error = ModelVersions::of_uniform(&lvd).unwrap_err();
+
+        assert_eq!(ModelVersions::of(&lvd).point, 1);
+        assert_eq!(
+            (error.type_name, error.version, error.other),
+            ("Point", 1, 2)
+        );
+        assert_ne!(
+            to_bytes(
+                LvdData::from(lvd.clone())
+                    .into_lvd(&ModelVersions::of(&lvd))
+                    .unwrap()
+            ),
+            to_bytes(lvd),
+            "converting back changes the objects of the other version"
+        );
+    }
+
+    #[test]
+    fn unsupported_section() {
+        let data = LvdData {
+            ptrainer_ranges: vec![PTrainerRangeData::default()],
+            ..Default::default()
+        };
+        let versions = ModelVersions {
+            lvd: 11,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            data.into_lvd(&versions),
+            Err(ModelError::UnsupportedSection {
+                section: SectionKind::PTrainerRanges,
+                version: 11
+            })
+        ));
+    }
+}
@@ -0,0 +1,183 @@
+//! The flattened form of the data common to every LVD object.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+use crate::{
+    id::Id,
+    model::{vector::Vector3Data, ModelError, ModelObject, ModelVersions},
+    objects::base::{Base, MetaInfo, VersionInfo},
+    string::{FixedString56, FixedString64},
+    version::{Upgrade, Versioned},
+};
+
+/// The flattened form of a [`Base`], including its [`MetaInfo`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BaseData {
+    /// The name of the object.
+    pub name: FixedString56,
+
+    // TODO: Field documentation.
+    pub editor_version: u32,
+
+    // TODO: Field documentation.
+    pub format_version: u32,
+
+    /// The name of the object as seen by the game when classed as dynamic.
+    pub dynamic_name: FixedString64,
+
+    /// The displacement of the object when classed as dynamic.
+    pub dynamic_offset: Vector3Data,
+
+    /// Determines if the object is classed as dynamic.
+    pub is_dynamic: bool,
+
+    /// The numeric identifier of the instanced object.
+    ///
+    /// Must be nonzero for the object to be classed as an instanced object.
+    pub instance_id: Id,
+
+    /// The displacement of the object when classed as an instanced object.
+    pub instance_offset: Vector3Data,
+
+    /// The index of the joint from the parent model to parent the object to when classed as dynamic.
+    pub joint_index: i32,
+
+    /// The name of the joint from the parent model to parent the object to when classed as dynamic.
+    pub joint_name: FixedString64,
+}
+
+impl Default for BaseData {
+    fn default() -> Self {
+        Base::default().into()
+    }
+}
+
+impl From<Base> for BaseData {
+    fn from(base: Base) -> Self {
+        match base.into_latest() {
+            Base::V4 {
+                meta_info,
+                dynamic_name,
+                dynamic_offset,
+                is_dynamic,
+                instance_id,
+                instance_offset,
+                joint_index,
+                joint_name,
+            } => {
+                let (name, editor_version, format_version) = match meta_info.inner {
+                    MetaInfo::V1 { version_info, name } => match version_info.inner {
+                        VersionInfo::V1 {
+                            editor_version,
+                            format_version,
+                        } => (name.inner, editor_version, format_version),
+                    },
+                };
+
+                Self {
+                    name,
+                    editor_version,
+                    format_version,
+                    dynamic_name: dynamic_name.inner,
+                    dynamic_offset: dynamic_offset.inner.into(),
+                    is_dynamic,
+                    instance_id: instance_id.inner,
+                    instance_offset: instance_offset.inner.into(),
+                    joint_index,
+                    joint_name: joint_name.inner,
+                }
+            }
+            _ => unreachable!("`into_latest` returns the latest version"),
+        }
+    }
+}
+
+impl BaseData {
+    /// Converts the common data into the metadata of an object which stores it directly,
+    /// dropping every other field.
+    pub(crate) fn into_meta_info(self) -> Versioned<MetaInfo> {
+        meta_info(self.name, self.editor_version, self.format_version)
+    }
+
+    /// Converts the common data into the version chosen by `versions`, wrapped for use as a field.
+    pub(crate) fn into_versioned(
+        self,
+        versions: &ModelVersions,
+    ) -> Result<Versioned<Base>, ModelError> {
+        self.into_object(versions).map(Versioned::new)
+    }
+}
+
+impl ModelObject for BaseData {
+    type Object = Base;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<Base, ModelError> {
+        let Self {
+            name,
+            editor_version,
+            format_version,
+            dynamic_name,
+            dynamic_offset,
+            is_dynamic,
+            instance_id,
+            instance_offset,
+            joint_index,
+            joint_name,
+        } = self;
+        let meta_info = meta_info(name, editor_version, format_version);
+        let dynamic_name = Versioned::new(dynamic_name);
+        let dynamic_offset = Versioned::new(dynamic_offset.into());
+        let instance_id = Versioned::new(instance_id);
+        let instance_offset = Versioned::new(instance_offset.into());
+        let joint_name = Versioned::new(joint_name);
+
+        match versions.base {
+            1 => Ok(Base::V1 {
+                meta_info,
+                dynamic_name,
+            }),
+            2 => Ok(Base::V2 {
+                meta_info,
+                dynamic_name,
+                dynamic_offset,
+            }),
+            3 => Ok(Base::V3 {
+                meta_info,
+                dynamic_name,
+                dynamic_offset,
+                is_dynamic,
+                instance_id,
+                instance_offset,
+            }),
+            4 => Ok(Base::V4 {
+                meta_info,
+                dynamic_name,
+                dynamic_offset,
+                is_dynamic,
+                instance_id,
+                instance_offset,
+                joint_index,
+                joint_name,
+            }),
+            version => Err(ModelError::version("Base", version)),
+        }
+    }
+}
+
+/// Returns the metadata with the given name and version metadata.
+fn meta_info(name: FixedString56, editor_version: u32, format_version: u32) -> Versioned<MetaInfo> {
+    Versioned::new(MetaInfo::V1 {
+        version_info: Versioned::new(VersionInfo::V1 {
+            editor_version,
+            format_version,
+        }),
+        name: Versioned::new(name),
+    })
+}
@@ -0,0 +1,332 @@
+//! The flattened forms of [`Collision`] and its subobjects.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+use crate::{
+    model::{
+        array_from, base::BaseData, vec_from, vector::Vector2Data, ModelError, ModelObject,
+        ModelVersions,
+    },
+    objects::collision::{
+        attribute::{AttributeFlags, MaterialType},
+        Collision, CollisionAttribute, CollisionCliff, CollisionFlags, CollisionSpiritsFloor,
+    },
+    string::FixedString64,
+    version::{Upgrade, Versioned},
+};
+
+/// The flattened form of a [`Collision`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollisionData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The global attributes of the collision.
+    pub flags: CollisionFlags,
+
+    /// The collection of vertices forming the geometry of the collision.
+    pub vertices: Vec<Vector2Data>,
+
+    /// The collection of unit normal vectors defining the tangible side of each edge.
+    pub normals: Vec<Vector2Data>,
+
+    /// The collection of supplementary data for edges flagged as grabbable.
+    pub cliffs: Vec<CollisionCliffData>,
+
+    /// The collection of properties and attributes for each edge in the collision.
+    pub attributes: Vec<CollisionAttributeData>,
+
+    /// The collection of entries related to hazardous floors in spirit battles.
+    pub spirits_floors: Vec<CollisionSpiritsFloorData>,
+}
+
+impl Default for CollisionData {
+    fn default() -> Self {
+        Collision::default().into()
+    }
+}
+
+impl From<Collision> for CollisionData {
+    fn from(collision: Collision) -> Self {
+        match collision.into_latest() {
+            Collision::V4 {
+                base,
+                flags,
+                vertices,
+                normals,
+                cliffs,
+                attributes,
+                spirits_floors,
+            } => Self {
+                base: base.inner.into(),
+                flags,
+                vertices: vec_from(vertices),
+                normals: vec_from(normals),
+                cliffs: vec_from(cliffs),
+                attributes: vec_from(attributes),
+                spirits_floors: vec_from(spirits_floors),
+            },
+            _ => unreachable!("`into_latest` returns the latest version"),
+        }
+    }
+}
+
+impl ModelObject for CollisionData {
+    type Object = Collision;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<Collision, ModelError> {
+        let vertices = array_from(self.vertices, versions)?;
+        let normals = array_from(self.normals, versions)?;
+        let cliffs = array_from(self.cliffs, versions)?;
+
+        match versions.collision {
+            1 => Ok(Collision::V1 {
+                meta_info: self.base.into_meta_info(),
+                flags: self.flags,
+                vertices,
+                normals,
+                cliffs,
+            }),
+            2 => Ok(Collision::V2 {
+                base: self.base.into_versioned(versions)?,
+                flags: self.flags,
+                vertices,
+                normals,
+                cliffs,
+            }),
+            3 => Ok(Collision::V3 {
+                base: self.base.into_versioned(versions)?,
+                flags: self.flags,
+                vertices,
+                normals,
+                cliffs,
+                attributes: array_from(self.attributes, versions)?,
+            }),
+            4 => Ok(Collision::V4 {
+                base: self.base.into_versioned(versions)?,
+                flags: self.flags,
+                vertices,
+                normals,
+                cliffs,
+                attributes: array_from(self.attributes, versions)?,
+                spirits_floors: array_from(self.spirits_floors, versions)?,
+            }),
+            version => Err(ModelError::version("Collision", version)),
+        }
+    }
+}
+
+/// The flattened form of a [`CollisionCliff`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollisionCliffData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The position of the cliff.
+    ///
+    /// This should be equal to the position of the corresponding vertex.
+    pub pos: Vector2Data,
+
+    /// The facing direction of the cliff.
+    ///
+    /// A value of `-1.0` corresponds to the left and a value of `1.0` corresponds to the right.
+    pub lr: f32,
+
+    /// The index of the edge in the associated collision to link the object with.
+    pub line_index: u32,
+}
+
+impl Default for CollisionCliffData {
+    fn default() -> Self {
+        CollisionCliff::default().into()
+    }
+}
+
+impl From<CollisionCliff> for CollisionCliffData {
+    fn from(cliff: CollisionCliff) -> Self {
+        match cliff.into_latest() {
+            CollisionCliff::V3 {
+                base,
+                pos,
+                lr,
+                line_index,
+            } => Self {
+                base: base.inner.into(),
+                pos: pos.inner.into(),
+                lr,
+                line_index,
+            },
+            _ => unreachable!("`into_latest` returns the latest version"),
+        }
+    }
+}
+
+impl ModelObject for CollisionCliffData {
+    type Object = CollisionCliff;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<CollisionCliff, ModelError> {
+        let pos = Versioned::new(self.pos.into());
+
+        match versions.collision_cliff {
+            1 => Ok(CollisionCliff::V1 { pos, lr: self.lr }),
+            2 => Ok(CollisionCliff::V2 {
+                base: self.base.into_versioned(versions)?,
+                pos,
+                lr: self.lr,
+            }),
+            3 => Ok(CollisionCliff::V3 {
+                base: self.base.into_versioned(versions)?,
+                pos,
+                lr: self.lr,
+                line_index: self.line_index,
+            }),
+            version => Err(ModelError::version("CollisionCliff", version)),
+        }
+    }
+}
+
+/// The flattened form of a [`CollisionAttribute`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollisionAttributeData {
+    /// The material preset of the edge.
+    pub material: MaterialType,
+
+    /// The attributes of the edge.
+    pub flags: AttributeFlags,
+}
+
+impl Default for CollisionAttributeData {
+    fn default() -> Self {
+        CollisionAttribute::default().into()
+    }
+}
+
+impl From<CollisionAttribute> for CollisionAttributeData {
+    fn from(attribute: CollisionAttribute) -> Self {
+        match attribute {
+            CollisionAttribute::V1 { material, flags } => Self { material, flags },
+        }
+    }
+}
+
+impl ModelObject for CollisionAttributeData {
+    type Object = CollisionAttribute;
+
+    fn into_object(self, _versions: &ModelVersions) -> Result<CollisionAttribute, ModelError> {
+        Ok(CollisionAttribute::V1 {
+            material: self.material,
+            flags: self.flags,
+        })
+    }
+}
+
+/// The flattened form of a [`CollisionSpiritsFloor`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollisionSpiritsFloorData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The index of the edge in the associated collision to link the object with.
+    pub line_index: u32,
+
+    /// The name of the line group that the object is associated with.
+    pub line_group: FixedString64,
+
+    // TODO: Field documentation. Usually 1.0. Unused?
+    pub unk1: f32,
+
+    // TODO: Field documentation. Usually 1.0. Unused?
+    pub unk2: f32,
+
+    // TODO: Field documentation. Always 1.0. Unused?
+    pub unk3: f32,
+
+    // TODO: Field documentation. Always 1.0. Unused?
+    pub unk4: f32,
+
+    // TODO: Field documentation. Always 0.0. Unused?
+    pub unk5: f32,
+
+    // TODO: Field documentation. Always 0.0. Unused?
+    pub unk6: f32,
+}
+
+impl Default for CollisionSpiritsFloorData {
+    fn default() -> Self {
+        CollisionSpiritsFloor::default().into()
+    }
+}
+
+impl From<CollisionSpiritsFloor> for CollisionSpiritsFloorData {
+    fn from(spirits_floor: CollisionSpiritsFloor) -> Self {
+        match spirits_floor.into_latest() {
+            CollisionSpiritsFloor::V2 {
+                base,
+                line_index,
+                line_group,
+                unk1,
+                unk2,
+                unk3,
+                unk4,
+                unk5,
+                unk6,
+            } => Self {
+                base: base.inner.into(),
+                line_index,
+                line_group: line_group.inner,
+                unk1,
+                unk2,
+                unk3,
+                unk4,
+                unk5,
+                unk6,
+            },
+            _ => unreachable!("`into_latest` returns the latest version"),
+        }
+    }
+}
+
+impl ModelObject for CollisionSpiritsFloorData {
+    type Object = CollisionSpiritsFloor;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<CollisionSpiritsFloor, ModelError> {
+        let base = self.base.into_versioned(versions)?;
+        let line_group = Versioned::new(self.line_group);
+
+        match versions.collision_spirits_floor {
+            1 => Ok(CollisionSpiritsFloor::V1 {
+                base,
+                line_index: self.line_index,
+                line_group,
+            }),
+            2 => Ok(CollisionSpiritsFloor::V2 {
+                base,
+                line_index: self.line_index,
+                line_group,
+                unk1: self.unk1,
+                unk2: self.unk2,
+                unk3: self.unk3,
+                unk4: self.unk4,
+                unk5: self.unk5,
+                unk6: self.unk6,
+            }),
+            version => Err(ModelError::version("CollisionSpiritsFloor", version)),
+        }
+    }
+}
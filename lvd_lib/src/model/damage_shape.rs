@@ -0,0 +1,70 @@
+//! The flattened form of [`DamageShape`].
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+use crate::{
+    model::{base::BaseData, ModelError, ModelObject, ModelVersions},
+    objects::DamageShape,
+    shape::Shape3,
+    version::Versioned,
+};
+
+/// The flattened form of a [`DamageShape`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone)]
+pub struct DamageShapeData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The three-dimensional geometric representation of the object.
+    pub shape: Shape3,
+
+    /// Determines if the damage shape is an attack collision.
+    pub is_damager: bool,
+
+    // TODO: Field documentation.
+    pub id: u32,
+}
+
+impl Default for DamageShapeData {
+    fn default() -> Self {
+        DamageShape::default().into()
+    }
+}
+
+impl From<DamageShape> for DamageShapeData {
+    fn from(damage_shape: DamageShape) -> Self {
+        match damage_shape {
+            DamageShape::V1 {
+                base,
+                shape,
+                is_damager,
+                id,
+            } => Self {
+                base: base.inner.into(),
+                shape: shape.inner,
+                is_damager,
+                id,
+            },
+        }
+    }
+}
+
+impl ModelObject for DamageShapeData {
+    type Object = DamageShape;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<DamageShape, ModelError> {
+        Ok(DamageShape::V1 {
+            base: self.base.into_versioned(versions)?,
+            shape: Versioned::new(self.shape),
+            is_damager: self.is_damager,
+            id: self.id,
+        })
+    }
+}
@@ -0,0 +1,125 @@
+//! The flattened form of [`EnemyGenerator`].
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+use crate::{
+    model::{
+        array_from,
+        base::BaseData,
+        shape::{shape_array_from, shapes_from, Shape2Data},
+        vec_from, ModelError, ModelObject, ModelVersions,
+    },
+    objects::EnemyGenerator,
+    tag::Tag,
+    version::{Upgrade, Versioned},
+};
+
+/// The flattened form of an [`EnemyGenerator`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnemyGeneratorData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The collection of shapes where enemies can appear from.
+    pub appear_shapes: Vec<Shape2Data>,
+
+    /// The collection of shapes for responding to fighter presence.
+    pub trigger_shapes: Vec<Shape2Data>,
+
+    // TODO: Field documentation.
+    pub unk1: Vec<Shape2Data>,
+
+    /// The identifier for matching and filtering like objects.
+    pub tag: Tag,
+
+    /// The collection of identifiers for matching and filtering appear regions.
+    pub appear_tags: Vec<Tag>,
+
+    // TODO: Field documentation.
+    pub unk2: Vec<Tag>,
+
+    /// The collection of identifiers for matching and filtering trigger regions.
+    pub trigger_tags: Vec<Tag>,
+}
+
+impl Default for EnemyGeneratorData {
+    fn default() -> Self {
+        EnemyGenerator::default().into()
+    }
+}
+
+impl From<EnemyGenerator> for EnemyGeneratorData {
+    fn from(enemy_generator: EnemyGenerator) -> Self {
+        match enemy_generator.into_latest() {
+            EnemyGenerator::V3 {
+                base,
+                appear_shapes,
+                trigger_shapes,
+                unk1,
+                tag,
+                appear_tags,
+                unk2,
+                trigger_tags,
+            } => Self {
+                base: base.inner.into(),
+                appear_shapes: shapes_from(appear_shapes),
+                trigger_shapes: shapes_from(trigger_shapes),
+                unk1: shapes_from(unk1),
+                tag: tag.inner,
+                appear_tags: vec_from(appear_tags),
+                unk2: vec_from(unk2),
+                trigger_tags: vec_from(trigger_tags),
+            },
+            _ => unreachable!("`into_latest` returns the latest version"),
+        }
+    }
+}
+
+impl ModelObject for EnemyGeneratorData {
+    type Object = EnemyGenerator;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<EnemyGenerator, ModelError> {
+        let base = self.base.into_versioned(versions)?;
+        let appear_shapes = shape_array_from(self.appear_shapes);
+        let trigger_shapes = shape_array_from(self.trigger_shapes);
+        let unk1 = shape_array_from(self.unk1);
+        let tag = Versioned::new(self.tag);
+
+        match versions.enemy_generator {
+            1 => Ok(EnemyGenerator::V1 {
+                base,
+                appear_shapes,
+                trigger_shapes,
+                unk1,
+                tag,
+            }),
+            2 => Ok(EnemyGenerator::V2 {
+                base,
+                appear_shapes,
+                trigger_shapes,
+                unk1,
+                tag,
+                appear_tags: array_from(self.appear_tags, versions)?,
+                unk2: array_from(self.unk2, versions)?,
+            }),
+            3 => Ok(EnemyGenerator::V3 {
+                base,
+                appear_shapes,
+                trigger_shapes,
+                unk1,
+                tag,
+                appear_tags: array_from(self.appear_tags, versions)?,
+                unk2: array_from(self.unk2, versions)?,
+                trigger_tags: array_from(self.trigger_tags, versions)?,
+            }),
+            version => Err(ModelError::version("EnemyGenerator", version)),
+        }
+    }
+}
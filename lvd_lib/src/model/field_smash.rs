@@ -0,0 +1,556 @@
+//! The flattened forms of the objects exclusive to Smash Run in Super Smash Bros. for Nintendo 3DS.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+use crate::{
+    id::Id,
+    model::{
+        base::BaseData,
+        region::RegionData,
+        shape::{path_from, points_from, RectData, Shape2Data},
+        vector::Vector2Data,
+        ModelError, ModelObject, ModelVersions,
+    },
+    objects::{
+        AreaHint, AreaLight, FsAreaCam, FsAreaLock, FsCamLimit, FsItem, FsStartPoint, FsUnknown,
+        SplitArea,
+    },
+    shape::Shape3,
+    string::FixedString32,
+    tag::Tag,
+    version::{Upgrade, Versioned},
+};
+
+/// The flattened form of an [`FsItem`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FsItemData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The two-dimensional geometric representation of the object.
+    pub shape: Shape2Data,
+
+    /// The identifier for matching and filtering like objects.
+    pub tag: Tag,
+}
+
+impl Default for FsItemData {
+    fn default() -> Self {
+        FsItem::default().into()
+    }
+}
+
+impl From<FsItem> for FsItemData {
+    fn from(item: FsItem) -> Self {
+        match item {
+            FsItem::V1 { base, shape, tag } => Self {
+                base: base.inner.into(),
+                shape: shape.inner.into(),
+                tag: tag.inner,
+            },
+        }
+    }
+}
+
+impl ModelObject for FsItemData {
+    type Object = FsItem;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<FsItem, ModelError> {
+        Ok(FsItem::V1 {
+            base: self.base.into_versioned(versions)?,
+            shape: Versioned::new(self.shape.into()),
+            tag: Versioned::new(self.tag),
+        })
+    }
+}
+
+/// The flattened form of an [`FsUnknown`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FsUnknownData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    // TODO: Field documentation.
+    pub unk1: RectData,
+
+    // TODO: Field documentation.
+    pub unk2: FsCamLimitData,
+
+    // TODO: Field documentation.
+    pub unk3: u32,
+}
+
+impl Default for FsUnknownData {
+    fn default() -> Self {
+        FsUnknown::default().into()
+    }
+}
+
+impl From<FsUnknown> for FsUnknownData {
+    fn from(unknown: FsUnknown) -> Self {
+        match unknown.into_latest() {
+            FsUnknown::V2 {
+                base,
+                unk1,
+                unk2,
+                unk3,
+            } => Self {
+                base: base.inner.into(),
+                unk1: unk1.inner.into(),
+                unk2: unk2.inner.into(),
+                unk3,
+            },
+            _ => unreachable!("`into_latest` returns the latest version"),
+        }
+    }
+}
+
+impl ModelObject for FsUnknownData {
+    type Object = FsUnknown;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<FsUnknown, ModelError> {
+        let base = self.base.into_versioned(versions)?;
+        let unk1 = Versioned::new(self.unk1.into());
+        let unk2 = Versioned::new(self.unk2.into_object(versions)?);
+
+        match versions.fs_unknown {
+            1 => Ok(FsUnknown::V1 { base, unk1, unk2 }),
+            2 => Ok(FsUnknown::V2 {
+                base,
+                unk1,
+                unk2,
+                unk3: self.unk3,
+            }),
+            version => Err(ModelError::version("FsUnknown", version)),
+        }
+    }
+}
+
+/// The flattened form of an [`FsAreaCam`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FsAreaCamData {
+    // TODO: Field documentation.
+    pub region: RegionData,
+
+    // TODO: Field documentation.
+    pub unk: u32,
+}
+
+impl Default for FsAreaCamData {
+    fn default() -> Self {
+        FsAreaCam::default().into()
+    }
+}
+
+impl From<FsAreaCam> for FsAreaCamData {
+    fn from(area_cam: FsAreaCam) -> Self {
+        match area_cam {
+            FsAreaCam::V1 { region, unk } => Self {
+                region: region.inner.into(),
+                unk,
+            },
+        }
+    }
+}
+
+impl ModelObject for FsAreaCamData {
+    type Object = FsAreaCam;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<FsAreaCam, ModelError> {
+        Ok(FsAreaCam::V1 {
+            region: Versioned::new(self.region.into_object(versions)?),
+            unk: self.unk,
+        })
+    }
+}
+
+/// The flattened form of an [`FsAreaLock`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FsAreaLockData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The edge coordinates of the region for restricted camera movement.
+    pub camera_region: RectData,
+
+    /// The edge coordinates of the trigger region for activating the restricted camera movement.
+    pub trigger_region: RectData,
+
+    // TODO: Field documentation.
+    pub unk1: u32,
+
+    // TODO: Field documentation.
+    pub unk2: Vector2Data,
+}
+
+impl Default for FsAreaLockData {
+    fn default() -> Self {
+        FsAreaLock::default().into()
+    }
+}
+
+impl From<FsAreaLock> for FsAreaLockData {
+    fn from(area_lock: FsAreaLock) -> Self {
+        match area_lock.into_latest() {
+            FsAreaLock::V2 {
+                base,
+                camera_region,
+                trigger_region,
+                unk1,
+                unk2,
+            } => Self {
+                base: base.inner.into(),
+                camera_region: camera_region.inner.into(),
+                trigger_region: trigger_region.inner.into(),
+                unk1,
+                unk2: unk2.inner.into(),
+            },
+            _ => unreachable!("`into_latest` returns the latest version"),
+        }
+    }
+}
+
+impl ModelObject for FsAreaLockData {
+    type Object = FsAreaLock;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<FsAreaLock, ModelError> {
+        let base = self.base.into_versioned(versions)?;
+        let camera_region = Versioned::new(self.camera_region.into());
+        let trigger_region = Versioned::new(self.trigger_region.into());
+
+        match versions.fs_area_lock {
+            1 => Ok(FsAreaLock::V1 {
+                base,
+                camera_region,
+                trigger_region,
+                unk1: self.unk1,
+            }),
+            2 => Ok(FsAreaLock::V2 {
+                base,
+                camera_region,
+                trigger_region,
+                unk1: self.unk1,
+                unk2: Versioned::new(self.unk2.into()),
+            }),
+            version => Err(ModelError::version("FsAreaLock", version)),
+        }
+    }
+}
+
+/// The flattened form of an [`FsCamLimit`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FsCamLimitData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The points of the path shape forming the camera limit region.
+    pub path: Vec<Vector2Data>,
+}
+
+impl Default for FsCamLimitData {
+    fn default() -> Self {
+        FsCamLimit::default().into()
+    }
+}
+
+impl From<FsCamLimit> for FsCamLimitData {
+    fn from(cam_limit: FsCamLimit) -> Self {
+        match cam_limit {
+            FsCamLimit::V1 { base, path } => Self {
+                base: base.inner.into(),
+                path: points_from(path),
+            },
+        }
+    }
+}
+
+impl ModelObject for FsCamLimitData {
+    type Object = FsCamLimit;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<FsCamLimit, ModelError> {
+        Ok(FsCamLimit::V1 {
+            base: self.base.into_versioned(versions)?,
+            path: path_from(self.path),
+        })
+    }
+}
+
+/// The flattened form of an [`AreaLight`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AreaLightData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The two-dimensional geometric representation of the object.
+    pub shape: Shape2Data,
+
+    // TODO: Field documentation.
+    pub unk1: FixedString32,
+
+    // TODO: Field documentation.
+    pub unk2: FixedString32,
+}
+
+impl Default for AreaLightData {
+    fn default() -> Self {
+        AreaLight::default().into()
+    }
+}
+
+impl From<AreaLight> for AreaLightData {
+    fn from(area_light: AreaLight) -> Self {
+        match area_light.into_latest() {
+            AreaLight::V2 {
+                base,
+                shape,
+                unk1,
+                unk2,
+            } => Self {
+                base: base.inner.into(),
+                shape: shape.inner.into(),
+                unk1: unk1.inner,
+                unk2: unk2.inner,
+            },
+            _ => unreachable!("`into_latest` returns the latest version"),
+        }
+    }
+}
+
+impl ModelObject for AreaLightData {
+    type Object = AreaLight;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<AreaLight, ModelError> {
+        let base = self.base.into_versioned(versions)?;
+        let shape = Versioned::new(self.shape.into());
+
+        match versions.area_light {
+            1 => Ok(AreaLight::V1 { base, shape }),
+            2 => Ok(AreaLight::V2 {
+                base,
+                shape,
+                unk1: Versioned::new(self.unk1),
+                unk2: Versioned::new(self.unk2),
+            }),
+            version => Err(ModelError::version("AreaLight", version)),
+        }
+    }
+}
+
+/// The flattened form of an [`FsStartPoint`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FsStartPointData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The position of the point.
+    pub pos: Vector2Data,
+
+    // TODO: Field documentation.
+    pub id: Id,
+}
+
+impl Default for FsStartPointData {
+    fn default() -> Self {
+        FsStartPoint::default().into()
+    }
+}
+
+impl From<FsStartPoint> for FsStartPointData {
+    fn from(start_point: FsStartPoint) -> Self {
+        match start_point {
+            FsStartPoint::V1 { base, pos, id } => Self {
+                base: base.inner.into(),
+                pos: pos.inner.into(),
+                id: id.inner,
+            },
+        }
+    }
+}
+
+impl ModelObject for FsStartPointData {
+    type Object = FsStartPoint;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<FsStartPoint, ModelError> {
+        Ok(FsStartPoint::V1 {
+            base: self.base.into_versioned(versions)?,
+            pos: Versioned::new(self.pos.into()),
+            id: Versioned::new(self.id),
+        })
+    }
+}
+
+/// The flattened form of an [`AreaHint`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone)]
+pub struct AreaHintData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The three-dimensional geometric representation of the object.
+    pub shape: Shape3,
+
+    // TODO: Field documentation.
+    pub unk1: i32,
+
+    // TODO: Field documentation.
+    pub unk2: i32,
+
+    // TODO: Field documentation.
+    pub unk3: i32,
+
+    // TODO: Field documentation.
+    pub unk4: i32,
+
+    // TODO: Field documentation.
+    pub unk5: u8,
+
+    // TODO: Field documentation.
+    pub unk6: i32,
+
+    // TODO: Field documentation.
+    pub unk7: i32,
+}
+
+impl Default for AreaHintData {
+    fn default() -> Self {
+        AreaHint::default().into()
+    }
+}
+
+impl From<AreaHint> for AreaHintData {
+    fn from(area_hint: AreaHint) -> Self {
+        match area_hint.into_latest() {
+            AreaHint::V3 {
+                base,
+                shape,
+                unk1,
+                unk2,
+                unk3,
+                unk4,
+                unk5,
+                unk6,
+                unk7,
+            } => Self {
+                base: base.inner.into(),
+                shape: shape.inner,
+                unk1,
+                unk2,
+                unk3,
+                unk4,
+                unk5,
+                unk6,
+                unk7,
+            },
+            _ => unreachable!("`into_latest` returns the latest version"),
+        }
+    }
+}
+
+impl ModelObject for AreaHintData {
+    type Object = AreaHint;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<AreaHint, ModelError> {
+        let base = self.base.into_versioned(versions)?;
+        let shape = Versioned::new(self.shape);
+
+        match versions.area_hint {
+            1 => Ok(AreaHint::V1 {
+                base,
+                shape,
+                unk1: self.unk1,
+                unk2: self.unk2,
+                unk3: self.unk3,
+                unk4: self.unk4,
+            }),
+            2 => Ok(AreaHint::V2 {
+                base,
+                shape,
+                unk1: self.unk1,
+                unk2: self.unk2,
+                unk3: self.unk3,
+                unk4: self.unk4,
+                unk5: self.unk5,
+            }),
+            3 => Ok(AreaHint::V3 {
+                base,
+                shape,
+                unk1: self.unk1,
+                unk2: self.unk2,
+                unk3: self.unk3,
+                unk4: self.unk4,
+                unk5: self.unk5,
+                unk6: self.unk6,
+                unk7: self.unk7,
+            }),
+            version => Err(ModelError::version("AreaHint", version)),
+        }
+    }
+}
+
+/// The flattened form of a [`SplitArea`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone)]
+pub struct SplitAreaData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The three-dimensional geometric representation of the object.
+    pub shape: Shape3,
+}
+
+impl Default for SplitAreaData {
+    fn default() -> Self {
+        SplitArea::default().into()
+    }
+}
+
+impl From<SplitArea> for SplitAreaData {
+    fn from(split_area: SplitArea) -> Self {
+        match split_area {
+            SplitArea::V1 { base, shape } => Self {
+                base: base.inner.into(),
+                shape: shape.inner,
+            },
+        }
+    }
+}
+
+impl ModelObject for SplitAreaData {
+    type Object = SplitArea;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<SplitArea, ModelError> {
+        Ok(SplitArea::V1 {
+            base: self.base.into_versioned(versions)?,
+            shape: Versioned::new(self.shape),
+        })
+    }
+}
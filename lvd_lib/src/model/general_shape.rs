@@ -0,0 +1,107 @@
+//! The flattened forms of [`GeneralShape2`] and [`GeneralShape3`].
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+use crate::{
+    model::{base::BaseData, shape::Shape2Data, ModelError, ModelObject, ModelVersions},
+    objects::{GeneralShape2, GeneralShape3},
+    shape::Shape3,
+    tag::Tag,
+    version::Versioned,
+};
+
+/// The flattened form of a [`GeneralShape2`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneralShape2Data {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The identifier for matching and filtering like objects.
+    pub tag: Tag,
+
+    /// The two-dimensional geometric representation of the object.
+    pub shape: Shape2Data,
+}
+
+impl Default for GeneralShape2Data {
+    fn default() -> Self {
+        GeneralShape2::default().into()
+    }
+}
+
+impl From<GeneralShape2> for GeneralShape2Data {
+    fn from(general_shape: GeneralShape2) -> Self {
+        match general_shape {
+            GeneralShape2::V1 { base, tag, shape } => Self {
+                base: base.inner.into(),
+                tag: tag.inner,
+                shape: shape.inner.into(),
+            },
+        }
+    }
+}
+
+impl ModelObject for GeneralShape2Data {
+    type Object = GeneralShape2;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<GeneralShape2, ModelError> {
+        Ok(GeneralShape2::V1 {
+            base: self.base.into_versioned(versions)?,
+            tag: Versioned::new(self.tag),
+            shape: Versioned::new(self.shape.into()),
+        })
+    }
+}
+
+/// The flattened form of a [`GeneralShape3`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone)]
+pub struct GeneralShape3Data {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The identifier for matching and filtering like objects.
+    pub tag: Tag,
+
+    /// The three-dimensional geometric representation of the object.
+    pub shape: Shape3,
+}
+
+impl Default for GeneralShape3Data {
+    fn default() -> Self {
+        GeneralShape3::default().into()
+    }
+}
+
+impl From<GeneralShape3> for GeneralShape3Data {
+    fn from(general_shape: GeneralShape3) -> Self {
+        match general_shape {
+            GeneralShape3::V1 { base, tag, shape } => Self {
+                base: base.inner.into(),
+                tag: tag.inner,
+                shape: shape.inner,
+            },
+        }
+    }
+}
+
+impl ModelObject for GeneralShape3Data {
+    type Object = GeneralShape3;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<GeneralShape3, ModelError> {
+        Ok(GeneralShape3::V1 {
+            base: self.base.into_versioned(versions)?,
+            tag: Versioned::new(self.tag),
+            shape: Versioned::new(self.shape),
+        })
+    }
+}
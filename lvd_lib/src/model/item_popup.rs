@@ -0,0 +1,64 @@
+//! The flattened form of [`ItemPopup`].
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+use crate::{
+    model::{
+        base::BaseData,
+        shape::{shape_array_from, shapes_from, Shape2Data},
+        ModelError, ModelObject, ModelVersions,
+    },
+    objects::ItemPopup,
+    tag::Tag,
+    version::Versioned,
+};
+
+/// The flattened form of an [`ItemPopup`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemPopupData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The identifier for matching and filtering like objects.
+    pub tag: Tag,
+
+    /// The collection of shapes where items will appear from.
+    pub shapes: Vec<Shape2Data>,
+}
+
+impl Default for ItemPopupData {
+    fn default() -> Self {
+        ItemPopup::default().into()
+    }
+}
+
+impl From<ItemPopup> for ItemPopupData {
+    fn from(item_popup: ItemPopup) -> Self {
+        match item_popup {
+            ItemPopup::V1 { base, tag, shapes } => Self {
+                base: base.inner.into(),
+                tag: tag.inner,
+                shapes: shapes_from(shapes),
+            },
+        }
+    }
+}
+
+impl ModelObject for ItemPopupData {
+    type Object = ItemPopup;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<ItemPopup, ModelError> {
+        Ok(ItemPopup::V1 {
+            base: self.base.into_versioned(versions)?,
+            tag: Versioned::new(self.tag),
+            shapes: shape_array_from(self.shapes),
+        })
+    }
+}
@@ -0,0 +1,64 @@
+//! The flattened form of [`Point`].
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+use crate::{
+    model::{base::BaseData, vector::Vector2Data, ModelError, ModelObject, ModelVersions},
+    objects::Point,
+    version::{Upgrade, Versioned},
+};
+
+/// The flattened form of a [`Point`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The position of the point.
+    pub pos: Vector2Data,
+}
+
+impl Default for PointData {
+    fn default() -> Self {
+        Point::default().into()
+    }
+}
+
+impl From<Point> for PointData {
+    fn from(point: Point) -> Self {
+        match point.into_latest() {
+            Point::V2 { base, pos } => Self {
+                base: base.inner.into(),
+                pos: pos.inner.into(),
+            },
+            _ => unreachable!("`into_latest` returns the latest version"),
+        }
+    }
+}
+
+impl ModelObject for PointData {
+    type Object = Point;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<Point, ModelError> {
+        let pos = Versioned::new(self.pos.into());
+
+        match versions.point {
+            1 => Ok(Point::V1 {
+                meta_info: self.base.into_meta_info(),
+                pos,
+            }),
+            2 => Ok(Point::V2 {
+                base: self.base.into_versioned(versions)?,
+                pos,
+            }),
+            version => Err(ModelError::version("Point", version)),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! The flattened forms of [`PTrainerRange`] and [`PTrainerFloatingFloor`].
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+use crate::{
+    model::{
+        array_from, base::BaseData, vec_from, vector::Vector3Data, ModelError, ModelObject,
+        ModelVersions,
+    },
+    objects::{PTrainerFloatingFloor, PTrainerRange},
+    string::FixedString64,
+    version::{Upgrade, Versioned},
+};
+
+/// The flattened form of a [`PTrainerRange`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PTrainerRangeData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The minimum position a Pokémon Trainer can move to.
+    pub range_min: Vector3Data,
+
+    /// The maximum position a Pokémon Trainer can move to.
+    pub range_max: Vector3Data,
+
+    /// The collection of starting positions for each Pokémon Trainer in the range.
+    pub trainers: Vec<Vector3Data>,
+
+    /// The name of a model for the range to inherit select transformations from.
+    pub parent_model_name: FixedString64,
+
+    /// The name of a joint from the parent model for the range to inherit select transformations from.
+    pub parent_joint_name: FixedString64,
+}
+
+impl Default for PTrainerRangeData {
+    fn default() -> Self {
+        PTrainerRange::default().into()
+    }
+}
+
+impl From<PTrainerRange> for PTrainerRangeData {
+    fn from(range: PTrainerRange) -> Self {
+        match range.into_latest() {
+            PTrainerRange::V4 {
+                base,
+                range_min,
+                range_max,
+                trainers,
+                parent_model_name,
+                parent_joint_name,
+            } => Self {
+                base: base.inner.into(),
+                range_min: range_min.inner.into(),
+                range_max: range_max.inner.into(),
+                trainers: vec_from(trainers),
+                parent_model_name: parent_model_name.inner,
+                parent_joint_name: parent_joint_name.inner,
+            },
+            _ => unreachable!("`into_latest` returns the latest version"),
+        }
+    }
+}
+
+impl ModelObject for PTrainerRangeData {
+    type Object = PTrainerRange;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<PTrainerRange, ModelError> {
+        let base = self.base.into_versioned(versions)?;
+        let range_min = Versioned::new(self.range_min.into());
+        let range_max = Versioned::new(self.range_max.into());
+        let trainers = array_from(self.trainers, versions)?;
+
+        match versions.ptrainer_range {
+            1 => Ok(PTrainerRange::V1 {
+                base,
+                range_min,
+                range_max,
+                trainers,
+            }),
+            4 => Ok(PTrainerRange::V4 {
+                base,
+                range_min,
+                range_max,
+                trainers,
+                parent_model_name: Versioned::new(self.parent_model_name),
+                parent_joint_name: Versioned::new(self.parent_joint_name),
+            }),
+            version => Err(ModelError::version("PTrainerRange", version)),
+        }
+    }
+}
+
+/// The flattened form of a [`PTrainerFloatingFloor`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PTrainerFloatingFloorData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The position of the floating platform.
+    pub pos: Vector3Data,
+}
+
+impl Default for PTrainerFloatingFloorData {
+    fn default() -> Self {
+        PTrainerFloatingFloor::default().into()
+    }
+}
+
+impl From<PTrainerFloatingFloor> for PTrainerFloatingFloorData {
+    fn from(floating_floor: PTrainerFloatingFloor) -> Self {
+        match floating_floor {
+            PTrainerFloatingFloor::V1 { base, pos } => Self {
+                base: base.inner.into(),
+                pos: pos.inner.into(),
+            },
+        }
+    }
+}
+
+impl ModelObject for PTrainerFloatingFloorData {
+    type Object = PTrainerFloatingFloor;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<PTrainerFloatingFloor, ModelError> {
+        Ok(PTrainerFloatingFloor::V1 {
+            base: self.base.into_versioned(versions)?,
+            pos: Versioned::new(self.pos.into()),
+        })
+    }
+}
@@ -0,0 +1,64 @@
+//! The flattened form of [`Region`].
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+use crate::{
+    model::{base::BaseData, shape::RectData, ModelError, ModelObject, ModelVersions},
+    objects::Region,
+    version::{Upgrade, Versioned},
+};
+
+/// The flattened form of a [`Region`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionData {
+    /// The common data of the object.
+    pub base: BaseData,
+
+    /// The edge coordinates of the region.
+    pub rect: RectData,
+}
+
+impl Default for RegionData {
+    fn default() -> Self {
+        Region::default().into()
+    }
+}
+
+impl From<Region> for RegionData {
+    fn from(region: Region) -> Self {
+        match region.into_latest() {
+            Region::V2 { base, rect } => Self {
+                base: base.inner.into(),
+                rect: rect.inner.into(),
+            },
+            _ => unreachable!("`into_latest` returns the latest version"),
+        }
+    }
+}
+
+impl ModelObject for RegionData {
+    type Object = Region;
+
+    fn into_object(self, versions: &ModelVersions) -> Result<Region, ModelError> {
+        let rect = Versioned::new(self.rect.into());
+
+        match versions.region {
+            1 => Ok(Region::V1 {
+                meta_info: self.base.into_meta_info(),
+                rect,
+            }),
+            2 => Ok(Region::V2 {
+                base: self.base.into_versioned(versions)?,
+                rect,
+            }),
+            version => Err(ModelError::version("Region", version)),
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! The flattened forms of the geometric shape types.
+//!
+//! Three-dimensional shapes have no versioned fields, so the model uses [`Shape3`](crate::shape::Shape3) as it is.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+use crate::{
+    model::vector::Vector2Data,
+    shape::{Path, Rect, Shape2, ShapeArray2},
+    vector::Vector2,
+    version::Versioned,
+};
+
+/// The flattened form of a [`Rect`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RectData {
+    /// The coordinate of the left edge.
+    pub left: f32,
+
+    /// The coordinate of the right edge.
+    pub right: f32,
+
+    /// The coordinate of the top edge.
+    pub top: f32,
+
+    /// The coordinate of the bottom edge.
+    pub bottom: f32,
+}
+
+impl From<Rect> for RectData {
+    fn from(rect: Rect) -> Self {
+        match rect {
+            Rect::V1 {
+                left,
+                right,
+                top,
+                bottom,
+            } => Self {
+                left,
+                right,
+                top,
+                bottom,
+            },
+        }
+    }
+}
+
+impl From<RectData> for Rect {
+    fn from(rect: RectData) -> Self {
+        Self::new(rect.left, rect.right, rect.top, rect.bottom)
+    }
+}
+
+/// The flattened form of a [`Shape2`].
+///
+/// The binary format stores a path with every shape type, which should always be empty for
+/// shape types other than [`Path`](Self::Path). It is kept so unusual data is not lost,
+/// but omitted from the serialized form when empty.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape2Data {
+    /// The point shape type.
+    Point {
+        /// The position along the x-axis.
+        pos_x: f32,
+
+        /// The position along the y-axis.
+        pos_y: f32,
+
+        /// The collection of points forming the path shape, which should always be empty.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        path: Vec<Vector2Data>,
+    },
+
+    /// The circle shape type.
+    Circle {
+        /// The position along the x-axis.
+        pos_x: f32,
+
+        /// The position along the y-axis.
+        pos_y: f32,
+
+        /// The radius of the circle.
+        radius: f32,
+
+        /// The collection of points forming the path shape, which should always be empty.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        path: Vec<Vector2Data>,
+    },
+
+    /// The rectangle shape type.
+    Rect {
+        /// The coordinate of the left edge.
+        left: f32,
+
+        /// The coordinate of the right edge.
+        right: f32,
+
+        /// The coordinate of the bottom edge.
+        bottom: f32,
+
+        /// The coordinate of the top edge.
+        top: f32,
+
+        /// The collection of points forming the path shape, which should always be empty.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        path: Vec<Vector2Data>,
+    },
+
+    /// The path shape type.
+    Path {
+        /// The collection of points forming the path shape.
+        points: Vec<Vector2Data>,
+    },
+
+    /// A shape type not known to this library.
+    Unknown {
+        /// The numeric value of the shape type.
+        ty: u32,
+
+        /// The raw data of the shape.
+        data: [u32; 4],
+
+        /// The collection of points forming the path shape.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        path: Vec<Vector2Data>,
+    },
+}
+
+impl Default for Shape2Data {
+    fn default() -> Self {
+        Shape2::default().into()
+    }
+}
+
+impl From<Shape2> for Shape2Data {
+    fn from(shape: Shape2) -> Self {
+        match shape {
+            Shape2::Point { pos_x, pos_y, path } => Self::Point {
+                pos_x,
+                pos_y,
+                path: points_from(path),
+            },
+            Shape2::Circle {
+                pos_x,
+                pos_y,
+                radius,
+                path,
+            } => Self::Circle {
+                pos_x,
+                pos_y,
+                radius,
+                path: points_from(path),
+            },
+            Shape2::Rect {
+                left,
+                right,
+                bottom,
+                top,
+                path,
+            } => Self::Rect {
+                left,
+                right,
+                bottom,
+                top,
+                path: points_from(path),
+            },
+            Shape2::Path { path } => Self::Path {
+                points: points_from(path),
+            },
+            Shape2::Unknown { ty, data, path } => Self::Unknown {
+                ty,
+                data,
+                path: points_from(path),
+            },
+        }
+    }
+}
+
+impl From<Shape2Data> for Shape2 {
+    fn from(shape: Shape2Data) -> Self {
+        match shape {
+            Shape2Data::Point { pos_x, pos_y, path } => Self::Point {
+                pos_x,
+                pos_y,
+                path: path_from(path),
+            },
+            Shape2Data::Circle {
+                pos_x,
+                pos_y,
+                radius,
+                path,
+            } => Self::Circle {
+                pos_x,
+                pos_y,
+                radius,
+                path: path_from(path),
+            },
+            Shape2Data::Rect {
+                left,
+                right,
+                bottom,
+                top,
+                path,
+            } => Self::Rect {
+                left,
+                right,
+                bottom,
+                top,
+                path: path_from(path),
+            },
+            Shape2Data::Path { points } => Self::Path {
+                path: path_from(points),
+            },
+            Shape2Data::Unknown { ty, data, path } => Self::Unknown {
+                ty,
+                data,
+                path: path_from(path),
+            },
+        }
+    }
+}
+
+/// Converts a path into its collection of points.
+pub(crate) fn points_from(path: Versioned<Path>) -> Vec<Vector2Data> {
+    match path.inner {
+        Path::V1 { points } => points.inner.into_iter().map(Vector2Data::from).collect(),
+    }
+}
+
+/// Converts a collection of points into a path.
+pub(crate) fn path_from(points: Vec<Vector2Data>) -> Versioned<Path> {
+    Versioned::new(Path::new(points.into_iter().map(Vector2::from)))
+}
+
+/// Converts a collection of shapes into its flattened form.
+pub(crate) fn shapes_from(shapes: Versioned<ShapeArray2>) -> Vec<Shape2Data> {
+    match shapes.inner {
+        ShapeArray2::V1 { shapes } => shapes
+            .inner
+            .into_iter()
+            .map(|element| element.0.inner.into())
+            .collect(),
+    }
+}
+
+/// Converts a flattened collection of shapes into a collection of shapes.
+pub(crate) fn shape_array_from(shapes: Vec<Shape2Data>) -> Versioned<ShapeArray2> {
+    Versioned::new(ShapeArray2::new(shapes.into_iter().map(Shape2::from)))
+}
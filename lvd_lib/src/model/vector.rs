@@ -0,0 +1,93 @@
+//! The flattened forms of the basic vector types.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+use crate::{
+    model::{ModelError, ModelObject, ModelVersions},
+    vector::{Vector2, Vector3},
+};
+
+/// The flattened form of a [`Vector2`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Vector2Data {
+    /// The component along the x-axis.
+    pub x: f32,
+
+    /// The component along the y-axis.
+    pub y: f32,
+}
+
+impl Vector2Data {
+    /// Creates a new `Vector2Data` from the given components.
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<Vector2> for Vector2Data {
+    fn from(vector: Vector2) -> Self {
+        Self::new(vector.x(), vector.y())
+    }
+}
+
+impl From<Vector2Data> for Vector2 {
+    fn from(vector: Vector2Data) -> Self {
+        Self::new(vector.x, vector.y)
+    }
+}
+
+/// The flattened form of a [`Vector3`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Vector3Data {
+    /// The component along the x-axis.
+    pub x: f32,
+
+    /// The component along the y-axis.
+    pub y: f32,
+
+    /// The component along the z-axis.
+    pub z: f32,
+}
+
+impl Vector3Data {
+    /// Creates a new `Vector3Data` from the given components.
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl From<Vector3> for Vector3Data {
+    fn from(vector: Vector3) -> Self {
+        Self::new(vector.x(), vector.y(), vector.z())
+    }
+}
+
+impl From<Vector3Data> for Vector3 {
+    fn from(vector: Vector3Data) -> Self {
+        Self::new(vector.x, vector.y, vector.z)
+    }
+}
+
+impl ModelObject for Vector2Data {
+    type Object = Vector2;
+
+    fn into_object(self, _versions: &ModelVersions) -> Result<Vector2, ModelError> {
+        Ok(self.into())
+    }
+}
+
+impl ModelObject for Vector3Data {
+    type Object = Vector3;
+
+    fn into_object(self, _versions: &ModelVersions) -> Result<Vector3, ModelError> {
+        Ok(self.into())
+    }
+}
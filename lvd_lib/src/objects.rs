@@ -1,5 +1,183 @@
 //! The supported LVD objects for reading and writing.
 
+use std::fmt;
+
+use crate::{
+    hash::ContentHash,
+    shape::{Shape2, Shape3},
+    size::BinarySize,
+    string::FixedString56,
+    tag::Tag,
+    transform::CoordinateMap,
+    version::Upgrade,
+};
+
+use base::{Base, MetaInfo};
+
+/// A trait for accessing the data common to every LVD object.
+///
+/// Each object type stores its name, tag, and other common data in the fields of every one of
+/// its versions. This trait hides those differences, so operations on every object of a file,
+/// such as [`LvdFile::rename_prefix`](crate::LvdFile::rename_prefix) and
+/// [`Lvd::for_each_object_mut`](crate::Lvd::for_each_object_mut), are written once rather than
+/// once for each object type and version.
+pub trait LvdObject: BinarySize + ContentHash {
+    /// Returns the metadata of the object.
+    fn meta_info(&self) -> &MetaInfo;
+
+    /// Returns the metadata of the object mutably.
+    fn meta_info_mut(&mut self) -> &mut MetaInfo;
+
+    /// Returns the common data of the object, if supported by the object's version.
+    fn base(&self) -> Option<&Base>;
+
+    /// Returns the common data of the object mutably, if supported by the object's version.
+    fn base_mut(&mut self) -> Option<&mut Base>;
+
+    /// Returns the name of the object.
+    fn name(&self) -> &FixedString56 {
+        self.meta_info().name()
+    }
+
+    /// Returns the identifier for matching and filtering like objects, if the object has one.
+    fn tag(&self) -> Option<&Tag> {
+        None
+    }
+
+    /// Returns the identifier for matching and filtering like objects mutably, if the object has one.
+    fn tag_mut(&mut self) -> Option<&mut Tag> {
+        None
+    }
+
+    /// Returns the two-dimensional shapes of the object.
+    fn shapes2(&self) -> Vec<&Shape2> {
+        Vec::new()
+    }
+
+    /// Returns the three-dimensional shapes of the object.
+    fn shapes3(&self) -> Vec<&Shape3> {
+        Vec::new()
+    }
+
+    /// Applies the transform, or another mapping of coordinates, to the positions and shapes of the object.
+    ///
+    /// Objects exclusive to Smash Run are left unchanged.
+    fn transform(&mut self, _transform: &dyn CoordinateMap) {}
+}
+
+/// Writes the summary of an object used by its [`fmt::Display`] implementation,
+/// made of its type, name, and tag, followed by the given details.
+///
+/// The summary of a collision is written as follows:
+///
+/// ```text
+/// Collision "COL_00_Floor01": 24 vertices, 2 cliffs, throughable
+/// ```
+pub(crate) fn write_summary(
+    f: &mut fmt::Formatter<'_>,
+    type_name: &str,
+    object: &dyn LvdObject,
+    details: &[String],
+) -> fmt::Result {
+    match object.name().to_str() {
+        Ok(name) => write!(f, "{type_name} {name:?}")?,
+        Err(_) => write!(f, "{type_name} <invalid name>")?,
+    }
+
+    let tag = object
+        .tag()
+        .filter(|tag| **tag != Tag::default())
+        .map(|tag| format!("tag {tag}"));
+
+    for (index, detail) in tag.iter().chain(details).enumerate() {
+        let separator = if index == 0 { ": " } else { ", " };
+
+        write!(f, "{separator}{detail}")?;
+    }
+
+    Ok(())
+}
+
+/// Converts the common data of an object into its latest version, as done by every object's
+/// [`Upgrade::into_latest`] implementation.
+pub(crate) fn upgrade_base<T: LvdObject>(mut object: T) -> T {
+    if let Some(base) = object.base_mut() {
+        base.upgrade();
+    }
+
+    object
+}
+
+/// Returns the count followed by the singular or plural form of a noun,
+/// such as `1 cliff` or `2 cliffs`.
+pub(crate) fn count(count: usize, singular: &str, plural: &str) -> String {
+    match count {
+        1 => format!("1 {singular}"),
+        count => format!("{count} {plural}"),
+    }
+}
+
+/// Implements [`LvdObject`] for a versioned object type given the variants
+/// storing [`MetaInfo`] directly, the variants storing a [`Base`], and the
+/// variants storing a [`Tag`], and any overridden trait methods.
+///
+/// The accessors only differ in which variants hold which fields, so the macro keeps the
+/// implementations of every object type from drifting apart as versions are added.
+macro_rules! impl_lvd_object {
+    ($ty:ty {
+        $(meta_info: [$($meta:ident),+],)?
+        base: [$($base:ident),+]
+        $(, tag: [$($tagged:ident),+])? $(,)?
+        $(; $($methods:item)*)?
+    }) => {
+        impl $crate::objects::LvdObject for $ty {
+            fn meta_info(&self) -> &$crate::objects::base::MetaInfo {
+                match self {
+                    $($(Self::$meta { meta_info, .. } => meta_info,)+)?
+                    $(Self::$base { base, .. } => base.meta_info(),)+
+                }
+            }
+
+            fn meta_info_mut(&mut self) -> &mut $crate::objects::base::MetaInfo {
+                match self {
+                    $($(Self::$meta { meta_info, .. } => meta_info,)+)?
+                    $(Self::$base { base, .. } => base.meta_info_mut(),)+
+                }
+            }
+
+            fn base(&self) -> Option<&$crate::objects::base::Base> {
+                match self {
+                    $($(Self::$meta { .. } => None,)+)?
+                    $(Self::$base { base, .. } => Some(base),)+
+                }
+            }
+
+            fn base_mut(&mut self) -> Option<&mut $crate::objects::base::Base> {
+                match self {
+                    $($(Self::$meta { .. } => None,)+)?
+                    $(Self::$base { base, .. } => Some(base),)+
+                }
+            }
+
+            $(
+                fn tag(&self) -> Option<&$crate::tag::Tag> {
+                    match self {
+                        $(Self::$tagged { tag, .. } => Some(tag),)+
+                    }
+                }
+
+                fn tag_mut(&mut self) -> Option<&mut $crate::tag::Tag> {
+                    match self {
+                        $(Self::$tagged { tag, .. } => Some(tag),)+
+                    }
+                }
+            )?
+
+            $($($methods)*)?
+        }
+    };
+}
+
 pub mod base;
 pub mod collision;
 pub mod damage_shape;
@@ -1,22 +1,29 @@
 //! The [`Base`] and [`MetaInfo`] types store common data for an LVD object.
 
+use std::str::FromStr;
+
 use binrw::binrw;
+use thiserror::Error;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
 use crate::{
     id::Id,
-    string::{FixedString56, FixedString64},
+    string::{FixedString56, FixedString64, FromStrError},
     vector::Vector3,
-    version::{Version, Versioned},
+    version::{Upgrade, Version, Versioned},
 };
 
 /// The common data for an LVD object.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum Base {
     /// The first version of the `Base` type.
     ///
@@ -120,11 +127,187 @@ impl Version for Base {
     }
 }
 
+impl Default for Base {
+    fn default() -> Self {
+        Self::V4 {
+            meta_info: Versioned::new(MetaInfo::default()),
+            dynamic_name: Versioned::new(FixedString64::new()),
+            dynamic_offset: Versioned::new(Vector3::default()),
+            is_dynamic: false,
+            instance_id: Versioned::new(Id::default()),
+            instance_offset: Versioned::new(Vector3::default()),
+            // Unlike the other fields, the joint index is not zeroed,
+            // since an index of zero refers to the first joint of the parent model.
+            joint_index: -1,
+            joint_name: Versioned::new(FixedString64::new()),
+        }
+    }
+}
+
+impl Upgrade for Base {
+    const LATEST_VERSION: u8 = 4;
+
+    fn into_latest(self) -> Self {
+        match self {
+            Self::V1 {
+                meta_info,
+                dynamic_name,
+            } => Self::V2 {
+                meta_info,
+                dynamic_name,
+                dynamic_offset: Versioned::new(Vector3::default()),
+            }
+            .into_latest(),
+            Self::V2 {
+                meta_info,
+                dynamic_name,
+                dynamic_offset,
+            } => Self::V3 {
+                meta_info,
+                dynamic_name,
+                dynamic_offset,
+                is_dynamic: false,
+                instance_id: Versioned::new(Id::default()),
+                instance_offset: Versioned::new(Vector3::default()),
+            }
+            .into_latest(),
+            Self::V3 {
+                meta_info,
+                dynamic_name,
+                dynamic_offset,
+                is_dynamic,
+                instance_id,
+                instance_offset,
+            } => Self::V4 {
+                meta_info,
+                dynamic_name,
+                dynamic_offset,
+                is_dynamic,
+                instance_id,
+                instance_offset,
+                joint_index: -1,
+                joint_name: Versioned::new(FixedString64::new()),
+            },
+            Self::V4 { .. } => self,
+        }
+    }
+}
+
+impl Base {
+    /// Returns the latest version of the common data with the given metadata and default values
+    /// for every other field, for upgrading objects which store their metadata directly.
+    pub(crate) fn from_meta_info(meta_info: Versioned<MetaInfo>) -> Versioned<Self> {
+        Versioned::new(
+            Self::V1 {
+                meta_info,
+                dynamic_name: Versioned::new(FixedString64::new()),
+            }
+            .into_latest(),
+        )
+    }
+
+    /// Returns the metadata of the object.
+    pub fn meta_info(&self) -> &MetaInfo {
+        match self {
+            Self::V1 { meta_info, .. }
+            | Self::V2 { meta_info, .. }
+            | Self::V3 { meta_info, .. }
+            | Self::V4 { meta_info, .. } => meta_info,
+        }
+    }
+
+    /// Returns the metadata of the object mutably.
+    pub fn meta_info_mut(&mut self) -> &mut MetaInfo {
+        match self {
+            Self::V1 { meta_info, .. }
+            | Self::V2 { meta_info, .. }
+            | Self::V3 { meta_info, .. }
+            | Self::V4 { meta_info, .. } => meta_info,
+        }
+    }
+
+    /// Returns the name of the object as seen by the game when classed as dynamic.
+    pub fn dynamic_name(&self) -> &FixedString64 {
+        match self {
+            Self::V1 { dynamic_name, .. }
+            | Self::V2 { dynamic_name, .. }
+            | Self::V3 { dynamic_name, .. }
+            | Self::V4 { dynamic_name, .. } => dynamic_name,
+        }
+    }
+
+    /// Returns the name of the object as seen by the game when classed as dynamic mutably.
+    pub fn dynamic_name_mut(&mut self) -> &mut FixedString64 {
+        match self {
+            Self::V1 { dynamic_name, .. }
+            | Self::V2 { dynamic_name, .. }
+            | Self::V3 { dynamic_name, .. }
+            | Self::V4 { dynamic_name, .. } => dynamic_name,
+        }
+    }
+
+    /// Returns the name of the joint the object is parented to, if supported by the version.
+    pub fn joint_name(&self) -> Option<&FixedString64> {
+        match self {
+            Self::V4 { joint_name, .. } => Some(joint_name),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the joint the object is parented to mutably, if supported by the version.
+    pub fn joint_name_mut(&mut self) -> Option<&mut FixedString64> {
+        match self {
+            Self::V4 { joint_name, .. } => Some(joint_name),
+            _ => None,
+        }
+    }
+
+    /// Classes the object as dynamic and parents it to the joint named `joint_name`
+    /// from the parent model.
+    ///
+    /// The joint index is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version does not support joints,
+    /// or if the name exceeds the buffer's capacity.
+    pub fn bind_to_joint(&mut self, joint_name: &str) -> Result<(), JointBindingError> {
+        let name = FixedString64::from_str(joint_name)?;
+
+        match self {
+            Self::V4 {
+                is_dynamic,
+                joint_name,
+                ..
+            } => {
+                *is_dynamic = true;
+                **joint_name = name;
+
+                Ok(())
+            }
+            _ => Err(JointBindingError::UnsupportedVersion(self.version())),
+        }
+    }
+}
+
+/// The error type used when binding an object to a joint with [`Base::bind_to_joint`].
+#[derive(Debug, PartialEq, Error)]
+pub enum JointBindingError {
+    /// The version of the object's common data does not support joints.
+    #[error("base version {0} does not support joints")]
+    UnsupportedVersion(u8),
+
+    /// The name of the joint exceeds the buffer's capacity.
+    #[error(transparent)]
+    InvalidJointName(#[from] FromStrError<64>),
+}
+
 /// The metadata for an LVD object.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum MetaInfo {
     /// The first version of the `MetaInfo` type.
     #[br(pre_assert(version == 1))]
@@ -145,11 +328,37 @@ impl Version for MetaInfo {
     }
 }
 
+impl Default for MetaInfo {
+    fn default() -> Self {
+        Self::V1 {
+            version_info: Versioned::new(VersionInfo::default()),
+            name: Versioned::new(FixedString56::new()),
+        }
+    }
+}
+
+impl MetaInfo {
+    /// Returns the name of the object.
+    pub fn name(&self) -> &FixedString56 {
+        match self {
+            Self::V1 { name, .. } => name,
+        }
+    }
+
+    /// Returns the name of the object mutably.
+    pub fn name_mut(&mut self) -> &mut FixedString56 {
+        match self {
+            Self::V1 { name, .. } => name,
+        }
+    }
+}
+
 /// The version metadata for an LVD object.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum VersionInfo {
     /// The first version of the `VersionInfo` type.
     #[br(pre_assert(version == 1))]
@@ -169,3 +378,37 @@ impl Version for VersionInfo {
         }
     }
 }
+
+impl Default for VersionInfo {
+    fn default() -> Self {
+        Self::V1 {
+            editor_version: 0,
+            format_version: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_latest_and_unbound() {
+        let base = Base::default();
+
+        assert_eq!(base.version(), Base::LATEST_VERSION);
+        assert_eq!(base.meta_info().version(), 1);
+        assert!(base.meta_info().name().is_empty());
+        assert!(base.dynamic_name().is_empty());
+        assert!(base.joint_name().is_some_and(|name| name.is_empty()));
+        assert!(matches!(
+            base,
+            Base::V4 {
+                is_dynamic: false,
+                joint_index: -1,
+                ..
+            }
+        ));
+        assert_eq!(base.clone().into_latest().version(), Base::LATEST_VERSION);
+    }
+}
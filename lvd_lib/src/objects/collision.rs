@@ -2,33 +2,55 @@
 //!
 //! Extra data is stored to define properties of each edge in the collision.
 
+use std::{fmt, str::FromStr};
+
 use binrw::binrw;
+use thiserror::Error;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
 use crate::{
     array::Array,
-    objects::base::{Base, MetaInfo},
+    objects::{
+        base::{Base, MetaInfo},
+        count, upgrade_base, write_summary, LvdObject,
+    },
+    string::{FixedString64, FromStrError},
+    transform::{CoordinateMap, Transform},
     vector::Vector2,
-    version::{Version, Versioned},
+    version::{Upgrade, Version, Versioned},
 };
 
 pub mod attribute;
 pub mod cliff;
+pub mod edge;
 pub mod flags;
+mod geometry;
+mod join;
 pub mod spirits_floor;
 
+use attribute::{AttributeFlags, MaterialType};
+
 pub use attribute::CollisionAttribute;
 pub use cliff::CollisionCliff;
+pub use edge::{Edge, EdgeMut};
 pub use flags::CollisionFlags;
+pub use geometry::{SubdivideError, Winding};
+pub use join::JoinError;
 pub use spirits_floor::CollisionSpiritsFloor;
 
+pub(crate) use geometry::segment_distance;
+
 /// An LVD object representing a two-dimensional polygonal collision.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum Collision {
     /// The first version of the `Collision` type.
     ///
@@ -134,3 +156,466 @@ impl Version for Collision {
         }
     }
 }
+
+impl Default for Collision {
+    fn default() -> Self {
+        Self::V4 {
+            base: Versioned::new(Base::default()),
+            flags: CollisionFlags::new(),
+            vertices: Versioned::new(Array::default()),
+            normals: Versioned::new(Array::default()),
+            cliffs: Versioned::new(Array::default()),
+            attributes: Versioned::new(Array::default()),
+            spirits_floors: Versioned::new(Array::default()),
+        }
+    }
+}
+
+impl Upgrade for Collision {
+    const LATEST_VERSION: u8 = 4;
+
+    fn into_latest(self) -> Self {
+        match self {
+            Self::V1 {
+                meta_info,
+                flags,
+                vertices,
+                normals,
+                cliffs,
+            } => Self::V2 {
+                base: Base::from_meta_info(meta_info),
+                flags,
+                vertices,
+                normals,
+                cliffs,
+            }
+            .into_latest(),
+            Self::V2 {
+                base,
+                flags,
+                vertices,
+                normals,
+                cliffs,
+            } => {
+                let attributes = (0..normals.len())
+                    .map(|_| CollisionAttribute::default())
+                    .collect();
+
+                Self::V3 {
+                    base,
+                    flags,
+                    vertices,
+                    normals,
+                    cliffs,
+                    attributes: Versioned::new(attributes),
+                }
+                .into_latest()
+            }
+            Self::V3 {
+                base,
+                flags,
+                vertices,
+                normals,
+                cliffs,
+                attributes,
+            } => Self::V4 {
+                base,
+                flags,
+                vertices,
+                normals,
+                cliffs,
+                attributes,
+                spirits_floors: Versioned::new(Array::default()),
+            }
+            .into_latest(),
+            Self::V4 {
+                base,
+                flags,
+                vertices,
+                normals,
+                cliffs,
+                attributes,
+                spirits_floors,
+            } => upgrade_base(Self::V4 {
+                base,
+                flags,
+                vertices,
+                normals,
+                cliffs: cliffs.into_latest(),
+                attributes: attributes.into_latest(),
+                spirits_floors: spirits_floors.into_latest(),
+            }),
+        }
+    }
+}
+
+impl Collision {
+    /// Creates a new drop-through platform between the given endpoints.
+    ///
+    /// The platform is a single floor edge with the `throughable` attribute, a cliff at
+    /// each end, and the throughable collision flag set. The `cloud` attribute is left unset,
+    /// as only some stages use it for their soft platforms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{objects::Collision, vector::Vector2};
+    ///
+    /// let platform = Collision::new_soft_platform(Vector2::new(-30.0, 40.0), Vector2::new(30.0, 40.0));
+    ///
+    /// assert!(platform.flags().throughable());
+    /// assert!(platform.is_floor_edge(0));
+    /// ```
+    pub fn new_soft_platform(left: Vector2, right: Vector2) -> Self {
+        let normal = (right - left)
+            .perp()
+            .normalize()
+            .unwrap_or(Vector2::new(0.0, 1.0));
+        let cliff = |pos, lr| CollisionCliff::V3 {
+            base: Versioned::new(Base::default()),
+            pos: Versioned::new(pos),
+            lr,
+            line_index: 0,
+        };
+        let mut collision = Self::V4 {
+            base: Versioned::new(Base::default()),
+            flags: CollisionFlags::new().with_throughable(true),
+            vertices: Versioned::new(Array::new(vec![left, right])),
+            normals: Versioned::new(Array::new(vec![normal])),
+            cliffs: Versioned::new(Array::new(vec![cliff(left, -1.0), cliff(right, 1.0)])),
+            attributes: Versioned::new(Array::new(vec![CollisionAttribute::V1 {
+                material: MaterialType::Rock,
+                flags: AttributeFlags::new().with_throughable(true),
+            }])),
+            spirits_floors: Versioned::new(Array::default()),
+        };
+
+        collision.infer_edge_flags();
+
+        collision
+    }
+
+    /// Returns the global attributes of the collision.
+    pub fn flags(&self) -> &CollisionFlags {
+        match self {
+            Self::V1 { flags, .. }
+            | Self::V2 { flags, .. }
+            | Self::V3 { flags, .. }
+            | Self::V4 { flags, .. } => flags,
+        }
+    }
+
+    /// Returns the global attributes of the collision mutably.
+    pub fn flags_mut(&mut self) -> &mut CollisionFlags {
+        match self {
+            Self::V1 { flags, .. }
+            | Self::V2 { flags, .. }
+            | Self::V3 { flags, .. }
+            | Self::V4 { flags, .. } => flags,
+        }
+    }
+
+    /// Returns the collection of vertices forming the geometry of the collision.
+    pub fn vertices(&self) -> &Array<Vector2> {
+        match self {
+            Self::V1 { vertices, .. }
+            | Self::V2 { vertices, .. }
+            | Self::V3 { vertices, .. }
+            | Self::V4 { vertices, .. } => vertices,
+        }
+    }
+
+    /// Returns the collection of vertices forming the geometry of the collision mutably.
+    pub fn vertices_mut(&mut self) -> &mut Array<Vector2> {
+        match self {
+            Self::V1 { vertices, .. }
+            | Self::V2 { vertices, .. }
+            | Self::V3 { vertices, .. }
+            | Self::V4 { vertices, .. } => vertices,
+        }
+    }
+
+    /// Returns the collection of unit normal vectors for each edge.
+    pub fn normals(&self) -> &Array<Vector2> {
+        match self {
+            Self::V1 { normals, .. }
+            | Self::V2 { normals, .. }
+            | Self::V3 { normals, .. }
+            | Self::V4 { normals, .. } => normals,
+        }
+    }
+
+    /// Returns the collection of unit normal vectors for each edge mutably.
+    pub fn normals_mut(&mut self) -> &mut Array<Vector2> {
+        match self {
+            Self::V1 { normals, .. }
+            | Self::V2 { normals, .. }
+            | Self::V3 { normals, .. }
+            | Self::V4 { normals, .. } => normals,
+        }
+    }
+
+    /// Returns the collection of supplementary data for grabbable edges.
+    pub fn cliffs(&self) -> &Array<CollisionCliff> {
+        match self {
+            Self::V1 { cliffs, .. }
+            | Self::V2 { cliffs, .. }
+            | Self::V3 { cliffs, .. }
+            | Self::V4 { cliffs, .. } => cliffs,
+        }
+    }
+
+    /// Returns the collection of supplementary data for grabbable edges mutably.
+    pub fn cliffs_mut(&mut self) -> &mut Array<CollisionCliff> {
+        match self {
+            Self::V1 { cliffs, .. }
+            | Self::V2 { cliffs, .. }
+            | Self::V3 { cliffs, .. }
+            | Self::V4 { cliffs, .. } => cliffs,
+        }
+    }
+
+    /// Returns the collection of properties and attributes for each edge, if supported by the version.
+    pub fn attributes(&self) -> Option<&Array<CollisionAttribute>> {
+        match self {
+            Self::V3 { attributes, .. } | Self::V4 { attributes, .. } => Some(attributes),
+            _ => None,
+        }
+    }
+
+    /// Returns the collection of properties and attributes for each edge mutably, if supported by the version.
+    pub fn attributes_mut(&mut self) -> Option<&mut Array<CollisionAttribute>> {
+        match self {
+            Self::V3 { attributes, .. } | Self::V4 { attributes, .. } => Some(attributes),
+            _ => None,
+        }
+    }
+
+    /// Returns the collection of entries related to hazardous floors in spirit battles, if supported by the version.
+    pub fn spirits_floors(&self) -> Option<&Array<CollisionSpiritsFloor>> {
+        match self {
+            Self::V4 { spirits_floors, .. } => Some(spirits_floors),
+            _ => None,
+        }
+    }
+
+    /// Returns the collection of entries related to hazardous floors in spirit battles mutably, if supported by the version.
+    pub fn spirits_floors_mut(&mut self) -> Option<&mut Array<CollisionSpiritsFloor>> {
+        match self {
+            Self::V4 { spirits_floors, .. } => Some(spirits_floors),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of edges in the collision.
+    pub fn edge_count(&self) -> usize {
+        self.normals().len()
+    }
+
+    /// Moves the collision from the space of the joint it is bound to into world space,
+    /// given the joint's world transform, such as one frame of the joint's animation.
+    ///
+    /// The vertices, normals, and cliff positions are transformed. The binding is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{objects::Collision, transform::Transform, vector::{Vector2, Vector3}};
+    ///
+    /// let mut collision =
+    ///     Collision::new_soft_platform(Vector2::new(-10.0, 0.0), Vector2::new(10.0, 0.0));
+    ///
+    /// collision.apply_joint_transform(&Transform::from_translation(Vector3::new(0.0, 20.0, 0.0)));
+    ///
+    /// assert_eq!(*collision.vertices().elements()[0], Vector2::new(-10.0, 20.0));
+    /// ```
+    pub fn apply_joint_transform(&mut self, transform: &Transform) {
+        LvdObject::transform(self, transform);
+    }
+
+    /// Returns `true` if the edge at the given index faces upwards.
+    ///
+    /// An edge faces upwards when the vertical component of its unit normal vector
+    /// is positive and greater than the magnitude of its horizontal component.
+    pub fn is_floor_edge(&self, index: usize) -> bool {
+        self.normals()
+            .elements()
+            .get(index)
+            .is_some_and(|normal| normal.y() > normal.x().abs())
+    }
+
+    /// Creates a spirits floor entry for every floor edge in the collision
+    /// belonging to the line group `group_name`.
+    ///
+    /// Edges which already have an associated entry are skipped.
+    /// The unknown values of each entry are set to `1.0` for `unk1` through `unk4` and `0.0`
+    /// for `unk5` and `unk6`, though what they control is not known.
+    /// On success, the number of created entries is returned.
+    pub fn generate_spirits_floors(
+        &mut self,
+        group_name: &str,
+    ) -> Result<usize, SpiritsFloorError> {
+        let line_group = FixedString64::from_str(group_name)?;
+        let floor_edges = (0..self.edge_count())
+            .filter(|&index| self.is_floor_edge(index))
+            .collect::<Vec<_>>();
+        let version = self.version();
+        let Some(spirits_floors) = self.spirits_floors_mut() else {
+            return Err(SpiritsFloorError::UnsupportedVersion(version));
+        };
+        let mut count = 0;
+
+        for index in floor_edges {
+            let line_index = index as u32;
+
+            if spirits_floors
+                .iter()
+                .any(|spirits_floor| spirits_floor.line_index() == line_index)
+            {
+                continue;
+            }
+
+            spirits_floors.push(CollisionSpiritsFloor::V2 {
+                base: Versioned::new(Base::default()),
+                line_index,
+                line_group: Versioned::new(line_group.clone()),
+                unk1: 1.0,
+                unk2: 1.0,
+                unk3: 1.0,
+                unk4: 1.0,
+                unk5: 0.0,
+                unk6: 0.0,
+            });
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
+
+impl_lvd_object!(Collision {
+    meta_info: [V1],
+    base: [V2, V3, V4];
+
+    fn transform(&mut self, transform: &dyn CoordinateMap) {
+        for vertex in self.vertices_mut().iter_mut() {
+            *vertex = transform.map_point2(*vertex);
+        }
+
+        for normal in self.normals_mut().iter_mut() {
+            *normal = transform.map_normal2(*normal);
+        }
+
+        for cliff in self.cliffs_mut().iter_mut() {
+            *cliff.pos_mut() = transform.map_point2(cliff.pos());
+        }
+    }
+});
+
+impl fmt::Display for Collision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut details = vec![
+            count(self.vertices().len(), "vertex", "vertices"),
+            count(self.cliffs().len(), "cliff", "cliffs"),
+        ];
+
+        if self.flags().throughable() {
+            details.push("throughable".to_string());
+        }
+
+        if self.flags().dynamic() {
+            details.push("dynamic".to_string());
+        }
+
+        write_summary(f, "Collision", self, &details)
+    }
+}
+
+/// The error type used when generating spirits floor entries for a [`Collision`].
+#[derive(Debug, PartialEq, Error)]
+pub enum SpiritsFloorError {
+    /// The version of the collision does not support spirits floor entries.
+    #[error("collision version {0} does not support spirits floors")]
+    UnsupportedVersion(u8),
+
+    /// The name of the line group exceeds the buffer's capacity.
+    #[error(transparent)]
+    InvalidGroupName(#[from] FromStrError<64>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a clockwise box with a floor, a right wall, a ceiling, and a left wall.
+    fn solid_box() -> Collision {
+        let mut collision = Collision::default();
+
+        *collision.vertices_mut() = [
+            (-10.0, 0.0),
+            (10.0, 0.0),
+            (10.0, -5.0),
+            (-10.0, -5.0),
+            (-10.0, 0.0),
+        ]
+        .into_iter()
+        .map(|(x, y)| Vector2::new(x, y))
+        .collect();
+        *collision.normals_mut() = [(0.0, 1.0), (1.0, 0.0), (0.0, -1.0), (-1.0, 0.0)]
+            .into_iter()
+            .map(|(x, y)| Vector2::new(x, y))
+            .collect();
+        collision
+    }
+
+    #[test]
+    fn generate_spirits_floors_for_floor_edges() {
+        let mut collision = solid_box();
+
+        assert_eq!(collision.generate_spirits_floors("Poison"), Ok(1));
+
+        let spirits_floors = collision.spirits_floors().unwrap().elements();
+
+        assert!(matches!(
+            *spirits_floors[0],
+            CollisionSpiritsFloor::V2 {
+                line_index: 0,
+                ref line_group,
+                unk1: 1.0,
+                unk5: 0.0,
+                ..
+            } if line_group.to_str() == Ok("Poison")
+        ));
+        assert_eq!(
+            collision.generate_spirits_floors("Poison"),
+            Ok(0),
+            "edges with an entry are skipped"
+        );
+        assert_eq!(collision.spirits_floors().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn generate_spirits_floors_errors() {
+        let mut collision = solid_box();
+
+        assert!(matches!(
+            collision.generate_spirits_floors(&"a".repeat(64)),
+            Err(SpiritsFloorError::InvalidGroupName(_))
+        ));
+        assert!(collision.spirits_floors().unwrap().is_empty());
+
+        let mut old = Collision::V3 {
+            base: Versioned::new(Base::default()),
+            flags: CollisionFlags::new(),
+            vertices: Versioned::new(collision.vertices().clone()),
+            normals: Versioned::new(collision.normals().clone()),
+            cliffs: Versioned::new(Array::default()),
+            attributes: Versioned::new(Array::default()),
+        };
+
+        assert_eq!(
+            old.generate_spirits_floors("Poison"),
+            Err(SpiritsFloorError::UnsupportedVersion(3))
+        );
+    }
+}
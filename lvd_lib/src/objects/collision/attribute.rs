@@ -1,18 +1,34 @@
 //! The [`CollisionAttribute`] object stores data representing the properties and attributes of an edge.
 
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+};
+
 use binrw::binrw;
 use modular_bitfield::prelude::*;
 
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+#[cfg(feature = "schemars")]
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{ArrayValidation, InstanceType, Schema, SchemaObject, SubschemaValidation},
+    JsonSchema,
+};
 
-use crate::version::Version;
+use crate::version::{Upgrade, Version};
 
 /// The properties and attributes of an edge.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum CollisionAttribute {
     /// The first version of the `CollisionAttribute` type.
     #[br(pre_assert(version == 1))]
@@ -33,56 +49,294 @@ impl Version for CollisionAttribute {
     }
 }
 
-/// The material presets representing how an edge is visually, audibly, and physically interacted with.
-#[binrw]
-#[brw(repr(u32))]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
-pub enum MaterialType {
-    None = 0,
-    Rock = 1,
-    Grass = 2,
-    Soil = 3,
-    Wood = 4,
-    Iron = 5,
-    Nibuiron = 6,
-    Carpet = 7,
-    Numenume = 8,
-    Creature = 9,
-    Asase = 10,
-    Soft = 11,
-    Turuturu = 12,
-    Snow = 13,
-    Ice = 14,
-    Gamewatch = 15,
-    Oil = 16,
-    Danbouru = 17,
-    Damage1 = 18,
-    Damage2 = 19,
-    Damage3 = 20,
-    Plankton = 21,
-    Cloud = 22,
-    Akuukan = 23,
-    Brick = 24,
-    Noattr = 25,
-    Mario = 26,
-    Wirenetting = 27,
-    Sand = 28,
-    Homerun = 29,
-    AsaseEarth = 30,
-    Death = 31,
-    Ringmat = 32,
-    Glass = 33,
-    Slipdx = 34,
-    SpPoison = 35,
-    SpFlame = 36,
-    SpElectricShock = 37,
-    SpSleep = 38,
-    SpFreezing = 39,
-    SpAdhesion = 40,
-    IceNoSlip = 41,
-    CloudNoThrough = 42,
-    JackMementoes = 43,
+impl Default for CollisionAttribute {
+    fn default() -> Self {
+        Self::V1 {
+            material: MaterialType::None,
+            flags: AttributeFlags::new(),
+        }
+    }
+}
+
+impl Upgrade for CollisionAttribute {
+    const LATEST_VERSION: u8 = 1;
+
+    fn into_latest(self) -> Self {
+        self
+    }
+}
+
+impl CollisionAttribute {
+    /// Returns the material preset of the edge.
+    pub fn material(&self) -> MaterialType {
+        match self {
+            Self::V1 { material, .. } => *material,
+        }
+    }
+
+    /// Returns the material preset of the edge mutably.
+    pub fn material_mut(&mut self) -> &mut MaterialType {
+        match self {
+            Self::V1 { material, .. } => material,
+        }
+    }
+
+    /// Returns the attributes of the edge.
+    pub fn flags(&self) -> &AttributeFlags {
+        match self {
+            Self::V1 { flags, .. } => flags,
+        }
+    }
+
+    /// Returns the attributes of the edge mutably.
+    pub fn flags_mut(&mut self) -> &mut AttributeFlags {
+        match self {
+            Self::V1 { flags, .. } => flags,
+        }
+    }
+}
+
+/// Defines the [`MaterialType`] type given each material's variant, value, and alias.
+macro_rules! material_types {
+    ($($variant:ident = $value:literal => $alias:literal,)+) => {
+        /// The material presets representing how an edge is visually, audibly, and physically interacted with.
+        ///
+        /// When serialized, known materials are represented by their alias, such as `ice` or `grass`,
+        /// and unknown materials by their numeric value. When deserialized, the variant name,
+        /// the alias, or the numeric value are all accepted.
+        #[binrw]
+        #[br(map = |value: u32| Self::from(value))]
+        #[bw(map = |material: &Self| material.to_u32())]
+        #[derive(Debug, Clone, Copy)]
+        pub enum MaterialType {
+            $($variant,)+
+
+            /// A material with a value not known to this library.
+            ///
+            /// Materials are compared and hashed by their value, so `Unknown` holding the value
+            /// of a known material is equal to that material. [`MaterialType::from_u32`] never
+            /// returns such a material.
+            Unknown(u32),
+        }
+
+        impl MaterialType {
+            /// The collection of every known material.
+            pub const ALL: &'static [Self] = &[$(Self::$variant),+];
+
+            /// Returns the material with the given value,
+            /// or [`MaterialType::Unknown`] if no known material has it.
+            pub const fn from_u32(value: u32) -> Self {
+                match value {
+                    $($value => Self::$variant,)+
+                    value => Self::Unknown(value),
+                }
+            }
+
+            /// Returns the value of the material.
+            ///
+            /// This replaces casting a material with `as u32`,
+            /// which is not possible as [`MaterialType::Unknown`] holds a value.
+            pub const fn to_u32(self) -> u32 {
+                match self {
+                    $(Self::$variant => $value,)+
+                    Self::Unknown(value) => value,
+                }
+            }
+
+            /// Returns the name of the variant, or `None` if the material is unknown.
+            pub const fn name(&self) -> Option<&'static str> {
+                match self {
+                    $(Self::$variant => Some(stringify!($variant)),)+
+                    Self::Unknown(_) => None,
+                }
+            }
+
+            /// Returns the friendly alias of the material, or `None` if the material is unknown.
+            pub const fn alias(&self) -> Option<&'static str> {
+                match self {
+                    $(Self::$variant => Some($alias),)+
+                    Self::Unknown(_) => None,
+                }
+            }
+
+            /// Returns the known material with the given variant name or alias.
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $(stringify!($variant) | $alias => Some(Self::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+
+        impl From<u32> for MaterialType {
+            fn from(value: u32) -> Self {
+                Self::from_u32(value)
+            }
+        }
+
+        impl From<MaterialType> for u32 {
+            fn from(value: MaterialType) -> Self {
+                value.to_u32()
+            }
+        }
+    };
+}
+
+material_types! {
+    None = 0 => "none",
+    Rock = 1 => "rock",
+    Grass = 2 => "grass",
+    Soil = 3 => "soil",
+    Wood = 4 => "wood",
+    Iron = 5 => "iron",
+    Nibuiron = 6 => "nibuiron",
+    Carpet = 7 => "carpet",
+    Numenume = 8 => "numenume",
+    Creature = 9 => "creature",
+    Asase = 10 => "asase",
+    Soft = 11 => "soft",
+    Turuturu = 12 => "turuturu",
+    Snow = 13 => "snow",
+    Ice = 14 => "ice",
+    Gamewatch = 15 => "gamewatch",
+    Oil = 16 => "oil",
+    Danbouru = 17 => "danbouru",
+    Damage1 = 18 => "damage1",
+    Damage2 = 19 => "damage2",
+    Damage3 = 20 => "damage3",
+    Plankton = 21 => "plankton",
+    Cloud = 22 => "cloud",
+    Akuukan = 23 => "akuukan",
+    Brick = 24 => "brick",
+    Noattr = 25 => "noattr",
+    Mario = 26 => "mario",
+    Wirenetting = 27 => "wirenetting",
+    Sand = 28 => "sand",
+    Homerun = 29 => "homerun",
+    AsaseEarth = 30 => "asase_earth",
+    Death = 31 => "death",
+    Ringmat = 32 => "ringmat",
+    Glass = 33 => "glass",
+    Slipdx = 34 => "slipdx",
+    SpPoison = 35 => "sp_poison",
+    SpFlame = 36 => "sp_flame",
+    SpElectricShock = 37 => "sp_electric_shock",
+    SpSleep = 38 => "sp_sleep",
+    SpFreezing = 39 => "sp_freezing",
+    SpAdhesion = 40 => "sp_adhesion",
+    IceNoSlip = 41 => "ice_no_slip",
+    CloudNoThrough = 42 => "cloud_no_through",
+    JackMementoes = 43 => "jack_mementoes",
+}
+
+impl PartialEq for MaterialType {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_u32() == other.to_u32()
+    }
+}
+
+impl Eq for MaterialType {}
+
+impl Hash for MaterialType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_u32().hash(state);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for MaterialType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.alias() {
+            Some(alias) => serializer.serialize_str(alias),
+            None => serializer.serialize_u32(self.to_u32()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for MaterialType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MaterialTypeVisitor;
+
+        impl Visitor<'_> for MaterialTypeVisitor {
+            type Value = MaterialType;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a material name, alias, or unsigned 32-bit integer")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                MaterialType::from_name(value)
+                    .ok_or_else(|| E::custom(format!("unknown material `{value}`")))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                u32::try_from(value)
+                    .map(MaterialType::from)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                u32::try_from(value)
+                    .map(MaterialType::from)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+            }
+        }
+
+        deserializer.deserialize_any(MaterialTypeVisitor)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for MaterialType {
+    fn schema_name() -> String {
+        "MaterialType".to_string()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        let names = Self::ALL
+            .iter()
+            .flat_map(|m| [m.name().unwrap(), m.alias().unwrap()])
+            .map(|name| name.into())
+            .collect();
+
+        SchemaObject {
+            subschemas: Some(Box::new(SubschemaValidation {
+                any_of: Some(vec![
+                    SchemaObject {
+                        instance_type: Some(InstanceType::String.into()),
+                        enum_values: Some(names),
+                        ..Default::default()
+                    }
+                    .into(),
+                    SchemaObject {
+                        instance_type: Some(InstanceType::Integer.into()),
+                        format: Some("uint32".to_string()),
+                        ..Default::default()
+                    }
+                    .into(),
+                ]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
 }
 
 /// The attributes of an edge.
@@ -90,12 +344,9 @@ pub enum MaterialType {
 #[binrw]
 #[br(map = |f: u64| Self::from_bytes(f.to_le_bytes()))]
 #[bw(map = |f: &Self| u64::from_le_bytes(f.into_bytes()))]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[cfg_attr(
-    feature = "serde",
-    serde(from = "AttributeDataFlags", into = "AttributeDataFlags")
-)]
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(into = "AttributeDataFlags"))]
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
 pub struct AttributeFlags {
     pub length0: bool,
     pub packman_final_ignore: bool,
@@ -134,6 +385,310 @@ pub struct AttributeFlags {
     __: B32,
 }
 
+impl AttributeFlags {
+    /// The names of the attributes, in the order of their bits.
+    pub const NAMES: [&'static str; 32] = [
+        "length0",
+        "packman_final_ignore",
+        "fall",
+        "ignore_ray_check",
+        "dive",
+        "unpaintable",
+        "item",
+        "ignore_fighter_other",
+        "right",
+        "left",
+        "upper",
+        "under",
+        "not_attach",
+        "throughable",
+        "hang_l",
+        "hang_r",
+        "ignore_link_from_left",
+        "cloud",
+        "ignore_link_from_right",
+        "not_expand_near_search",
+        "ignore",
+        "breakable",
+        "immediate_relanding_ban",
+        "ignore_line_type1",
+        "pickel_block",
+        "deceleration",
+        "virtual_hit_line_up",
+        "virtual_hit_line_left",
+        "virtual_hit_line_right",
+        "virtual_hit_line_down",
+        "virtual_wall_hit_line",
+        "ignore_boss",
+    ];
+
+    /// Returns the attributes with the given names set, or the first unknown name as an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::objects::collision::attribute::AttributeFlags;
+    ///
+    /// let flags = AttributeFlags::from_names(["throughable", "hang_l"]).unwrap();
+    ///
+    /// assert!(flags.throughable() && flags.hang_l() && !flags.hang_r());
+    /// assert_eq!(AttributeFlags::from_names(["hang"]), Err("hang"));
+    /// ```
+    pub fn from_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Result<Self, &'a str> {
+        names
+            .into_iter()
+            .map(|name| Flag::from_name(name).ok_or(name))
+            .collect()
+    }
+
+    /// Returns the attributes set in either `self` or `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::objects::collision::attribute::{AttributeFlags, Flag};
+    ///
+    /// let hang = AttributeFlags::from_iter([Flag::HangL, Flag::HangR]);
+    /// let platform = AttributeFlags::from_iter([Flag::Throughable, Flag::HangL]);
+    ///
+    /// assert_eq!(hang.union(platform).iter().count(), 3);
+    /// assert_eq!(hang.intersect(platform).iter().collect::<Vec<_>>(), [Flag::HangL]);
+    /// assert_eq!(hang.difference(platform).iter().collect::<Vec<_>>(), [Flag::HangR]);
+    /// ```
+    pub fn union(self, other: Self) -> Self {
+        Self::from_bits(self.bits() | other.bits())
+    }
+
+    /// Returns the attributes set in both `self` and `other`.
+    pub fn intersect(self, other: Self) -> Self {
+        Self::from_bits(self.bits() & other.bits())
+    }
+
+    /// Returns the attributes set in `self` but not in `other`.
+    pub fn difference(self, other: Self) -> Self {
+        Self::from_bits(self.bits() & !other.bits())
+    }
+
+    /// Returns `true` if the attribute is set.
+    pub fn contains(self, flag: Flag) -> bool {
+        self.bits() & flag.bit() != 0
+    }
+
+    /// Returns `true` if no attribute is set.
+    pub fn is_empty(self) -> bool {
+        self.bits() == 0
+    }
+
+    /// Returns an iterator over the set attributes, in the order of their bits.
+    pub fn iter(self) -> impl Iterator<Item = Flag> {
+        Flag::ALL
+            .into_iter()
+            .filter(move |&flag| self.contains(flag))
+    }
+
+    /// Returns the attributes as an integer with one bit per attribute.
+    fn bits(self) -> u64 {
+        u64::from_le_bytes(self.into_bytes())
+    }
+
+    /// Returns the attributes represented by an integer with one bit per attribute.
+    fn from_bits(bits: u64) -> Self {
+        Self::from_bytes(bits.to_le_bytes())
+    }
+}
+
+// Implemented by hand, as the implementation `bitfield` generates for `#[derive(Debug)]` writes
+// trait objects with redundant parentheses.
+impl fmt::Debug for AttributeFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("AttributeFlags");
+
+        for flag in Flag::ALL {
+            debug.field(flag.name(), &self.contains(flag));
+        }
+
+        debug.finish()
+    }
+}
+
+impl FromIterator<Flag> for AttributeFlags {
+    fn from_iter<I: IntoIterator<Item = Flag>>(iter: I) -> Self {
+        Self::from_bits(iter.into_iter().fold(0, |bits, flag| bits | flag.bit()))
+    }
+}
+
+/// A single attribute of an edge, as stored in [`AttributeFlags`].
+#[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Flag {
+    Length0,
+    PackmanFinalIgnore,
+    Fall,
+    IgnoreRayCheck,
+    Dive,
+    Unpaintable,
+    Item,
+    IgnoreFighterOther,
+    Right,
+    Left,
+    Upper,
+    Under,
+    NotAttach,
+    Throughable,
+    HangL,
+    HangR,
+    IgnoreLinkFromLeft,
+    Cloud,
+    IgnoreLinkFromRight,
+    NotExpandNearSearch,
+    Ignore,
+    Breakable,
+    ImmediateRelandingBan,
+    IgnoreLineType1,
+    PickelBlock,
+    Deceleration,
+    VirtualHitLineUp,
+    VirtualHitLineLeft,
+    VirtualHitLineRight,
+    VirtualHitLineDown,
+    VirtualWallHitLine,
+    IgnoreBoss,
+}
+
+impl Flag {
+    /// The collection of every attribute, in the order of their bits.
+    pub const ALL: [Self; 32] = [
+        Self::Length0,
+        Self::PackmanFinalIgnore,
+        Self::Fall,
+        Self::IgnoreRayCheck,
+        Self::Dive,
+        Self::Unpaintable,
+        Self::Item,
+        Self::IgnoreFighterOther,
+        Self::Right,
+        Self::Left,
+        Self::Upper,
+        Self::Under,
+        Self::NotAttach,
+        Self::Throughable,
+        Self::HangL,
+        Self::HangR,
+        Self::IgnoreLinkFromLeft,
+        Self::Cloud,
+        Self::IgnoreLinkFromRight,
+        Self::NotExpandNearSearch,
+        Self::Ignore,
+        Self::Breakable,
+        Self::ImmediateRelandingBan,
+        Self::IgnoreLineType1,
+        Self::PickelBlock,
+        Self::Deceleration,
+        Self::VirtualHitLineUp,
+        Self::VirtualHitLineLeft,
+        Self::VirtualHitLineRight,
+        Self::VirtualHitLineDown,
+        Self::VirtualWallHitLine,
+        Self::IgnoreBoss,
+    ];
+
+    /// Returns the name of the attribute, as listed in [`AttributeFlags::NAMES`].
+    pub const fn name(self) -> &'static str {
+        AttributeFlags::NAMES[self as usize]
+    }
+
+    /// Returns the attribute with the given name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|flag| flag.name() == name)
+    }
+
+    /// Returns the bit of the attribute in [`AttributeFlags`].
+    const fn bit(self) -> u64 {
+        1 << self as u64
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for AttributeFlags {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct AttributeFlagsVisitor;
+
+        impl<'de> Visitor<'de> for AttributeFlagsVisitor {
+            type Value = AttributeFlags;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter
+                    .write_str("a mapping of attributes to booleans or a list of attribute names")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut names = Vec::new();
+
+                while let Some(name) = seq.next_element::<String>()? {
+                    names.push(name);
+                }
+
+                AttributeFlags::from_names(names.iter().map(String::as_str))
+                    .map_err(|name| de::Error::unknown_variant(name, &AttributeFlags::NAMES))
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                AttributeDataFlags::deserialize(de::value::MapAccessDeserializer::new(map))
+                    .map(AttributeFlags::from)
+            }
+        }
+
+        deserializer.deserialize_any(AttributeFlagsVisitor)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for AttributeFlags {
+    fn schema_name() -> String {
+        "AttributeFlags".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let names = Self::NAMES.iter().map(|&name| name.into()).collect();
+
+        SchemaObject {
+            subschemas: Some(Box::new(SubschemaValidation {
+                any_of: Some(vec![
+                    AttributeDataFlags::json_schema(gen),
+                    SchemaObject {
+                        instance_type: Some(InstanceType::Array.into()),
+                        array: Some(Box::new(ArrayValidation {
+                            items: Some(
+                                Schema::from(SchemaObject {
+                                    instance_type: Some(InstanceType::String.into()),
+                                    enum_values: Some(names),
+                                    ..Default::default()
+                                })
+                                .into(),
+                            ),
+                            unique_items: Some(true),
+                            ..Default::default()
+                        })),
+                        ..Default::default()
+                    }
+                    .into(),
+                ]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
 #[cfg(feature = "serde")]
 impl From<AttributeDataFlags> for AttributeFlags {
     fn from(value: AttributeDataFlags) -> Self {
@@ -175,6 +730,7 @@ impl From<AttributeDataFlags> for AttributeFlags {
 
 #[cfg(feature = "serde")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
 struct AttributeDataFlags {
     length0: bool,
     packman_final_ignore: bool,
@@ -249,3 +805,128 @@ impl From<AttributeFlags> for AttributeDataFlags {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn material_type_from_value() {
+        assert_eq!(MaterialType::from(14), MaterialType::Ice);
+        assert_eq!(MaterialType::from(43), MaterialType::JackMementoes);
+        assert_eq!(MaterialType::from(44), MaterialType::Unknown(44));
+        assert_eq!(u32::from(MaterialType::Grass), 2);
+        assert_eq!(u32::from(MaterialType::Unknown(100)), 100);
+        assert_eq!(MaterialType::from_u32(14), MaterialType::Ice);
+        assert_eq!(MaterialType::Ice.to_u32(), 14);
+    }
+
+    #[test]
+    fn material_type_unknown_with_known_value() {
+        let unknown = MaterialType::Unknown(14);
+
+        assert_eq!(unknown, MaterialType::Ice);
+        assert_ne!(unknown, MaterialType::Unknown(15));
+        assert_eq!(
+            std::collections::HashSet::from([unknown, MaterialType::Ice]).len(),
+            1
+        );
+        assert!(matches!(
+            MaterialType::from(u32::from(unknown)),
+            MaterialType::Ice
+        ));
+    }
+
+    #[test]
+    fn material_type_values_match_order() {
+        for (value, material) in MaterialType::ALL.iter().enumerate() {
+            assert_eq!(material.to_u32(), value as u32);
+            assert!(material.name().is_some());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn material_type_deserialize_known_value() {
+        let material: MaterialType = serde_json::from_str("14").unwrap();
+
+        assert!(matches!(material, MaterialType::Ice));
+        assert_eq!(serde_json::to_string(&material).unwrap(), "\"ice\"");
+        assert_eq!(
+            serde_json::to_string(&MaterialType::Unknown(99)).unwrap(),
+            "99"
+        );
+    }
+
+    #[test]
+    fn material_type_from_name() {
+        assert_eq!(MaterialType::from_name("Ice"), Some(MaterialType::Ice));
+        assert_eq!(MaterialType::from_name("ice"), Some(MaterialType::Ice));
+        assert_eq!(
+            MaterialType::from_name("sp_electric_shock"),
+            Some(MaterialType::SpElectricShock)
+        );
+        assert_eq!(MaterialType::from_name("lava"), None);
+    }
+
+    #[test]
+    fn attribute_flags_set_operations() {
+        let flags = AttributeFlags::new()
+            .with_throughable(true)
+            .with_hang_l(true)
+            .with_ignore_boss(true);
+        let collected: AttributeFlags = flags.iter().collect();
+
+        assert_eq!(collected, flags);
+        assert!(flags.contains(Flag::IgnoreBoss) && !flags.contains(Flag::HangR));
+        assert!(flags.difference(flags).is_empty());
+        assert_eq!(
+            flags.intersect(AttributeFlags::from_iter([Flag::HangL, Flag::HangR])),
+            AttributeFlags::new().with_hang_l(true)
+        );
+        assert_eq!(Flag::from_name("pickel_block"), Some(Flag::PickelBlock));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn attribute_flags_from_names() {
+        let flags: AttributeFlags =
+            serde_json::from_str(r#"["throughable", "hang_l", "hang_r"]"#).unwrap();
+        let fields = serde_json::to_value(flags).unwrap();
+
+        assert!(flags.throughable() && flags.hang_l() && flags.hang_r() && !flags.fall());
+        assert_eq!(
+            serde_json::from_value::<AttributeFlags>(fields).unwrap(),
+            flags
+        );
+        assert!(serde_json::from_str::<AttributeFlags>(r#"["hang"]"#)
+            .unwrap_err()
+            .to_string()
+            .starts_with("unknown variant `hang`"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn material_type_serde() {
+        assert_eq!(
+            serde_json::to_string(&MaterialType::Grass).unwrap(),
+            "\"grass\""
+        );
+        assert_eq!(
+            serde_json::to_string(&MaterialType::Unknown(50)).unwrap(),
+            "50"
+        );
+        assert_eq!(
+            serde_json::from_str::<MaterialType>("\"Grass\"").unwrap(),
+            MaterialType::Grass
+        );
+        assert_eq!(
+            serde_json::from_str::<MaterialType>("2").unwrap(),
+            MaterialType::Grass
+        );
+        assert_eq!(
+            serde_json::from_str::<MaterialType>("50").unwrap(),
+            MaterialType::Unknown(50)
+        );
+    }
+}
@@ -5,17 +5,22 @@ use binrw::binrw;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
 use crate::{
     objects::base::Base,
+    shape::Rect,
     vector::Vector2,
-    version::{Version, Versioned},
+    version::{Upgrade, Version, Versioned},
 };
 
 /// An LVD subobject to a [`Collision`](crate::objects::collision::Collision) representing a grabbable edge.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum CollisionCliff {
     /// The first version of the `CollisionCliff` type.
     ///
@@ -75,6 +80,26 @@ pub enum CollisionCliff {
     },
 }
 
+/// The extent of the area around a cliff within which a fighter grabs it,
+/// relative to the cliff's position and facing direction.
+///
+/// The game takes these distances from the parameters of each fighter, which this library
+/// does not read, so they are supplied by the caller.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct GrabRange {
+    /// The distance the area extends away from the stage, in the facing direction.
+    pub outward: f32,
+
+    /// The distance the area extends over the stage, against the facing direction.
+    pub inward: f32,
+
+    /// The distance the area extends above the cliff.
+    pub above: f32,
+
+    /// The distance the area extends below the cliff.
+    pub below: f32,
+}
+
 impl Version for CollisionCliff {
     fn version(&self) -> u8 {
         match self {
@@ -84,3 +109,152 @@ impl Version for CollisionCliff {
         }
     }
 }
+
+impl Default for CollisionCliff {
+    fn default() -> Self {
+        Self::V3 {
+            base: Versioned::new(Base::default()),
+            pos: Versioned::new(Vector2::default()),
+            lr: 0.0,
+            line_index: 0,
+        }
+    }
+}
+
+impl Upgrade for CollisionCliff {
+    const LATEST_VERSION: u8 = 3;
+
+    fn into_latest(self) -> Self {
+        match self {
+            Self::V1 { pos, lr } => Self::V2 {
+                base: Versioned::new(Base::default()),
+                pos,
+                lr,
+            }
+            .into_latest(),
+            Self::V2 { base, pos, lr } => Self::V3 {
+                base: base.into_latest(),
+                pos,
+                lr,
+                line_index: 0,
+            },
+            Self::V3 {
+                base,
+                pos,
+                lr,
+                line_index,
+            } => Self::V3 {
+                base: base.into_latest(),
+                pos,
+                lr,
+                line_index,
+            },
+        }
+    }
+}
+
+impl CollisionCliff {
+    /// Returns the common data of the object, if supported by the version.
+    pub fn base(&self) -> Option<&Base> {
+        match self {
+            Self::V1 { .. } => None,
+            Self::V2 { base, .. } | Self::V3 { base, .. } => Some(base),
+        }
+    }
+
+    /// Returns the common data of the object mutably, if supported by the version.
+    pub fn base_mut(&mut self) -> Option<&mut Base> {
+        match self {
+            Self::V1 { .. } => None,
+            Self::V2 { base, .. } | Self::V3 { base, .. } => Some(base),
+        }
+    }
+
+    /// Returns the position of the cliff.
+    pub fn pos(&self) -> Vector2 {
+        match self {
+            Self::V1 { pos, .. } | Self::V2 { pos, .. } | Self::V3 { pos, .. } => **pos,
+        }
+    }
+
+    /// Returns the position of the cliff mutably.
+    pub fn pos_mut(&mut self) -> &mut Vector2 {
+        match self {
+            Self::V1 { pos, .. } | Self::V2 { pos, .. } | Self::V3 { pos, .. } => pos,
+        }
+    }
+
+    /// Returns the facing direction of the cliff,
+    /// where `-1.0` corresponds to the left and `1.0` corresponds to the right.
+    pub fn lr(&self) -> f32 {
+        match self {
+            Self::V1 { lr, .. } | Self::V2 { lr, .. } | Self::V3 { lr, .. } => *lr,
+        }
+    }
+
+    /// Returns the facing direction of the cliff mutably.
+    pub fn lr_mut(&mut self) -> &mut f32 {
+        match self {
+            Self::V1 { lr, .. } | Self::V2 { lr, .. } | Self::V3 { lr, .. } => lr,
+        }
+    }
+
+    /// Returns the index of the edge in the associated collision the cliff is linked with,
+    /// if supported by the version.
+    pub fn line_index(&self) -> Option<u32> {
+        match self {
+            Self::V1 { .. } | Self::V2 { .. } => None,
+            Self::V3 { line_index, .. } => Some(*line_index),
+        }
+    }
+
+    /// Returns the index of the edge in the associated collision the cliff is linked with mutably,
+    /// if supported by the version.
+    pub fn line_index_mut(&mut self) -> Option<&mut u32> {
+        match self {
+            Self::V1 { .. } | Self::V2 { .. } => None,
+            Self::V3 { line_index, .. } => Some(line_index),
+        }
+    }
+
+    /// Returns the area within which a fighter with the given grab range grabs the cliff.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{
+    ///     objects::collision::{cliff::GrabRange, CollisionCliff},
+    ///     shape::Rect,
+    ///     vector::Vector2,
+    /// };
+    ///
+    /// let mut cliff = CollisionCliff::default();
+    ///
+    /// *cliff.pos_mut() = Vector2::new(-80.0, 0.0);
+    /// *cliff.lr_mut() = -1.0;
+    ///
+    /// let range = GrabRange {
+    ///     outward: 15.0,
+    ///     inward: 5.0,
+    ///     above: 10.0,
+    ///     below: 20.0,
+    /// };
+    ///
+    /// assert_eq!(cliff.grab_box(&range), Rect::new(-95.0, -75.0, 10.0, -20.0));
+    /// ```
+    pub fn grab_box(&self, range: &GrabRange) -> Rect {
+        let pos = self.pos();
+        let (left, right) = if self.lr() < 0.0 {
+            (range.outward, range.inward)
+        } else {
+            (range.inward, range.outward)
+        };
+
+        Rect::new(
+            pos.x() - left,
+            pos.x() + right,
+            pos.y() + range.above,
+            pos.y() - range.below,
+        )
+    }
+}
@@ -0,0 +1,192 @@
+//! A view of each edge of a [`Collision`] joining its per-edge data.
+//!
+//! A collision stores the data of its edges in separate arrays related by index. This module
+//! contains the [`Edge`] and [`EdgeMut`] types, which gather the data of a single edge, and the
+//! [`Collision::edges`] and [`Collision::edge_mut`] methods returning them.
+
+use crate::vector::Vector2;
+
+use super::{Collision, CollisionAttribute, CollisionCliff, CollisionSpiritsFloor};
+
+/// The data of a single edge of a [`Collision`].
+#[derive(Debug, Clone)]
+pub struct Edge<'a> {
+    /// The index of the edge.
+    pub index: usize,
+
+    /// The position of the vertex at the start of the edge.
+    pub start: Vector2,
+
+    /// The position of the vertex at the end of the edge.
+    pub end: Vector2,
+
+    /// The unit normal vector of the edge.
+    pub normal: Vector2,
+
+    /// The properties of the edge, if the collision's version stores them.
+    pub attribute: Option<&'a CollisionAttribute>,
+
+    /// The cliffs referring to the edge by index.
+    ///
+    /// A platform made of a single edge has a cliff at each end, both referring to the edge.
+    pub cliffs: Vec<&'a CollisionCliff>,
+
+    /// The spirits floor entry referring to the edge by index, if any.
+    pub spirits_floor: Option<&'a CollisionSpiritsFloor>,
+}
+
+/// The data of a single edge of a [`Collision`], borrowed mutably.
+#[derive(Debug)]
+pub struct EdgeMut<'a> {
+    /// The index of the edge.
+    pub index: usize,
+
+    /// The position of the vertex at the start of the edge.
+    ///
+    /// The vertex is shared with the end of the previous edge, if any.
+    pub start: &'a mut Vector2,
+
+    /// The position of the vertex at the end of the edge.
+    ///
+    /// The vertex is shared with the start of the next edge, if any.
+    pub end: &'a mut Vector2,
+
+    /// The unit normal vector of the edge.
+    pub normal: &'a mut Vector2,
+
+    /// The properties of the edge, if the collision's version stores them.
+    pub attribute: Option<&'a mut CollisionAttribute>,
+
+    /// The cliffs referring to the edge by index.
+    pub cliffs: Vec<&'a mut CollisionCliff>,
+
+    /// The spirits floor entry referring to the edge by index, if any.
+    pub spirits_floor: Option<&'a mut CollisionSpiritsFloor>,
+}
+
+impl Collision {
+    /// Returns an iterator over the edges of the collision.
+    ///
+    /// Edges are only yielded while both of their vertices exist, so a collision with
+    /// inconsistent vertex and edge counts yields fewer than [`edge_count`](Self::edge_count).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{objects::Collision, vector::Vector2};
+    ///
+    /// let platform = Collision::new_soft_platform(Vector2::new(-10.0, 5.0), Vector2::new(10.0, 5.0));
+    /// let edge = platform.edges().next().unwrap();
+    ///
+    /// assert_eq!(edge.end - edge.start, Vector2::new(20.0, 0.0));
+    /// assert_eq!(edge.cliffs.len(), 2);
+    /// ```
+    pub fn edges(&self) -> impl Iterator<Item = Edge<'_>> {
+        let vertices = self.vertices().elements();
+
+        self.normals()
+            .iter()
+            .zip(vertices.windows(2))
+            .enumerate()
+            .map(|(index, (&normal, pair))| Edge {
+                index,
+                start: *pair[0],
+                end: *pair[1],
+                normal,
+                attribute: self
+                    .attributes()
+                    .and_then(|attributes| attributes.elements().get(index))
+                    .map(|attribute| &attribute.inner),
+                cliffs: self
+                    .cliffs()
+                    .iter()
+                    .filter(|cliff| cliff.line_index() == Some(index as u32))
+                    .collect(),
+                spirits_floor: self.spirits_floors().and_then(|spirits_floors| {
+                    spirits_floors
+                        .iter()
+                        .find(|spirits_floor| spirits_floor.line_index() == index as u32)
+                }),
+            })
+    }
+
+    /// Returns the edge at the given index mutably, or `None` if either of its vertices
+    /// or its normal does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{objects::Collision, vector::Vector2};
+    ///
+    /// let mut platform =
+    ///     Collision::new_soft_platform(Vector2::new(-10.0, 5.0), Vector2::new(10.0, 5.0));
+    /// let edge = platform.edge_mut(0).unwrap();
+    ///
+    /// edge.attribute.unwrap().flags_mut().set_throughable(false);
+    ///
+    /// assert!(platform.edge_mut(1).is_none());
+    /// ```
+    pub fn edge_mut(&mut self, index: usize) -> Option<EdgeMut<'_>> {
+        let (vertices, normals, cliffs, attributes, spirits_floors) = match self {
+            Self::V1 {
+                vertices,
+                normals,
+                cliffs,
+                ..
+            }
+            | Self::V2 {
+                vertices,
+                normals,
+                cliffs,
+                ..
+            } => (vertices, normals, cliffs, None, None),
+            Self::V3 {
+                vertices,
+                normals,
+                cliffs,
+                attributes,
+                ..
+            } => (vertices, normals, cliffs, Some(attributes), None),
+            Self::V4 {
+                vertices,
+                normals,
+                cliffs,
+                attributes,
+                spirits_floors,
+                ..
+            } => (
+                vertices,
+                normals,
+                cliffs,
+                Some(attributes),
+                Some(spirits_floors),
+            ),
+        };
+        let vertices = vertices.elements_mut();
+
+        if index + 1 >= vertices.len() {
+            return None;
+        }
+
+        let (head, tail) = vertices.split_at_mut(index + 1);
+
+        Some(EdgeMut {
+            index,
+            start: head.last_mut()?,
+            end: tail.first_mut()?,
+            normal: normals.elements_mut().get_mut(index)?,
+            attribute: attributes
+                .and_then(|attributes| attributes.elements_mut().get_mut(index))
+                .map(|attribute| &mut attribute.inner),
+            cliffs: cliffs
+                .iter_mut()
+                .filter(|cliff| cliff.line_index() == Some(index as u32))
+                .collect(),
+            spirits_floor: spirits_floors.and_then(|spirits_floors| {
+                spirits_floors
+                    .iter_mut()
+                    .find(|spirits_floor| spirits_floor.line_index() == index as u32)
+            }),
+        })
+    }
+}
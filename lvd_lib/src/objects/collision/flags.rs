@@ -1,22 +1,31 @@
 //! The [`CollisionFlags`] type represents the global attributes of a collision.
 
+use std::fmt;
+
 use binrw::binrw;
 use modular_bitfield::prelude::*;
 
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+
+#[cfg(feature = "schemars")]
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{ArrayValidation, InstanceType, Schema, SchemaObject, SubschemaValidation},
+    JsonSchema,
+};
 
 /// The global attributes of a collision.
 #[bitfield]
 #[binrw]
 #[br(map = |f: u32| Self::from_bytes(f.to_le_bytes()))]
 #[bw(map = |f: &Self| u32::from_le_bytes(f.into_bytes()))]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[cfg_attr(
-    feature = "serde",
-    serde(from = "CollisionDataFlags", into = "CollisionDataFlags")
-)]
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(into = "CollisionDataFlags"))]
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
 pub struct CollisionFlags {
     /// Determines if the collision's floor edges can be dropped through
     pub throughable: bool,
@@ -31,6 +40,130 @@ pub struct CollisionFlags {
     __: B15,
 }
 
+impl CollisionFlags {
+    /// The names of the attributes, in the order of their bits.
+    pub const NAMES: [&'static str; 2] = ["throughable", "dynamic"];
+
+    /// Returns the attributes with the given names set, or the first unknown name as an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::objects::collision::CollisionFlags;
+    ///
+    /// let flags = CollisionFlags::from_names(["dynamic"]).unwrap();
+    ///
+    /// assert!(flags.dynamic() && !flags.throughable());
+    /// assert_eq!(CollisionFlags::from_names(["static"]), Err("static"));
+    /// ```
+    pub fn from_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Result<Self, &'a str> {
+        let mut flags = Self::new();
+
+        for name in names {
+            match name {
+                "throughable" => flags.set_throughable(true),
+                "dynamic" => flags.set_dynamic(true),
+                _ => return Err(name),
+            }
+        }
+
+        Ok(flags)
+    }
+}
+
+// Implemented by hand, as the implementation `bitfield` generates for `#[derive(Debug)]` writes
+// trait objects with redundant parentheses.
+impl fmt::Debug for CollisionFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CollisionFlags")
+            .field("throughable", &self.throughable())
+            .field("dynamic", &self.dynamic())
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for CollisionFlags {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct CollisionFlagsVisitor;
+
+        impl<'de> Visitor<'de> for CollisionFlagsVisitor {
+            type Value = CollisionFlags;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter
+                    .write_str("a mapping of attributes to booleans or a list of attribute names")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut names = Vec::new();
+
+                while let Some(name) = seq.next_element::<String>()? {
+                    names.push(name);
+                }
+
+                CollisionFlags::from_names(names.iter().map(String::as_str))
+                    .map_err(|name| de::Error::unknown_variant(name, &CollisionFlags::NAMES))
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                CollisionDataFlags::deserialize(de::value::MapAccessDeserializer::new(map))
+                    .map(CollisionFlags::from)
+            }
+        }
+
+        deserializer.deserialize_any(CollisionFlagsVisitor)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for CollisionFlags {
+    fn schema_name() -> String {
+        "CollisionFlags".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let names = Self::NAMES.iter().map(|&name| name.into()).collect();
+
+        SchemaObject {
+            subschemas: Some(Box::new(SubschemaValidation {
+                any_of: Some(vec![
+                    CollisionDataFlags::json_schema(gen),
+                    SchemaObject {
+                        instance_type: Some(InstanceType::Array.into()),
+                        array: Some(Box::new(ArrayValidation {
+                            items: Some(
+                                Schema::from(SchemaObject {
+                                    instance_type: Some(InstanceType::String.into()),
+                                    enum_values: Some(names),
+                                    ..Default::default()
+                                })
+                                .into(),
+                            ),
+                            unique_items: Some(true),
+                            ..Default::default()
+                        })),
+                        ..Default::default()
+                    }
+                    .into(),
+                ]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
 #[cfg(feature = "serde")]
 impl From<CollisionDataFlags> for CollisionFlags {
     fn from(value: CollisionDataFlags) -> Self {
@@ -42,6 +175,7 @@ impl From<CollisionDataFlags> for CollisionFlags {
 
 #[cfg(feature = "serde")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
 struct CollisionDataFlags {
     throughable: bool,
     dynamic: bool,
@@ -0,0 +1,1019 @@
+//! Operations on the geometry of a [`Collision`].
+
+use std::{cmp::Ordering, ops::Range};
+
+use thiserror::Error;
+
+use crate::{objects::collision::attribute::AttributeFlags, vector::Vector2, version::Versioned};
+
+use super::Collision;
+
+/// The error type used when subdividing a [`Collision`] with [`Collision::subdivide`].
+#[derive(Debug, PartialEq, Error)]
+pub enum SubdivideError {
+    /// The maximum edge length is not finite or not positive.
+    #[error("maximum edge length {0} is not a finite positive number")]
+    InvalidLength(f32),
+
+    /// The collision's vertex count is not one more than its edge count.
+    #[error("collision has {vertices} vertices but {edges} edges")]
+    InconsistentEdges {
+        /// The number of vertices.
+        vertices: usize,
+
+        /// The number of edges.
+        edges: usize,
+    },
+
+    /// Splitting an edge, such as one of infinite length, would give the collision more than
+    /// [`Collision::MAX_SUBDIVIDED_VERTICES`] vertices.
+    #[error(
+        "splitting edge {0} would give the collision more than {max} vertices",
+        max = Collision::MAX_SUBDIVIDED_VERTICES
+    )]
+    TooManyVertices(usize),
+}
+
+/// The direction in which the vertices of a collision go around the area they enclose.
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum Winding {
+    /// The vertices go around clockwise, so the normals facing away from the enclosed area
+    /// are to the left of each edge, as with platforms running from left to right.
+    Clockwise,
+
+    /// The vertices go around counterclockwise.
+    CounterClockwise,
+}
+
+impl Collision {
+    /// The length below which an edge is considered to have zero length.
+    pub const ZERO_LENGTH: f32 = 1e-4;
+
+    /// The largest number of vertices [`subdivide`](Self::subdivide) gives a collision.
+    pub const MAX_SUBDIVIDED_VERTICES: usize = 0x10000;
+
+    /// Sets the direction and `length0` attributes of every edge from its geometry.
+    ///
+    /// The `upper` and `under` attributes are set for edges whose normal faces mostly upwards
+    /// or downwards, and the `right` and `left` attributes for edges whose normal faces mostly
+    /// to the right or left. The `length0` attribute is set for edges shorter than
+    /// [`ZERO_LENGTH`](Self::ZERO_LENGTH). Every other attribute is left unchanged.
+    /// The number of edges whose attributes changed is returned.
+    pub fn infer_edge_flags(&mut self) -> usize {
+        let vertices = self.vertices().iter().copied().collect::<Vec<_>>();
+        let normals = self.normals().iter().copied().collect::<Vec<_>>();
+        let Some(attributes) = self.attributes_mut() else {
+            return 0;
+        };
+        let mut count = 0;
+
+        for (edge, attribute) in attributes.iter_mut().enumerate() {
+            let Some(&normal) = normals.get(edge) else {
+                break;
+            };
+            let length = match (vertices.get(edge), vertices.get(edge + 1)) {
+                (Some(&start), Some(&end)) => (end - start).length(),
+                _ => f32::INFINITY,
+            };
+            let flags = attribute.flags_mut();
+            let old_flags = *flags;
+
+            set_direction_flags(flags, normal, length);
+
+            if *flags != old_flags {
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    /// Removes vertices which deviate from the outline by no more than `tolerance`,
+    /// using the Ramer–Douglas–Peucker algorithm.
+    ///
+    /// The first and last vertices are always kept. Each remaining edge takes the attributes
+    /// of the longest edge it replaces, its normal is recalculated to face the same side,
+    /// and the edge indices of cliffs and spirits floor entries are updated to match.
+    /// The collision is left unchanged if its vertex and edge counts are inconsistent.
+    /// On success, the number of removed vertices is returned.
+    pub fn simplify(&mut self, tolerance: f32) -> usize {
+        let vertices = self.vertices().iter().copied().collect::<Vec<_>>();
+
+        if vertices.len() < 3 || vertices.len() != self.edge_count() + 1 {
+            return 0;
+        }
+
+        let mut keep = vec![false; vertices.len()];
+        let mut stack = vec![(0, vertices.len() - 1)];
+
+        keep[0] = true;
+        keep[vertices.len() - 1] = true;
+
+        while let Some((start, end)) = stack.pop() {
+            let farthest = (start + 1..end)
+                .map(|i| {
+                    (
+                        i,
+                        segment_distance(vertices[i], vertices[start], vertices[end]),
+                    )
+                })
+                .max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+            if let Some((index, distance)) = farthest {
+                if distance > tolerance {
+                    keep[index] = true;
+                    stack.push((start, index));
+                    stack.push((index, end));
+                }
+            }
+        }
+
+        let kept = (0..vertices.len()).filter(|&i| keep[i]).collect::<Vec<_>>();
+        let spans = kept.windows(2).map(|w| w[0]..w[1]).collect::<Vec<_>>();
+        let removed = vertices.len() - kept.len();
+
+        if removed > 0 {
+            let vertices = kept.iter().map(|&i| vertices[i]).collect();
+
+            self.rebuild_edges(vertices, &spans);
+        }
+
+        removed
+    }
+
+    /// Splits every edge longer than `max_edge_length` into equal segments no longer than it.
+    ///
+    /// Each segment takes the normal and attributes of the edge it was split from,
+    /// spirits floor entries are duplicated for each segment, and cliffs are moved to the
+    /// segment nearest their position. Edges whose length is NaN are left unsplit.
+    /// On success, the number of inserted vertices is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error without changing the collision if `max_edge_length` is not finite or
+    /// not positive, its vertex and edge counts are inconsistent, or the result would have more
+    /// than [`MAX_SUBDIVIDED_VERTICES`](Self::MAX_SUBDIVIDED_VERTICES) vertices.
+    pub fn subdivide(&mut self, max_edge_length: f32) -> Result<usize, SubdivideError> {
+        if !max_edge_length.is_finite() || max_edge_length <= 0.0 {
+            return Err(SubdivideError::InvalidLength(max_edge_length));
+        }
+
+        let old_vertices = self.vertices().iter().copied().collect::<Vec<_>>();
+
+        if old_vertices.len() != self.edge_count() + 1 {
+            return Err(SubdivideError::InconsistentEdges {
+                vertices: old_vertices.len(),
+                edges: self.edge_count(),
+            });
+        }
+
+        let mut counts = Vec::with_capacity(self.edge_count());
+        let mut total = 1;
+
+        for (i, edge) in old_vertices.windows(2).enumerate() {
+            // NaN is ignored by `max`, and infinity fails the comparison below.
+            let count = ((edge[1] - edge[0]).length() / max_edge_length)
+                .ceil()
+                .max(1.0);
+
+            if count > (Self::MAX_SUBDIVIDED_VERTICES - total) as f32 {
+                return Err(SubdivideError::TooManyVertices(i));
+            }
+
+            total += count as usize;
+            counts.push(count as usize);
+        }
+
+        let mut vertices = Vec::with_capacity(total);
+        let mut spans = Vec::with_capacity(total - 1);
+
+        vertices.push(old_vertices[0]);
+
+        for (i, (edge, &count)) in old_vertices.windows(2).zip(&counts).enumerate() {
+            let direction = edge[1] - edge[0];
+
+            for step in 1..count {
+                vertices.push(edge[0] + direction * (step as f32 / count as f32));
+            }
+
+            vertices.push(edge[1]);
+            spans.extend(std::iter::repeat_n(i..i + 1, count));
+        }
+
+        let inserted = vertices.len() - old_vertices.len();
+
+        if inserted > 0 {
+            self.rebuild_edges(vertices, &spans);
+        }
+
+        Ok(inserted)
+    }
+
+    /// Moves each cliff onto the end of its edge nearest its position and turns it to face away
+    /// from the other end, removing cliffs whose edge does not exist.
+    ///
+    /// Cliffs without an edge index, as in versions before the third, belong to the edge
+    /// nearest their position. Cliffs on vertical edges keep their facing direction.
+    /// The number of cliffs moved, turned, or removed is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{objects::Collision, vector::Vector2};
+    ///
+    /// let mut platform =
+    ///     Collision::new_soft_platform(Vector2::new(-10.0, 5.0), Vector2::new(10.0, 5.0));
+    /// let cliff = platform.cliffs_mut().iter_mut().next().unwrap();
+    ///
+    /// *cliff.pos_mut() = Vector2::new(-9.0, 4.0);
+    /// *cliff.lr_mut() = 1.0;
+    ///
+    /// assert_eq!(platform.fix_cliffs(), 1);
+    ///
+    /// let cliff = platform.cliffs().iter().next().unwrap();
+    ///
+    /// assert_eq!((cliff.pos(), cliff.lr()), (Vector2::new(-10.0, 5.0), -1.0));
+    /// ```
+    pub fn fix_cliffs(&mut self) -> usize {
+        let edges = self
+            .edges()
+            .map(|edge| (edge.start, edge.end))
+            .collect::<Vec<_>>();
+        let nearest_edge = |pos: Vector2| {
+            edges.iter().min_by(|(a_start, a_end), (b_start, b_end)| {
+                segment_distance(pos, *a_start, *a_end)
+                    .total_cmp(&segment_distance(pos, *b_start, *b_end))
+            })
+        };
+        let cliffs = self.cliffs_mut().elements_mut();
+        let old_len = cliffs.len();
+        let mut count = 0;
+
+        cliffs.retain_mut(|cliff| {
+            let edge = match cliff.line_index() {
+                Some(line_index) => edges.get(line_index as usize),
+                None => nearest_edge(cliff.pos()),
+            };
+            let Some(&(start, end)) = edge else {
+                return false;
+            };
+            let pos = cliff.pos();
+            let (vertex, other) = match (pos - start).length() <= (pos - end).length() {
+                true => (start, end),
+                false => (end, start),
+            };
+            let lr = match vertex.x().total_cmp(&other.x()) {
+                Ordering::Less => -1.0,
+                Ordering::Greater => 1.0,
+                Ordering::Equal => cliff.lr(),
+            };
+
+            if pos != vertex || lr != cliff.lr() {
+                *cliff.pos_mut() = vertex;
+                *cliff.lr_mut() = lr;
+                count += 1;
+            }
+
+            true
+        });
+
+        count + old_len - cliffs.len()
+    }
+
+    /// Returns the winding of the vertices, treating the last vertex as connected to the first,
+    /// or `None` if they enclose no area, as with a straight platform.
+    pub fn winding(&self) -> Option<Winding> {
+        let vertices = self.vertices().iter().copied().collect::<Vec<_>>();
+        let doubled_area = vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .map(|(a, b)| a.x() * b.y() - b.x() * a.y())
+            .sum::<f32>();
+
+        match doubled_area {
+            area if area < 0.0 => Some(Winding::Clockwise),
+            area if area > 0.0 => Some(Winding::CounterClockwise),
+            _ => None,
+        }
+    }
+
+    /// Reverses the order of the vertices if their winding differs from `winding`,
+    /// returning `true` if they were reversed.
+    ///
+    /// The normals, attributes, cliff and spirits floor edge indices are reordered to follow
+    /// their edges. Normals and cliff directions are flipped, and the `upper`, `under`, `left`,
+    /// and `right` attributes are swapped to match the flipped normals. The collision is left
+    /// unchanged if its winding is undefined or its vertex and edge counts are inconsistent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{
+    ///     objects::{collision::Winding, Collision},
+    ///     vector::Vector2,
+    /// };
+    ///
+    /// let mut collision = Collision::default();
+    ///
+    /// *collision.vertices_mut() = [(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]
+    ///     .into_iter()
+    ///     .map(|(x, y)| Vector2::new(x, y))
+    ///     .collect();
+    /// *collision.normals_mut() = [(0.0, 1.0), (-1.0, 0.0), (0.0, -1.0)]
+    ///     .into_iter()
+    ///     .map(|(x, y)| Vector2::new(x, y))
+    ///     .collect();
+    ///
+    /// assert!(collision.ensure_winding(Winding::Clockwise));
+    /// assert_eq!(collision.winding(), Some(Winding::Clockwise));
+    /// ```
+    pub fn ensure_winding(&mut self, winding: Winding) -> bool {
+        let edge_count = self.edge_count();
+
+        if self.vertices().len() != edge_count + 1
+            || self.winding().is_none_or(|current| current == winding)
+        {
+            return false;
+        }
+
+        let reverse_index = |index: u32| match (edge_count as u32).checked_sub(index + 1) {
+            Some(reversed) => reversed,
+            None => index,
+        };
+
+        self.vertices_mut().elements_mut().reverse();
+        self.normals_mut().elements_mut().reverse();
+
+        for normal in self.normals_mut().iter_mut() {
+            *normal = -*normal;
+        }
+
+        if let Some(attributes) = self.attributes_mut() {
+            attributes.elements_mut().reverse();
+
+            for attribute in attributes.iter_mut() {
+                let flags = attribute.flags_mut();
+                let old = *flags;
+
+                flags.set_upper(old.under());
+                flags.set_under(old.upper());
+                flags.set_left(old.right());
+                flags.set_right(old.left());
+            }
+        }
+
+        for cliff in self.cliffs_mut().iter_mut() {
+            *cliff.lr_mut() = -cliff.lr();
+
+            if let Some(line_index) = cliff.line_index_mut() {
+                *line_index = reverse_index(*line_index);
+            }
+        }
+
+        if let Some(spirits_floors) = self.spirits_floors_mut() {
+            for spirits_floor in spirits_floors.iter_mut() {
+                let line_index = spirits_floor.line_index_mut();
+
+                *line_index = reverse_index(*line_index);
+            }
+        }
+
+        true
+    }
+
+    /// Returns `true` if the collision is a closed loop, with its last vertex equal to its first.
+    pub fn is_closed(&self) -> bool {
+        let vertices = self.vertices().elements();
+
+        vertices.len() > 1 && *vertices[0] == *vertices[vertices.len() - 1]
+    }
+
+    /// Makes the collision a closed loop by appending a copy of its first vertex,
+    /// returning `true` if the collision changed.
+    ///
+    /// The added edge gets a normal facing the same side as those of the other edges,
+    /// and a copy of the attributes of the previous edge with its direction and `length0`
+    /// attributes set from its geometry. The collision is left unchanged if it is already closed,
+    /// has fewer than three vertices, or has inconsistent vertex and edge counts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{objects::Collision, vector::Vector2};
+    ///
+    /// let mut collision = Collision::default();
+    ///
+    /// *collision.vertices_mut() = [(0.0, 0.0), (0.0, 10.0), (10.0, 10.0)]
+    ///     .into_iter()
+    ///     .map(|(x, y)| Vector2::new(x, y))
+    ///     .collect();
+    /// *collision.normals_mut() = [(-1.0, 0.0), (0.0, 1.0)]
+    ///     .into_iter()
+    ///     .map(|(x, y)| Vector2::new(x, y))
+    ///     .collect();
+    ///
+    /// assert!(collision.close());
+    /// assert!(collision.is_closed());
+    /// assert_eq!(collision.edge_count(), 3);
+    /// ```
+    pub fn close(&mut self) -> bool {
+        let vertices = self.vertices().iter().copied().collect::<Vec<_>>();
+
+        if self.is_closed() || vertices.len() < 3 || vertices.len() != self.edge_count() + 1 {
+            return false;
+        }
+
+        let (first, last) = (vertices[0], vertices[vertices.len() - 1]);
+        let facing = vertices
+            .windows(2)
+            .zip(self.normals().iter())
+            .map(|(edge, &normal)| (edge[1] - edge[0]).perp().dot(normal))
+            .sum::<f32>();
+        let normal = match (first - last).perp().normalize() {
+            Some(normal) if facing < 0.0 => -normal,
+            Some(normal) => normal,
+            None => Vector2::new(0.0, 1.0),
+        };
+        let length = (first - last).length();
+
+        if let Some(attributes) = self.attributes_mut() {
+            let mut attribute = attributes.iter().last().cloned().unwrap_or_default();
+
+            set_direction_flags(attribute.flags_mut(), normal, length);
+            attributes.push(attribute);
+        }
+
+        self.vertices_mut().push(first);
+        self.normals_mut().push(normal);
+
+        true
+    }
+
+    /// Makes a closed loop an open polyline by removing its last vertex, which duplicates the
+    /// first, along with the edge ending there, returning `true` if the collision changed.
+    ///
+    /// The normal and attributes of the removed edge are removed, as are the cliffs and spirits
+    /// floor entries referring to it. The collision is left unchanged if it is not closed or has
+    /// inconsistent vertex and edge counts.
+    pub fn open(&mut self) -> bool {
+        let edge_count = self.edge_count();
+
+        if !self.is_closed() || self.vertices().len() != edge_count + 1 {
+            return false;
+        }
+
+        let last_edge = edge_count as u32 - 1;
+
+        self.vertices_mut().elements_mut().pop();
+        self.normals_mut().elements_mut().pop();
+
+        if let Some(attributes) = self.attributes_mut() {
+            attributes.elements_mut().truncate(edge_count - 1);
+        }
+
+        self.cliffs_mut()
+            .elements_mut()
+            .retain(|cliff| cliff.line_index() != Some(last_edge));
+
+        if let Some(spirits_floors) = self.spirits_floors_mut() {
+            spirits_floors
+                .elements_mut()
+                .retain(|spirits_floor| spirits_floor.line_index() != last_edge);
+        }
+
+        true
+    }
+
+    /// Cuts off the parts of the collision beyond the vertical lines at `left` and `right`,
+    /// returning `true` if the collision changed.
+    ///
+    /// Each edge crossing a line is shortened to end on it, and edges beyond the lines are
+    /// removed along with their cliffs and spirits floor entries. Where the collision leaves the
+    /// range and comes back, or a closed collision is cut, the gap is capped with an edge facing
+    /// away from the range, taking the attributes of the edge leaving it. A cliff facing away
+    /// from the range is added at each new vertex ending a floor edge on a line. The collision
+    /// is left unchanged if it lies within the range, fewer than two vertices would remain,
+    /// or its vertex and edge counts are inconsistent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{objects::Collision, vector::Vector2};
+    ///
+    /// let mut collision = Collision::default();
+    ///
+    /// *collision.vertices_mut() = [(-300.0, 0.0), (300.0, 0.0)]
+    ///     .into_iter()
+    ///     .map(|(x, y)| Vector2::new(x, y))
+    ///     .collect();
+    /// *collision.normals_mut() = vec![Vector2::new(0.0, 1.0)].into_iter().collect();
+    ///
+    /// assert!(collision.trim_x(-200.0, 200.0));
+    /// assert_eq!(collision.vertices().iter().next(), Some(&Vector2::new(-200.0, 0.0)));
+    /// assert_eq!(collision.cliffs().len(), 2);
+    /// ```
+    pub fn trim_x(&mut self, left: f32, right: f32) -> bool {
+        let old_vertices = self.vertices().iter().copied().collect::<Vec<_>>();
+
+        if old_vertices.len() != self.edge_count() + 1
+            || old_vertices.iter().all(|v| v.x() >= left && v.x() <= right)
+        {
+            return false;
+        }
+
+        let mut vertices = Vec::<Vector2>::new();
+        let mut cut = Vec::new();
+        let mut spans = Vec::new();
+        let mut caps = Vec::new();
+
+        for (i, edge) in old_vertices.windows(2).enumerate() {
+            let Some((start, end)) = clip_segment(edge[0], edge[1], left, right) else {
+                continue;
+            };
+
+            // Only the touching point of an edge ending on a line remains.
+            if start == end && edge[0] != edge[1] {
+                continue;
+            }
+
+            match vertices.last() {
+                None => {
+                    vertices.push(start);
+                    cut.push(start != edge[0]);
+                }
+                Some(&last) if last != start => {
+                    caps.push(spans.len());
+                    spans.push(cap_span(&spans));
+                    vertices.push(start);
+                    cut.push(start != edge[0]);
+                }
+                Some(_) => {}
+            }
+
+            spans.push(i..i + 1);
+            vertices.push(end);
+            cut.push(end != edge[1]);
+        }
+
+        if vertices.len() < 2 {
+            return false;
+        }
+
+        if self.is_closed() && vertices[0] != vertices[vertices.len() - 1] {
+            caps.push(spans.len());
+            spans.push(cap_span(&spans));
+            vertices.push(vertices[0]);
+            cut.push(cut[0]);
+        }
+
+        let kept = |index: usize| spans.iter().any(|span| span.contains(&index));
+
+        self.cliffs_mut().elements_mut().retain(|cliff| {
+            let x = cliff.pos().x();
+
+            x >= left && x <= right && cliff.line_index().is_none_or(|i| kept(i as usize))
+        });
+
+        if let Some(spirits_floors) = self.spirits_floors_mut() {
+            spirits_floors
+                .elements_mut()
+                .retain(|spirits_floor| kept(spirits_floor.line_index() as usize));
+        }
+
+        self.rebuild_edges(vertices.clone(), &spans);
+
+        for &cap in &caps {
+            let (start, end) = (vertices[cap], vertices[cap + 1]);
+            let normal = match (start.x(), end.x()) {
+                (a, b) if a == left && b == left => Vector2::new(-1.0, 0.0),
+                (a, b) if a == right && b == right => Vector2::new(1.0, 0.0),
+                _ => continue,
+            };
+
+            self.normals_mut().elements_mut()[cap] = Versioned::new(normal);
+
+            if let Some(attribute) = self
+                .attributes_mut()
+                .and_then(|attributes| attributes.iter_mut().nth(cap))
+            {
+                set_direction_flags(attribute.flags_mut(), normal, (end - start).length());
+            }
+        }
+
+        let template = self.cliffs().iter().next().cloned().unwrap_or_default();
+
+        for (index, &pos) in vertices.iter().enumerate() {
+            let lr = match pos.x() {
+                x if x == left => -1.0,
+                x if x == right => 1.0,
+                _ => continue,
+            };
+            let floor = [index.checked_sub(1), Some(index)]
+                .into_iter()
+                .flatten()
+                .find(|&edge| !caps.contains(&edge) && self.is_floor_edge(edge));
+            let has_cliff = self
+                .cliffs()
+                .iter()
+                .any(|cliff| (cliff.pos() - pos).length() < Self::ZERO_LENGTH);
+
+            let Some(edge) = floor.filter(|_| cut[index] && !has_cliff) else {
+                continue;
+            };
+            let mut cliff = template.clone();
+
+            if let Some(base) = cliff.base_mut() {
+                *base = Default::default();
+            }
+
+            if let Some(line_index) = cliff.line_index_mut() {
+                *line_index = edge as u32;
+            }
+
+            *cliff.pos_mut() = pos;
+            *cliff.lr_mut() = lr;
+            self.cliffs_mut().push(cliff);
+        }
+
+        true
+    }
+
+    /// Replaces the vertices of the collision, where each resulting edge is derived from the span
+    /// of original edges at the same index, and updates the per-edge data to match.
+    fn rebuild_edges(&mut self, vertices: Vec<Vector2>, spans: &[Range<usize>]) {
+        let old_vertices = self.vertices().iter().copied().collect::<Vec<_>>();
+        let old_normals = self.normals().iter().copied().collect::<Vec<_>>();
+        let sources = spans
+            .iter()
+            .map(|span| {
+                span.clone()
+                    .max_by(|&a, &b| {
+                        let length = |i: usize| (old_vertices[i + 1] - old_vertices[i]).length();
+
+                        length(a).total_cmp(&length(b))
+                    })
+                    .unwrap_or(span.start)
+            })
+            .collect::<Vec<_>>();
+        let normals = sources
+            .iter()
+            .zip(vertices.windows(2))
+            .map(|(&source, edge)| {
+                let old_normal = old_normals[source];
+
+                match (edge[1] - edge[0]).perp().normalize() {
+                    Some(normal) if normal.dot(old_normal) < 0.0 => -normal,
+                    Some(normal) => normal,
+                    None => old_normal,
+                }
+            })
+            .collect();
+
+        if let Some(attributes) = self.attributes_mut() {
+            if attributes.len() == old_normals.len() {
+                let old_attributes = attributes.iter().cloned().collect::<Vec<_>>();
+
+                *attributes = sources
+                    .iter()
+                    .map(|&source| old_attributes[source].clone())
+                    .collect();
+            }
+        }
+
+        for cliff in self.cliffs_mut().iter_mut() {
+            let pos = cliff.pos();
+
+            if let Some(line_index) = cliff.line_index_mut() {
+                let nearest = (0..spans.len())
+                    .filter(|&j| spans[j].contains(&(*line_index as usize)))
+                    .min_by(|&a, &b| {
+                        let distance = |j: usize| {
+                            let start = (vertices[j] - pos).length();
+                            let end = (vertices[j + 1] - pos).length();
+
+                            start.min(end)
+                        };
+
+                        distance(a).total_cmp(&distance(b))
+                    });
+
+                if let Some(j) = nearest {
+                    *line_index = j as u32;
+                }
+            }
+        }
+
+        if let Some(spirits_floors) = self.spirits_floors_mut() {
+            let old_spirits_floors = spirits_floors.elements().to_vec();
+            let in_span = |span: &Range<usize>, index: u32| span.contains(&(index as usize));
+            let elements = spirits_floors.elements_mut();
+
+            elements.clear();
+
+            for (j, span) in spans.iter().enumerate() {
+                let found = old_spirits_floors
+                    .iter()
+                    .find(|s| in_span(span, s.line_index()));
+
+                if let Some(spirits_floor) = found {
+                    let mut spirits_floor = spirits_floor.clone();
+
+                    *spirits_floor.line_index_mut() = j as u32;
+                    elements.push(spirits_floor);
+                }
+            }
+
+            elements.extend(
+                old_spirits_floors
+                    .into_iter()
+                    .filter(|s| !spans.iter().any(|span| in_span(span, s.line_index()))),
+            );
+        }
+
+        *self.vertices_mut() = vertices.into_iter().collect();
+        *self.normals_mut() = normals;
+    }
+}
+
+/// Sets the direction attributes of an edge from its normal, and its `length0` attribute
+/// from its length.
+fn set_direction_flags(flags: &mut AttributeFlags, normal: Vector2, length: f32) {
+    let (x, y) = (normal.x(), normal.y());
+
+    flags.set_upper(y > x.abs());
+    flags.set_under(-y > x.abs());
+    flags.set_right(x >= y.abs() && x > 0.0);
+    flags.set_left(-x >= y.abs() && x < 0.0);
+    flags.set_length0(length < Collision::ZERO_LENGTH);
+}
+
+/// Returns the part of the line segment between `start` and `end` lying between the vertical
+/// lines at `left` and `right`, or `None` if no part of it does.
+///
+/// Points cut by a line lie exactly on it.
+fn clip_segment(start: Vector2, end: Vector2, left: f32, right: f32) -> Option<(Vector2, Vector2)> {
+    let dx = end.x() - start.x();
+    let (t0, t1) = if dx == 0.0 {
+        (0.0, 1.0)
+    } else {
+        let (a, b) = ((left - start.x()) / dx, (right - start.x()) / dx);
+
+        (a.min(b).max(0.0), a.max(b).min(1.0))
+    };
+
+    if t0 > t1 || start.x().max(end.x()) < left || start.x().min(end.x()) > right {
+        return None;
+    }
+
+    let point = |t: f32| match t {
+        0.0 => start,
+        1.0 => end,
+        t => {
+            let point = start + (end - start) * t;
+
+            Vector2::new(point.x().clamp(left, right), point.y())
+        }
+    };
+
+    Some((point(t0), point(t1)))
+}
+
+/// Returns the span of a cap edge following the given spans, which covers no original edges
+/// but starts at the edge preceding the cap, so the cap takes its attributes.
+fn cap_span(spans: &[Range<usize>]) -> Range<usize> {
+    let source = spans.last().map_or(0, |span| span.start);
+
+    source..source
+}
+
+/// Returns the shortest distance from `point` to the line segment between `start` and `end`.
+pub(crate) fn segment_distance(point: Vector2, start: Vector2, end: Vector2) -> f32 {
+    let edge = end - start;
+    let length_squared = edge.dot(edge);
+
+    if length_squared == 0.0 {
+        return (point - start).length();
+    }
+
+    let t = ((point - start).dot(edge) / length_squared).clamp(0.0, 1.0);
+
+    (point - (start + edge * t)).length()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        array::Array,
+        objects::{
+            base::Base,
+            collision::{
+                attribute::{AttributeFlags, MaterialType},
+                CollisionAttribute, CollisionCliff, CollisionFlags,
+            },
+        },
+        vector::Vector2,
+        version::Versioned,
+    };
+
+    use super::*;
+
+    /// Returns a collision through the given points with upward facing normals.
+    fn collision(points: &[(f32, f32)]) -> Collision {
+        let vertices = points
+            .iter()
+            .map(|&(x, y)| Vector2::new(x, y))
+            .collect::<Array<_>>();
+        let edge_count = points.len() - 1;
+
+        Collision::V4 {
+            base: Versioned::new(Base::default()),
+            flags: CollisionFlags::new(),
+            vertices: Versioned::new(vertices),
+            normals: Versioned::new(
+                vec![Vector2::new(0.0, 1.0); edge_count]
+                    .into_iter()
+                    .collect(),
+            ),
+            cliffs: Versioned::new(Array::new(vec![CollisionCliff::V3 {
+                base: Versioned::new(Base::default()),
+                pos: Versioned::new(Vector2::new(points[edge_count].0, points[edge_count].1)),
+                lr: 1.0,
+                line_index: edge_count as u32 - 1,
+            }])),
+            attributes: Versioned::new(
+                (0..edge_count as u32)
+                    .map(|i| CollisionAttribute::V1 {
+                        material: MaterialType::from(i),
+                        flags: AttributeFlags::new(),
+                    })
+                    .collect(),
+            ),
+            spirits_floors: Versioned::new(Array::default()),
+        }
+    }
+
+    #[test]
+    fn ensure_winding_reverses_edges() {
+        let mut collision = collision(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0)]);
+
+        assert_eq!(collision.winding(), Some(Winding::CounterClockwise));
+        assert!(!collision.ensure_winding(Winding::CounterClockwise));
+        assert!(collision.ensure_winding(Winding::Clockwise));
+
+        let vertices = collision.vertices().iter().copied().collect::<Vec<_>>();
+        let materials = collision
+            .attributes()
+            .unwrap()
+            .iter()
+            .map(CollisionAttribute::material)
+            .collect::<Vec<_>>();
+        let cliff = collision.cliffs().iter().next().unwrap();
+
+        assert_eq!(
+            vertices,
+            [
+                Vector2::new(4.0, 4.0),
+                Vector2::new(4.0, 0.0),
+                Vector2::new(0.0, 0.0)
+            ]
+        );
+        assert_eq!(materials, [MaterialType::from(1), MaterialType::from(0)]);
+        assert_eq!(
+            collision.normals().iter().next(),
+            Some(&Vector2::new(0.0, -1.0))
+        );
+        assert_eq!((cliff.line_index(), cliff.lr()), (Some(0), -1.0));
+    }
+
+    #[test]
+    fn fix_cliffs_snaps_turns_and_removes() {
+        let mut collision = collision(&[(0.0, 0.0), (4.0, 0.0), (8.0, 2.0)]);
+
+        collision.cliffs_mut().push(CollisionCliff::V3 {
+            base: Versioned::new(Base::default()),
+            pos: Versioned::new(Vector2::new(0.5, 0.5)),
+            lr: 1.0,
+            line_index: 0,
+        });
+        collision.cliffs_mut().push(CollisionCliff::V3 {
+            base: Versioned::new(Base::default()),
+            pos: Versioned::new(Vector2::default()),
+            lr: -1.0,
+            line_index: 2,
+        });
+
+        assert_eq!(collision.fix_cliffs(), 2);
+        assert_eq!(collision.fix_cliffs(), 0);
+
+        let cliffs = collision
+            .cliffs()
+            .iter()
+            .map(|cliff| (cliff.pos(), cliff.lr()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            cliffs,
+            [
+                (Vector2::new(8.0, 2.0), 1.0),
+                (Vector2::new(0.0, 0.0), -1.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn close_and_open() {
+        let mut collision = collision(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0)]);
+
+        assert!(!collision.open());
+        assert!(collision.close());
+        assert!(!collision.close());
+        assert_eq!(collision.attributes().unwrap().len(), 3);
+
+        let normal = *collision.normals().iter().last().unwrap();
+
+        assert!((normal - Vector2::new(1.0, -1.0).normalize().unwrap()).length() < 1e-6);
+        assert!(collision.open());
+        assert_eq!(collision.vertices().len(), 3);
+        assert_eq!(collision.normals().len(), 2);
+        assert_eq!(collision.attributes().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn simplify_collinear_vertices() {
+        let mut collision =
+            collision(&[(0.0, 0.0), (1.0, 0.0), (4.0, 0.0), (4.0, 0.05), (6.0, 2.0)]);
+
+        assert_eq!(collision.simplify(0.1), 2);
+
+        let vertices = collision.vertices().iter().copied().collect::<Vec<_>>();
+        let materials = collision
+            .attributes()
+            .unwrap()
+            .iter()
+            .map(CollisionAttribute::material)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vertices,
+            [
+                Vector2::new(0.0, 0.0),
+                Vector2::new(4.0, 0.0),
+                Vector2::new(6.0, 2.0)
+            ]
+        );
+        assert_eq!(materials, [MaterialType::from(1), MaterialType::from(3)]);
+        assert_eq!(collision.normals().len(), 2);
+        assert_eq!(
+            collision.cliffs().iter().next().unwrap().line_index(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn subdivide_long_edges() {
+        let mut collision = collision(&[(0.0, 0.0), (10.0, 0.0), (12.0, 0.0)]);
+
+        assert_eq!(collision.subdivide(4.0), Ok(2));
+
+        let vertices = collision.vertices().iter().copied().collect::<Vec<_>>();
+
+        assert_eq!(vertices.len(), 5);
+        assert!((vertices[1] - Vector2::new(10.0 / 3.0, 0.0)).length() < 1e-5);
+        assert_eq!(vertices[3], Vector2::new(10.0, 0.0));
+        assert_eq!(collision.edge_count(), 4);
+    }
+
+    #[test]
+    fn subdivide_rejects_invalid_lengths() {
+        let mut short = collision(&[(0.0, 0.0), (10.0, 0.0)]);
+
+        for length in [0.0, -1.0, f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            assert!(matches!(
+                short.subdivide(length),
+                Err(SubdivideError::InvalidLength(_))
+            ));
+        }
+
+        assert_eq!(
+            short.subdivide(f32::MIN_POSITIVE),
+            Err(SubdivideError::TooManyVertices(0))
+        );
+        assert_eq!(short.vertices().len(), 2);
+
+        let mut infinite = collision(&[(0.0, 0.0), (1.0, 0.0), (f32::INFINITY, 0.0)]);
+
+        for length in [1.0, f32::MAX] {
+            assert_eq!(
+                infinite.subdivide(length),
+                Err(SubdivideError::TooManyVertices(1))
+            );
+        }
+
+        assert_eq!(infinite.vertices().len(), 3);
+
+        let mut huge = collision(&[(-f32::MAX, 0.0), (0.0, 0.0), (f32::MAX, 0.0)]);
+
+        assert_eq!(huge.subdivide(1.0), Err(SubdivideError::TooManyVertices(0)));
+        assert_eq!(huge.subdivide(f32::MAX), Ok(0));
+    }
+}
@@ -0,0 +1,274 @@
+//! Splitting a [`Collision`] in two and joining two into one.
+
+use std::ops::Range;
+
+use thiserror::Error;
+
+use crate::{
+    vector::Vector2,
+    version::{Version, Versioned},
+};
+
+use super::{geometry::segment_distance, Collision};
+
+/// The error type used when joining two [`Collision`]s.
+#[derive(Debug, PartialEq, Error)]
+pub enum JoinError {
+    /// The collisions have different versions.
+    #[error("cannot join collisions of versions {0} and {1}")]
+    VersionMismatch(u8, u8),
+
+    /// A collision's vertex count is not one more than its edge count.
+    #[error("collision has {vertices} vertices but {edges} edges")]
+    InconsistentEdges {
+        /// The number of vertices.
+        vertices: usize,
+
+        /// The number of edges.
+        edges: usize,
+    },
+
+    /// Neither collision ends where the other starts.
+    #[error("neither collision ends where the other starts")]
+    NotAdjacent,
+}
+
+impl Collision {
+    /// Splits the collision at the vertex starting the edge at the given index, returning
+    /// a collision with the preceding edges and a collision with the edge and those following it.
+    ///
+    /// Both collisions keep the name and other common data of the original. Each takes the
+    /// normals, attributes, cliffs, and spirits floor entries of its edges, with edge indices
+    /// updated to match. Cliffs without an edge index go to the collision with the nearest edge.
+    /// Returns `None` if `edge_index` is not between the first and last edges, or the vertex and
+    /// edge counts are inconsistent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{objects::Collision, vector::Vector2};
+    ///
+    /// let mut collision = Collision::default();
+    ///
+    /// *collision.vertices_mut() = [(0.0, 0.0), (10.0, 0.0), (20.0, 0.0)]
+    ///     .into_iter()
+    ///     .map(|(x, y)| Vector2::new(x, y))
+    ///     .collect();
+    /// *collision.normals_mut() = vec![Vector2::new(0.0, 1.0); 2].into_iter().collect();
+    ///
+    /// let (left, right) = collision.split_at(1).unwrap();
+    ///
+    /// assert_eq!(left.vertices().len(), 2);
+    /// assert_eq!(right.vertices().len(), 2);
+    /// ```
+    pub fn split_at(&self, edge_index: usize) -> Option<(Self, Self)> {
+        let edge_count = self.edge_count();
+
+        if self.vertices().len() != edge_count + 1 || edge_index == 0 || edge_index >= edge_count {
+            return None;
+        }
+
+        Some((
+            self.edge_range(0..edge_index),
+            self.edge_range(edge_index..edge_count),
+        ))
+    }
+
+    /// Joins two collisions into one, where one ends within `tolerance` of where the other
+    /// starts, returning a collision with the edges of the collision which ends there followed
+    /// by those of the other.
+    ///
+    /// The joined collision keeps the name and other common data of `a`. Its per-edge data is
+    /// concatenated in edge order, with edge indices updated to match, except for cliffs within
+    /// `tolerance` of the joined vertex, which no longer marks the end of a ledge.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the collisions have different versions, either has inconsistent
+    /// vertex and edge counts, or neither ends within `tolerance` of where the other starts.
+    pub fn join(a: &Self, b: &Self, tolerance: f32) -> Result<Self, JoinError> {
+        if a.version() != b.version() {
+            return Err(JoinError::VersionMismatch(a.version(), b.version()));
+        }
+
+        let (first, second) = match (endpoints(a)?, endpoints(b)?) {
+            ((_, a_end), (b_start, _)) if (b_start - a_end).length() <= tolerance => (a, b),
+            ((a_start, _), (_, b_end)) if (a_start - b_end).length() <= tolerance => (b, a),
+            _ => return Err(JoinError::NotAdjacent),
+        };
+        let offset = first.edge_count() as u32;
+        let joint = endpoints(first)?.1;
+        let mut joined = a.clone();
+
+        *joined.vertices_mut().elements_mut() = first
+            .vertices()
+            .elements()
+            .iter()
+            .chain(&second.vertices().elements()[1..])
+            .cloned()
+            .collect();
+        *joined.normals_mut().elements_mut() = first
+            .normals()
+            .elements()
+            .iter()
+            .chain(second.normals().elements())
+            .cloned()
+            .collect();
+
+        if let (Some(attributes), Some(first_attributes), Some(second_attributes)) = (
+            joined
+                .attributes_mut()
+                .map(|attributes| attributes.elements_mut()),
+            first.attributes(),
+            second.attributes(),
+        ) {
+            *attributes = first_attributes
+                .elements()
+                .iter()
+                .chain(second_attributes.elements())
+                .cloned()
+                .collect();
+        }
+
+        let second_cliffs = second.cliffs().iter().cloned().map(|mut cliff| {
+            if let Some(line_index) = cliff.line_index_mut() {
+                *line_index += offset;
+            }
+
+            cliff
+        });
+
+        *joined.cliffs_mut().elements_mut() = first
+            .cliffs()
+            .iter()
+            .cloned()
+            .chain(second_cliffs)
+            .filter(|cliff| (cliff.pos() - joint).length() > tolerance)
+            .map(Versioned::new)
+            .collect();
+
+        if let (Some(spirits_floors), Some(first_spirits_floors), Some(second_spirits_floors)) = (
+            joined
+                .spirits_floors_mut()
+                .map(|spirits_floors| spirits_floors.elements_mut()),
+            first.spirits_floors(),
+            second.spirits_floors(),
+        ) {
+            let second_spirits_floors = second_spirits_floors.iter().cloned().map(|mut floor| {
+                *floor.line_index_mut() += offset;
+
+                floor
+            });
+
+            *spirits_floors = first_spirits_floors
+                .iter()
+                .cloned()
+                .chain(second_spirits_floors)
+                .map(Versioned::new)
+                .collect();
+        }
+
+        Ok(joined)
+    }
+
+    /// Returns a copy of the collision with only the edges in the given range.
+    fn edge_range(&self, edges: Range<usize>) -> Self {
+        let vertices = self.vertices().iter().copied().collect::<Vec<_>>();
+        let nearest_edge = |pos: Vector2| {
+            (0..self.edge_count()).min_by(|&a, &b| {
+                let distance = |i: usize| segment_distance(pos, vertices[i], vertices[i + 1]);
+
+                distance(a).total_cmp(&distance(b))
+            })
+        };
+        let start = edges.start as u32;
+        let mut collision = self.clone();
+
+        *collision.vertices_mut().elements_mut() =
+            self.vertices().elements()[edges.start..=edges.end].to_vec();
+        *collision.normals_mut().elements_mut() = self.normals().elements()[edges.clone()].to_vec();
+
+        if let Some(attributes) = collision.attributes_mut() {
+            if let Some(range) = attributes.elements().get(edges.clone()) {
+                *attributes.elements_mut() = range.to_vec();
+            }
+        }
+
+        collision.cliffs_mut().elements_mut().retain_mut(|cliff| {
+            let edge = match cliff.line_index() {
+                Some(line_index) => Some(line_index as usize),
+                None => nearest_edge(cliff.pos()),
+            };
+
+            if let Some(line_index) = cliff.line_index_mut() {
+                *line_index = line_index.wrapping_sub(start);
+            }
+
+            edge.is_some_and(|edge| edges.contains(&edge))
+        });
+
+        if let Some(spirits_floors) = collision.spirits_floors_mut() {
+            spirits_floors.elements_mut().retain_mut(|spirits_floor| {
+                let edge = spirits_floor.line_index();
+
+                *spirits_floor.line_index_mut() = edge.wrapping_sub(start);
+
+                edges.contains(&(edge as usize))
+            });
+        }
+
+        collision
+    }
+}
+
+/// Returns the first and last vertices of a collision with consistent vertex and edge counts.
+fn endpoints(collision: &Collision) -> Result<(Vector2, Vector2), JoinError> {
+    let vertices = collision.vertices().elements();
+    let edges = collision.edge_count();
+
+    match (vertices.first(), vertices.last()) {
+        (Some(first), Some(last)) if vertices.len() == edges + 1 => Ok((**first, **last)),
+        _ => Err(JoinError::InconsistentEdges {
+            vertices: vertices.len(),
+            edges,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_and_join() {
+        let collision =
+            Collision::new_soft_platform(Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0));
+        let mut long = collision.clone();
+
+        long.subdivide(5.0).unwrap();
+
+        let (left, right) = long.split_at(1).unwrap();
+
+        assert_eq!(left.cliffs().len(), 1);
+        assert_eq!(right.cliffs().iter().next().unwrap().line_index(), Some(0));
+        assert!(long.split_at(0).is_none());
+        assert!(long.split_at(2).is_none());
+
+        let joined = Collision::join(&right, &left, 0.01).unwrap();
+        let vertices = joined.vertices().iter().copied().collect::<Vec<_>>();
+
+        assert_eq!(
+            vertices,
+            [
+                Vector2::new(0.0, 0.0),
+                Vector2::new(5.0, 0.0),
+                Vector2::new(10.0, 0.0)
+            ]
+        );
+        assert_eq!(joined.cliffs().len(), 2);
+        assert_eq!(
+            Collision::join(&left, &left, 0.01).unwrap_err(),
+            JoinError::NotAdjacent
+        );
+    }
+}
@@ -5,17 +5,21 @@ use binrw::binrw;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
 use crate::{
-    objects::base::Base,
+    objects::{base::Base, upgrade_base},
     string::FixedString64,
-    version::{Version, Versioned},
+    version::{Upgrade, Version, Versioned},
 };
 
 /// An LVD subobject to a [`Collision`](crate::objects::collision::Collision) representing hazardous floors in spirit battles.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum CollisionSpiritsFloor {
     /// The first version of the `CollisionSpiritsFloor` type.
     ///
@@ -75,3 +79,63 @@ impl Version for CollisionSpiritsFloor {
         }
     }
 }
+
+impl Default for CollisionSpiritsFloor {
+    fn default() -> Self {
+        Self::V2 {
+            base: Versioned::new(Base::default()),
+            line_index: 0,
+            line_group: Versioned::new(FixedString64::new()),
+            unk1: 0.0,
+            unk2: 0.0,
+            unk3: 0.0,
+            unk4: 0.0,
+            unk5: 0.0,
+            unk6: 0.0,
+        }
+    }
+}
+
+impl Upgrade for CollisionSpiritsFloor {
+    const LATEST_VERSION: u8 = 2;
+
+    fn into_latest(self) -> Self {
+        match self {
+            Self::V1 {
+                base,
+                line_index,
+                line_group,
+            } => Self::V2 {
+                base,
+                line_index,
+                line_group,
+                unk1: 0.0,
+                unk2: 0.0,
+                unk3: 0.0,
+                unk4: 0.0,
+                unk5: 0.0,
+                unk6: 0.0,
+            }
+            .into_latest(),
+            Self::V2 { .. } => upgrade_base(self),
+        }
+    }
+}
+
+impl CollisionSpiritsFloor {
+    /// Returns the index of the edge in the associated collision the object is linked with.
+    pub fn line_index(&self) -> u32 {
+        match self {
+            Self::V1 { line_index, .. } | Self::V2 { line_index, .. } => *line_index,
+        }
+    }
+
+    /// Returns the index of the edge in the associated collision the object is linked with mutably.
+    pub fn line_index_mut(&mut self) -> &mut u32 {
+        match self {
+            Self::V1 { line_index, .. } | Self::V2 { line_index, .. } => line_index,
+        }
+    }
+}
+
+impl_lvd_object!(CollisionSpiritsFloor { base: [V1, V2] });
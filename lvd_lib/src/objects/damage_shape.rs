@@ -1,21 +1,32 @@
 //! The [`DamageShape`] object stores data representing a three-dimensional damage or attack collision shape.
 
+use std::fmt;
+
 use binrw::binrw;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
 use crate::{
-    objects::base::Base,
+    objects::{
+        base::{Base, JointBindingError},
+        upgrade_base, write_summary,
+    },
     shape::Shape3,
-    version::{Version, Versioned},
+    transform::CoordinateMap,
+    vector::Vector3,
+    version::{Upgrade, Version, Versioned},
 };
 
 /// An LVD object representing a three-dimensional damage or attack collision shape.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum DamageShape {
     /// The first version of the `DamageShape` type.
     #[br(pre_assert(version == 1))]
@@ -43,3 +54,137 @@ impl Version for DamageShape {
         }
     }
 }
+
+impl Default for DamageShape {
+    fn default() -> Self {
+        Self::V1 {
+            base: Versioned::new(Base::default()),
+            shape: Versioned::new(Shape3::default()),
+            is_damager: false,
+            id: 0,
+        }
+    }
+}
+
+impl Upgrade for DamageShape {
+    const LATEST_VERSION: u8 = 1;
+
+    fn into_latest(self) -> Self {
+        upgrade_base(self)
+    }
+}
+
+impl DamageShape {
+    /// Returns the numeric identifier of the damage shape.
+    pub fn id(&self) -> u32 {
+        match self {
+            Self::V1 { id, .. } => *id,
+        }
+    }
+
+    /// Returns the numeric identifier of the damage shape mutably.
+    pub fn id_mut(&mut self) -> &mut u32 {
+        match self {
+            Self::V1 { id, .. } => id,
+        }
+    }
+
+    /// Returns the three-dimensional geometric representation of the damage shape.
+    pub fn shape(&self) -> &Shape3 {
+        match self {
+            Self::V1 { shape, .. } => shape,
+        }
+    }
+
+    /// Returns the three-dimensional geometric representation of the damage shape mutably.
+    pub fn shape_mut(&mut self) -> &mut Shape3 {
+        match self {
+            Self::V1 { shape, .. } => shape,
+        }
+    }
+
+    /// Returns `true` if the point lies within the damage shape or on its surface.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{objects::DamageShape, shape::Shape3, vector::Vector3};
+    ///
+    /// let mut damage_shape = DamageShape::default();
+    /// *damage_shape.shape_mut() = Shape3::sphere(0.0, 10.0, 0.0, 5.0);
+    ///
+    /// assert!(damage_shape.contains_point(Vector3::new(0.0, 12.0, 0.0)));
+    /// ```
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        self.shape().contains_point(point)
+    }
+
+    /// Returns `true` if the damage shape overlaps or touches the given shape.
+    pub fn intersects(&self, shape: &Shape3) -> bool {
+        self.shape().intersects(shape)
+    }
+
+    /// Classes the damage shape as dynamic and parents it to the joint named `joint_name`
+    /// from the parent model, so that it follows the joint's animation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version of the common data does not support joints,
+    /// or if the name exceeds the buffer's capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::objects::{DamageShape, LvdObject};
+    ///
+    /// let mut damage_shape = DamageShape::default();
+    /// damage_shape.bind_to_joint("saw_blade").unwrap();
+    ///
+    /// assert_eq!(
+    ///     damage_shape.base().unwrap().joint_name().unwrap().to_str(),
+    ///     Ok("saw_blade")
+    /// );
+    /// ```
+    pub fn bind_to_joint(&mut self, joint_name: &str) -> Result<(), JointBindingError> {
+        match self {
+            Self::V1 { base, .. } => base.bind_to_joint(joint_name),
+        }
+    }
+}
+
+impl_lvd_object!(DamageShape {
+    base: [V1];
+
+    fn shapes3(&self) -> Vec<&Shape3> {
+        match self {
+            Self::V1 { shape, .. } => vec![&shape.inner],
+        }
+    }
+
+    fn transform(&mut self, transform: &dyn CoordinateMap) {
+        match self {
+            Self::V1 { shape, .. } => shape.transform(transform),
+        }
+    }
+});
+
+impl fmt::Display for DamageShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V1 {
+                shape,
+                is_damager,
+                id,
+                ..
+            } => {
+                let mut details = vec![shape.to_string(), format!("id {id}")];
+
+                if *is_damager {
+                    details.push("damager".to_string());
+                }
+
+                write_summary(f, "DamageShape", self, &details)
+            }
+        }
+    }
+}
@@ -1,23 +1,30 @@
 //! The [`EnemyGenerator`] object stores data representing a collection of shapes to generate enemies from.
 
+use std::fmt;
+
 use binrw::binrw;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
 use crate::{
     array::Array,
-    objects::base::Base,
-    shape::ShapeArray2,
+    objects::{base::Base, count, upgrade_base, write_summary},
+    shape::{Shape2, ShapeArray2},
     tag::Tag,
-    version::{Version, Versioned},
+    transform::CoordinateMap,
+    version::{Upgrade, Version, Versioned},
 };
 
 /// An LVD object representing a collection of shapes to generate enemies from.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum EnemyGenerator {
     /// The first version of the `EnemyGenerator` type.
     ///
@@ -108,3 +115,266 @@ impl Version for EnemyGenerator {
         }
     }
 }
+
+impl Default for EnemyGenerator {
+    fn default() -> Self {
+        Self::V3 {
+            base: Versioned::new(Base::default()),
+            appear_shapes: Versioned::new(ShapeArray2::default()),
+            trigger_shapes: Versioned::new(ShapeArray2::default()),
+            unk1: Versioned::new(ShapeArray2::default()),
+            tag: Versioned::new(Tag::default()),
+            appear_tags: Versioned::new(Array::default()),
+            unk2: Versioned::new(Array::default()),
+            trigger_tags: Versioned::new(Array::default()),
+        }
+    }
+}
+
+impl Upgrade for EnemyGenerator {
+    const LATEST_VERSION: u8 = 3;
+
+    fn into_latest(self) -> Self {
+        match self {
+            Self::V1 {
+                base,
+                appear_shapes,
+                trigger_shapes,
+                unk1,
+                tag,
+            } => Self::V2 {
+                base,
+                appear_shapes,
+                trigger_shapes,
+                unk1,
+                tag,
+                appear_tags: Versioned::new(Array::default()),
+                unk2: Versioned::new(Array::default()),
+            }
+            .into_latest(),
+            Self::V2 {
+                base,
+                appear_shapes,
+                trigger_shapes,
+                unk1,
+                tag,
+                appear_tags,
+                unk2,
+            } => Self::V3 {
+                base,
+                appear_shapes,
+                trigger_shapes,
+                unk1,
+                tag,
+                appear_tags,
+                unk2,
+                trigger_tags: Versioned::new(Array::default()),
+            }
+            .into_latest(),
+            Self::V3 { .. } => upgrade_base(self),
+        }
+    }
+}
+
+impl EnemyGenerator {
+    /// Returns the collection of shapes where enemies can appear from.
+    pub fn appear_shapes(&self) -> &ShapeArray2 {
+        match self {
+            Self::V1 { appear_shapes, .. }
+            | Self::V2 { appear_shapes, .. }
+            | Self::V3 { appear_shapes, .. } => appear_shapes,
+        }
+    }
+
+    /// Returns the collection of identifiers for each appear shape, if supported by the version.
+    pub fn appear_tags(&self) -> Option<&Array<Tag>> {
+        match self {
+            Self::V1 { .. } => None,
+            Self::V2 { appear_tags, .. } | Self::V3 { appear_tags, .. } => Some(appear_tags),
+        }
+    }
+
+    /// Returns the collections of appear shapes and their identifiers mutably.
+    fn appear_shapes_and_tags_mut(&mut self) -> (&mut ShapeArray2, Option<&mut Array<Tag>>) {
+        match self {
+            Self::V1 { appear_shapes, .. } => (appear_shapes, None),
+            Self::V2 {
+                appear_shapes,
+                appear_tags,
+                ..
+            }
+            | Self::V3 {
+                appear_shapes,
+                appear_tags,
+                ..
+            } => (appear_shapes, Some(appear_tags)),
+        }
+    }
+
+    /// Appends a shape where enemies can appear from along with its identifier.
+    ///
+    /// Any identifiers missing for existing shapes are filled with the default identifier,
+    /// and any without a shape are removed, so that each shape keeps its identifier.
+    /// The identifier is discarded if the version does not support appear tags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{objects::EnemyGenerator, shape::Shape2, tag::Tag};
+    ///
+    /// let mut generator = EnemyGenerator::default();
+    /// let tag = Tag::try_from("ENM0001").unwrap();
+    ///
+    /// generator.add_appear_shape(Shape2::point(0.0, 10.0), tag);
+    ///
+    /// assert_eq!(generator.appear_shapes().len(), 1);
+    /// assert_eq!(generator.appear_tag(0), Some(tag));
+    /// ```
+    pub fn add_appear_shape(&mut self, shape: Shape2, tag: Tag) {
+        let (appear_shapes, appear_tags) = self.appear_shapes_and_tags_mut();
+
+        if let Some(appear_tags) = appear_tags {
+            appear_tags.elements_mut().truncate(appear_shapes.len());
+            appear_tags
+                .elements_mut()
+                .resize(appear_shapes.len(), Versioned::new(Tag::default()));
+            appear_tags.push(tag);
+        }
+
+        appear_shapes.push(shape);
+    }
+
+    /// Removes the appear shape at the given index along with its identifier,
+    /// returning them, or `None` if the index is out of bounds.
+    ///
+    /// The identifier is `None` if the version does not support appear tags
+    /// or the shape had no identifier.
+    pub fn remove_appear_shape(&mut self, index: usize) -> Option<(Shape2, Option<Tag>)> {
+        let (appear_shapes, appear_tags) = self.appear_shapes_and_tags_mut();
+        let shape = appear_shapes.remove(index)?;
+        let tag = appear_tags
+            .filter(|appear_tags| index < appear_tags.len())
+            .map(|appear_tags| appear_tags.remove(index));
+
+        Some((shape, tag))
+    }
+
+    /// Returns the identifier of the appear shape at the given index, if any.
+    pub fn appear_tag(&self, index: usize) -> Option<Tag> {
+        self.appear_tags()?.iter().nth(index).copied()
+    }
+
+    /// Returns the appear shapes paired with their identifiers, if any.
+    pub fn tagged_appear_shapes(&self) -> Vec<(&Shape2, Option<Tag>)> {
+        self.appear_shapes()
+            .shapes()
+            .enumerate()
+            .map(|(index, shape)| (shape, self.appear_tag(index)))
+            .collect()
+    }
+
+    /// Returns the appear shapes with the given identifier.
+    pub fn appear_shapes_with_tag(&self, tag: Tag) -> Vec<&Shape2> {
+        self.tagged_appear_shapes()
+            .into_iter()
+            .filter(|&(_, shape_tag)| shape_tag == Some(tag))
+            .map(|(shape, _)| shape)
+            .collect()
+    }
+
+    /// Returns `true` if every appear shape has exactly one identifier,
+    /// or if the version does not support appear tags.
+    pub fn appear_tags_aligned(&self) -> bool {
+        self.appear_tags()
+            .is_none_or(|appear_tags| appear_tags.len() == self.appear_shapes().len())
+    }
+}
+
+impl_lvd_object!(EnemyGenerator {
+    base: [V1, V2, V3],
+    tag: [V1, V2, V3];
+
+    fn shapes2(&self) -> Vec<&Shape2> {
+        match self {
+            Self::V1 {
+                appear_shapes,
+                trigger_shapes,
+                unk1,
+                ..
+            }
+            | Self::V2 {
+                appear_shapes,
+                trigger_shapes,
+                unk1,
+                ..
+            }
+            | Self::V3 {
+                appear_shapes,
+                trigger_shapes,
+                unk1,
+                ..
+            } => appear_shapes
+                .shapes()
+                .chain(trigger_shapes.shapes())
+                .chain(unk1.shapes())
+                .collect(),
+        }
+    }
+
+    fn transform(&mut self, transform: &dyn CoordinateMap) {
+        match self {
+            Self::V1 {
+                appear_shapes,
+                trigger_shapes,
+                unk1,
+                ..
+            }
+            | Self::V2 {
+                appear_shapes,
+                trigger_shapes,
+                unk1,
+                ..
+            }
+            | Self::V3 {
+                appear_shapes,
+                trigger_shapes,
+                unk1,
+                ..
+            } => {
+                appear_shapes.transform(transform);
+                trigger_shapes.transform(transform);
+                unk1.transform(transform);
+            }
+        }
+    }
+});
+
+impl fmt::Display for EnemyGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V1 {
+                appear_shapes,
+                trigger_shapes,
+                ..
+            }
+            | Self::V2 {
+                appear_shapes,
+                trigger_shapes,
+                ..
+            }
+            | Self::V3 {
+                appear_shapes,
+                trigger_shapes,
+                ..
+            } => write_summary(
+                f,
+                "EnemyGenerator",
+                self,
+                &[
+                    count(appear_shapes.len(), "appear shape", "appear shapes"),
+                    count(trigger_shapes.len(), "trigger shape", "trigger shapes"),
+                ],
+            ),
+        }
+    }
+}
@@ -1,25 +1,35 @@
 //! The objects exclusive to Smash Run in Super Smash Bros. for Nintendo 3DS.
 
+use std::fmt;
+
 use binrw::binrw;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
 use crate::{
     id::Id,
-    objects::{base::Base, region::Region},
+    objects::{
+        base::{Base, MetaInfo},
+        region::Region,
+        upgrade_base, write_summary, LvdObject,
+    },
     shape::{Path, Rect, Shape2, Shape3},
     string::FixedString32,
     tag::Tag,
     vector::Vector2,
-    version::{Version, Versioned},
+    version::{Upgrade, Version, Versioned},
 };
 
 /// An LVD object representing a two-dimensional shape where a stat boost or item can appear when in view.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum FsItem {
     /// The first version of the `FsItem` type.
     #[br(pre_assert(version == 1))]
@@ -43,11 +53,49 @@ impl Version for FsItem {
     }
 }
 
+impl Default for FsItem {
+    fn default() -> Self {
+        Self::V1 {
+            base: Versioned::new(Base::default()),
+            shape: Versioned::new(Shape2::default()),
+            tag: Versioned::new(Tag::default()),
+        }
+    }
+}
+
+impl Upgrade for FsItem {
+    const LATEST_VERSION: u8 = 1;
+
+    fn into_latest(self) -> Self {
+        upgrade_base(self)
+    }
+}
+
+impl_lvd_object!(FsItem {
+    base: [V1],
+    tag: [V1];
+
+    fn shapes2(&self) -> Vec<&Shape2> {
+        match self {
+            Self::V1 { shape, .. } => vec![&shape.inner],
+        }
+    }
+});
+
+impl fmt::Display for FsItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V1 { shape, .. } => write_summary(f, "FsItem", self, &[shape.to_string()]),
+        }
+    }
+}
+
 // TODO: Type documentation.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum FsUnknown {
     /// The first version of the `FsUnknown` type.
     ///
@@ -93,11 +141,58 @@ impl Version for FsUnknown {
     }
 }
 
+impl Default for FsUnknown {
+    fn default() -> Self {
+        Self::V2 {
+            base: Versioned::new(Base::default()),
+            unk1: Versioned::new(Rect::default()),
+            unk2: Versioned::new(FsCamLimit::default()),
+            unk3: 0,
+        }
+    }
+}
+
+impl Upgrade for FsUnknown {
+    const LATEST_VERSION: u8 = 2;
+
+    fn into_latest(self) -> Self {
+        match self {
+            Self::V1 { base, unk1, unk2 } => Self::V2 {
+                base,
+                unk1,
+                unk2,
+                unk3: 0,
+            }
+            .into_latest(),
+            Self::V2 {
+                base,
+                unk1,
+                unk2,
+                unk3,
+            } => upgrade_base(Self::V2 {
+                base,
+                unk1,
+                unk2: unk2.into_latest(),
+                unk3,
+            }),
+        }
+    }
+}
+
+impl_lvd_object!(FsUnknown { base: [V1, V2] });
+
+impl fmt::Display for FsUnknown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_summary(f, "FsUnknown", self, &[])
+    }
+}
+
 // TODO: Type documentation.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum FsAreaCam {
     /// The first version of the `FsAreaCam` type.
     #[br(pre_assert(version == 1))]
@@ -118,11 +213,70 @@ impl Version for FsAreaCam {
     }
 }
 
+impl Default for FsAreaCam {
+    fn default() -> Self {
+        Self::V1 {
+            region: Versioned::new(Region::default()),
+            unk: 0,
+        }
+    }
+}
+
+impl Upgrade for FsAreaCam {
+    const LATEST_VERSION: u8 = 1;
+
+    fn into_latest(self) -> Self {
+        match self {
+            Self::V1 { region, unk } => Self::V1 {
+                region: region.into_latest(),
+                unk,
+            },
+        }
+    }
+}
+
+impl LvdObject for FsAreaCam {
+    fn meta_info(&self) -> &MetaInfo {
+        match self {
+            Self::V1 { region, .. } => region.meta_info(),
+        }
+    }
+
+    fn meta_info_mut(&mut self) -> &mut MetaInfo {
+        match self {
+            Self::V1 { region, .. } => region.meta_info_mut(),
+        }
+    }
+
+    fn base(&self) -> Option<&Base> {
+        match self {
+            Self::V1 { region, .. } => region.base(),
+        }
+    }
+
+    fn base_mut(&mut self) -> Option<&mut Base> {
+        match self {
+            Self::V1 { region, .. } => region.base_mut(),
+        }
+    }
+}
+
+impl fmt::Display for FsAreaCam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V1 { region, .. } => {
+                write_summary(f, "FsAreaCam", self, &[region.rect().to_string()])
+            }
+        }
+    }
+}
+
 /// An LVD object representing a region to restrict camera movement within on entrance of a trigger.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum FsAreaLock {
     /// The first version of the `FsAreaLock` type.
     ///
@@ -173,11 +327,93 @@ impl Version for FsAreaLock {
     }
 }
 
+impl Default for FsAreaLock {
+    fn default() -> Self {
+        Self::V2 {
+            base: Versioned::new(Base::default()),
+            camera_region: Versioned::new(Rect::default()),
+            trigger_region: Versioned::new(Rect::default()),
+            unk1: 0,
+            unk2: Versioned::new(Vector2::default()),
+        }
+    }
+}
+
+impl Upgrade for FsAreaLock {
+    const LATEST_VERSION: u8 = 2;
+
+    fn into_latest(self) -> Self {
+        match self {
+            Self::V1 {
+                base,
+                camera_region,
+                trigger_region,
+                unk1,
+            } => Self::V2 {
+                base,
+                camera_region,
+                trigger_region,
+                unk1,
+                unk2: Versioned::new(Vector2::default()),
+            }
+            .into_latest(),
+            Self::V2 { .. } => upgrade_base(self),
+        }
+    }
+}
+
+impl FsAreaLock {
+    /// Returns the edge coordinates of the region for restricted camera movement.
+    pub fn camera_region(&self) -> &Rect {
+        match self {
+            Self::V1 { camera_region, .. } | Self::V2 { camera_region, .. } => camera_region,
+        }
+    }
+
+    /// Returns the edge coordinates of the region for restricted camera movement mutably.
+    pub fn camera_region_mut(&mut self) -> &mut Rect {
+        match self {
+            Self::V1 { camera_region, .. } | Self::V2 { camera_region, .. } => camera_region,
+        }
+    }
+
+    /// Returns the edge coordinates of the trigger region for activating the restricted camera movement.
+    pub fn trigger_region(&self) -> &Rect {
+        match self {
+            Self::V1 { trigger_region, .. } | Self::V2 { trigger_region, .. } => trigger_region,
+        }
+    }
+
+    /// Returns the edge coordinates of the trigger region for activating the restricted camera movement mutably.
+    pub fn trigger_region_mut(&mut self) -> &mut Rect {
+        match self {
+            Self::V1 { trigger_region, .. } | Self::V2 { trigger_region, .. } => trigger_region,
+        }
+    }
+}
+
+impl_lvd_object!(FsAreaLock { base: [V1, V2] });
+
+impl fmt::Display for FsAreaLock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_summary(
+            f,
+            "FsAreaLock",
+            self,
+            &[
+                format!("camera {}", self.camera_region()),
+                format!("trigger {}", self.trigger_region()),
+            ],
+        )
+    }
+}
+
 /// An LVD object representing a region to restrict camera movement within.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum FsCamLimit {
     /// The first version of the `FsCamLimit` type.
     #[br(pre_assert(version == 1))]
@@ -198,11 +434,39 @@ impl Version for FsCamLimit {
     }
 }
 
+impl Default for FsCamLimit {
+    fn default() -> Self {
+        Self::V1 {
+            base: Versioned::new(Base::default()),
+            path: Versioned::new(Path::default()),
+        }
+    }
+}
+
+impl Upgrade for FsCamLimit {
+    const LATEST_VERSION: u8 = 1;
+
+    fn into_latest(self) -> Self {
+        upgrade_base(self)
+    }
+}
+
+impl_lvd_object!(FsCamLimit { base: [V1] });
+
+impl fmt::Display for FsCamLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V1 { path, .. } => write_summary(f, "FsCamLimit", self, &[path.to_string()]),
+        }
+    }
+}
+
 // TODO: Type documentation.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum AreaLight {
     /// The first version of the `AreaLight` type.
     ///
@@ -244,11 +508,61 @@ impl Version for AreaLight {
     }
 }
 
+impl Default for AreaLight {
+    fn default() -> Self {
+        Self::V2 {
+            base: Versioned::new(Base::default()),
+            shape: Versioned::new(Shape2::default()),
+            unk1: Versioned::new(FixedString32::new()),
+            unk2: Versioned::new(FixedString32::new()),
+        }
+    }
+}
+
+impl Upgrade for AreaLight {
+    const LATEST_VERSION: u8 = 2;
+
+    fn into_latest(self) -> Self {
+        match self {
+            Self::V1 { base, shape } => Self::V2 {
+                base,
+                shape,
+                unk1: Versioned::new(FixedString32::new()),
+                unk2: Versioned::new(FixedString32::new()),
+            }
+            .into_latest(),
+            Self::V2 { .. } => upgrade_base(self),
+        }
+    }
+}
+
+impl_lvd_object!(AreaLight {
+    base: [V1, V2];
+
+    fn shapes2(&self) -> Vec<&Shape2> {
+        match self {
+            Self::V1 { shape, .. } => vec![&shape.inner],
+            Self::V2 { shape, .. } => vec![&shape.inner],
+        }
+    }
+});
+
+impl fmt::Display for AreaLight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V1 { shape, .. } | Self::V2 { shape, .. } => {
+                write_summary(f, "AreaLight", self, &[shape.to_string()])
+            }
+        }
+    }
+}
+
 /// An LVD object representing a two-dimensional point where a fighter can start and restart from.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum FsStartPoint {
     /// The first version of the `FsStartPoint` type.
     #[br(pre_assert(version == 1))]
@@ -272,11 +586,73 @@ impl Version for FsStartPoint {
     }
 }
 
+impl Default for FsStartPoint {
+    fn default() -> Self {
+        Self::V1 {
+            base: Versioned::new(Base::default()),
+            pos: Versioned::new(Vector2::default()),
+            id: Versioned::new(Id::default()),
+        }
+    }
+}
+
+impl Upgrade for FsStartPoint {
+    const LATEST_VERSION: u8 = 1;
+
+    fn into_latest(self) -> Self {
+        upgrade_base(self)
+    }
+}
+
+impl FsStartPoint {
+    /// Returns the position of the point.
+    pub fn pos(&self) -> Vector2 {
+        match self {
+            Self::V1 { pos, .. } => **pos,
+        }
+    }
+
+    /// Returns the position of the point mutably.
+    pub fn pos_mut(&mut self) -> &mut Vector2 {
+        match self {
+            Self::V1 { pos, .. } => pos,
+        }
+    }
+
+    /// Returns the numeric identifier of the point.
+    pub fn id(&self) -> &Id {
+        match self {
+            Self::V1 { id, .. } => id,
+        }
+    }
+
+    /// Returns the numeric identifier of the point mutably.
+    pub fn id_mut(&mut self) -> &mut Id {
+        match self {
+            Self::V1 { id, .. } => id,
+        }
+    }
+}
+
+impl_lvd_object!(FsStartPoint { base: [V1] });
+
+impl fmt::Display for FsStartPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_summary(
+            f,
+            "FsStartPoint",
+            self,
+            &[format!("at {}", self.pos()), format!("id {}", self.id().0)],
+        )
+    }
+}
+
 // TODO: Type documentation.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum AreaHint {
     /// The first version of the `AreaHint` type.
     ///
@@ -374,11 +750,97 @@ impl Version for AreaHint {
     }
 }
 
+impl Default for AreaHint {
+    fn default() -> Self {
+        Self::V3 {
+            base: Versioned::new(Base::default()),
+            shape: Versioned::new(Shape3::default()),
+            unk1: 0,
+            unk2: 0,
+            unk3: 0,
+            unk4: 0,
+            unk5: 0,
+            unk6: 0,
+            unk7: 0,
+        }
+    }
+}
+
+impl Upgrade for AreaHint {
+    const LATEST_VERSION: u8 = 3;
+
+    fn into_latest(self) -> Self {
+        match self {
+            Self::V1 {
+                base,
+                shape,
+                unk1,
+                unk2,
+                unk3,
+                unk4,
+            } => Self::V2 {
+                base,
+                shape,
+                unk1,
+                unk2,
+                unk3,
+                unk4,
+                unk5: 0,
+            }
+            .into_latest(),
+            Self::V2 {
+                base,
+                shape,
+                unk1,
+                unk2,
+                unk3,
+                unk4,
+                unk5,
+            } => Self::V3 {
+                base,
+                shape,
+                unk1,
+                unk2,
+                unk3,
+                unk4,
+                unk5,
+                unk6: 0,
+                unk7: 0,
+            }
+            .into_latest(),
+            Self::V3 { .. } => upgrade_base(self),
+        }
+    }
+}
+
+impl_lvd_object!(AreaHint {
+    base: [V1, V2, V3];
+
+    fn shapes3(&self) -> Vec<&Shape3> {
+        match self {
+            Self::V1 { shape, .. } => vec![&shape.inner],
+            Self::V2 { shape, .. } => vec![&shape.inner],
+            Self::V3 { shape, .. } => vec![&shape.inner],
+        }
+    }
+});
+
+impl fmt::Display for AreaHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V1 { shape, .. } | Self::V2 { shape, .. } | Self::V3 { shape, .. } => {
+                write_summary(f, "AreaHint", self, &[shape.to_string()])
+            }
+        }
+    }
+}
+
 // TODO: Type documentation.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum SplitArea {
     /// The first version of the `SplitArea` type.
     #[br(pre_assert(version == 1))]
@@ -398,3 +860,38 @@ impl Version for SplitArea {
         }
     }
 }
+
+impl Default for SplitArea {
+    fn default() -> Self {
+        Self::V1 {
+            base: Versioned::new(Base::default()),
+            shape: Versioned::new(Shape3::default()),
+        }
+    }
+}
+
+impl Upgrade for SplitArea {
+    const LATEST_VERSION: u8 = 1;
+
+    fn into_latest(self) -> Self {
+        upgrade_base(self)
+    }
+}
+
+impl_lvd_object!(SplitArea {
+    base: [V1];
+
+    fn shapes3(&self) -> Vec<&Shape3> {
+        match self {
+            Self::V1 { shape, .. } => vec![&shape.inner],
+        }
+    }
+});
+
+impl fmt::Display for SplitArea {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V1 { shape, .. } => write_summary(f, "SplitArea", self, &[shape.to_string()]),
+        }
+    }
+}
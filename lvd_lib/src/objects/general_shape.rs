@@ -1,22 +1,29 @@
 //! The [`GeneralShape2`] and [`GeneralShape3`] objects store data representing general-purpose shapes.
 
+use std::fmt;
+
 use binrw::binrw;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
 use crate::{
-    objects::base::Base,
+    objects::{base::Base, upgrade_base, write_summary},
     shape::{Shape2, Shape3},
     tag::Tag,
-    version::{Version, Versioned},
+    transform::CoordinateMap,
+    version::{Upgrade, Version, Versioned},
 };
 
 /// An LVD object representing a general-purpose two-dimensional shape.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum GeneralShape2 {
     /// The first version of the `GeneralShape2` type.
     #[br(pre_assert(version == 1))]
@@ -40,11 +47,55 @@ impl Version for GeneralShape2 {
     }
 }
 
+impl Default for GeneralShape2 {
+    fn default() -> Self {
+        Self::V1 {
+            base: Versioned::new(Base::default()),
+            tag: Versioned::new(Tag::default()),
+            shape: Versioned::new(Shape2::default()),
+        }
+    }
+}
+
+impl Upgrade for GeneralShape2 {
+    const LATEST_VERSION: u8 = 1;
+
+    fn into_latest(self) -> Self {
+        upgrade_base(self)
+    }
+}
+
+impl_lvd_object!(GeneralShape2 {
+    base: [V1],
+    tag: [V1];
+
+    fn shapes2(&self) -> Vec<&Shape2> {
+        match self {
+            Self::V1 { shape, .. } => vec![&shape.inner],
+        }
+    }
+
+    fn transform(&mut self, transform: &dyn CoordinateMap) {
+        match self {
+            Self::V1 { shape, .. } => shape.transform(transform),
+        }
+    }
+});
+
+impl fmt::Display for GeneralShape2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V1 { shape, .. } => write_summary(f, "GeneralShape2", self, &[shape.to_string()]),
+        }
+    }
+}
+
 /// An LVD object representing a general-purpose three-dimensional shape.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum GeneralShape3 {
     /// The first version of the `GeneralShape3` type.
     #[br(pre_assert(version == 1))]
@@ -67,3 +118,46 @@ impl Version for GeneralShape3 {
         }
     }
 }
+
+impl Default for GeneralShape3 {
+    fn default() -> Self {
+        Self::V1 {
+            base: Versioned::new(Base::default()),
+            tag: Versioned::new(Tag::default()),
+            shape: Versioned::new(Shape3::default()),
+        }
+    }
+}
+
+impl Upgrade for GeneralShape3 {
+    const LATEST_VERSION: u8 = 1;
+
+    fn into_latest(self) -> Self {
+        upgrade_base(self)
+    }
+}
+
+impl_lvd_object!(GeneralShape3 {
+    base: [V1],
+    tag: [V1];
+
+    fn shapes3(&self) -> Vec<&Shape3> {
+        match self {
+            Self::V1 { shape, .. } => vec![&shape.inner],
+        }
+    }
+
+    fn transform(&mut self, transform: &dyn CoordinateMap) {
+        match self {
+            Self::V1 { shape, .. } => shape.transform(transform),
+        }
+    }
+});
+
+impl fmt::Display for GeneralShape3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V1 { shape, .. } => write_summary(f, "GeneralShape3", self, &[shape.to_string()]),
+        }
+    }
+}
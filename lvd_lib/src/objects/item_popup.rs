@@ -1,22 +1,29 @@
 //! The [`ItemPopup`] object stores data representing a collection of shapes where items will appear from.
 
+use std::fmt;
+
 use binrw::binrw;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
 use crate::{
-    objects::base::Base,
-    shape::ShapeArray2,
+    objects::{base::Base, count, upgrade_base, write_summary},
+    shape::{Shape2, ShapeArray2},
     tag::Tag,
-    version::{Version, Versioned},
+    transform::CoordinateMap,
+    version::{Upgrade, Version, Versioned},
 };
 
 /// An LVD object representing a collection of shapes where items will appear from.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum ItemPopup {
     /// The first version of the `ItemPopup` type.
     #[br(pre_assert(version == 1))]
@@ -39,3 +46,51 @@ impl Version for ItemPopup {
         }
     }
 }
+
+impl Default for ItemPopup {
+    fn default() -> Self {
+        Self::V1 {
+            base: Versioned::new(Base::default()),
+            tag: Versioned::new(Tag::default()),
+            shapes: Versioned::new(ShapeArray2::default()),
+        }
+    }
+}
+
+impl Upgrade for ItemPopup {
+    const LATEST_VERSION: u8 = 1;
+
+    fn into_latest(self) -> Self {
+        upgrade_base(self)
+    }
+}
+
+impl_lvd_object!(ItemPopup {
+    base: [V1],
+    tag: [V1];
+
+    fn shapes2(&self) -> Vec<&Shape2> {
+        match self {
+            Self::V1 { shapes, .. } => shapes.shapes().collect(),
+        }
+    }
+
+    fn transform(&mut self, transform: &dyn CoordinateMap) {
+        match self {
+            Self::V1 { shapes, .. } => shapes.transform(transform),
+        }
+    }
+});
+
+impl fmt::Display for ItemPopup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V1 { shapes, .. } => write_summary(
+                f,
+                "ItemPopup",
+                self,
+                &[count(shapes.len(), "shape", "shapes")],
+            ),
+        }
+    }
+}
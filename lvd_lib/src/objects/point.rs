@@ -1,21 +1,31 @@
 //! The [`Point`] object stores data representing a two-dimensional point.
 
+use std::fmt;
+
 use binrw::binrw;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
 use crate::{
-    objects::base::{Base, MetaInfo},
+    objects::{
+        base::{Base, MetaInfo},
+        upgrade_base, write_summary,
+    },
+    transform::CoordinateMap,
     vector::Vector2,
-    version::{Version, Versioned},
+    version::{Upgrade, Version, Versioned},
 };
 
 /// An LVD object representing a two-dimensional point.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum Point {
     /// The first version of the `Point` type.
     ///
@@ -50,3 +60,59 @@ impl Version for Point {
         }
     }
 }
+
+impl Default for Point {
+    fn default() -> Self {
+        Self::V2 {
+            base: Versioned::new(Base::default()),
+            pos: Versioned::new(Vector2::default()),
+        }
+    }
+}
+
+impl Upgrade for Point {
+    const LATEST_VERSION: u8 = 2;
+
+    fn into_latest(self) -> Self {
+        match self {
+            Self::V1 { meta_info, pos } => Self::V2 {
+                base: Base::from_meta_info(meta_info),
+                pos,
+            },
+            Self::V2 { .. } => upgrade_base(self),
+        }
+    }
+}
+
+impl Point {
+    /// Returns the position of the point.
+    pub fn pos(&self) -> Vector2 {
+        match self {
+            Self::V1 { pos, .. } | Self::V2 { pos, .. } => **pos,
+        }
+    }
+
+    /// Returns the position of the point mutably.
+    pub fn pos_mut(&mut self) -> &mut Vector2 {
+        match self {
+            Self::V1 { pos, .. } | Self::V2 { pos, .. } => pos,
+        }
+    }
+}
+
+impl_lvd_object!(Point {
+    meta_info: [V1],
+    base: [V2];
+
+    fn transform(&mut self, transform: &dyn CoordinateMap) {
+        match self {
+            Self::V1 { pos, .. } | Self::V2 { pos, .. } => **pos = transform.map_point2(**pos),
+        }
+    }
+});
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_summary(f, "Point", self, &[format!("at {}", self.pos())])
+    }
+}
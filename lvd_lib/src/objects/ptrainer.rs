@@ -1,23 +1,30 @@
 //! The [`PTrainerRange`] and [`PTrainerFloatingFloor`] objects store data representing locations or objects where one or more Pokémon Trainers can reside.
 
+use std::fmt;
+
 use binrw::binrw;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
 use crate::{
     array::Array,
-    objects::base::Base,
+    objects::{base::Base, count, upgrade_base, write_summary, Collision, LvdObject},
     string::FixedString64,
-    vector::Vector3,
-    version::{Version, Versioned},
+    transform::CoordinateMap,
+    vector::{Vector2, Vector3},
+    version::{Upgrade, Version, Versioned},
 };
 
 /// An LVD object representing the range in which one or more Pokémon Trainers can move around within.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum PTrainerRange {
     /// The first version of the `PTrainerRange` type.
     ///
@@ -72,11 +79,255 @@ impl Version for PTrainerRange {
     }
 }
 
+impl Default for PTrainerRange {
+    fn default() -> Self {
+        Self::V4 {
+            base: Versioned::new(Base::default()),
+            range_min: Versioned::new(Vector3::default()),
+            range_max: Versioned::new(Vector3::default()),
+            trainers: Versioned::new(Array::default()),
+            parent_model_name: Versioned::new(FixedString64::new()),
+            parent_joint_name: Versioned::new(FixedString64::new()),
+        }
+    }
+}
+
+impl Upgrade for PTrainerRange {
+    const LATEST_VERSION: u8 = 4;
+
+    fn into_latest(self) -> Self {
+        match self {
+            Self::V1 {
+                base,
+                range_min,
+                range_max,
+                trainers,
+            } => Self::V4 {
+                base,
+                range_min,
+                range_max,
+                trainers,
+                parent_model_name: Versioned::new(FixedString64::new()),
+                parent_joint_name: Versioned::new(FixedString64::new()),
+            }
+            .into_latest(),
+            Self::V4 { .. } => upgrade_base(self),
+        }
+    }
+}
+
+impl PTrainerRange {
+    /// The number of trainer positions placed by [`PTrainerRange::from_collision`].
+    const DEFAULT_TRAINER_COUNT: usize = 3;
+
+    /// Creates a range spanning the floor edges of the collision at the depth `z`,
+    /// or returns `None` if the collision has no floor edges.
+    ///
+    /// The trainer positions are spaced evenly across the range, each on the highest floor
+    /// edge beneath it. The parent model and joint names are taken from the dynamic name and
+    /// joint name of the collision, so the range follows the collision when it moves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{
+    ///     objects::{Collision, PTrainerRange},
+    ///     vector::{Vector2, Vector3},
+    /// };
+    ///
+    /// let platform = Collision::new_soft_platform(Vector2::new(-60.0, 0.0), Vector2::new(60.0, 0.0));
+    /// let range = PTrainerRange::from_collision(&platform, -20.0).unwrap();
+    ///
+    /// assert_eq!(range.range_min(), Vector3::new(-60.0, 0.0, -20.0));
+    /// assert_eq!(range.trainers().len(), 3);
+    /// ```
+    pub fn from_collision(collision: &Collision, z: f32) -> Option<Self> {
+        let vertices = collision.vertices().elements();
+        let floor_edges = (0..collision.edge_count())
+            .filter(|&index| collision.is_floor_edge(index))
+            .map(|index| (*vertices[index], *vertices[index + 1]))
+            .collect::<Vec<_>>();
+        let points = floor_edges.iter().flat_map(|&(start, end)| [start, end]);
+        let min = |f: fn(&Vector2) -> f32| points.clone().map(|point| f(&point)).reduce(f32::min);
+        let max = |f: fn(&Vector2) -> f32| points.clone().map(|point| f(&point)).reduce(f32::max);
+        let (min_x, min_y, max_x, max_y) = (
+            min(Vector2::x)?,
+            min(Vector2::y)?,
+            max(Vector2::x)?,
+            max(Vector2::y)?,
+        );
+        let height_at = |x: f32| {
+            floor_edges
+                .iter()
+                .filter(|(start, end)| start.x().min(end.x()) <= x && x <= start.x().max(end.x()))
+                .map(|(start, end)| {
+                    let t = (x - start.x()) / (end.x() - start.x());
+
+                    if t.is_finite() {
+                        start.y() + (end.y() - start.y()) * t
+                    } else {
+                        start.y().max(end.y())
+                    }
+                })
+                .reduce(f32::max)
+                .unwrap_or(max_y)
+        };
+        let trainers = (0..Self::DEFAULT_TRAINER_COUNT)
+            .map(|index| {
+                let fraction = (index as f32 + 0.5) / Self::DEFAULT_TRAINER_COUNT as f32;
+                let x = min_x + (max_x - min_x) * fraction;
+
+                Vector3::new(x, height_at(x), z)
+            })
+            .collect();
+        let base = collision.base();
+
+        Some(Self::V4 {
+            base: Versioned::new(Base::default()),
+            range_min: Versioned::new(Vector3::new(min_x, min_y, z)),
+            range_max: Versioned::new(Vector3::new(max_x, max_y, z)),
+            trainers: Versioned::new(Array::new(trainers)),
+            parent_model_name: Versioned::new(
+                base.map(|base| base.dynamic_name().clone())
+                    .unwrap_or_default(),
+            ),
+            parent_joint_name: Versioned::new(
+                base.and_then(Base::joint_name).cloned().unwrap_or_default(),
+            ),
+        })
+    }
+
+    /// Returns the minimum position a Pokémon Trainer can move to.
+    pub fn range_min(&self) -> Vector3 {
+        match self {
+            Self::V1 { range_min, .. } | Self::V4 { range_min, .. } => **range_min,
+        }
+    }
+
+    /// Returns the maximum position a Pokémon Trainer can move to.
+    pub fn range_max(&self) -> Vector3 {
+        match self {
+            Self::V1 { range_max, .. } | Self::V4 { range_max, .. } => **range_max,
+        }
+    }
+
+    /// Returns the collection of starting positions for each Pokémon Trainer in the range.
+    pub fn trainers(&self) -> &Array<Vector3> {
+        match self {
+            Self::V1 { trainers, .. } | Self::V4 { trainers, .. } => trainers,
+        }
+    }
+
+    /// Returns the collection of starting positions for each Pokémon Trainer in the range mutably.
+    pub fn trainers_mut(&mut self) -> &mut Array<Vector3> {
+        match self {
+            Self::V1 { trainers, .. } | Self::V4 { trainers, .. } => trainers,
+        }
+    }
+
+    /// Returns the name of the model for the range to inherit select transformations from, if supported by the version.
+    pub fn parent_model_name(&self) -> Option<&FixedString64> {
+        match self {
+            Self::V1 { .. } => None,
+            Self::V4 {
+                parent_model_name, ..
+            } => Some(parent_model_name),
+        }
+    }
+
+    /// Returns the name of the model for the range to inherit select transformations from mutably, if supported by the version.
+    pub fn parent_model_name_mut(&mut self) -> Option<&mut FixedString64> {
+        match self {
+            Self::V1 { .. } => None,
+            Self::V4 {
+                parent_model_name, ..
+            } => Some(parent_model_name),
+        }
+    }
+
+    /// Returns the name of the joint for the range to inherit select transformations from, if supported by the version.
+    pub fn parent_joint_name(&self) -> Option<&FixedString64> {
+        match self {
+            Self::V1 { .. } => None,
+            Self::V4 {
+                parent_joint_name, ..
+            } => Some(parent_joint_name),
+        }
+    }
+
+    /// Returns the name of the joint for the range to inherit select transformations from mutably, if supported by the version.
+    pub fn parent_joint_name_mut(&mut self) -> Option<&mut FixedString64> {
+        match self {
+            Self::V1 { .. } => None,
+            Self::V4 {
+                parent_joint_name, ..
+            } => Some(parent_joint_name),
+        }
+    }
+}
+
+impl_lvd_object!(PTrainerRange {
+    base: [V1, V4];
+
+    fn transform(&mut self, transform: &dyn CoordinateMap) {
+        match self {
+            Self::V1 {
+                range_min,
+                range_max,
+                trainers,
+                ..
+            }
+            | Self::V4 {
+                range_min,
+                range_max,
+                trainers,
+                ..
+            } => {
+                let mut corners = Vec::with_capacity(8);
+
+                for x in [range_min.x(), range_max.x()] {
+                    for y in [range_min.y(), range_max.y()] {
+                        for z in [range_min.z(), range_max.z()] {
+                            corners.push(transform.map_point3(Vector3::new(x, y, z)));
+                        }
+                    }
+                }
+
+                let min = |f: fn(&Vector3) -> f32| corners.iter().map(f).fold(f32::INFINITY, f32::min);
+                let max =
+                    |f: fn(&Vector3) -> f32| corners.iter().map(f).fold(f32::NEG_INFINITY, f32::max);
+
+                **range_min = Vector3::new(min(Vector3::x), min(Vector3::y), min(Vector3::z));
+                **range_max = Vector3::new(max(Vector3::x), max(Vector3::y), max(Vector3::z));
+
+                for trainer in trainers.iter_mut() {
+                    *trainer = transform.map_point3(*trainer);
+                }
+            }
+        }
+    }
+});
+
+impl fmt::Display for PTrainerRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_summary(
+            f,
+            "PTrainerRange",
+            self,
+            &[
+                format!("from {} to {}", self.range_min(), self.range_max()),
+                count(self.trainers().len(), "trainer", "trainers"),
+            ],
+        )
+    }
+}
+
 /// An LVD object representing a Pokémon Trainer's floating platform.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum PTrainerFloatingFloor {
     /// The first version of the `PTrainerFloatingFloor` type.
     #[br(pre_assert(version == 1))]
@@ -96,3 +347,57 @@ impl Version for PTrainerFloatingFloor {
         }
     }
 }
+
+impl Default for PTrainerFloatingFloor {
+    fn default() -> Self {
+        Self::V1 {
+            base: Versioned::new(Base::default()),
+            pos: Versioned::new(Vector3::default()),
+        }
+    }
+}
+
+impl Upgrade for PTrainerFloatingFloor {
+    const LATEST_VERSION: u8 = 1;
+
+    fn into_latest(self) -> Self {
+        upgrade_base(self)
+    }
+}
+
+impl PTrainerFloatingFloor {
+    /// Returns the position of the floating platform.
+    pub fn pos(&self) -> Vector3 {
+        match self {
+            Self::V1 { pos, .. } => **pos,
+        }
+    }
+
+    /// Returns the position of the floating platform mutably.
+    pub fn pos_mut(&mut self) -> &mut Vector3 {
+        match self {
+            Self::V1 { pos, .. } => pos,
+        }
+    }
+}
+
+impl_lvd_object!(PTrainerFloatingFloor {
+    base: [V1];
+
+    fn transform(&mut self, transform: &dyn CoordinateMap) {
+        match self {
+            Self::V1 { pos, .. } => **pos = transform.map_point3(**pos),
+        }
+    }
+});
+
+impl fmt::Display for PTrainerFloatingFloor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_summary(
+            f,
+            "PTrainerFloatingFloor",
+            self,
+            &[format!("at {}", self.pos())],
+        )
+    }
+}
@@ -1,21 +1,31 @@
 //! The [`Region`] object stores data representing a two-dimensional rectangle.
 
+use std::fmt;
+
 use binrw::binrw;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
 use crate::{
-    objects::base::{Base, MetaInfo},
+    objects::{
+        base::{Base, MetaInfo},
+        upgrade_base, write_summary,
+    },
     shape::Rect,
-    version::{Version, Versioned},
+    transform::CoordinateMap,
+    version::{Upgrade, Version, Versioned},
 };
 
 /// An LVD object representing a two-dimensional rectangle.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum Region {
     /// The first version of the `Region` type.
     ///
@@ -50,3 +60,57 @@ impl Version for Region {
         }
     }
 }
+
+impl Default for Region {
+    fn default() -> Self {
+        Self::V2 {
+            base: Versioned::new(Base::default()),
+            rect: Versioned::new(Rect::default()),
+        }
+    }
+}
+
+impl Upgrade for Region {
+    const LATEST_VERSION: u8 = 2;
+
+    fn into_latest(self) -> Self {
+        match self {
+            Self::V1 { meta_info, rect } => Self::V2 {
+                base: Base::from_meta_info(meta_info),
+                rect,
+            },
+            Self::V2 { .. } => upgrade_base(self),
+        }
+    }
+}
+
+impl Region {
+    /// Returns the edge coordinates of the region.
+    pub fn rect(&self) -> Rect {
+        match self {
+            Self::V1 { rect, .. } | Self::V2 { rect, .. } => **rect,
+        }
+    }
+
+    /// Returns the edge coordinates of the region mutably.
+    pub fn rect_mut(&mut self) -> &mut Rect {
+        match self {
+            Self::V1 { rect, .. } | Self::V2 { rect, .. } => rect,
+        }
+    }
+}
+
+impl_lvd_object!(Region {
+    meta_info: [V1],
+    base: [V2];
+
+    fn transform(&mut self, transform: &dyn CoordinateMap) {
+        self.rect_mut().transform(transform);
+    }
+});
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_summary(f, "Region", self, &[self.rect().to_string()])
+    }
+}
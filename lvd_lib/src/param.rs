@@ -0,0 +1,298 @@
+//! Cross-checking of stage bounds against stage param files.
+//!
+//! The camera and blast zone bounds of a stage are stored both in its LVD file and in its
+//! accompanying stage param (`stprm`) file. This module contains the [`StageBounds`] type holding
+//! the bounds extracted from a param file, and the [`LvdFile::check_bounds`] and
+//! [`LvdFile::sync_bounds`] methods for finding and fixing mismatches between the two.
+//!
+//! Param files are not parsed by this library. The values are expected to be extracted
+//! with an existing param editor and supplied by the caller.
+
+use std::fmt;
+
+use crate::{array::Array, objects::Region, shape::Rect, LvdFile, ObjectRef, SectionKind};
+
+/// The bounds of a stage as stored in its stage param file.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct StageBounds {
+    /// The bounds of the camera, compared against the camera regions.
+    pub camera: Option<Rect>,
+
+    /// The bounds of the blast zone, compared against the death regions.
+    pub blast: Option<Rect>,
+}
+
+/// An edge of a rectangle.
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum Edge {
+    /// The left edge.
+    Left,
+
+    /// The right edge.
+    Right,
+
+    /// The top edge.
+    Top,
+
+    /// The bottom edge.
+    Bottom,
+}
+
+impl Edge {
+    /// Every edge of a rectangle.
+    pub const ALL: [Self; 4] = [Self::Left, Self::Right, Self::Top, Self::Bottom];
+
+    /// Returns the coordinate of the edge in the given rectangle.
+    pub fn get(self, rect: &Rect) -> f32 {
+        let Rect::V1 {
+            left,
+            right,
+            top,
+            bottom,
+        } = rect;
+
+        match self {
+            Self::Left => *left,
+            Self::Right => *right,
+            Self::Top => *top,
+            Self::Bottom => *bottom,
+        }
+    }
+
+    /// Returns the coordinate of the edge in the given rectangle mutably.
+    pub fn get_mut(self, rect: &mut Rect) -> &mut f32 {
+        let Rect::V1 {
+            left,
+            right,
+            top,
+            bottom,
+        } = rect;
+
+        match self {
+            Self::Left => left,
+            Self::Right => right,
+            Self::Top => top,
+            Self::Bottom => bottom,
+        }
+    }
+}
+
+impl fmt::Display for Edge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Left => write!(f, "left"),
+            Self::Right => write!(f, "right"),
+            Self::Top => write!(f, "top"),
+            Self::Bottom => write!(f, "bottom"),
+        }
+    }
+}
+
+/// A difference between an edge of a region and the corresponding stage param value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundsMismatch {
+    /// The region containing the edge.
+    pub region: ObjectRef,
+
+    /// The mismatched edge.
+    pub edge: Edge,
+
+    /// The coordinate of the edge in the LVD file.
+    pub lvd: f32,
+
+    /// The coordinate of the edge in the stage param file.
+    pub param: f32,
+}
+
+impl fmt::Display for BoundsMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "object {} in section `{}`: {} edge is {} but the stage param is {}",
+            self.region.index,
+            self.region.section.name(),
+            self.edge,
+            self.lvd,
+            self.param
+        )
+    }
+}
+
+impl LvdFile {
+    /// Returns the edges of every camera and death region which differ from the given
+    /// stage bounds by more than `tolerance`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{param::StageBounds, shape::Rect, LvdFile};
+    ///
+    /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    /// let bounds = StageBounds {
+    ///     camera: None,
+    ///     blast: Some(Rect::V1 {
+    ///         left: -240.0,
+    ///         right: 240.0,
+    ///         top: 192.0,
+    ///         bottom: -140.0,
+    ///     }),
+    /// };
+    ///
+    /// for mismatch in file.check_bounds(&bounds, 0.01) {
+    ///     println!("{mismatch}");
+    /// }
+    /// ```
+    pub fn check_bounds(&self, bounds: &StageBounds, tolerance: f32) -> Vec<BoundsMismatch> {
+        let mut mismatches = Vec::new();
+
+        for (section, regions, expected) in self.bounded_regions(bounds) {
+            for (index, region) in regions.iter().enumerate() {
+                let rect = region.rect();
+
+                for edge in Edge::ALL {
+                    let lvd = edge.get(&rect);
+                    let param = edge.get(&expected);
+
+                    if (lvd - param).abs() > tolerance {
+                        mismatches.push(BoundsMismatch {
+                            region: ObjectRef::new(section, index),
+                            edge,
+                            lvd,
+                            param,
+                        });
+                    }
+                }
+            }
+        }
+
+        mismatches
+    }
+
+    /// Sets the edges of every camera and death region to the given stage bounds.
+    /// The number of changed edges is returned.
+    pub fn sync_bounds(&mut self, bounds: &StageBounds) -> usize {
+        sync_regions(self.data.camera_regions_mut(), bounds.camera)
+            + sync_regions(self.data.death_regions_mut(), bounds.blast)
+    }
+
+    /// Returns the sections of regions with stage bounds to compare against.
+    fn bounded_regions(&self, bounds: &StageBounds) -> Vec<(SectionKind, &Array<Region>, Rect)> {
+        [
+            (
+                SectionKind::CameraRegions,
+                self.data.camera_regions(),
+                bounds.camera,
+            ),
+            (
+                SectionKind::DeathRegions,
+                self.data.death_regions(),
+                bounds.blast,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(section, regions, expected)| Some((section, regions?, expected?)))
+        .collect()
+    }
+}
+
+/// Sets the edges of every region to the given rectangle, returning the number of changed edges.
+fn sync_regions(regions: Option<&mut Array<Region>>, expected: Option<Rect>) -> usize {
+    let (Some(regions), Some(expected)) = (regions, expected) else {
+        return 0;
+    };
+    let mut count = 0;
+
+    for region in regions.iter_mut() {
+        for edge in Edge::ALL {
+            let value = edge.get_mut(region.rect_mut());
+
+            if *value != edge.get(&expected) {
+                *value = edge.get(&expected);
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::platform_file;
+
+    use super::*;
+
+    const BLAST: Rect = Rect::new(-240.0, 240.0, 192.0, -140.0);
+
+    fn file_with_regions(camera: Rect, death: Rect) -> LvdFile {
+        let mut file = platform_file();
+        let mut camera_region = Region::default();
+        let mut death_region = Region::default();
+
+        *camera_region.rect_mut() = camera;
+        *death_region.rect_mut() = death;
+        file.data.camera_regions_mut().unwrap().push(camera_region);
+        file.data.death_regions_mut().unwrap().push(death_region);
+
+        file
+    }
+
+    #[test]
+    fn check_bounds_within_tolerance() {
+        let file = file_with_regions(
+            Rect::new(-170.0, 170.0, 100.0, -50.0),
+            Rect::new(-240.005, 240.0, 192.5, -140.0),
+        );
+        let bounds = StageBounds {
+            camera: None,
+            blast: Some(BLAST),
+        };
+
+        assert_eq!(
+            file.check_bounds(&bounds, 0.01),
+            [BoundsMismatch {
+                region: ObjectRef::new(SectionKind::DeathRegions, 0),
+                edge: Edge::Top,
+                lvd: 192.5,
+                param: 192.0,
+            }],
+            "the camera region is not checked without camera bounds"
+        );
+        assert_eq!(file.check_bounds(&bounds, 0.0).len(), 2);
+        assert!(file.check_bounds(&bounds, 1.0).is_empty());
+        assert!(file.check_bounds(&StageBounds::default(), 0.0).is_empty());
+    }
+
+    #[test]
+    fn sync_bounds_counts_changed_edges() {
+        let camera = Rect::new(-170.0, 170.0, 100.0, -50.0);
+        let mut file = file_with_regions(camera, Rect::new(-200.0, 240.0, 192.0, -100.0));
+        let bounds = StageBounds {
+            camera: Some(camera),
+            blast: Some(BLAST),
+        };
+
+        assert_eq!(file.sync_bounds(&bounds), 2);
+        assert_eq!(
+            file.data.death_regions().unwrap().elements()[0].rect(),
+            BLAST
+        );
+        assert!(file.check_bounds(&bounds, 0.0).is_empty());
+        assert_eq!(file.sync_bounds(&bounds), 0);
+    }
+
+    #[test]
+    fn mismatch_display() {
+        let mismatch = BoundsMismatch {
+            region: ObjectRef::new(SectionKind::CameraRegions, 1),
+            edge: Edge::Left,
+            lvd: -170.0,
+            param: -180.5,
+        };
+
+        assert_eq!(
+            mismatch.to_string(),
+            "object 1 in section `camera_regions`: left edge is -170 but the stage param is -180.5"
+        );
+    }
+}
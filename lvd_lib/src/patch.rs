@@ -0,0 +1,269 @@
+//! Partial modification of LVD files.
+//!
+//! This module contains the [`Patch`] type and an error type that may result when applying a patch.
+//!
+//! A patch addresses objects by section and name, and only contains the fields to change.
+//! Fields nested within versioned types are addressed without their version,
+//! so a patch is written as follows:
+//!
+//! ```yaml
+//! collisions:
+//!   COL_00_Floor01:
+//!     flags:
+//!       throughable: true
+//! camera_regions:
+//!   CAMERA_00:
+//!     rect:
+//!       left: -180.0
+//!       right: 180.0
+//! ```
+//!
+//! Sequences are patched element by element. Elements beyond the original length are
+//! created from a copy of the last original element, and elements beyond the patch's
+//! length are removed.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use thiserror::Error;
+
+use crate::{LvdFile, SectionKind};
+
+/// A partial modification of the objects in an LVD file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Patch {
+    /// The changes to apply, keyed by section and object name.
+    pub sections: BTreeMap<SectionKind, BTreeMap<String, Value>>,
+}
+
+impl LvdFile {
+    /// Applies the given patch to the file.
+    ///
+    /// The file is left unchanged if an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{patch::Patch, LvdFile};
+    ///
+    /// let mut file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    /// let patch: Patch = serde_yaml::from_str("death_regions: {DEATH_00: {rect: {top: 260.0}}}").unwrap();
+    ///
+    /// file.apply_patch(&patch).unwrap();
+    /// ```
+    pub fn apply_patch(&mut self, patch: &Patch) -> Result<(), PatchError> {
+        let mut value = serde_yaml::to_value(&self.data)?;
+
+        for (&section, objects) in &patch.sections {
+            for (name, changes) in objects {
+                let index = (0..self
+                    .data
+                    .section_len(section)
+                    .ok_or(PatchError::SectionNotFound(section))?)
+                    .find(|&index| {
+                        self.data
+                            .object(section, index)
+                            .is_some_and(|object| object.name() == name)
+                    })
+                    .ok_or_else(|| PatchError::ObjectNotFound {
+                        section,
+                        name: name.clone(),
+                    })?;
+                let object = variant_mut(&mut value)
+                    .get_mut(section.name())
+                    .map(variant_mut)
+                    .and_then(|array| array.get_mut("elements"))
+                    .and_then(|elements| elements.get_mut(index))
+                    .ok_or(PatchError::SectionNotFound(section))?;
+
+                merge(object, changes, name)?;
+            }
+        }
+
+        self.data = serde_yaml::from_value(value)?;
+
+        Ok(())
+    }
+}
+
+/// Returns the value wrapped by a versioned type's variant, or the value itself if it is not wrapped.
+fn variant_mut(value: &mut Value) -> &mut Value {
+    if !is_variant(value) {
+        return value;
+    }
+
+    match value {
+        Value::Tagged(tagged) => &mut tagged.value,
+        _ => unreachable!(),
+    }
+}
+
+/// Returns `true` if the value is a variant of a versioned type, such as `!V1 {...}`.
+fn is_variant(value: &Value) -> bool {
+    matches!(value, Value::Tagged(tagged) if is_version_tag(&tagged.tag.to_string()))
+}
+
+/// Returns `true` if the tag names a variant of a versioned type, such as `!V1`.
+fn is_version_tag(tag: &str) -> bool {
+    tag.trim_start_matches('!')
+        .strip_prefix('V')
+        .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Recursively merges the changes into the target value.
+fn merge(target: &mut Value, changes: &Value, path: &str) -> Result<(), PatchError> {
+    match changes {
+        Value::Mapping(changes) if !changes.keys().all(|key| target.get(key).is_some()) => {
+            if is_variant(target) {
+                return merge(variant_mut(target), &Value::Mapping(changes.clone()), path);
+            }
+
+            let key = changes
+                .keys()
+                .find(|key| target.get(*key).is_none())
+                .unwrap();
+
+            Err(PatchError::UnknownField(format!(
+                "{path}.{}",
+                key_name(key)
+            )))
+        }
+        Value::Mapping(changes) => {
+            for (key, change) in changes {
+                let path = format!("{path}.{}", key_name(key));
+
+                merge(target.get_mut(key).unwrap(), change, &path)?;
+            }
+
+            Ok(())
+        }
+        Value::Sequence(changes) => {
+            let elements = match target {
+                Value::Sequence(elements) => elements,
+                _ => match variant_mut(target).get_mut("elements") {
+                    Some(Value::Sequence(elements)) => elements,
+                    _ => {
+                        *target = Value::Sequence(changes.clone());
+                        return Ok(());
+                    }
+                },
+            };
+
+            elements.truncate(changes.len());
+
+            for (index, change) in changes.iter().enumerate() {
+                if index == elements.len() {
+                    let template = elements.last().cloned().unwrap_or(Value::Null);
+
+                    elements.push(template);
+                }
+
+                let element = &mut elements[index];
+
+                if element.is_null() {
+                    *element = change.clone();
+                } else {
+                    merge(element, change, &format!("{path}[{index}]"))?;
+                }
+            }
+
+            Ok(())
+        }
+        _ => {
+            *target = changes.clone();
+
+            Ok(())
+        }
+    }
+}
+
+/// Returns the name of a field of a patch.
+fn key_name(key: &Value) -> String {
+    match key {
+        Value::String(key) => key.clone(),
+        key => serde_yaml::to_string(key)
+            .unwrap_or_default()
+            .trim_end()
+            .to_string(),
+    }
+}
+
+/// The error type used when applying a [`Patch`].
+#[derive(Debug, Error)]
+pub enum PatchError {
+    /// The section is not supported by the file's version.
+    #[error("section `{}` is not supported by the file's version", .0.name())]
+    SectionNotFound(SectionKind),
+
+    /// No object with the given name exists in the section.
+    #[error("no object named `{name}` exists in section `{}`", .section.name())]
+    ObjectNotFound {
+        /// The section searched for the object.
+        section: SectionKind,
+
+        /// The name of the object.
+        name: String,
+    },
+
+    /// The patch contains a field which does not exist on the object.
+    #[error("field `{0}` does not exist")]
+    UnknownField(String),
+
+    /// The patched data could not be converted.
+    #[error(transparent)]
+    Serde(#[from] serde_yaml::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yaml(text: &str) -> Value {
+        serde_yaml::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn merge_through_variants() {
+        let mut target =
+            yaml("!V2 {base: !V4 {is_dynamic: false}, rect: !V1 {left: 0.0, top: 1.0}}");
+
+        merge(&mut target, &yaml("{rect: {left: -5.0}}"), "test").unwrap();
+
+        assert_eq!(
+            target,
+            yaml("!V2 {base: !V4 {is_dynamic: false}, rect: !V1 {left: -5.0, top: 1.0}}")
+        );
+    }
+
+    #[test]
+    fn merge_sequences() {
+        let mut target = yaml("!V1 {elements: [!V1 {x: 0.0, y: 0.0}]}");
+
+        merge(&mut target, &yaml("[{x: 1.0}, {x: 2.0, y: 3.0}]"), "test").unwrap();
+
+        assert_eq!(
+            target,
+            yaml("!V1 {elements: [!V1 {x: 1.0, y: 0.0}, !V1 {x: 2.0, y: 3.0}]}")
+        );
+    }
+
+    #[test]
+    fn merge_unknown_field() {
+        let mut target = yaml("!V1 {x: 0.0}");
+        let result = merge(&mut target, &yaml("{w: 1.0}"), "test");
+
+        assert!(matches!(result, Err(PatchError::UnknownField(field)) if field == "test.w"));
+    }
+
+    #[test]
+    fn merge_non_finite_values() {
+        let mut target = yaml("!V1 {x: 0.0, y: 0.0}");
+
+        merge(&mut target, &yaml("{x: .nan, y: -.inf}"), "test").unwrap();
+
+        assert!(target["x"].as_f64().is_some_and(f64::is_nan));
+        assert_eq!(target["y"].as_f64(), Some(f64::NEG_INFINITY));
+    }
+}
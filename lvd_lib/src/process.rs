@@ -0,0 +1,252 @@
+//! Custom passes over the sections of an LVD file.
+//!
+//! This module contains the [`SectionProcessor`] trait, which other crates implement to check or
+//! modify the sections of a file, such as a pack of tournament legality rules, and the
+//! [`LvdFile::process_with`] method for running a list of processors over a file.
+
+use crate::{
+    validation::{Issue, IssueKind, Severity},
+    Lvd, LvdFile, ObjectRef, SectionKind,
+};
+
+/// A trait for a custom pass over each section of an LVD file, run with [`LvdFile::process_with`].
+///
+/// Every method except [`name`](SectionProcessor::name) has a default implementation which does
+/// nothing, so an implementation only needs to override the methods it uses. Methods added to
+/// the trait in later versions will also have default implementations.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lvd_lib::{
+///     process::SectionProcessor,
+///     validation::{Issue, Severity},
+///     Lvd, LvdFile, ObjectRef, SectionKind,
+/// };
+///
+/// struct CollisionPrefix;
+///
+/// impl SectionProcessor for CollisionPrefix {
+///     fn name(&self) -> &str {
+///         "collision-prefix"
+///     }
+///
+///     fn applies_to(&self, section: SectionKind) -> bool {
+///         section == SectionKind::Collisions
+///     }
+///
+///     fn validate(&self, data: &Lvd, section: SectionKind) -> Vec<Issue> {
+///         (0..data.section_len(section).unwrap_or_default())
+///             .filter(|&index| {
+///                 let name = data.object(section, index).unwrap().name();
+///
+///                 !name.to_str().unwrap_or_default().starts_with("COL_")
+///             })
+///             .map(|index| {
+///                 Issue::custom(
+///                     ObjectRef::new(section, index),
+///                     self.name(),
+///                     Severity::Warning,
+///                     "name does not start with `COL_`",
+///                 )
+///             })
+///             .collect()
+///     }
+/// }
+///
+/// let mut file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+///
+/// for issue in file.process_with(&[&CollisionPrefix]) {
+///     println!("{issue}");
+/// }
+/// ```
+pub trait SectionProcessor {
+    /// Returns the name of the processor, which is included in the issues it reports.
+    fn name(&self) -> &str;
+
+    /// Returns `true` if the processor should be run on the section.
+    ///
+    /// Every section is processed by default.
+    fn applies_to(&self, section: SectionKind) -> bool {
+        let _ = section;
+
+        true
+    }
+
+    /// Modifies the objects of the section.
+    ///
+    /// Called on each section the processor applies to before the section is validated.
+    fn transform(&self, data: &mut Lvd, section: SectionKind) {
+        let _ = (data, section);
+    }
+
+    /// Returns the problems found in the objects of the section.
+    fn validate(&self, data: &Lvd, section: SectionKind) -> Vec<Issue> {
+        let _ = (data, section);
+
+        Vec::new()
+    }
+}
+
+impl Issue {
+    /// Creates a new `Issue` reported by a [`SectionProcessor`] with the given name.
+    pub fn custom(
+        object: ObjectRef,
+        processor: &str,
+        severity: Severity,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            object,
+            kind: IssueKind::Custom {
+                processor: processor.to_string(),
+                severity,
+                message: message.into(),
+            },
+        }
+    }
+}
+
+impl LvdFile {
+    /// Runs each processor over every section of the file it applies to, in the order given,
+    /// returning the problems reported by the processors.
+    ///
+    /// Each processor transforms and then validates every section before the next processor
+    /// is run, so later processors see the changes made by earlier ones. Sections not
+    /// supported by the file's version are skipped.
+    pub fn process_with(&mut self, processors: &[&dyn SectionProcessor]) -> Vec<Issue> {
+        let sections = self
+            .data
+            .sections()
+            .map(|(kind, _)| kind)
+            .collect::<Vec<_>>();
+        let mut issues = Vec::new();
+
+        for processor in processors {
+            for &section in &sections {
+                if !processor.applies_to(section) {
+                    continue;
+                }
+
+                processor.transform(&mut self.data, section);
+                issues.extend(processor.validate(&self.data, section));
+            }
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        fixtures::platform_file,
+        objects::{LvdObject, Point},
+    };
+
+    /// Renames every object without a name and reports objects named `BAD`.
+    struct Names;
+
+    impl SectionProcessor for Names {
+        fn name(&self) -> &str {
+            "names"
+        }
+
+        fn transform(&self, data: &mut Lvd, section: SectionKind) {
+            for index in 0..data.section_len(section).unwrap_or_default() {
+                let object = data.object_mut(section, index).unwrap();
+
+                if object.name().is_empty() {
+                    *object.meta_info_mut().name_mut() = "BAD".try_into().unwrap();
+                }
+            }
+        }
+
+        fn validate(&self, data: &Lvd, section: SectionKind) -> Vec<Issue> {
+            (0..data.section_len(section).unwrap_or_default())
+                .filter(|&index| *data.object(section, index).unwrap().name() == "BAD")
+                .map(|index| {
+                    Issue::custom(
+                        ObjectRef::new(section, index),
+                        self.name(),
+                        Severity::Warning,
+                        "object is named BAD",
+                    )
+                })
+                .collect()
+        }
+    }
+
+    /// Clears the names of collisions, leaving every other section unchanged.
+    struct ClearCollisionNames;
+
+    impl SectionProcessor for ClearCollisionNames {
+        fn name(&self) -> &str {
+            "clear"
+        }
+
+        fn applies_to(&self, section: SectionKind) -> bool {
+            section == SectionKind::Collisions
+        }
+
+        fn transform(&self, data: &mut Lvd, section: SectionKind) {
+            assert_eq!(section, SectionKind::Collisions);
+
+            for collision in data.collisions_mut().unwrap().iter_mut() {
+                *collision.meta_info_mut().name_mut() = Default::default();
+            }
+        }
+    }
+
+    #[test]
+    fn process_with_transforms_then_validates() {
+        let mut file = platform_file();
+        let issues = file.process_with(&[&Names]);
+
+        assert_eq!(
+            issues,
+            [Issue::custom(
+                ObjectRef::new(SectionKind::Collisions, 0),
+                "names",
+                Severity::Warning,
+                "object is named BAD"
+            )]
+        );
+        assert_eq!(
+            issues[0].to_string(),
+            "object 0 in section `collisions`: names: object is named BAD"
+        );
+    }
+
+    #[test]
+    fn processors_run_in_order() {
+        let mut file = platform_file();
+
+        file.data
+            .start_positions_mut()
+            .unwrap()
+            .push(Point::default());
+
+        let issues = file.process_with(&[&Names, &ClearCollisionNames]);
+        let objects = issues.iter().map(|issue| issue.object).collect::<Vec<_>>();
+
+        assert_eq!(
+            objects,
+            [
+                ObjectRef::new(SectionKind::Collisions, 0),
+                ObjectRef::new(SectionKind::StartPositions, 0),
+            ]
+        );
+        assert!(file.data.collisions().unwrap().elements()[0]
+            .name()
+            .is_empty());
+        assert_eq!(
+            *file.data.start_positions().unwrap().elements()[0].name(),
+            "BAD",
+            "processors only change the sections they apply to"
+        );
+        assert!(file.process_with(&[]).is_empty());
+    }
+}
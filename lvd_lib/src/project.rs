@@ -0,0 +1,434 @@
+//! Stage projects made of several LVD files.
+//!
+//! This module contains the [`StageProject`] type, a directory holding a `project.yaml` manifest
+//! and the LVD files it lists, such as the `normal` and `battle` forms of a stage, along with
+//! methods for loading and saving every file at once and for applying transformations and
+//! renames to all of them together.
+
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    finite::{CheckedWriteError, NonFinitePolicy},
+    rename::RenameError,
+    transform::CoordinateMap,
+    LvdFile,
+};
+
+/// The file name of the manifest of a [`StageProject`].
+pub const MANIFEST_FILE_NAME: &str = "project.yaml";
+
+/// The serialized form of a `project.yaml` manifest.
+///
+/// ```yaml
+/// stage: battlefield
+/// files:
+///   - name: normal
+///     path: battlefield_00.lvd
+///   - name: battle
+///     path: battlefield_01.lvd
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectManifest {
+    /// The internal name of the stage, such as `battlefield`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stage: Option<String>,
+
+    /// The LVD files of the project.
+    pub files: Vec<ProjectManifestEntry>,
+}
+
+/// An LVD file listed in a [`ProjectManifest`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectManifestEntry {
+    /// The name of the file within the project, such as `normal` or `battle`.
+    pub name: String,
+
+    /// The path of the file, relative to the project directory.
+    pub path: PathBuf,
+}
+
+/// An LVD file of a [`StageProject`].
+#[derive(Debug, Clone)]
+pub struct ProjectFile {
+    /// The name of the file within the project, such as `normal` or `battle`.
+    pub name: String,
+
+    /// The path of the file, relative to the project directory.
+    pub path: PathBuf,
+
+    /// The contents of the file.
+    pub lvd: LvdFile,
+}
+
+/// A directory of coordinated LVD files described by a `project.yaml` manifest.
+#[derive(Debug, Clone)]
+pub struct StageProject {
+    /// The project directory.
+    pub dir: PathBuf,
+
+    /// The internal name of the stage, such as `battlefield`.
+    pub stage: Option<String>,
+
+    /// The LVD files of the project, in manifest order.
+    pub files: Vec<ProjectFile>,
+}
+
+impl StageProject {
+    /// Creates a new `StageProject` in the given directory with no files.
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            stage: None,
+            files: Vec::new(),
+        }
+    }
+
+    /// Reads the manifest in the given directory and every LVD file it lists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be read or parsed, if two files share a name,
+    /// or if any file cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{finite::NonFinitePolicy, project::StageProject};
+    ///
+    /// let mut project = StageProject::load("my_stage").unwrap();
+    ///
+    /// project.rename_prefix("COL_00_", "COL_01_").unwrap();
+    /// project.save(NonFinitePolicy::Reject).unwrap();
+    /// ```
+    pub fn load<P: AsRef<Path>>(dir: P) -> Result<Self, ProjectError> {
+        let dir = dir.as_ref();
+        let manifest_path = dir.join(MANIFEST_FILE_NAME);
+        let yaml = fs::read_to_string(&manifest_path).map_err(|source| ProjectError::Io {
+            path: manifest_path,
+            source,
+        })?;
+        let manifest = serde_yaml::from_str::<ProjectManifest>(&yaml)?;
+
+        check_names(manifest.files.iter().map(|entry| entry.name.as_str()))?;
+
+        let files = manifest
+            .files
+            .into_iter()
+            .map(|entry| {
+                let lvd = LvdFile::from_file(dir.join(&entry.path)).map_err(|source| {
+                    ProjectError::Read {
+                        name: entry.name.clone(),
+                        source,
+                    }
+                })?;
+
+                Ok(ProjectFile {
+                    name: entry.name,
+                    path: entry.path,
+                    lvd,
+                })
+            })
+            .collect::<Result<_, ProjectError>>()?;
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            stage: manifest.stage,
+            files,
+        })
+    }
+
+    /// Writes the manifest and every LVD file of the project, creating any missing
+    /// directories.
+    ///
+    /// Every file is encoded with [`LvdFile::write_checked`] and the given policy before any
+    /// file is written, so nothing is written if any file is rejected. Each file is then
+    /// written beside its destination and moved into place once all of them have been written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if two files share a name, if any file is rejected by
+    /// [`LvdFile::write_checked`], or if any file cannot be written.
+    pub fn save(&self, policy: NonFinitePolicy) -> Result<(), ProjectError> {
+        check_names(self.files.iter().map(|file| file.name.as_str()))?;
+
+        let mut outputs = self
+            .files
+            .iter()
+            .map(|file| {
+                let mut cursor = io::Cursor::new(Vec::new());
+
+                file.lvd
+                    .write_checked(&mut cursor, policy)
+                    .map_err(|source| ProjectError::Write {
+                        name: file.name.clone(),
+                        source,
+                    })?;
+
+                Ok((self.dir.join(&file.path), cursor.into_inner()))
+            })
+            .collect::<Result<Vec<_>, ProjectError>>()?;
+
+        outputs.push((
+            self.dir.join(MANIFEST_FILE_NAME),
+            serde_yaml::to_string(&self.manifest())?.into_bytes(),
+        ));
+
+        let mut staged = Vec::with_capacity(outputs.len());
+
+        for (path, bytes) in outputs {
+            let mut staged_path = path.clone().into_os_string();
+
+            staged_path.push(".tmp");
+
+            let staged_path = PathBuf::from(staged_path);
+            let result = path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| fs::write(&staged_path, bytes));
+
+            if let Err(source) = result {
+                for (staged_path, _) in staged {
+                    let _ = fs::remove_file(staged_path);
+                }
+
+                return Err(ProjectError::Io { path, source });
+            }
+
+            staged.push((staged_path, path));
+        }
+
+        for (staged_path, path) in staged {
+            fs::rename(&staged_path, &path).map_err(|source| ProjectError::Io { path, source })?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the manifest describing the project's files.
+    pub fn manifest(&self) -> ProjectManifest {
+        ProjectManifest {
+            stage: self.stage.clone(),
+            files: self
+                .files
+                .iter()
+                .map(|file| ProjectManifestEntry {
+                    name: file.name.clone(),
+                    path: file.path.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns a reference to the LVD file with the given name, or `None` if there is none.
+    pub fn file(&self, name: &str) -> Option<&LvdFile> {
+        self.files
+            .iter()
+            .find(|file| file.name == name)
+            .map(|file| &file.lvd)
+    }
+
+    /// Returns a mutable reference to the LVD file with the given name, or `None` if there is
+    /// none.
+    pub fn file_mut(&mut self, name: &str) -> Option<&mut LvdFile> {
+        self.files
+            .iter_mut()
+            .find(|file| file.name == name)
+            .map(|file| &mut file.lvd)
+    }
+
+    /// Applies the coordinate mapping to every object of every file.
+    ///
+    /// As with [`LvdObject::transform`](crate::objects::LvdObject::transform), objects exclusive
+    /// to Smash Run are left unchanged.
+    pub fn transform(&mut self, map: &dyn CoordinateMap) {
+        for file in &mut self.files {
+            file.lvd
+                .data
+                .for_each_object_mut(|object| object.transform(map));
+        }
+    }
+
+    /// Replaces the prefix `old` with `new` in every name referenced by every file, as
+    /// described by [`LvdFile::rename_prefix`], returning the total number of renamed strings.
+    ///
+    /// Every file is renamed before any change is kept, so the project is left unchanged if an
+    /// error is returned for any file.
+    pub fn rename_prefix(&mut self, old: &str, new: &str) -> Result<usize, RenameError> {
+        let mut renamed = self.files.clone();
+        let mut count = 0;
+
+        for file in &mut renamed {
+            count += file.lvd.rename_prefix(old, new)?;
+        }
+
+        self.files = renamed;
+
+        Ok(count)
+    }
+}
+
+/// The error type used when loading or saving a [`StageProject`].
+#[derive(Debug, Error)]
+pub enum ProjectError {
+    /// A file or directory could not be read or written.
+    #[error("{}: {source}", path.display())]
+    Io {
+        /// The path of the file or directory.
+        path: PathBuf,
+
+        /// The underlying error.
+        source: io::Error,
+    },
+
+    /// The manifest could not be parsed or serialized.
+    #[error("invalid manifest: {0}")]
+    Manifest(#[from] serde_yaml::Error),
+
+    /// More than one file has the same name.
+    #[error("more than one file is named `{0}`")]
+    DuplicateName(String),
+
+    /// An LVD file could not be read.
+    #[error("file `{name}`: {source}")]
+    Read {
+        /// The name of the file within the project.
+        name: String,
+
+        /// The underlying error.
+        source: binrw::Error,
+    },
+
+    /// An LVD file was rejected when encoding it.
+    #[error("file `{name}`: {source}")]
+    Write {
+        /// The name of the file within the project.
+        name: String,
+
+        /// The underlying error.
+        source: CheckedWriteError,
+    },
+}
+
+/// Returns an error naming the first name which appears more than once.
+fn check_names<'a>(names: impl Iterator<Item = &'a str>) -> Result<(), ProjectError> {
+    let mut seen = HashSet::new();
+
+    for name in names {
+        if !seen.insert(name) {
+            return Err(ProjectError::DuplicateName(name.to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        fixtures::platform_file,
+        objects::LvdObject,
+        transform::Transform,
+        vector::{Vector2, Vector3},
+    };
+
+    fn lvd_file(name: &str) -> LvdFile {
+        let mut file = platform_file();
+
+        *file.data.collisions_mut().unwrap().elements_mut()[0]
+            .meta_info_mut()
+            .name_mut() = name.try_into().unwrap();
+
+        file
+    }
+
+    #[test]
+    fn save_and_load_project() {
+        let dir = std::env::temp_dir().join(format!("lvd_lib_project_{}", std::process::id()));
+        let mut project = StageProject::new(&dir);
+
+        project.stage = Some("battlefield".to_string());
+        project.files = ["normal", "battle"]
+            .into_iter()
+            .enumerate()
+            .map(|(index, name)| ProjectFile {
+                name: name.to_string(),
+                path: PathBuf::from(format!("battlefield_{index:02}.lvd")),
+                lvd: lvd_file("COL_00_Floor"),
+            })
+            .collect();
+
+        project.transform(&Transform::from_translation(Vector3::new(5.0, 0.0, 0.0)));
+        assert_eq!(project.rename_prefix("COL_00_", "COL_01_"), Ok(2));
+        assert!(project
+            .rename_prefix(
+                "COL_",
+                "A_VERY_LONG_PREFIX_WHICH_DOES_NOT_FIT_IN_A_COLLISION_NAME_"
+            )
+            .is_err());
+
+        project.save(NonFinitePolicy::Reject).unwrap();
+
+        let loaded = StageProject::load(&dir).unwrap();
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(loaded.manifest(), project.manifest());
+
+        let collision = loaded.file("battle").unwrap().data.collisions().unwrap();
+        let collision = collision.iter().next().unwrap();
+
+        assert_eq!(collision.name().to_str(), Ok("COL_01_Floor"));
+        assert_eq!(
+            collision.vertices().iter().next(),
+            Some(&Vector2::new(-5.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn save_writes_nothing_on_error() {
+        let dir =
+            std::env::temp_dir().join(format!("lvd_lib_project_error_{}", std::process::id()));
+        let mut project = StageProject::new(&dir);
+        let file = |name: &str, lvd| ProjectFile {
+            name: name.to_string(),
+            path: PathBuf::from(format!("{name}.lvd")),
+            lvd,
+        };
+        let mut non_finite = platform_file();
+
+        *non_finite.data.collisions_mut().unwrap().elements_mut()[0]
+            .edge_mut(0)
+            .unwrap()
+            .start = Vector2::new(f32::NAN, 0.0);
+        project.files = vec![
+            file("normal", platform_file()),
+            file("normal", platform_file()),
+        ];
+
+        assert!(matches!(
+            project.save(NonFinitePolicy::Reject),
+            Err(ProjectError::DuplicateName(name)) if name == "normal"
+        ));
+
+        project.files[1] = file("battle", non_finite);
+
+        assert!(matches!(
+            project.save(NonFinitePolicy::Reject),
+            Err(ProjectError::Write { name, .. }) if name == "battle"
+        ));
+        assert!(!dir.exists());
+        assert!(matches!(
+            StageProject::load(&dir),
+            Err(ProjectError::Io { .. })
+        ));
+    }
+}
@@ -0,0 +1,281 @@
+//! Rounding of coordinates to a grid and removal of float noise.
+//!
+//! Values edited in other tools often pick up rounding errors, such as `12.000001` in place of
+//! `12.0`, which clutter diffs of the YAML form and leave hairline seams between collisions
+//! meant to meet. This module contains the [`LvdFile::quantize`] and
+//! [`LvdFile::strip_float_noise`] methods, and the [`Quantize`] and [`StripNoise`]
+//! coordinate mappings for applying the same rounding to individual objects.
+
+use crate::{
+    transform::CoordinateMap,
+    vector::{Vector2, Vector3},
+    LvdFile,
+};
+
+/// A coordinate mapping which rounds positions, directions, and lengths to the nearest
+/// multiple of a grid size. Normals are left unchanged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantize {
+    /// The distance between adjacent grid lines.
+    pub grid: f32,
+}
+
+impl Quantize {
+    /// Returns the value rounded to the nearest multiple of the grid size, without float noise.
+    ///
+    /// The value is returned unchanged if the grid size is not positive and finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::quantize::Quantize;
+    ///
+    /// assert_eq!(Quantize { grid: 0.5 }.round(12.3), 12.5);
+    /// assert_eq!(Quantize { grid: 0.1 }.round(0.26), 0.3);
+    /// ```
+    pub fn round(&self, value: f32) -> f32 {
+        if !(self.grid.is_finite() && self.grid > 0.0) {
+            return value;
+        }
+
+        strip_noise((value / self.grid).round() * self.grid)
+    }
+}
+
+impl CoordinateMap for Quantize {
+    fn map_point2(&self, point: Vector2) -> Vector2 {
+        Vector2::new(self.round(point.x()), self.round(point.y()))
+    }
+
+    fn map_point3(&self, point: Vector3) -> Vector3 {
+        Vector3::new(
+            self.round(point.x()),
+            self.round(point.y()),
+            self.round(point.z()),
+        )
+    }
+
+    fn map_vector3(&self, vector: Vector3) -> Vector3 {
+        self.map_point3(vector)
+    }
+
+    fn map_normal2(&self, normal: Vector2) -> Vector2 {
+        normal
+    }
+
+    fn map_length2(&self, length: f32) -> f32 {
+        self.round(length)
+    }
+
+    fn map_length3(&self, length: f32) -> f32 {
+        self.round(length)
+    }
+}
+
+/// A coordinate mapping which removes float noise from every value with [`strip_noise`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct StripNoise;
+
+impl CoordinateMap for StripNoise {
+    fn map_point2(&self, point: Vector2) -> Vector2 {
+        Vector2::new(strip_noise(point.x()), strip_noise(point.y()))
+    }
+
+    fn map_point3(&self, point: Vector3) -> Vector3 {
+        Vector3::new(
+            strip_noise(point.x()),
+            strip_noise(point.y()),
+            strip_noise(point.z()),
+        )
+    }
+
+    fn map_vector3(&self, vector: Vector3) -> Vector3 {
+        self.map_point3(vector)
+    }
+
+    fn map_normal2(&self, normal: Vector2) -> Vector2 {
+        self.map_point2(normal)
+    }
+
+    fn map_length2(&self, length: f32) -> f32 {
+        strip_noise(length)
+    }
+
+    fn map_length3(&self, length: f32) -> f32 {
+        strip_noise(length)
+    }
+}
+
+/// The most decimal places a value is rounded to when removing float noise.
+const MAX_DECIMAL_PLACES: i32 = 4;
+
+/// The distance from a value, in units of the float precision at the value's magnitude,
+/// within which a rounded value is considered noise.
+const NOISE_ULPS: f32 = 2.0;
+
+/// Returns the value with the fewest decimal places, up to four, within two units of float
+/// precision of the given value, or the value unchanged if there is none.
+///
+/// Values less than one are compared at the precision of one, so noise near zero is removed.
+///
+/// # Examples
+///
+/// ```
+/// use lvd_lib::quantize::strip_noise;
+///
+/// assert_eq!(strip_noise(12.000001), 12.0);
+/// assert_eq!(strip_noise(0.30000004), 0.3);
+/// assert_eq!(strip_noise(0.0000001), 0.0);
+/// assert_eq!(strip_noise(1.2345678), 1.2345678);
+/// ```
+pub fn strip_noise(value: f32) -> f32 {
+    if !value.is_finite() {
+        return value;
+    }
+
+    let tolerance = f64::from(value.abs().max(1.0) * NOISE_ULPS * f32::EPSILON);
+
+    (0..=MAX_DECIMAL_PLACES)
+        .map(|places| {
+            let scale = 10f64.powi(places);
+
+            (f64::from(value) * scale).round() / scale
+        })
+        .find(|rounded| (rounded - f64::from(value)).abs() <= tolerance)
+        .map_or(value, |rounded| rounded as f32)
+}
+
+impl LvdFile {
+    /// Rounds the positions and sizes of every object to the nearest multiple of `grid`.
+    ///
+    /// Normals are left unchanged, as are objects exclusive to Smash Run.
+    /// The file is left unchanged if `grid` is not positive and finite.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::LvdFile;
+    ///
+    /// let mut file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    ///
+    /// file.quantize(0.5);
+    /// ```
+    pub fn quantize(&mut self, grid: f32) {
+        let quantize = Quantize { grid };
+
+        self.data
+            .for_each_object_mut(|object| object.transform(&quantize));
+    }
+
+    /// Removes float noise from the positions, normals, and sizes of every object,
+    /// such as rounding `12.000001` to `12.0`, using [`strip_noise`].
+    ///
+    /// Objects exclusive to Smash Run are left unchanged.
+    pub fn strip_float_noise(&mut self) {
+        self.data
+            .for_each_object_mut(|object| object.transform(&StripNoise));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures::file_with_collisions, objects::Collision};
+
+    use super::*;
+
+    fn vertices(file: &LvdFile) -> Vec<Vector2> {
+        file.data.collisions().unwrap().elements()[0]
+            .vertices()
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn round_halfway_values_away_from_zero() {
+        let quantize = Quantize { grid: 0.5 };
+
+        assert_eq!(quantize.round(0.25), 0.5);
+        assert_eq!(quantize.round(-0.25), -0.5);
+        assert_eq!(quantize.round(0.2499), 0.0);
+        assert_eq!(quantize.round(-0.74), -0.5);
+        assert_eq!(Quantize { grid: 0.1 }.round(0.15), 0.2);
+        assert_eq!(Quantize { grid: 16.0 }.round(24.0), 32.0);
+    }
+
+    #[test]
+    fn round_with_invalid_grid_or_value() {
+        for grid in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            assert_eq!(Quantize { grid }.round(12.3), 12.3, "grid {grid}");
+        }
+
+        let quantize = Quantize { grid: 0.5 };
+
+        assert!(quantize.round(f32::NAN).is_nan());
+        assert_eq!(quantize.round(f32::INFINITY), f32::INFINITY);
+    }
+
+    #[test]
+    fn strip_noise_boundaries() {
+        assert_eq!(strip_noise(-12.000001), -12.0);
+        assert_eq!(strip_noise(1.0000001), 1.0);
+        assert_eq!(
+            strip_noise(1.0000005),
+            1.0000005,
+            "beyond two units of precision"
+        );
+        assert_eq!(strip_noise(1.00001), 1.00001, "beyond four decimal places");
+        assert_eq!(strip_noise(-0.0000001), 0.0);
+        assert_eq!(strip_noise(f32::NEG_INFINITY), f32::NEG_INFINITY);
+        assert!(strip_noise(f32::NAN).is_nan());
+    }
+
+    #[test]
+    fn quantize_file_leaves_normals() {
+        let mut file = file_with_collisions([Collision::new_soft_platform(
+            Vector2::new(-10.2, 0.1),
+            Vector2::new(10.3, 4.9),
+        )]);
+        let normals = file.data.collisions().unwrap().elements()[0]
+            .normals()
+            .iter()
+            .copied()
+            .collect::<Vec<_>>();
+
+        file.quantize(0.5);
+
+        assert_eq!(
+            vertices(&file),
+            [Vector2::new(-10.0, 0.0), Vector2::new(10.5, 5.0)]
+        );
+        assert_eq!(
+            file.data.collisions().unwrap().elements()[0]
+                .normals()
+                .iter()
+                .copied()
+                .collect::<Vec<_>>(),
+            normals
+        );
+
+        let original = file.to_bytes().unwrap();
+
+        file.quantize(0.0);
+
+        assert_eq!(file.to_bytes().unwrap(), original);
+    }
+
+    #[test]
+    fn strip_float_noise_from_file() {
+        let mut file = file_with_collisions([Collision::new_soft_platform(
+            Vector2::new(-12.000001, 0.30000004),
+            Vector2::new(1.2345678, 0.0),
+        )]);
+
+        file.strip_float_noise();
+
+        assert_eq!(
+            vertices(&file),
+            [Vector2::new(-12.0, 0.3), Vector2::new(1.2345678, 0.0)]
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Data not understood by this library, preserved as raw bytes.
+//!
+//! This module contains the [`UnknownSection`] type.
+
+use std::fmt::Write;
+
+use binrw::{binrw, helpers::until_eof};
+
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "schemars")]
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{InstanceType, Schema, SchemaObject, StringValidation},
+    JsonSchema,
+};
+
+/// The raw bytes following the known sections of an LVD file.
+///
+/// Files from later game versions may append sections not known to this library.
+/// Their data is kept so the file can be edited and written back without losing it.
+/// When serialized, the bytes are represented by a hexadecimal string.
+#[binrw]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UnknownSection {
+    /// The unparsed bytes.
+    #[br(parse_with = until_eof)]
+    pub bytes: Vec<u8>,
+}
+
+impl UnknownSection {
+    /// Returns `true` if there are no unparsed bytes.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns the hexadecimal representation of the bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::raw::UnknownSection;
+    ///
+    /// let section = UnknownSection { bytes: vec![0x01, 0xAB] };
+    /// assert_eq!(section.to_hex(), "01ab");
+    /// ```
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.bytes)
+    }
+
+    /// Creates a new `UnknownSection` from a hexadecimal string,
+    /// or returns `None` if the string is not valid hexadecimal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::raw::UnknownSection;
+    ///
+    /// let section = UnknownSection::from_hex("01ab").unwrap();
+    /// assert_eq!(section.bytes, [0x01, 0xAB]);
+    /// ```
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        from_hex(hex).map(|bytes| Self { bytes })
+    }
+}
+
+/// Returns the hexadecimal representation of the bytes.
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        write!(hex, "{byte:02x}").unwrap();
+        hex
+    })
+}
+
+/// Returns the bytes represented by a hexadecimal string,
+/// or `None` if the string is not valid hexadecimal.
+pub(crate) fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    Some(
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect(),
+    )
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for UnknownSection {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for UnknownSection {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+
+        Self::from_hex(&hex).ok_or_else(|| de::Error::custom("invalid hexadecimal string"))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for UnknownSection {
+    fn schema_name() -> String {
+        "UnknownSection".to_string()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            string: Some(Box::new(StringValidation {
+                pattern: Some("^([0-9A-Fa-f]{2})*$".to_string()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{
+        version::{Version, Versioned},
+        Lvd, LvdFile,
+    };
+
+    #[test]
+    fn trailing_bytes_round_trip() {
+        let mut bytes = vec![0, 0, 0, 1, 1, 1, b'L', b'V', b'D', b'1'];
+        bytes.extend([1, 0, 0, 0, 0].repeat(6));
+        bytes.extend([0xDE, 0xAD, 0xBE, 0xEF]);
+
+        let file = LvdFile::read(&mut Cursor::new(&bytes)).unwrap();
+
+        assert_eq!(file.data.unknown_sections().bytes, [0xDE, 0xAD, 0xBE, 0xEF]);
+
+        let mut writer = Cursor::new(Vec::new());
+        file.write(&mut writer).unwrap();
+
+        assert_eq!(writer.into_inner(), bytes);
+    }
+
+    #[test]
+    fn newer_version_reads_as_latest() {
+        let latest = LvdFile {
+            data: Versioned::new(Lvd::empty(13).unwrap()),
+        }
+        .to_bytes()
+        .unwrap();
+        let mut bytes = latest.clone();
+        bytes[4] = 14;
+        bytes.extend([1, 0, 0, 0, 0]);
+
+        assert!(
+            LvdFile::from_bytes(&bytes).is_err(),
+            "newer versions are only read as the latest version on request"
+        );
+
+        let file = LvdFile::from_bytes_as_latest(&bytes).unwrap();
+
+        assert_eq!(file.data.version(), 13);
+        assert_eq!(file.data.unknown_sections().bytes, [1, 0, 0, 0, 0]);
+        assert_eq!(file.to_bytes().unwrap()[..latest.len()], latest);
+    }
+}
@@ -0,0 +1,268 @@
+//! Renaming of the names stored by LVD objects.
+//!
+//! This module contains the [`LvdFile::rename_prefix`] method and an error type that may result when renaming.
+
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::{
+    objects::{base::Base, LvdObject},
+    string::FixedString,
+    Lvd, LvdFile,
+};
+
+impl LvdFile {
+    /// Replaces the prefix `old` with `new` in every name referenced by the file.
+    ///
+    /// This includes object names, dynamic names, joint names, and the parent model
+    /// and joint names of Pokémon Trainer ranges. Empty names are never modified.
+    ///
+    /// Every affected name is checked against its capacity before any name is modified,
+    /// so the file is left unchanged if an error is returned.
+    /// On success, the number of renamed strings is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::LvdFile;
+    ///
+    /// let mut file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    /// let count = file.rename_prefix("COL_00_", "COL_01_").unwrap();
+    /// ```
+    pub fn rename_prefix(&mut self, old: &str, new: &str) -> Result<usize, RenameError> {
+        let mut renamer = PrefixRenamer {
+            old,
+            new,
+            apply: false,
+            count: 0,
+        };
+
+        renamer.rename_lvd(&mut self.data)?;
+
+        renamer.apply = true;
+        renamer.count = 0;
+        renamer.rename_lvd(&mut self.data)?;
+
+        Ok(renamer.count)
+    }
+}
+
+/// The error type used when renaming the names stored by LVD objects.
+#[derive(Debug, PartialEq, Error)]
+pub enum RenameError {
+    /// The renamed string exceeds the capacity of its buffer.
+    #[error("renamed string `{name}` exceeds buffer capacity of {capacity} bytes")]
+    BufferOverflow {
+        /// The renamed string.
+        name: String,
+
+        /// The capacity of the buffer, excluding the nul byte.
+        capacity: usize,
+    },
+}
+
+struct PrefixRenamer<'a> {
+    old: &'a str,
+    new: &'a str,
+    apply: bool,
+    count: usize,
+}
+
+impl PrefixRenamer<'_> {
+    fn rename<const N: usize>(&mut self, string: &mut FixedString<N>) -> Result<(), RenameError> {
+        if string.is_empty() {
+            return Ok(());
+        }
+
+        let Some(suffix) = string.to_str().ok().and_then(|s| s.strip_prefix(self.old)) else {
+            return Ok(());
+        };
+        let name = format!("{}{}", self.new, suffix);
+        let value = FixedString::from_str(&name).map_err(|_| RenameError::BufferOverflow {
+            name,
+            capacity: FixedString::<N>::CAPACITY,
+        })?;
+
+        self.count += 1;
+
+        if self.apply {
+            *string = value;
+        }
+
+        Ok(())
+    }
+
+    fn rename_base(&mut self, base: &mut Base) -> Result<(), RenameError> {
+        self.rename(base.meta_info_mut().name_mut())?;
+        self.rename(base.dynamic_name_mut())?;
+
+        if let Some(joint_name) = base.joint_name_mut() {
+            self.rename(joint_name)?;
+        }
+
+        Ok(())
+    }
+
+    fn rename_object(&mut self, object: &mut dyn LvdObject) -> Result<(), RenameError> {
+        match object.base_mut() {
+            Some(base) => self.rename_base(base),
+            None => self.rename(object.meta_info_mut().name_mut()),
+        }
+    }
+
+    fn rename_lvd(&mut self, lvd: &mut Lvd) -> Result<(), RenameError> {
+        let mut result = Ok(());
+
+        lvd.for_each_object_mut(|object| {
+            if result.is_ok() {
+                result = self.rename_object(object);
+            }
+        });
+        result?;
+
+        if let Some(collisions) = lvd.collisions_mut() {
+            for collision in collisions.iter_mut() {
+                for cliff in collision.cliffs_mut().iter_mut() {
+                    if let Some(base) = cliff.base_mut() {
+                        self.rename_base(base)?;
+                    }
+                }
+
+                if let Some(spirits_floors) = collision.spirits_floors_mut() {
+                    for spirits_floor in spirits_floors.iter_mut() {
+                        self.rename_object(spirits_floor)?;
+                    }
+                }
+            }
+        }
+
+        if let Some(ptrainer_ranges) = lvd.ptrainer_ranges_mut() {
+            for ptrainer_range in ptrainer_ranges.iter_mut() {
+                if let Some(name) = ptrainer_range.parent_model_name_mut() {
+                    self.rename(name)?;
+                }
+
+                if let Some(name) = ptrainer_range.parent_joint_name_mut() {
+                    self.rename(name)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixtures::file_with_collisions,
+        objects::{Collision, PTrainerRange},
+        vector::Vector2,
+    };
+
+    use super::*;
+
+    fn file(model_name: &str) -> LvdFile {
+        let mut floor =
+            Collision::new_soft_platform(Vector2::new(-10.0, 0.0), Vector2::new(10.0, 0.0));
+        let base = floor.base_mut().unwrap();
+
+        *base.meta_info_mut().name_mut() = "COL_00_Floor".try_into().unwrap();
+        *base.dynamic_name_mut() = "COL_00_Dynamic".try_into().unwrap();
+        base.bind_to_joint("COL_00_Joint").unwrap();
+
+        let mut wall =
+            Collision::new_soft_platform(Vector2::new(10.0, 0.0), Vector2::new(10.0, -20.0));
+
+        *wall.meta_info_mut().name_mut() = "COL_10_Wall".try_into().unwrap();
+
+        let mut file = file_with_collisions([floor, wall]);
+        let mut range = PTrainerRange::default();
+
+        *range.parent_model_name_mut().unwrap() = model_name.try_into().unwrap();
+        file.data.ptrainer_ranges_mut().unwrap().push(range);
+        file
+    }
+
+    fn names(file: &LvdFile) -> Vec<String> {
+        let collisions = file
+            .data
+            .collisions()
+            .unwrap()
+            .iter()
+            .flat_map(|collision| {
+                let base = collision.base().unwrap();
+
+                [
+                    Some(base.meta_info().name().to_string_lossy()),
+                    Some(base.dynamic_name().to_string_lossy()),
+                    base.joint_name().map(|name| name.to_string_lossy()),
+                ]
+            });
+        let ranges = file
+            .data
+            .ptrainer_ranges()
+            .unwrap()
+            .iter()
+            .map(|range| range.parent_model_name().map(|name| name.to_string_lossy()));
+
+        collisions
+            .chain(ranges)
+            .flatten()
+            .map(|name| name.into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn rename_prefix_renames_every_name() {
+        let mut file = file("COL_00_Model");
+
+        assert_eq!(file.rename_prefix("COL_00_", "COL_01_"), Ok(4));
+        assert_eq!(
+            names(&file),
+            [
+                "COL_01_Floor",
+                "COL_01_Dynamic",
+                "COL_01_Joint",
+                "COL_10_Wall",
+                "",
+                "",
+                "COL_01_Model"
+            ]
+        );
+    }
+
+    #[test]
+    fn rename_prefix_without_matches() {
+        let mut file = file("COL_00_Model");
+        let bytes = file.to_bytes().unwrap();
+
+        assert_eq!(file.rename_prefix("COL_99_", "COL_01_"), Ok(0));
+        assert_eq!(file.rename_prefix("col_00_", "COL_01_"), Ok(0));
+        assert_eq!(file.rename_prefix("COL_00_Floor_", "X"), Ok(0));
+        assert_eq!(file.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn rename_prefix_overflow_leaves_file_unchanged() {
+        // The object names fit their 55 byte buffers once renamed, but the parent model name,
+        // which is renamed last, does not fit its 63 byte buffer.
+        let model_name = format!("COL_00_{}", "M".repeat(20));
+        let new = "X".repeat(45);
+        let mut file = file(&model_name);
+        let bytes = file.to_bytes().unwrap();
+
+        assert_eq!(
+            file.rename_prefix("COL_00_", &new),
+            Err(RenameError::BufferOverflow {
+                name: format!("{new}{}", "M".repeat(20)),
+                capacity: 63,
+            })
+        );
+        assert_eq!(file.to_bytes().unwrap(), bytes, "nothing was renamed");
+
+        // A name filling its buffer exactly still fits.
+        assert_eq!(file.rename_prefix("COL_00_", &"X".repeat(43)), Ok(4));
+    }
+}
@@ -0,0 +1,564 @@
+//! Rendering of stage previews as images.
+//!
+//! This module contains the [`LvdFile::render`] and [`LvdFile::render_png`] methods, which draw
+//! the selected sections of a file as seen from the front, the [`RenderOptions`] type setting the
+//! size of the image and the sections drawn, and an error type that may result when rendering.
+
+use thiserror::Error;
+use tiny_skia::{
+    Color, ColorU8, FillRule, Paint, PathBuilder, Pixmap, Stroke, StrokeDash, Transform,
+};
+
+use crate::{
+    array::Array,
+    shape::{Rect, Shape2},
+    vector::Vector2,
+    Lvd, LvdFile, SectionKind,
+};
+
+/// The sections which can be drawn, in the order they are drawn from back to front.
+pub const LAYERS: &[SectionKind] = &[
+    SectionKind::DeathRegions,
+    SectionKind::ShrinkedDeathRegions,
+    SectionKind::CameraRegions,
+    SectionKind::ShrinkedCameraRegions,
+    SectionKind::ItemPopups,
+    SectionKind::GeneralShapes2,
+    SectionKind::Collisions,
+    SectionKind::StartPositions,
+    SectionKind::RestartPositions,
+];
+
+/// The bounds drawn when none of the drawn sections have any objects.
+const EMPTY_BOUNDS: Rect = Rect::new(-100.0, 100.0, 100.0, -100.0);
+
+/// The radius in pixels of the circle drawn for a point.
+const POINT_RADIUS: f32 = 5.0;
+
+/// The width in pixels of the lines drawn for edges and outlines.
+const LINE_WIDTH: f32 = 2.0;
+
+const BACKGROUND: ColorU8 = ColorU8::from_rgba(32, 32, 36, 255);
+const FLOOR: ColorU8 = ColorU8::from_rgba(235, 235, 235, 255);
+const WALL: ColorU8 = ColorU8::from_rgba(140, 140, 150, 255);
+const DYNAMIC: ColorU8 = ColorU8::from_rgba(255, 160, 60, 255);
+const DEATH_REGION: ColorU8 = ColorU8::from_rgba(230, 70, 70, 255);
+const CAMERA_REGION: ColorU8 = ColorU8::from_rgba(80, 150, 240, 255);
+const ITEM_POPUP: ColorU8 = ColorU8::from_rgba(240, 200, 60, 255);
+const GENERAL_SHAPE: ColorU8 = ColorU8::from_rgba(170, 110, 230, 255);
+const START_POSITION: ColorU8 = ColorU8::from_rgba(80, 210, 110, 255);
+const RESTART_POSITION: ColorU8 = ColorU8::from_rgba(80, 210, 210, 255);
+
+/// The size of a rendered image and the sections drawn on it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderOptions {
+    /// The width of the image in pixels.
+    pub width: u32,
+
+    /// The height of the image in pixels.
+    pub height: u32,
+
+    /// The sections to draw, each of which must be one of [`LAYERS`].
+    ///
+    /// Sections are drawn in the order of [`LAYERS`] regardless of their order here.
+    pub layers: Vec<SectionKind>,
+
+    /// The distance in pixels between the drawn objects and the edges of the image.
+    pub padding: f32,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            width: 1280,
+            height: 720,
+            layers: LAYERS.to_vec(),
+            padding: 16.0,
+        }
+    }
+}
+
+impl LvdFile {
+    /// Draws the sections of the file selected by `options`, as seen from the front.
+    ///
+    /// The view is fitted to the objects of the drawn sections, keeping their aspect ratio.
+    /// Sections not supported by the file's version are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a selected section cannot be drawn, or if the image size is zero.
+    pub fn render(&self, options: &RenderOptions) -> Result<Pixmap, RenderError> {
+        if let Some(&kind) = options.layers.iter().find(|kind| !LAYERS.contains(kind)) {
+            return Err(RenderError::UnsupportedLayer(kind));
+        }
+
+        let mut pixmap =
+            Pixmap::new(options.width, options.height).ok_or(RenderError::InvalidSize {
+                width: options.width,
+                height: options.height,
+            })?;
+        let layers = LAYERS
+            .iter()
+            .copied()
+            .filter(|kind| options.layers.contains(kind))
+            .collect::<Vec<_>>();
+        let bounds = layers
+            .iter()
+            .filter_map(|&kind| layer_bounds(&self.data, kind))
+            .reduce(|a, b| a.union(&b))
+            .unwrap_or(EMPTY_BOUNDS);
+        let mut canvas = Canvas {
+            transform: fit(&bounds, options),
+            pixmap: &mut pixmap,
+        };
+
+        canvas.pixmap.fill(Color::from_rgba8(
+            BACKGROUND.red(),
+            BACKGROUND.green(),
+            BACKGROUND.blue(),
+            BACKGROUND.alpha(),
+        ));
+
+        for kind in layers {
+            draw_layer(&mut canvas, &self.data, kind);
+        }
+
+        Ok(pixmap)
+    }
+
+    /// Draws the sections of the file selected by `options` as with [`render`](Self::render),
+    /// returning the image encoded as PNG.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`render`](Self::render),
+    /// or if the image cannot be encoded.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{render::RenderOptions, LvdFile, SectionKind};
+    ///
+    /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    /// let png = file
+    ///     .render_png(&RenderOptions {
+    ///         layers: vec![SectionKind::Collisions, SectionKind::StartPositions],
+    ///         ..Default::default()
+    ///     })
+    ///     .unwrap();
+    ///
+    /// std::fs::write("battlefield_00.png", png).unwrap();
+    /// ```
+    pub fn render_png(&self, options: &RenderOptions) -> Result<Vec<u8>, RenderError> {
+        self.render(options)?
+            .encode_png()
+            .map_err(|error| RenderError::Encode(error.to_string()))
+    }
+}
+
+/// An image being drawn, with the transform from stage coordinates to pixels.
+struct Canvas<'a> {
+    pixmap: &'a mut Pixmap,
+    transform: Transform,
+}
+
+impl Canvas<'_> {
+    /// Maps a position in stage coordinates to pixels.
+    fn map(&self, point: Vector2) -> (f32, f32) {
+        let mut point = tiny_skia::Point::from_xy(point.x(), point.y());
+
+        self.transform.map_point(&mut point);
+
+        (point.x, point.y)
+    }
+
+    /// Draws a line through the positions, closing it into a loop if `close` is `true`.
+    fn polyline(&mut self, points: &[Vector2], close: bool, color: ColorU8, dashed: bool) {
+        let mut builder = PathBuilder::new();
+
+        for (index, &point) in points.iter().enumerate() {
+            let (x, y) = self.map(point);
+
+            if index == 0 {
+                builder.move_to(x, y);
+            } else {
+                builder.line_to(x, y);
+            }
+        }
+
+        if close {
+            builder.close();
+        }
+
+        let Some(path) = builder.finish() else {
+            return;
+        };
+        let stroke = Stroke {
+            width: LINE_WIDTH,
+            dash: dashed
+                .then(|| StrokeDash::new(vec![8.0, 6.0], 0.0))
+                .flatten(),
+            ..Default::default()
+        };
+
+        self.pixmap
+            .stroke_path(&path, &paint(color), &stroke, Transform::identity(), None);
+    }
+
+    /// Draws a filled circle with its radius in pixels.
+    fn circle(&mut self, center: Vector2, radius: f32, color: ColorU8) {
+        let (x, y) = self.map(center);
+
+        if let Some(path) = PathBuilder::from_circle(x, y, radius.max(1.0)) {
+            self.pixmap.fill_path(
+                &path,
+                &paint(color),
+                FillRule::Winding,
+                Transform::identity(),
+                None,
+            );
+        }
+    }
+
+    /// Draws the outline of a rectangle in stage coordinates.
+    fn rect(&mut self, rect: &Rect, color: ColorU8, dashed: bool) {
+        self.polyline(&corners(rect), true, color, dashed);
+    }
+
+    /// Draws a two-dimensional shape in stage coordinates.
+    fn shape(&mut self, shape: &Shape2, color: ColorU8) {
+        match shape {
+            Shape2::Point { pos_x, pos_y, .. } => {
+                self.circle(Vector2::new(*pos_x, *pos_y), POINT_RADIUS, color)
+            }
+            Shape2::Circle {
+                pos_x,
+                pos_y,
+                radius,
+                ..
+            } => {
+                let radius = radius * self.transform.sx;
+                let (x, y) = self.map(Vector2::new(*pos_x, *pos_y));
+
+                if let Some(path) = PathBuilder::from_circle(x, y, radius.max(1.0)) {
+                    self.pixmap.stroke_path(
+                        &path,
+                        &paint(color),
+                        &Stroke {
+                            width: LINE_WIDTH,
+                            ..Default::default()
+                        },
+                        Transform::identity(),
+                        None,
+                    );
+                }
+            }
+            Shape2::Rect {
+                left,
+                right,
+                bottom,
+                top,
+                ..
+            } => self.rect(&Rect::new(*left, *right, *top, *bottom), color, false),
+            Shape2::Path { path } => {
+                let points = path.points().iter().copied().collect::<Vec<_>>();
+
+                self.polyline(&points, false, color, false);
+            }
+            Shape2::Unknown { .. } => (),
+        }
+    }
+}
+
+/// Returns a solid, anti-aliased paint of the color.
+fn paint(color: ColorU8) -> Paint<'static> {
+    let mut paint = Paint::default();
+
+    paint.set_color_rgba8(color.red(), color.green(), color.blue(), color.alpha());
+    paint.anti_alias = true;
+
+    paint
+}
+
+/// Returns the corners of a rectangle, in order around its outline.
+fn corners(rect: &Rect) -> [Vector2; 4] {
+    [
+        Vector2::new(rect.left(), rect.top()),
+        Vector2::new(rect.right(), rect.top()),
+        Vector2::new(rect.right(), rect.bottom()),
+        Vector2::new(rect.left(), rect.bottom()),
+    ]
+}
+
+/// Returns the transform from stage coordinates to pixels which fits `bounds` inside the
+/// padded image, centered and with the positive y-axis pointing up.
+fn fit(bounds: &Rect, options: &RenderOptions) -> Transform {
+    let width = (options.width as f32 - 2.0 * options.padding).max(1.0);
+    let height = (options.height as f32 - 2.0 * options.padding).max(1.0);
+    let scale =
+        (width / bounds.width().max(f32::EPSILON)).min(height / bounds.height().max(f32::EPSILON));
+    let center = bounds.center();
+
+    Transform::from_row(
+        scale,
+        0.0,
+        0.0,
+        -scale,
+        options.width as f32 / 2.0 - center.x() * scale,
+        options.height as f32 / 2.0 + center.y() * scale,
+    )
+}
+
+/// Returns the smallest rectangle containing the positions.
+fn bounds_of(points: impl IntoIterator<Item = Vector2>) -> Option<Rect> {
+    points
+        .into_iter()
+        .map(|p| Rect::new(p.x(), p.x(), p.y(), p.y()))
+        .reduce(|a, b| a.union(&b))
+}
+
+/// Returns the corners of a shape's bounding box, or its points for a path.
+fn shape_points(shape: &Shape2) -> Vec<Vector2> {
+    match shape {
+        Shape2::Point { pos_x, pos_y, .. } => vec![Vector2::new(*pos_x, *pos_y)],
+        Shape2::Circle {
+            pos_x,
+            pos_y,
+            radius,
+            ..
+        } => corners(&Rect::new(
+            pos_x - radius,
+            pos_x + radius,
+            pos_y + radius,
+            pos_y - radius,
+        ))
+        .to_vec(),
+        Shape2::Rect {
+            left,
+            right,
+            bottom,
+            top,
+            ..
+        } => corners(&Rect::new(*left, *right, *top, *bottom)).to_vec(),
+        Shape2::Path { path } => path.points().iter().copied().collect(),
+        Shape2::Unknown { .. } => Vec::new(),
+    }
+}
+
+/// Returns the regions of a region section.
+fn regions(lvd: &Lvd, kind: SectionKind) -> Vec<Rect> {
+    let section = match kind {
+        SectionKind::CameraRegions => lvd.camera_regions(),
+        SectionKind::DeathRegions => lvd.death_regions(),
+        SectionKind::ShrinkedCameraRegions => lvd.shrinked_camera_regions(),
+        SectionKind::ShrinkedDeathRegions => lvd.shrinked_death_regions(),
+        _ => None,
+    };
+
+    section
+        .into_iter()
+        .flat_map(Array::iter)
+        .map(|region| region.rect())
+        .collect()
+}
+
+/// Returns the positions of a point section.
+fn positions(lvd: &Lvd, kind: SectionKind) -> Vec<Vector2> {
+    let section = match kind {
+        SectionKind::StartPositions => lvd.start_positions(),
+        SectionKind::RestartPositions => lvd.restart_positions(),
+        _ => None,
+    };
+
+    section
+        .into_iter()
+        .flat_map(Array::iter)
+        .map(|point| point.pos())
+        .collect()
+}
+
+/// Returns the two-dimensional shapes of every object in a section.
+fn shapes(lvd: &Lvd, kind: SectionKind) -> Vec<&Shape2> {
+    (0..lvd.section_len(kind).unwrap_or_default())
+        .filter_map(|index| lvd.object(kind, index))
+        .flat_map(|object| object.shapes2())
+        .collect()
+}
+
+/// Returns the smallest rectangle containing every object drawn for a section,
+/// or `None` if nothing is drawn for it.
+fn layer_bounds(lvd: &Lvd, kind: SectionKind) -> Option<Rect> {
+    match kind {
+        SectionKind::Collisions => bounds_of(
+            lvd.collisions()
+                .into_iter()
+                .flat_map(Array::iter)
+                .flat_map(|collision| collision.vertices().iter().copied()),
+        ),
+        SectionKind::StartPositions | SectionKind::RestartPositions => {
+            bounds_of(positions(lvd, kind))
+        }
+        SectionKind::ItemPopups | SectionKind::GeneralShapes2 => {
+            bounds_of(shapes(lvd, kind).into_iter().flat_map(shape_points))
+        }
+        _ => regions(lvd, kind).into_iter().reduce(|a, b| a.union(&b)),
+    }
+}
+
+/// Draws every object of a section.
+fn draw_layer(canvas: &mut Canvas, lvd: &Lvd, kind: SectionKind) {
+    match kind {
+        SectionKind::Collisions => {
+            for collision in lvd.collisions().into_iter().flat_map(Array::iter) {
+                let vertices = collision.vertices().elements();
+
+                for (index, edge) in vertices.windows(2).enumerate() {
+                    let color = if collision.flags().dynamic() {
+                        DYNAMIC
+                    } else if collision.is_floor_edge(index) {
+                        FLOOR
+                    } else {
+                        WALL
+                    };
+
+                    canvas.polyline(&[*edge[0], *edge[1]], false, color, false);
+                }
+            }
+        }
+        SectionKind::StartPositions | SectionKind::RestartPositions => {
+            let color = match kind {
+                SectionKind::StartPositions => START_POSITION,
+                _ => RESTART_POSITION,
+            };
+
+            for position in positions(lvd, kind) {
+                canvas.circle(position, POINT_RADIUS, color);
+            }
+        }
+        SectionKind::ItemPopups | SectionKind::GeneralShapes2 => {
+            let color = match kind {
+                SectionKind::ItemPopups => ITEM_POPUP,
+                _ => GENERAL_SHAPE,
+            };
+
+            for shape in shapes(lvd, kind) {
+                canvas.shape(shape, color);
+            }
+        }
+        _ => {
+            let color = match kind {
+                SectionKind::CameraRegions | SectionKind::ShrinkedCameraRegions => CAMERA_REGION,
+                _ => DEATH_REGION,
+            };
+            let dashed = matches!(
+                kind,
+                SectionKind::ShrinkedCameraRegions | SectionKind::ShrinkedDeathRegions
+            );
+
+            for region in regions(lvd, kind) {
+                canvas.rect(&region, color, dashed);
+            }
+        }
+    }
+}
+
+/// The error type used when rendering a file.
+#[derive(Debug, PartialEq, Error)]
+pub enum RenderError {
+    /// The section does not store objects which can be drawn.
+    #[error("section `{}` cannot be rendered", .0.name())]
+    UnsupportedLayer(SectionKind),
+
+    /// The image has no pixels.
+    #[error("the image size {width}x{height} is invalid")]
+    InvalidSize {
+        /// The width of the image in pixels.
+        width: u32,
+
+        /// The height of the image in pixels.
+        height: u32,
+    },
+
+    /// The image could not be encoded as PNG.
+    #[error("failed to encode the image as PNG: {0}")]
+    Encode(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::{file_with_collisions, platform_file};
+
+    use super::*;
+
+    #[test]
+    fn platform_is_drawn_across_image() {
+        let options = RenderOptions {
+            width: 100,
+            height: 50,
+            layers: vec![SectionKind::Collisions],
+            padding: 10.0,
+        };
+        let pixmap = platform_file().render(&options).unwrap();
+        let background = BACKGROUND.premultiply();
+
+        assert_ne!(pixmap.pixel(50, 25).unwrap(), background);
+        assert_ne!(pixmap.pixel(12, 25).unwrap(), background);
+        assert_eq!(pixmap.pixel(5, 25).unwrap(), background);
+        assert_eq!(pixmap.pixel(50, 10).unwrap(), background);
+    }
+
+    #[test]
+    fn png_has_signature() {
+        let png = platform_file()
+            .render_png(&RenderOptions::default())
+            .unwrap();
+
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn unsupported_layer() {
+        let options = RenderOptions {
+            layers: vec![SectionKind::FsItems],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            platform_file().render(&options).unwrap_err(),
+            RenderError::UnsupportedLayer(SectionKind::FsItems)
+        );
+    }
+
+    #[test]
+    fn invalid_size() {
+        let options = RenderOptions {
+            width: 0,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            platform_file().render(&options).unwrap_err(),
+            RenderError::InvalidSize {
+                width: 0,
+                height: 720
+            }
+        );
+    }
+
+    #[test]
+    fn empty_layers_draw_background() {
+        let background = BACKGROUND.premultiply();
+        let options = RenderOptions {
+            width: 40,
+            height: 20,
+            layers: vec![SectionKind::StartPositions],
+            padding: 0.0,
+        };
+
+        for file in [file_with_collisions([]), platform_file()] {
+            let pixmap = file.render(&options).unwrap();
+
+            assert!(pixmap.pixels().iter().all(|&pixel| pixel == background));
+        }
+    }
+}
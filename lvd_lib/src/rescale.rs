@@ -0,0 +1,152 @@
+//! Resizing of whole stages.
+//!
+//! This module contains the [`LvdFile::rescale`] method, which scales every object of a stage so
+//! its collisions span a given width, then fits new camera and death regions around them with
+//! [`LvdFile::autofit_regions`]. Regions are fitted rather than scaled since the size of the
+//! characters, and so the room they need around the stage, does not change with the stage.
+
+use crate::{autofit::RegionMargins, transform::Transform, vector::Vector3, LvdFile};
+
+impl LvdFile {
+    /// Scales every object about the origin so the collisions span the target width, and
+    /// replaces the camera and death regions with regions extending beyond the scaled collisions
+    /// by the margins, returning the scale factor.
+    ///
+    /// Positions are scaled along every axis by the same factor, so spawns, items, and the
+    /// ranges of Pokémon Trainers keep their places relative to the collisions. As with
+    /// [`LvdObject::transform`](crate::objects::LvdObject::transform), objects exclusive to
+    /// Smash Run are left unchanged.
+    ///
+    /// Nothing is changed and `None` is returned if the file has no collision vertices,
+    /// if its collisions have no width, or if the target width is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{
+    ///     autofit::RegionMargins,
+    ///     objects::Collision,
+    ///     vector::Vector2,
+    ///     version::Versioned,
+    ///     Lvd, LvdFile,
+    /// };
+    ///
+    /// let mut data = Lvd::empty(13).unwrap();
+    ///
+    /// data.collisions_mut().unwrap().push(Collision::new_soft_platform(
+    ///     Vector2::new(-60.0, 0.0),
+    ///     Vector2::new(60.0, 0.0),
+    /// ));
+    ///
+    /// let mut file = LvdFile { data: Versioned::new(data) };
+    ///
+    /// assert_eq!(file.rescale(180.0, &RegionMargins::STANDARD), Some(1.5));
+    /// assert_eq!(file.collision_bounds().unwrap().width(), 180.0);
+    /// ```
+    pub fn rescale(&mut self, target_width: f32, margins: &RegionMargins) -> Option<f32> {
+        let width = self.collision_bounds()?.width();
+
+        if !(width > 0.0 && target_width > 0.0) {
+            return None;
+        }
+
+        let factor = target_width / width;
+        let transform = Transform::from_scale(Vector3::new(factor, factor, factor));
+
+        self.data
+            .for_each_object_mut(|object| object.transform(&transform));
+        self.autofit_regions(margins);
+
+        Some(factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixtures::file_with_collisions,
+        objects::{Collision, PTrainerRange, Point, Region},
+        shape::Rect,
+        vector::Vector2,
+    };
+
+    use super::*;
+
+    #[test]
+    fn rescale_stage() {
+        let mut file = file_with_collisions([Collision::new_soft_platform(
+            Vector2::new(-40.0, 0.0),
+            Vector2::new(80.0, 0.0),
+        )]);
+        let data = &mut file.data;
+        let mut spawn = Point::default();
+        let mut range = PTrainerRange::default();
+
+        *spawn.pos_mut() = Vector2::new(20.0, 10.0);
+        range.trainers_mut().push(Vector3::new(40.0, 20.0, 10.0));
+        data.start_positions_mut().unwrap().push(spawn);
+        data.ptrainer_ranges_mut().unwrap().push(range);
+        data.death_regions_mut().unwrap().push(Region::default());
+
+        assert_eq!(file.rescale(60.0, &RegionMargins::STANDARD), Some(0.5));
+        assert_eq!(
+            file.collision_bounds(),
+            Some(Rect::new(-20.0, 40.0, 0.0, 0.0))
+        );
+        assert_eq!(
+            file.data.start_positions().unwrap().elements()[0].pos(),
+            Vector2::new(10.0, 5.0)
+        );
+        assert_eq!(
+            file.data.ptrainer_ranges().unwrap().elements()[0]
+                .trainers()
+                .iter()
+                .copied()
+                .collect::<Vec<_>>(),
+            [Vector3::new(20.0, 10.0, 5.0)]
+        );
+        assert_eq!(
+            file.data
+                .death_regions()
+                .unwrap()
+                .iter()
+                .map(Region::rect)
+                .collect::<Vec<_>>(),
+            [RegionMargins::STANDARD
+                .death
+                .apply(&Rect::new(-20.0, 40.0, 0.0, 0.0))]
+        );
+        assert!(file
+            .data
+            .camera_regions()
+            .is_some_and(|regions| regions.len() == 1));
+        assert_eq!(
+            file.rescale(0.0, &RegionMargins::STANDARD),
+            None,
+            "a target width of zero is rejected"
+        );
+    }
+
+    #[test]
+    fn rescale_rejects_degenerate_stages() {
+        let mut empty = file_with_collisions([]);
+        let mut wall = file_with_collisions([Collision::new_soft_platform(
+            Vector2::new(10.0, 0.0),
+            Vector2::new(10.0, 50.0),
+        )]);
+        let original = wall.to_bytes().unwrap();
+
+        assert_eq!(empty.rescale(100.0, &RegionMargins::STANDARD), None);
+        assert_eq!(wall.rescale(100.0, &RegionMargins::STANDARD), None);
+        assert_eq!(wall.rescale(f32::NAN, &RegionMargins::STANDARD), None);
+        assert_eq!(wall.to_bytes().unwrap(), original, "nothing is changed");
+
+        let mut platform = file_with_collisions([Collision::new_soft_platform(
+            Vector2::new(-10.0, 0.0),
+            Vector2::new(10.0, 0.0),
+        )]);
+
+        assert_eq!(platform.rescale(-20.0, &RegionMargins::STANDARD), None);
+        assert!(platform.data.camera_regions().unwrap().is_empty());
+    }
+}
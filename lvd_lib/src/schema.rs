@@ -0,0 +1,121 @@
+//! JSON Schema generation for the serialized form of LVD files.
+//!
+//! This module contains the [`lvd_file_schema`] function for the raw form of the binary format,
+//! and the [`lvd_model_schema`] function for the flattened [`model`](crate::model) form.
+
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{InstanceType, RootSchema, SchemaObject},
+    schema_for,
+};
+
+use crate::{
+    model::{LvdData, ModelVersions},
+    Lvd, LvdFile,
+};
+
+/// Returns a JSON Schema describing the serialized form of an [`LvdFile`].
+///
+/// If `version` is specified, the schema only accepts the given [`Lvd`] version.
+/// Otherwise, every version is accepted. Returns `None` if the version is not known.
+///
+/// # Examples
+///
+/// ```
+/// use lvd_lib::schema::lvd_file_schema;
+///
+/// assert!(lvd_file_schema(Some(13)).is_some());
+/// assert!(lvd_file_schema(Some(14)).is_none());
+/// ```
+pub fn lvd_file_schema(version: Option<u8>) -> Option<RootSchema> {
+    let mut root = schema_for!(LvdFile);
+
+    if let Some(version) = version {
+        Lvd::empty(version)?;
+
+        let variant = format!("V{version}");
+
+        if let Some(one_of) = root
+            .schema
+            .subschemas
+            .as_mut()
+            .and_then(|s| s.one_of.as_mut())
+        {
+            one_of.retain(|schema| {
+                schema
+                    .clone()
+                    .into_object()
+                    .object
+                    .is_some_and(|o| o.required.contains(&variant))
+            });
+        }
+    }
+
+    Some(root)
+}
+
+/// Returns a JSON Schema describing the flattened model form of an LVD file: an [`LvdData`]
+/// mapping with the byte order of the file under an `endian` key and its [`ModelVersions`]
+/// under a `versions` key.
+///
+/// # Examples
+///
+/// ```
+/// use lvd_lib::schema::lvd_model_schema;
+///
+/// let schema = lvd_model_schema();
+///
+/// assert!(schema.schema.object.unwrap().properties.contains_key("collisions"));
+/// ```
+pub fn lvd_model_schema() -> RootSchema {
+    let mut gen = SchemaGenerator::default();
+    let versions = gen.subschema_for::<ModelVersions>();
+    let mut root = gen.root_schema_for::<LvdData>();
+    let endian = SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        enum_values: Some(vec!["big".into()]),
+        ..Default::default()
+    };
+    let properties = &mut root.schema.object().properties;
+
+    properties.insert("endian".to_string(), endian.into());
+    properties.insert("versions".to_string(), versions);
+
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant_count(root: &RootSchema) -> usize {
+        root.schema
+            .subschemas
+            .as_ref()
+            .and_then(|s| s.one_of.as_ref())
+            .map_or(0, Vec::len)
+    }
+
+    #[test]
+    fn schema_for_all_versions() {
+        assert_eq!(variant_count(&lvd_file_schema(None).unwrap()), 13);
+    }
+
+    #[test]
+    fn model_schema_properties() {
+        let root = lvd_model_schema();
+        let properties = &root.schema.object.as_ref().unwrap().properties;
+
+        assert!(properties.contains_key("endian"));
+        assert!(properties.contains_key("versions"));
+        assert!(properties.contains_key("start_positions"));
+        assert!(root.definitions.contains_key("ModelVersions"));
+    }
+
+    #[test]
+    fn schema_for_version() {
+        assert_eq!(variant_count(&lvd_file_schema(Some(13)).unwrap()), 1);
+        assert!(lvd_file_schema(Some(0)).is_none());
+        assert!(lvd_file_schema(Some(14)).is_none());
+    }
+}
@@ -3,14 +3,20 @@
 //! This module contains the [`Shape2`], [`ShapeArray2`] and [`ShapeArrayElement2`] types,
 //! the [`Shape3`] type, the [`Path`] type, and the [`Rect`] type.
 
+use std::fmt;
+
 use binrw::binrw;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
 use crate::{
     array::Array,
-    vector::Vector2,
+    objects::collision::segment_distance as point_segment_distance,
+    vector::{Vector2, Vector3},
     version::{Version, Versioned},
 };
 
@@ -18,7 +24,8 @@ use crate::{
 #[binrw]
 #[br(import(_version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum Shape2 {
     /// The point shape type.
     #[brw(magic = 1u32)]
@@ -80,6 +87,22 @@ pub enum Shape2 {
         #[brw(pad_before = 16)]
         path: Versioned<Path>,
     },
+
+    /// A shape type not known to this library.
+    ///
+    /// The shape's data is preserved as raw words so the shape can be written back unchanged.
+    /// Writing fails if the type value belongs to a known shape type.
+    #[bw(assert(!Shape2::KNOWN_TYPES.contains(ty), "unsupported unknown shape type {}", ty))]
+    Unknown {
+        /// The numeric value of the shape type.
+        ty: u32,
+
+        /// The raw data of the shape.
+        data: [u32; 4],
+
+        /// The collection of points forming the path shape.
+        path: Versioned<Path>,
+    },
 }
 
 impl Version for Shape2 {
@@ -88,12 +111,134 @@ impl Version for Shape2 {
     }
 }
 
+impl Default for Shape2 {
+    fn default() -> Self {
+        Self::Point {
+            pos_x: 0.0,
+            pos_y: 0.0,
+            path: Versioned::new(Path::default()),
+        }
+    }
+}
+
+impl fmt::Display for Shape2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Point { pos_x, pos_y, .. } => write!(f, "point at ({pos_x}, {pos_y})"),
+            Self::Circle {
+                pos_x,
+                pos_y,
+                radius,
+                ..
+            } => write!(f, "circle at ({pos_x}, {pos_y}) with radius {radius}"),
+            Self::Rect {
+                left,
+                right,
+                bottom,
+                top,
+                ..
+            } => write!(f, "rect from ({left}, {bottom}) to ({right}, {top})"),
+            Self::Path { path } => write!(f, "{}", path.inner),
+            Self::Unknown { ty, .. } => write!(f, "unknown shape type {ty}"),
+        }
+    }
+}
+
+impl Shape2 {
+    /// The numeric values of the shape types known to this library.
+    pub const KNOWN_TYPES: &'static [u32] = &[1, 2, 3, 4];
+
+    /// Returns the type value of the shape if it is an [`Unknown`](Shape2::Unknown) shape whose
+    /// type value belongs to a known shape type.
+    ///
+    /// Such a shape would be read back as a different variant, so it cannot be written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{shape::{Path, Shape2}, version::Versioned};
+    ///
+    /// let shape = Shape2::Unknown {
+    ///     ty: 2,
+    ///     data: [0; 4],
+    ///     path: Versioned::new(Path::default()),
+    /// };
+    ///
+    /// assert_eq!(shape.unsupported_type(), Some(2));
+    /// assert_eq!(Shape2::point(0.0, 0.0).unsupported_type(), None);
+    /// ```
+    pub fn unsupported_type(&self) -> Option<u32> {
+        match self {
+            Self::Unknown { ty, .. } if Self::KNOWN_TYPES.contains(ty) => Some(*ty),
+            _ => None,
+        }
+    }
+
+    /// Creates a new point shape at the given position.
+    pub fn point(x: f32, y: f32) -> Self {
+        Self::Point {
+            pos_x: x,
+            pos_y: y,
+            path: Versioned::new(Path::default()),
+        }
+    }
+
+    /// Creates a new circle shape with the given center and radius.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::shape::Shape2;
+    ///
+    /// let shape = Shape2::circle(0.0, 10.0, 5.0);
+    ///
+    /// assert!(matches!(shape, Shape2::Circle { radius: 5.0, .. }));
+    /// ```
+    pub fn circle(x: f32, y: f32, radius: f32) -> Self {
+        Self::Circle {
+            pos_x: x,
+            pos_y: y,
+            radius,
+            path: Versioned::new(Path::default()),
+        }
+    }
+
+    /// Creates a new rectangle shape with the given edge coordinates.
+    pub fn rect(left: f32, right: f32, bottom: f32, top: f32) -> Self {
+        Self::Rect {
+            left,
+            right,
+            bottom,
+            top,
+            path: Versioned::new(Path::default()),
+        }
+    }
+
+    /// Creates a new path shape through the given points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{shape::Shape2, vector::Vector2};
+    ///
+    /// let shape = Shape2::path([Vector2::new(-10.0, 0.0), Vector2::new(10.0, 0.0)]);
+    ///
+    /// assert!(matches!(shape, Shape2::Path { .. }));
+    /// ```
+    pub fn path<I: IntoIterator<Item = Vector2>>(points: I) -> Self {
+        Self::Path {
+            path: Versioned::new(Path::new(points)),
+        }
+    }
+}
+
 // TODO: Why is this type used for an array of two-dimensional shapes?
 /// A fixed-size collection of two-dimensional shapes.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum ShapeArray2 {
     /// The first version of the `ShapeArray2` type.
     #[br(pre_assert(version == 1))]
@@ -111,13 +256,73 @@ impl Version for ShapeArray2 {
     }
 }
 
+impl Default for ShapeArray2 {
+    fn default() -> Self {
+        Self::V1 {
+            shapes: Versioned::new(Array::default()),
+        }
+    }
+}
+
+impl ShapeArray2 {
+    /// Creates a new `ShapeArray2` from the given shapes.
+    pub fn new<I: IntoIterator<Item = Shape2>>(shapes: I) -> Self {
+        Self::V1 {
+            shapes: Versioned::new(Array::new(
+                shapes
+                    .into_iter()
+                    .map(|shape| ShapeArrayElement2(Versioned::new(shape)))
+                    .collect(),
+            )),
+        }
+    }
+
+    /// Returns an iterator over the two-dimensional shapes.
+    pub fn shapes(&self) -> impl Iterator<Item = &Shape2> {
+        match self {
+            Self::V1 { shapes } => shapes.iter().map(|element| &element.0.inner),
+        }
+    }
+
+    /// Returns the number of shapes.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::V1 { shapes } => shapes.len(),
+        }
+    }
+
+    /// Returns `true` if there are no shapes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends a shape to the end of the collection.
+    pub fn push(&mut self, shape: Shape2) {
+        match self {
+            Self::V1 { shapes } => shapes.push(ShapeArrayElement2(Versioned::new(shape))),
+        }
+    }
+
+    /// Removes and returns the shape at the given index, or `None` if the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Option<Shape2> {
+        match self {
+            Self::V1 { shapes } => {
+                let elements = shapes.elements_mut();
+
+                (index < elements.len()).then(|| elements.remove(index).inner.0.inner)
+            }
+        }
+    }
+}
+
 // TODO: Why is this type used as the element type for an array of two-dimensional shapes?
 /// The element type for a [`ShapeArray2`].
 #[binrw]
 #[br(import(_version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
 #[cfg_attr(feature = "serde", serde(transparent))]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ShapeArrayElement2(pub Versioned<Shape2>);
 
 impl Version for ShapeArrayElement2 {
@@ -130,7 +335,8 @@ impl Version for ShapeArrayElement2 {
 #[binrw]
 #[br(import(_version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum Shape3 {
     /// The box shape type.
     #[brw(magic = 1u32)]
@@ -210,6 +416,19 @@ pub enum Shape3 {
         #[brw(pad_after = 16)]
         pos_z: f32,
     },
+
+    /// A shape type not known to this library.
+    ///
+    /// The shape's data is preserved as raw words so the shape can be written back unchanged.
+    /// Writing fails if the type value belongs to a known shape type.
+    #[bw(assert(!Shape3::KNOWN_TYPES.contains(ty), "unsupported unknown shape type {}", ty))]
+    Unknown {
+        /// The numeric value of the shape type.
+        ty: u32,
+
+        /// The raw data of the shape.
+        data: [u32; 7],
+    },
 }
 
 impl Version for Shape3 {
@@ -218,11 +437,338 @@ impl Version for Shape3 {
     }
 }
 
+impl Default for Shape3 {
+    fn default() -> Self {
+        Self::Point {
+            pos_x: 0.0,
+            pos_y: 0.0,
+            pos_z: 0.0,
+        }
+    }
+}
+
+impl fmt::Display for Shape3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Box {
+                left,
+                right,
+                bottom,
+                top,
+                back,
+                front,
+            } => write!(
+                f,
+                "box from ({left}, {bottom}, {back}) to ({right}, {top}, {front})"
+            ),
+            Self::Sphere {
+                pos_x,
+                pos_y,
+                pos_z,
+                radius,
+            } => write!(
+                f,
+                "sphere at ({pos_x}, {pos_y}, {pos_z}) with radius {radius}"
+            ),
+            Self::Capsule {
+                pos_x,
+                pos_y,
+                pos_z,
+                vec_x,
+                vec_y,
+                vec_z,
+                radius,
+            } => write!(
+                f,
+                "capsule from ({pos_x}, {pos_y}, {pos_z}) to ({}, {}, {}) with radius {radius}",
+                pos_x + vec_x,
+                pos_y + vec_y,
+                pos_z + vec_z
+            ),
+            Self::Point {
+                pos_x,
+                pos_y,
+                pos_z,
+            } => write!(f, "point at ({pos_x}, {pos_y}, {pos_z})"),
+            Self::Unknown { ty, .. } => write!(f, "unknown shape type {ty}"),
+        }
+    }
+}
+
+impl Shape3 {
+    /// The numeric values of the shape types known to this library.
+    pub const KNOWN_TYPES: &'static [u32] = &[1, 2, 3, 4];
+
+    /// Returns the type value of the shape if it is an [`Unknown`](Shape3::Unknown) shape whose
+    /// type value belongs to a known shape type.
+    ///
+    /// Such a shape would be read back as a different variant, so it cannot be written.
+    pub fn unsupported_type(&self) -> Option<u32> {
+        match self {
+            Self::Unknown { ty, .. } if Self::KNOWN_TYPES.contains(ty) => Some(*ty),
+            _ => None,
+        }
+    }
+
+    /// Creates a new box shape with the given edge coordinates.
+    pub fn cuboid(left: f32, right: f32, bottom: f32, top: f32, back: f32, front: f32) -> Self {
+        Self::Box {
+            left,
+            right,
+            bottom,
+            top,
+            back,
+            front,
+        }
+    }
+
+    /// Creates a new sphere shape with the given center and radius.
+    pub fn sphere(x: f32, y: f32, z: f32, radius: f32) -> Self {
+        Self::Sphere {
+            pos_x: x,
+            pos_y: y,
+            pos_z: z,
+            radius,
+        }
+    }
+
+    /// Creates a new capsule shape between the given start point and endpoint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{shape::Shape3, vector::Vector3};
+    ///
+    /// let shape = Shape3::capsule(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 20.0, 0.0), 5.0);
+    ///
+    /// assert!(matches!(shape, Shape3::Capsule { vec_y: 20.0, .. }));
+    /// ```
+    pub fn capsule(start: Vector3, end: Vector3, radius: f32) -> Self {
+        Self::Capsule {
+            pos_x: start.x(),
+            pos_y: start.y(),
+            pos_z: start.z(),
+            vec_x: end.x() - start.x(),
+            vec_y: end.y() - start.y(),
+            vec_z: end.z() - start.z(),
+            radius,
+        }
+    }
+
+    /// Creates a new point shape at the given position.
+    pub fn point(x: f32, y: f32, z: f32) -> Self {
+        Self::Point {
+            pos_x: x,
+            pos_y: y,
+            pos_z: z,
+        }
+    }
+
+    /// Returns `true` if the point lies within the shape or on its surface.
+    ///
+    /// Shapes of unknown types contain no points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{shape::Shape3, vector::Vector3};
+    ///
+    /// let shape = Shape3::sphere(0.0, 10.0, 0.0, 5.0);
+    ///
+    /// assert!(shape.contains_point(Vector3::new(3.0, 10.0, 0.0)));
+    /// assert!(!shape.contains_point(Vector3::new(0.0, 0.0, 0.0)));
+    /// ```
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        let point = [point.x(), point.y(), point.z()];
+
+        self.core()
+            .is_some_and(|(core, radius)| core.distance(&Core::Segment(point, point)) <= radius)
+    }
+
+    /// Returns `true` if the two shapes overlap or touch.
+    ///
+    /// Shapes of unknown types intersect no shapes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{shape::Shape3, vector::Vector3};
+    ///
+    /// let capsule = Shape3::capsule(Vector3::new(-10.0, 0.0, 0.0), Vector3::new(10.0, 0.0, 0.0), 2.0);
+    ///
+    /// assert!(capsule.intersects(&Shape3::cuboid(-1.0, 1.0, 1.0, 5.0, -1.0, 1.0)));
+    /// assert!(!capsule.intersects(&Shape3::sphere(0.0, 10.0, 0.0, 5.0)));
+    /// ```
+    pub fn intersects(&self, other: &Self) -> bool {
+        match (self.core(), other.core()) {
+            (Some((a, ra)), Some((b, rb))) => a.distance(&b) <= ra + rb,
+            _ => false,
+        }
+    }
+
+    /// Returns the core of the shape and the distance from the core the shape covers,
+    /// or `None` if the shape type is unknown.
+    fn core(&self) -> Option<(Core, f32)> {
+        match *self {
+            Self::Box {
+                left,
+                right,
+                bottom,
+                top,
+                back,
+                front,
+            } => Some((Core::Box([left, bottom, back], [right, top, front]), 0.0)),
+            Self::Sphere {
+                pos_x,
+                pos_y,
+                pos_z,
+                radius,
+            } => {
+                let pos = [pos_x, pos_y, pos_z];
+
+                Some((Core::Segment(pos, pos), radius))
+            }
+            Self::Capsule {
+                pos_x,
+                pos_y,
+                pos_z,
+                vec_x,
+                vec_y,
+                vec_z,
+                radius,
+            } => Some((
+                Core::Segment(
+                    [pos_x, pos_y, pos_z],
+                    [pos_x + vec_x, pos_y + vec_y, pos_z + vec_z],
+                ),
+                radius,
+            )),
+            Self::Point {
+                pos_x,
+                pos_y,
+                pos_z,
+            } => {
+                let pos = [pos_x, pos_y, pos_z];
+
+                Some((Core::Segment(pos, pos), 0.0))
+            }
+            Self::Unknown { .. } => None,
+        }
+    }
+}
+
+/// The core of a three-dimensional shape, which the shape covers up to a distance.
+enum Core {
+    /// A line segment between two points, which may coincide.
+    Segment([f32; 3], [f32; 3]),
+
+    /// An axis-aligned box between its minimum and maximum corners.
+    Box([f32; 3], [f32; 3]),
+}
+
+impl Core {
+    /// Returns the shortest distance between the two cores.
+    fn distance(&self, other: &Self) -> f32 {
+        match (self, other) {
+            (Self::Segment(p1, q1), Self::Segment(p2, q2)) => segment_distance(*p1, *q1, *p2, *q2),
+            (Self::Box(min, max), Self::Segment(p, q))
+            | (Self::Segment(p, q), Self::Box(min, max)) => {
+                // The distance to a box is convex along the segment, so a ternary search converges.
+                let (mut start, mut end) = (0.0f32, 1.0f32);
+
+                for _ in 0..64 {
+                    let t1 = start + (end - start) / 3.0;
+                    let t2 = end - (end - start) / 3.0;
+
+                    if box_distance(*min, *max, lerp3(*p, *q, t1))
+                        <= box_distance(*min, *max, lerp3(*p, *q, t2))
+                    {
+                        end = t2;
+                    } else {
+                        start = t1;
+                    }
+                }
+
+                box_distance(*min, *max, lerp3(*p, *q, (start + end) / 2.0))
+            }
+            (Self::Box(min1, max1), Self::Box(min2, max2)) => (0..3)
+                .map(|i| (min1[i] - max2[i]).max(min2[i] - max1[i]).max(0.0).powi(2))
+                .sum::<f32>()
+                .sqrt(),
+        }
+    }
+}
+
+/// Returns the point at the fraction `t` of the way from `a` to `b`.
+fn lerp3(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    [0, 1, 2].map(|i| a[i] + (b[i] - a[i]) * t)
+}
+
+fn sub3(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [0, 1, 2].map(|i| a[i] - b[i])
+}
+
+fn dot3(a: [f32; 3], b: [f32; 3]) -> f32 {
+    (0..3).map(|i| a[i] * b[i]).sum()
+}
+
+/// Returns the shortest distance from a point to an axis-aligned box.
+fn box_distance(min: [f32; 3], max: [f32; 3], point: [f32; 3]) -> f32 {
+    (0..3)
+        .map(|i| (min[i] - point[i]).max(point[i] - max[i]).max(0.0).powi(2))
+        .sum::<f32>()
+        .sqrt()
+}
+
+/// Returns the shortest distance between the line segments `p1`–`q1` and `p2`–`q2`.
+fn segment_distance(p1: [f32; 3], q1: [f32; 3], p2: [f32; 3], q2: [f32; 3]) -> f32 {
+    let d1 = sub3(q1, p1);
+    let d2 = sub3(q2, p2);
+    let r = sub3(p1, p2);
+    let a = dot3(d1, d1);
+    let e = dot3(d2, d2);
+    let f = dot3(d2, r);
+
+    let (s, t) = if a <= f32::EPSILON && e <= f32::EPSILON {
+        (0.0, 0.0)
+    } else if a <= f32::EPSILON {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = dot3(d1, r);
+
+        if e <= f32::EPSILON {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = dot3(d1, d2);
+            let denominator = a * e - b * b;
+            let s = if denominator > 0.0 {
+                ((b * f - c * e) / denominator).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let t = (b * s + f) / e;
+
+            if t < 0.0 {
+                ((-c / a).clamp(0.0, 1.0), 0.0)
+            } else if t > 1.0 {
+                (((b - c) / a).clamp(0.0, 1.0), 1.0)
+            } else {
+                (s, t)
+            }
+        }
+    };
+
+    let difference = sub3(lerp3(p1, q1, s), lerp3(p2, q2, t));
+
+    dot3(difference, difference).sqrt()
+}
+
 /// A collection of two-dimensional points forming a path shape.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[derive(Debug, Clone)]
 pub enum Path {
     /// The first version of the `Path` type.
     #[br(pre_assert(version == 1))]
@@ -240,10 +786,206 @@ impl Version for Path {
     }
 }
 
+impl Default for Path {
+    fn default() -> Self {
+        Self::V1 {
+            points: Versioned::new(Array::default()),
+        }
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.points().len() {
+            1 => write!(f, "path of 1 point"),
+            len => write!(f, "path of {len} points"),
+        }
+    }
+}
+
+impl Path {
+    /// Creates a new `Path` through the given points.
+    pub fn new<I: IntoIterator<Item = Vector2>>(points: I) -> Self {
+        Self::V1 {
+            points: Versioned::new(Array::new(points.into_iter().collect())),
+        }
+    }
+
+    /// Returns the collection of points forming the path.
+    pub fn points(&self) -> &Array<Vector2> {
+        match self {
+            Self::V1 { points } => points,
+        }
+    }
+
+    /// Returns the collection of points forming the path mutably.
+    pub fn points_mut(&mut self) -> &mut Array<Vector2> {
+        match self {
+            Self::V1 { points } => points,
+        }
+    }
+
+    /// Returns the sum of the lengths of the path's segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{shape::Path, vector::Vector2};
+    ///
+    /// let path = Path::new([
+    ///     Vector2::new(0.0, 0.0),
+    ///     Vector2::new(3.0, 4.0),
+    ///     Vector2::new(3.0, 10.0),
+    /// ]);
+    ///
+    /// assert_eq!(path.total_length(), 11.0);
+    /// ```
+    pub fn total_length(&self) -> f32 {
+        self.segments()
+            .map(|(start, end)| (end - start).length())
+            .sum()
+    }
+
+    /// Returns the point at the fraction `t` of the path's total length,
+    /// where `0.0` is the first point and `1.0` is the last point.
+    ///
+    /// `t` is clamped to the range `0.0..=1.0`. Returns `None` if the path has no points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{shape::Path, vector::Vector2};
+    ///
+    /// let path = Path::new([Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0)]);
+    ///
+    /// assert_eq!(path.point_at(0.25), Some(Vector2::new(2.5, 0.0)));
+    /// ```
+    pub fn point_at(&self, t: f32) -> Option<Vector2> {
+        let first = *self.points().iter().next()?;
+        let mut remaining = t.clamp(0.0, 1.0) * self.total_length();
+        let mut last = first;
+
+        for (start, end) in self.segments() {
+            let length = (end - start).length();
+
+            if remaining <= length && length > 0.0 {
+                return Some(start + (end - start) * (remaining / length));
+            }
+
+            remaining -= length;
+            last = end;
+        }
+
+        Some(last)
+    }
+
+    /// Replaces the points of the path with `count` points spaced evenly along it,
+    /// keeping the first and last points.
+    ///
+    /// Paths with no points are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{shape::Path, vector::Vector2};
+    ///
+    /// let mut path = Path::new([Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0)]);
+    ///
+    /// path.resample(3);
+    ///
+    /// assert_eq!(path.points().iter().nth(1), Some(&Vector2::new(5.0, 0.0)));
+    /// ```
+    pub fn resample(&mut self, count: usize) {
+        if self.points().is_empty() {
+            return;
+        }
+
+        let points = (0..count)
+            .filter_map(|i| self.point_at(i as f32 / count.saturating_sub(1).max(1) as f32))
+            .collect();
+
+        *self.points_mut() = Array::new(points);
+    }
+
+    /// Removes points which deviate from the path by no more than `tolerance`,
+    /// using the Ramer–Douglas–Peucker algorithm, returning the number of removed points.
+    ///
+    /// The first and last points are always kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{shape::Path, vector::Vector2};
+    ///
+    /// let mut path = Path::new([
+    ///     Vector2::new(0.0, 0.0),
+    ///     Vector2::new(5.0, 0.1),
+    ///     Vector2::new(10.0, 0.0),
+    /// ]);
+    ///
+    /// assert_eq!(path.simplify(0.5), 1);
+    /// assert_eq!(path.points().len(), 2);
+    /// ```
+    pub fn simplify(&mut self, tolerance: f32) -> usize {
+        let points = self.points().iter().copied().collect::<Vec<_>>();
+
+        if points.len() < 3 {
+            return 0;
+        }
+
+        let mut keep = vec![false; points.len()];
+        let mut stack = vec![(0, points.len() - 1)];
+
+        keep[0] = true;
+        keep[points.len() - 1] = true;
+
+        while let Some((start, end)) = stack.pop() {
+            let farthest = (start + 1..end)
+                .map(|i| {
+                    let distance = point_segment_distance(points[i], points[start], points[end]);
+
+                    (i, distance)
+                })
+                .max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+            if let Some((index, distance)) = farthest {
+                if distance > tolerance {
+                    keep[index] = true;
+                    stack.push((start, index));
+                    stack.push((index, end));
+                }
+            }
+        }
+
+        let kept = (0..points.len())
+            .filter(|&i| keep[i])
+            .map(|i| points[i])
+            .collect::<Vec<_>>();
+        let removed = points.len() - kept.len();
+
+        *self.points_mut() = Array::new(kept);
+
+        removed
+    }
+
+    /// Reverses the order of the path's points.
+    pub fn reverse(&mut self) {
+        self.points_mut().elements_mut().reverse();
+    }
+
+    /// Returns an iterator over the start and end points of each segment of the path.
+    fn segments(&self) -> impl Iterator<Item = (Vector2, Vector2)> + '_ {
+        let points = self.points().elements();
+
+        points.windows(2).map(|pair| (*pair[0], *pair[1]))
+    }
+}
+
 /// A two-dimensional rectangle type.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Rect {
     /// The first version of the `Rect` type.
@@ -270,3 +1012,221 @@ impl Version for Rect {
         }
     }
 }
+
+impl Default for Rect {
+    fn default() -> Self {
+        Self::V1 {
+            left: 0.0,
+            right: 0.0,
+            top: 0.0,
+            bottom: 0.0,
+        }
+    }
+}
+
+impl fmt::Display for Rect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rect from ({}, {}) to ({}, {})",
+            self.left(),
+            self.bottom(),
+            self.right(),
+            self.top()
+        )
+    }
+}
+
+impl Rect {
+    /// Creates a new `Rect` with the given edge coordinates.
+    pub const fn new(left: f32, right: f32, top: f32, bottom: f32) -> Self {
+        Self::V1 {
+            left,
+            right,
+            top,
+            bottom,
+        }
+    }
+
+    /// Returns the coordinate of the left edge.
+    pub const fn left(&self) -> f32 {
+        match self {
+            Self::V1 { left, .. } => *left,
+        }
+    }
+
+    /// Returns the coordinate of the right edge.
+    pub const fn right(&self) -> f32 {
+        match self {
+            Self::V1 { right, .. } => *right,
+        }
+    }
+
+    /// Returns the coordinate of the top edge.
+    pub const fn top(&self) -> f32 {
+        match self {
+            Self::V1 { top, .. } => *top,
+        }
+    }
+
+    /// Returns the coordinate of the bottom edge.
+    pub const fn bottom(&self) -> f32 {
+        match self {
+            Self::V1 { bottom, .. } => *bottom,
+        }
+    }
+
+    /// Returns the distance between the left and right edges.
+    pub fn width(&self) -> f32 {
+        self.right() - self.left()
+    }
+
+    /// Returns the distance between the bottom and top edges.
+    pub fn height(&self) -> f32 {
+        self.top() - self.bottom()
+    }
+
+    /// Returns the ratio of the rectangle's width to its height.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::shape::Rect;
+    ///
+    /// assert_eq!(Rect::new(-160.0, 160.0, 90.0, -90.0).aspect_ratio(), 16.0 / 9.0);
+    /// ```
+    pub fn aspect_ratio(&self) -> f32 {
+        self.width() / self.height()
+    }
+
+    /// Returns the point halfway between the rectangle's edges.
+    pub fn center(&self) -> Vector2 {
+        Vector2::new(
+            (self.left() + self.right()) / 2.0,
+            (self.bottom() + self.top()) / 2.0,
+        )
+    }
+
+    /// Returns the rectangle with each edge moved outwards by `margin`,
+    /// or inwards if `margin` is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::shape::Rect;
+    ///
+    /// let camera = Rect::new(-170.0, 170.0, 140.0, -70.0);
+    ///
+    /// assert_eq!(camera.expand(10.0), Rect::new(-180.0, 180.0, 150.0, -80.0));
+    /// ```
+    pub fn expand(&self, margin: f32) -> Self {
+        Self::new(
+            self.left() - margin,
+            self.right() + margin,
+            self.top() + margin,
+            self.bottom() - margin,
+        )
+    }
+
+    /// Returns the area covered by both rectangles, or `None` if they do not overlap.
+    ///
+    /// Rectangles which only share an edge overlap with a width or height of zero.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let rect = Self::new(
+            self.left().max(other.left()),
+            self.right().min(other.right()),
+            self.top().min(other.top()),
+            self.bottom().max(other.bottom()),
+        );
+
+        (rect.width() >= 0.0 && rect.height() >= 0.0).then_some(rect)
+    }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Self) -> Self {
+        Self::new(
+            self.left().min(other.left()),
+            self.right().max(other.right()),
+            self.top().max(other.top()),
+            self.bottom().min(other.bottom()),
+        )
+    }
+
+    /// Returns `true` if the point lies within the rectangle or on its edges.
+    pub fn contains_point(&self, point: Vector2) -> bool {
+        (self.left()..=self.right()).contains(&point.x())
+            && (self.bottom()..=self.top()).contains(&point.y())
+    }
+
+    /// Returns `true` if the other rectangle lies entirely within the rectangle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::shape::Rect;
+    ///
+    /// let camera = Rect::new(-170.0, 170.0, 140.0, -70.0);
+    /// let blast = Rect::new(-240.0, 240.0, 192.0, -140.0);
+    ///
+    /// assert!(blast.contains_rect(&camera));
+    /// assert!(!camera.contains_rect(&blast));
+    /// ```
+    pub fn contains_rect(&self, other: &Self) -> bool {
+        other.left() >= self.left()
+            && other.right() <= self.right()
+            && other.top() <= self.top()
+            && other.bottom() >= self.bottom()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use binrw::{BinReaderExt, BinWrite};
+
+    use super::*;
+
+    #[test]
+    fn rect_intersection() {
+        let a = Rect::new(0.0, 10.0, 10.0, 0.0);
+        let b = Rect::new(5.0, 15.0, 5.0, -5.0);
+
+        assert_eq!(a.intersection(&b), Some(Rect::new(5.0, 10.0, 5.0, 0.0)));
+        assert_eq!(a.union(&b), Rect::new(0.0, 15.0, 10.0, -5.0));
+        assert_eq!(a.intersection(&Rect::new(20.0, 30.0, 10.0, 0.0)), None);
+    }
+
+    #[test]
+    fn shape3_intersections() {
+        let cuboid = Shape3::cuboid(0.0, 10.0, 0.0, 10.0, 0.0, 10.0);
+        let capsule = Shape3::capsule(
+            Vector3::new(-5.0, 12.0, 5.0),
+            Vector3::new(15.0, 12.0, 5.0),
+            2.5,
+        );
+        let sphere = Shape3::sphere(13.0, 13.0, 5.0, 5.0);
+
+        assert!(cuboid.intersects(&capsule));
+        assert!(!cuboid.intersects(&Shape3::point(5.0, 11.0, 5.0)));
+        assert!(cuboid.intersects(&sphere));
+        assert!(!sphere.intersects(&Shape3::sphere(-13.0, 13.0, 5.0, 5.0)));
+        assert!(capsule.contains_point(Vector3::new(15.0, 14.0, 5.0)));
+        assert!(!capsule.contains_point(Vector3::new(18.0, 12.0, 5.0)));
+    }
+
+    #[test]
+    fn unknown_shape3_round_trip() {
+        let bytes = (9u32..17).flat_map(u32::to_be_bytes).collect::<Vec<_>>();
+        let shape: Shape3 = Cursor::new(&bytes).read_be_args((1,)).unwrap();
+
+        assert!(
+            matches!(shape, Shape3::Unknown { ty: 9, data } if data == [10, 11, 12, 13, 14, 15, 16])
+        );
+
+        let mut writer = Cursor::new(Vec::new());
+        shape.write_be(&mut writer).unwrap();
+
+        assert_eq!(writer.into_inner(), bytes);
+    }
+}
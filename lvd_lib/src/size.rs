@@ -0,0 +1,108 @@
+//! Calculation of the serialized size of LVD data.
+//!
+//! This module contains the [`BinarySize`] trait, which is implemented for [`LvdFile`](crate::LvdFile)
+//! and every object type, so the byte ranges of values within a file can be found without keeping
+//! their bytes. The size is found by writing the value to a writer which discards its bytes,
+//! so it costs about as much as writing the value itself.
+
+use std::io::{self, Seek, SeekFrom, Write};
+
+use binrw::{BinWrite, Endian};
+
+/// A trait for calculating the number of bytes a value occupies when written.
+pub trait BinarySize {
+    /// Returns the number of bytes written when serializing `self`.
+    ///
+    /// The size of an object excludes its version number, which is written by its
+    /// [`Versioned`](crate::version::Versioned) wrapper. If `self` cannot be written, such as
+    /// when it contains a shape of an unsupported type, the number of bytes written before the
+    /// error is returned instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{size::BinarySize, vector::Vector2};
+    ///
+    /// assert_eq!(Vector2::new(1.0, 2.0).binary_size(), 8);
+    /// ```
+    fn binary_size(&self) -> u64;
+}
+
+impl<T: for<'a> BinWrite<Args<'a> = ()>> BinarySize for T {
+    fn binary_size(&self) -> u64 {
+        let mut counter = SizeCounter::default();
+
+        // Writing to the counter itself never fails, so an error comes from a value which
+        // cannot be written, and is reported when the value is actually written.
+        let _ = self.write_options(&mut counter, Endian::Big, ());
+
+        counter.len
+    }
+}
+
+/// A writer which discards the written bytes, keeping only the length of the output.
+#[derive(Default)]
+struct SizeCounter {
+    /// The current position in the output.
+    pos: u64,
+
+    /// The length of the output.
+    len: u64,
+}
+
+impl Write for SizeCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pos += buf.len() as u64;
+        self.len = self.len.max(self.pos);
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for SizeCounter {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+        };
+
+        self.pos = new_pos.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative position",
+            )
+        })?;
+
+        Ok(self.pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    use crate::{objects::Collision, vector::Vector2, version::Versioned};
+
+    #[test]
+    fn binary_size_matches_written_length() {
+        let collision = Versioned::new(Collision::new_soft_platform(
+            Vector2::new(-10.0, 5.0),
+            Vector2::new(10.0, 5.0),
+        ));
+        let mut writer = Cursor::new(Vec::new());
+
+        collision
+            .write_options(&mut writer, Endian::Big, ())
+            .unwrap();
+
+        assert_eq!(collision.binary_size(), writer.into_inner().len() as u64);
+        assert_eq!(collision.binary_size(), collision.inner.binary_size() + 1);
+    }
+}
@@ -0,0 +1,229 @@
+//! Automatic placement of spawn points.
+//!
+//! This module contains the [`LvdFile::auto_spawns`] method, which places the start and restart
+//! positions of a stage evenly along its main floor, and the [`LvdFile::main_floor`] method
+//! finding that floor.
+
+use crate::{
+    array::Array,
+    objects::{Collision, Point},
+    vector::Vector2,
+    LvdFile,
+};
+
+/// The greatest distance between neighbouring spawn points placed by [`LvdFile::auto_spawns`].
+///
+/// Spawn points are placed closer together when the main floor is too narrow for this spacing.
+pub const SPAWN_SPACING: f32 = 40.0;
+
+impl LvdFile {
+    /// Returns the index of the main floor, the solid collision with the widest top surface,
+    /// or `None` if no solid collision has a floor edge.
+    ///
+    /// The width of a collision's top surface is the sum of the horizontal lengths of its
+    /// floor edges.
+    pub fn main_floor(&self) -> Option<usize> {
+        self.data
+            .collisions()
+            .into_iter()
+            .flat_map(Array::iter)
+            .enumerate()
+            .filter(|(_, collision)| !collision.flags().throughable())
+            .map(|(index, collision)| (index, floor_width(collision)))
+            .filter(|&(_, width)| width > 0.0)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+    }
+
+    /// Replaces the start and restart positions with `count` points each, spread evenly along
+    /// the main floor and mirrored across its center, returning their positions.
+    ///
+    /// Points are centered on `x = 0` if the main floor spans it, or on the middle of the
+    /// floor otherwise, and are at most [`SPAWN_SPACING`] apart. Each point lies on the highest
+    /// floor edge of the main floor beneath it. The restart positions are placed at the same
+    /// positions as the start positions. Existing points keep their names and other common
+    /// data, in order. Nothing is changed if the file has no main floor, as described by
+    /// [`LvdFile::main_floor`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::LvdFile;
+    ///
+    /// let mut file = LvdFile::from_file("custom_00.lvd").unwrap();
+    ///
+    /// for pos in file.auto_spawns(4).unwrap() {
+    ///     println!("({}, {})", pos.x(), pos.y());
+    /// }
+    /// ```
+    pub fn auto_spawns(&mut self, count: u8) -> Option<Vec<Vector2>> {
+        let floor = self
+            .data
+            .collisions()?
+            .iter()
+            .nth(self.main_floor()?)?
+            .clone();
+        let (left, right) = floor
+            .edges()
+            .filter(|edge| floor.is_floor_edge(edge.index))
+            .flat_map(|edge| [edge.start.x(), edge.end.x()])
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(l, r), x| {
+                (l.min(x), r.max(x))
+            });
+        let center = if left < 0.0 && right > 0.0 {
+            0.0
+        } else {
+            (left + right) / 2.0
+        };
+        let half_width = (center - left).min(right - center);
+        let spacing = SPAWN_SPACING.min(2.0 * half_width / f32::from(count.max(1)));
+        let positions = (0..count)
+            .map(|i| {
+                let x = center + (f32::from(i) - (f32::from(count) - 1.0) / 2.0) * spacing;
+
+                Vector2::new(x, floor_height(&floor, x))
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(start_positions) = self.data.start_positions_mut() {
+            place_points(start_positions, &positions);
+        }
+
+        if let Some(restart_positions) = self.data.restart_positions_mut() {
+            place_points(restart_positions, &positions);
+        }
+
+        Some(positions)
+    }
+}
+
+/// Returns the sum of the horizontal lengths of the floor edges of the collision.
+fn floor_width(collision: &Collision) -> f32 {
+    collision
+        .edges()
+        .filter(|edge| collision.is_floor_edge(edge.index))
+        .map(|edge| (edge.end.x() - edge.start.x()).abs())
+        .sum()
+}
+
+/// Returns the height of the highest floor edge of the collision beneath `x`,
+/// or of the nearest floor vertex if no floor edge spans `x`.
+fn floor_height(collision: &Collision, x: f32) -> f32 {
+    let floors = collision
+        .edges()
+        .filter(|edge| collision.is_floor_edge(edge.index))
+        .collect::<Vec<_>>();
+    let spanning = floors
+        .iter()
+        .filter(|edge| {
+            edge.start.x().min(edge.end.x()) <= x && x <= edge.start.x().max(edge.end.x())
+        })
+        .map(|edge| {
+            let dx = edge.end.x() - edge.start.x();
+
+            if dx == 0.0 {
+                edge.start.y().max(edge.end.y())
+            } else {
+                edge.start.y() + (edge.end.y() - edge.start.y()) * (x - edge.start.x()) / dx
+            }
+        })
+        .max_by(f32::total_cmp);
+
+    spanning.unwrap_or_else(|| {
+        floors
+            .iter()
+            .flat_map(|edge| [edge.start, edge.end])
+            .min_by(|a, b| (a.x() - x).abs().total_cmp(&(b.x() - x).abs()))
+            .map_or(0.0, |v| v.y())
+    })
+}
+
+/// Replaces the points of a section with points at the positions, reusing existing points in order.
+fn place_points(section: &mut Array<Point>, positions: &[Vector2]) {
+    let template = section.iter().next().cloned().unwrap_or_default();
+    let mut points = section.iter().cloned().collect::<Vec<_>>();
+
+    points.resize(positions.len(), template);
+
+    for (point, &pos) in points.iter_mut().zip(positions) {
+        *point.pos_mut() = pos;
+    }
+
+    *section = Array::new(points);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        fixtures::{file_with_collisions, platform_file},
+        legality::Rule,
+        objects::{LvdObject, Point},
+    };
+
+    /// Returns a solid collision with a single floor edge between the points.
+    fn solid_floor(start: Vector2, end: Vector2) -> Collision {
+        let mut floor = Collision::new_soft_platform(start, end);
+
+        floor.flags_mut().set_throughable(false);
+        floor
+    }
+
+    #[test]
+    fn auto_spawns_on_main_floor() {
+        let mut file = file_with_collisions([
+            Collision::new_soft_platform(Vector2::new(-200.0, 50.0), Vector2::new(200.0, 50.0)),
+            solid_floor(Vector2::new(-90.0, 0.0), Vector2::new(70.0, 0.0)),
+        ]);
+
+        assert_eq!(file.main_floor(), Some(1));
+        assert_eq!(
+            file.auto_spawns(4).unwrap(),
+            [
+                Vector2::new(-52.5, 0.0),
+                Vector2::new(-17.5, 0.0),
+                Vector2::new(17.5, 0.0),
+                Vector2::new(52.5, 0.0),
+            ]
+        );
+        assert_eq!(file.data.restart_positions().unwrap().len(), 4);
+        assert!(file
+            .check_legality(&[Rule::SpawnMirroring], 0.01)
+            .is_legal());
+    }
+
+    #[test]
+    fn auto_spawns_on_narrow_sloped_floor() {
+        let mut file = platform_file();
+
+        assert_eq!(file.main_floor(), None, "platforms are not main floors");
+        assert_eq!(file.auto_spawns(4), None);
+
+        let mut start = Point::default();
+
+        *start.meta_info_mut().name_mut() = "START_00".try_into().unwrap();
+        file.data.start_positions_mut().unwrap().push(start);
+        file.data.collisions_mut().unwrap().push(solid_floor(
+            Vector2::new(100.0, 0.0),
+            Vector2::new(140.0, 20.0),
+        ));
+
+        assert_eq!(
+            file.auto_spawns(2).unwrap(),
+            [Vector2::new(110.0, 5.0), Vector2::new(130.0, 15.0)],
+            "points are spaced to fit the floor and centered on it"
+        );
+
+        let names = file
+            .data
+            .start_positions()
+            .unwrap()
+            .iter()
+            .map(|point| point.name().to_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names[0], "START_00");
+        assert_eq!(names.len(), 2);
+    }
+}
@@ -0,0 +1,187 @@
+//! Locations of LVD files within the game's file system.
+//!
+//! This module contains helpers for mapping a stage's internal name to the names and paths
+//! of its LVD files, as laid out in the game's archive and in mod folders.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Returns the file name of the LVD file at the given index for a stage.
+///
+/// # Examples
+///
+/// ```
+/// use lvd_lib::stage::lvd_file_name;
+///
+/// assert_eq!(lvd_file_name("battlefield", 0), "battlefield_00.lvd");
+/// ```
+pub fn lvd_file_name(stage: &str, index: u32) -> String {
+    format!("{stage}_{index:02}.lvd")
+}
+
+/// Returns the index of an LVD file for a stage from its file name or file stem,
+/// or `None` if the name does not belong to the stage.
+///
+/// # Examples
+///
+/// ```
+/// use lvd_lib::stage::lvd_file_index;
+///
+/// assert_eq!(lvd_file_index("battlefield", "battlefield_01.lvd"), Some(1));
+/// assert_eq!(lvd_file_index("battlefield", "battlefield_01"), Some(1));
+/// assert_eq!(lvd_file_index("battlefield", "fd_00.lvd"), None);
+/// ```
+pub fn lvd_file_index(stage: &str, file_name: &str) -> Option<u32> {
+    let stem = file_name.strip_suffix(".lvd").unwrap_or(file_name);
+    let index = stem.strip_prefix(stage)?.strip_prefix('_')?;
+
+    if index.len() == 2 && index.bytes().all(|b| b.is_ascii_digit()) {
+        index.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Returns the path of the directory containing a stage's LVD files,
+/// relative to the root of the game's file system or a mod folder.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use lvd_lib::stage::lvd_directory;
+///
+/// assert_eq!(lvd_directory("battlefield"), Path::new("stage/battlefield/normal/param"));
+/// ```
+pub fn lvd_directory(stage: &str) -> PathBuf {
+    ["stage", stage, "normal", "param"].iter().collect()
+}
+
+/// Returns the path of the LVD file at the given index for a stage,
+/// relative to the root of the game's file system or a mod folder.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use lvd_lib::stage::lvd_path;
+///
+/// assert_eq!(
+///     lvd_path("battlefield", 0),
+///     Path::new("stage/battlefield/normal/param/battlefield_00.lvd")
+/// );
+/// ```
+pub fn lvd_path(stage: &str, index: u32) -> PathBuf {
+    lvd_directory(stage).join(lvd_file_name(stage, index))
+}
+
+/// Returns the indices and paths of every LVD file for a stage found under `root`,
+/// the root of the game's file system or a mod folder, in order of index.
+///
+/// # Errors
+///
+/// Returns an error if the stage's LVD directory cannot be read.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lvd_lib::stage::find_lvd_files;
+///
+/// for (index, path) in find_lvd_files("my_mod", "battlefield").unwrap() {
+///     println!("{index}: {}", path.display());
+/// }
+/// ```
+pub fn find_lvd_files<P: AsRef<Path>>(root: P, stage: &str) -> io::Result<Vec<(u32, PathBuf)>> {
+    let mut files = Vec::new();
+
+    for entry in fs::read_dir(root.as_ref().join(lvd_directory(stage)))? {
+        let path = entry?.path();
+        let index = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .filter(|name| name.ends_with(".lvd"))
+            .and_then(|name| lvd_file_index(stage, name));
+
+        if let Some(index) = index {
+            files.push((index, path));
+        }
+    }
+
+    files.sort();
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lvd_file_index_round_trip() {
+        for index in [0, 1, 10, 99] {
+            assert_eq!(
+                lvd_file_index("battlefield", &lvd_file_name("battlefield", index)),
+                Some(index)
+            );
+        }
+
+        assert_eq!(lvd_file_name("battlefield", 100), "battlefield_100.lvd");
+        assert_eq!(
+            lvd_file_index("battlefield", "battlefield_100.lvd"),
+            None,
+            "indices have two digits"
+        );
+    }
+
+    #[test]
+    fn lvd_file_index_rejects_other_stages() {
+        assert_eq!(lvd_file_index("battlefield", "battlefield_l_00.lvd"), None);
+        assert_eq!(
+            lvd_file_index("battlefield_l", "battlefield_l_00.lvd"),
+            Some(0)
+        );
+        assert_eq!(lvd_file_index("battlefield", "battlefield00.lvd"), None);
+        assert_eq!(lvd_file_index("battlefield", "battlefield_0a.lvd"), None);
+        assert_eq!(lvd_file_index("battlefield", "battlefield_+1.lvd"), None);
+    }
+
+    #[test]
+    fn find_lvd_files_in_order() {
+        let root = std::env::temp_dir().join(format!("lvd_lib_stage_{}", std::process::id()));
+        let dir = root.join(lvd_directory("battlefield"));
+
+        fs::create_dir_all(&dir).unwrap();
+
+        for name in [
+            "battlefield_01.lvd",
+            "battlefield_00.lvd",
+            "battlefield_00.yaml",
+            "battlefield_l_00.lvd",
+            "fd_00.lvd",
+        ] {
+            fs::write(dir.join(name), []).unwrap();
+        }
+
+        let files = find_lvd_files(&root, "battlefield");
+        let missing = find_lvd_files(&root, "fd");
+
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            files.unwrap(),
+            [
+                (0, dir.join("battlefield_00.lvd")),
+                (1, dir.join("battlefield_01.lvd")),
+            ]
+        );
+        assert_eq!(
+            missing.unwrap_err().kind(),
+            io::ErrorKind::NotFound,
+            "a stage without an LVD directory is an error"
+        );
+    }
+}
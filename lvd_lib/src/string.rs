@@ -3,13 +3,34 @@
 //! This module contains the [`FixedString`] type, several type aliases for common
 //! capacities, and an error type that may result when converting from a string.
 
-use std::str::{self, FromStr, Utf8Error};
-
-use binrw::{binrw, BinRead, BinResult};
+use std::{
+    borrow::{Borrow, Cow},
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    str::{self, FromStr, Utf8Error},
+};
+
+use binrw::{binrw, BinResult};
 use thiserror::Error;
 
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{
+    de::{self, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+#[cfg(feature = "schemars")]
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{
+        InstanceType, ObjectValidation, Schema, SchemaObject, StringValidation, SubschemaValidation,
+    },
+    JsonSchema,
+};
 
 use crate::version::Version;
 
@@ -23,9 +44,13 @@ pub type FixedString56 = FixedString<56>;
 pub type FixedString64 = FixedString<64>;
 
 /// A nul-terminated string with a fixed capacity.
+///
+/// When serialized, strings which are valid UTF-8 are represented by a string. Other strings,
+/// such as some names encoded in Shift-JIS, are represented by a mapping holding their bytes
+/// as a hexadecimal string under `bytes`, so they are written back unchanged.
 #[binrw]
 #[br(import(version: u8), pre_assert(version == 1))]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FixedString<const N: usize> {
     #[br(parse_with = read_bytes)]
     inner: [u8; N],
@@ -99,6 +124,44 @@ impl<const N: usize> FixedString<N> {
         self.inner[0] == 0
     }
 
+    /// Creates a new `FixedString` from the bytes of a string, which do not need to be valid UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes contain a nul byte, which would end the string early,
+    /// or if the bytes and a nul byte exceed the buffer's capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::string::FixedString;
+    ///
+    /// let s = FixedString::<8>::from_bytes(b"\x83\x41").unwrap();
+    /// assert_eq!(s.as_bytes(), b"\x83\x41");
+    /// assert!(s.to_str().is_err());
+    /// assert!(FixedString::<8>::from_bytes(b"ab\0cd").is_err());
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FromStrError<N>> {
+        if let Some(position) = bytes.iter().position(|&byte| byte == 0) {
+            return Err(FromStrError::InteriorNul(position));
+        }
+
+        if bytes.len() >= N {
+            return Err(FromStrError::BufferOverflow);
+        }
+
+        let mut buffer = [0; N];
+
+        buffer[..bytes.len()].copy_from_slice(bytes);
+
+        Ok(Self { inner: buffer })
+    }
+
+    /// Returns the bytes of the contained string, excluding the nul byte.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.inner[..self.len()]
+    }
+
     /// Converts the underlying buffer to a string slice if it contains valid UTF-8.
     ///
     /// # Examples
@@ -130,6 +193,23 @@ impl<const N: usize> FixedString<N> {
     pub fn to_string(&self) -> Result<String, Utf8Error> {
         self.to_str().map(|s| s.to_string())
     }
+
+    /// Converts the underlying buffer to a string, replacing invalid UTF-8 sequences
+    /// with [`U+FFFD REPLACEMENT CHARACTER`](char::REPLACEMENT_CHARACTER).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use lvd_lib::string::FixedString;
+    ///
+    /// let s = FixedString::<64>::try_from("curve4").unwrap();
+    /// assert_eq!(s.to_string_lossy(), "curve4");
+    /// ```
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.inner[..self.len()])
+    }
 }
 
 impl<const N: usize> Default for FixedString<N> {
@@ -142,17 +222,7 @@ impl<const N: usize> FromStr for FixedString<N> {
     type Err = FromStrError<N>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() >= N {
-            return Err(Self::Err::BufferOverflow);
-        }
-
-        let mut buffer = [0; N];
-
-        for (index, byte) in s.as_bytes().iter().copied().enumerate() {
-            buffer[index] = byte;
-        }
-
-        Ok(Self { inner: buffer })
+        Self::from_bytes(s.as_bytes())
     }
 }
 
@@ -186,6 +256,34 @@ impl<const N: usize> PartialEq for FixedString<N> {
     }
 }
 
+impl<const N: usize> Eq for FixedString<N> {}
+
+impl<const N: usize> Hash for FixedString<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
+impl<const N: usize> PartialOrd for FixedString<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for FixedString<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+/// Allows maps keyed by `FixedString` to be queried with the bytes of a string,
+/// such as `map.get("COL_00_Floor01".as_bytes())`.
+impl<const N: usize> Borrow<[u8]> for FixedString<N> {
+    fn borrow(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
 impl<const N: usize> PartialEq<&String> for FixedString<N> {
     fn eq(&self, other: &&String) -> bool {
         &self.inner[..self.len()] == other.as_bytes()
@@ -204,31 +302,43 @@ impl<const N: usize> PartialEq<String> for FixedString<N> {
     }
 }
 
+/// Reads the buffer in as few reads as possible, clearing any bytes following the nul byte.
+///
+/// As when the buffer was read a byte at a time, the bytes following the nul byte may be
+/// missing at the end of the stream, in which case the stream is still advanced past the buffer.
 #[binrw::parser(reader)]
 fn read_bytes<const N: usize>() -> BinResult<[u8; N]> {
-    use std::io::SeekFrom;
+    use std::io::{ErrorKind, SeekFrom};
 
     let pos = reader.stream_position()?;
     let mut buffer = [0; N];
-    let mut index = 0;
+    let mut filled = 0;
+
+    while filled != N {
+        match reader.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(count) => filled += count,
+            Err(error) if error.kind() == ErrorKind::Interrupted => {}
+            Err(error) => return Err(error.into()),
+        }
+    }
 
-    while index != N {
-        let b = u8::read(reader)?;
+    match memchr::memchr(0, &buffer[..filled]) {
+        Some(len) => {
+            buffer[len..].fill(0);
 
-        if b == 0 {
-            reader.seek(SeekFrom::Start(pos + N as u64))?;
+            if filled != N {
+                reader.seek(SeekFrom::Start(pos + N as u64))?;
+            }
 
-            return Ok(buffer);
+            Ok(buffer)
         }
-
-        buffer[index] = b;
-        index += 1;
+        None if filled != N => Err(binrw::Error::Io(ErrorKind::UnexpectedEof.into())),
+        None => Err(binrw::Error::AssertFail {
+            pos: reader.stream_position()?,
+            message: "unable to read beyond the end of the buffer".to_string(),
+        }),
     }
-
-    Err(binrw::Error::AssertFail {
-        pos: reader.stream_position()?,
-        message: "unable to read beyond the end of the buffer".to_string(),
-    })
 }
 
 #[cfg(feature = "serde")]
@@ -237,19 +347,145 @@ impl<const N: usize> Serialize for FixedString<N> {
     where
         S: Serializer,
     {
-        serializer.serialize_str(self.to_str().unwrap())
+        match self.to_str() {
+            Ok(string) => serializer.serialize_str(string),
+            Err(_) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+
+                map.serialize_entry(BYTES_KEY, &crate::raw::to_hex(self.as_bytes()))?;
+                map.end()
+            }
+        }
     }
 }
 
+/// The key holding the hexadecimal bytes of a string which is not valid UTF-8.
+#[cfg(feature = "serde")]
+const BYTES_KEY: &str = "bytes";
+
 #[cfg(feature = "serde")]
 impl<'de, const N: usize> Deserialize<'de> for FixedString<N> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let string = String::deserialize(deserializer)?;
+        struct FixedStringVisitor<const N: usize>;
+
+        impl<'de, const N: usize> Visitor<'de> for FixedStringVisitor<N> {
+            type Value = FixedString<N>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string or a mapping with hexadecimal `bytes`")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                FixedString::from_str(value).map_err(E::custom)
+            }
+
+            // Unquoted YAML scalars such as `5` or `true` are names too.
+            fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                self.visit_str(&value.to_string())
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                self.visit_str(&value.to_string())
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                self.visit_str(&value.to_string())
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                self.visit_str(&format!("{value:?}"))
+            }
 
-        Self::from_str(&string).map_err(serde::de::Error::custom)
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let mut bytes = None;
+
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        BYTES_KEY if bytes.is_none() => bytes = Some(map.next_value::<String>()?),
+                        BYTES_KEY => return Err(de::Error::duplicate_field(BYTES_KEY)),
+                        key => return Err(de::Error::unknown_field(key, &[BYTES_KEY])),
+                    }
+                }
+
+                let hex = bytes.ok_or_else(|| de::Error::missing_field(BYTES_KEY))?;
+                let bytes = crate::raw::from_hex(&hex)
+                    .ok_or_else(|| de::Error::custom("invalid hexadecimal string"))?;
+
+                FixedString::from_bytes(&bytes).map_err(de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_any(FixedStringVisitor)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<const N: usize> JsonSchema for FixedString<N> {
+    fn schema_name() -> String {
+        format!("FixedString{N}")
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        let bytes = SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            string: Some(Box::new(StringValidation {
+                max_length: Some(Self::CAPACITY as u32 * 2),
+                pattern: Some("^([0-9A-Fa-f]{2})*$".to_string()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+
+        SchemaObject {
+            subschemas: Some(Box::new(SubschemaValidation {
+                any_of: Some(vec![
+                    SchemaObject {
+                        instance_type: Some(InstanceType::String.into()),
+                        string: Some(Box::new(StringValidation {
+                            max_length: Some(Self::CAPACITY as u32),
+                            ..Default::default()
+                        })),
+                        ..Default::default()
+                    }
+                    .into(),
+                    SchemaObject {
+                        instance_type: Some(InstanceType::Object.into()),
+                        object: Some(Box::new(ObjectValidation {
+                            properties: [("bytes".to_string(), bytes.into())].into(),
+                            required: ["bytes".to_string()].into(),
+                            additional_properties: Some(Box::new(false.into())),
+                            ..Default::default()
+                        })),
+                        ..Default::default()
+                    }
+                    .into(),
+                ]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
     }
 }
 
@@ -265,6 +501,10 @@ pub enum FromStrError<const N: usize> {
     /// The nul-terminated string exceeds the buffer's capacity.
     #[error("nul-terminated string exceeds buffer capacity of {} bytes", N)]
     BufferOverflow,
+
+    /// The string contains a nul byte at the given position.
+    #[error("string contains a nul byte at position {0}")]
+    InteriorNul(usize),
 }
 
 #[cfg(test)]
@@ -310,6 +550,20 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn read_fixed_string_truncated_after_nul() {
+        let mut reader = Cursor::new(b"DEATH\0");
+        let value = reader.read_be_args::<FixedString<8>>((1,)).unwrap();
+
+        assert_eq!(value.to_string().unwrap(), "DEATH");
+        assert_eq!(reader.position(), 8);
+
+        let mut reader = Cursor::new(b"DEATH");
+        let result = reader.read_be_args::<FixedString<8>>((1,));
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn fixed_string_from_str() {
         // Test empty string.
@@ -326,6 +580,23 @@ mod tests {
         let s = "GeneralPoint3D__tag____0000_Kir";
         let value = FixedString::<24>::from_str(s);
         assert_eq!(value, Err(FromStrError::<24>::BufferOverflow));
+
+        // Test string with an interior nul byte.
+        let s = "COL\0curve1";
+        let value = FixedString::<16>::from_str(s);
+        assert_eq!(value, Err(FromStrError::<16>::InteriorNul(3)));
+    }
+
+    #[test]
+    fn fixed_string_from_bytes_interior_nul() {
+        assert_eq!(
+            FixedString::<8>::from_bytes(b"\x83\0\x41"),
+            Err(FromStrError::<8>::InteriorNul(1))
+        );
+        assert_eq!(
+            FixedString::<8>::from_bytes(b"curve1\0"),
+            Err(FromStrError::<8>::InteriorNul(6))
+        );
     }
 
     #[test]
@@ -338,6 +609,25 @@ mod tests {
         assert_eq!(writer.into_inner(), b"curve1\0\0");
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn fixed_string_serde_bytes() {
+        let value = FixedString::<8>::from_bytes(b"\x83\x41\x83\x43").unwrap();
+        let json = serde_json::to_string(&value).unwrap();
+
+        assert_eq!(json, r#"{"bytes":"83418343"}"#);
+        assert_eq!(
+            serde_json::from_str::<FixedString<8>>(&json).unwrap(),
+            value
+        );
+        assert_eq!(
+            serde_json::from_str::<FixedString<8>>(r#""curve1""#).unwrap(),
+            "curve1"
+        );
+        assert!(serde_json::from_str::<FixedString<8>>(r#"{"bytes":"8"}"#).is_err());
+        assert!(serde_json::from_str::<FixedString<8>>(r#"{"bytes":"830041"}"#).is_err());
+    }
+
     #[test]
     fn write_fixed_string_empty() {
         let value = FixedString::<8>::new();
@@ -0,0 +1,193 @@
+//! Machine-readable summaries of LVD files.
+//!
+//! This module contains the [`LvdFile::summary`] method and the [`Summary`] type, which lists
+//! the number of objects in each section, the bounds of the stage, the legality rules it passes,
+//! and its content hash, for tools such as mod submission bots and stage listings.
+
+use std::{collections::BTreeMap, fmt};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    array::Array, hash::ContentHash, legality::Rule, model::RectData, objects::Region,
+    version::Version, LvdFile, SectionKind,
+};
+
+/// An overview of the contents of an LVD file.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    /// The file format version.
+    pub version: u8,
+
+    /// The [content hash](ContentHash) of the file, serialized as a hexadecimal string.
+    #[cfg_attr(feature = "serde", serde(with = "hex"))]
+    pub hash: u64,
+
+    /// The number of objects in each section supported by the file's version.
+    pub sections: BTreeMap<SectionKind, usize>,
+
+    /// The smallest rectangle containing every vertex of every collision.
+    pub collision_bounds: Option<RectData>,
+
+    /// The bounds of the first camera region.
+    pub camera_bounds: Option<RectData>,
+
+    /// The bounds of the first death region, which are the blast zones of the stage.
+    pub blast_zones: Option<RectData>,
+
+    /// Whether the file passes each competitive legality rule.
+    pub legality: BTreeMap<Rule, bool>,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "version: {}", self.version)?;
+        writeln!(f, "hash: {:016x}", self.hash)?;
+
+        for (name, bounds) in [
+            ("collision bounds", &self.collision_bounds),
+            ("camera bounds", &self.camera_bounds),
+            ("blast zones", &self.blast_zones),
+        ] {
+            if let Some(bounds) = bounds {
+                writeln!(
+                    f,
+                    "{name}: left {}, right {}, top {}, bottom {}",
+                    bounds.left, bounds.right, bounds.top, bounds.bottom
+                )?;
+            }
+        }
+
+        for (kind, &count) in self.sections.iter().filter(|(_, &count)| count > 0) {
+            writeln!(f, "{kind}: {count}")?;
+        }
+
+        for (rule, &passed) in &self.legality {
+            writeln!(f, "{} {rule}", if passed { "PASS" } else { "FAIL" })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl LvdFile {
+    /// Returns an overview of the file, checking every legality rule with positions within
+    /// `tolerance` of each other treated as equal.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::LvdFile;
+    ///
+    /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    ///
+    /// print!("{}", file.summary(0.01));
+    /// ```
+    pub fn summary(&self, tolerance: f32) -> Summary {
+        let first_rect = |section: Option<&Array<Region>>| {
+            section
+                .and_then(|regions| regions.iter().next())
+                .map(|region| region.rect().into())
+        };
+        let report = self.check_legality(Rule::ALL, tolerance);
+
+        Summary {
+            version: self.data.version(),
+            hash: self.content_hash(),
+            sections: SectionKind::ALL
+                .iter()
+                .filter_map(|&kind| Some((kind, self.data.section_len(kind)?)))
+                .collect(),
+            collision_bounds: self.collision_bounds().map(RectData::from),
+            camera_bounds: first_rect(self.data.camera_regions()),
+            blast_zones: first_rect(self.data.death_regions()),
+            legality: Rule::ALL
+                .iter()
+                .map(|&rule| (rule, report.violations_of(rule).next().is_none()))
+                .collect(),
+        }
+    }
+}
+
+/// Serialization of 64-bit integers as hexadecimal strings, which unlike JSON numbers
+/// are read without loss of precision by every JSON library.
+#[cfg(feature = "serde")]
+mod hex {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{value:016x}"))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+
+        u64::from_str_radix(&hex, 16).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixtures::{file_with_collisions, platform_file},
+        objects::Region,
+        shape::Rect,
+        version::Versioned,
+        Lvd,
+    };
+
+    use super::*;
+
+    #[test]
+    fn summary_of_platform() {
+        let file = platform_file();
+        let summary = file.summary(0.01);
+
+        assert_eq!(summary.version, 13);
+        assert_eq!(summary.hash, file.content_hash());
+        assert_eq!(summary.sections[&SectionKind::Collisions], 1);
+        assert_eq!(summary.sections[&SectionKind::AreaHints], 0);
+        assert_eq!(
+            summary.collision_bounds,
+            Some(RectData {
+                left: -10.0,
+                right: 10.0,
+                top: 0.0,
+                bottom: 0.0
+            })
+        );
+        assert_eq!(summary.blast_zones, None);
+        assert!(summary.legality[&Rule::NoHazards]);
+    }
+
+    #[test]
+    fn summary_uses_first_region_and_supported_sections() {
+        let mut file = file_with_collisions([]);
+        let regions = file.data.death_regions_mut().unwrap();
+
+        for left in [-200.0, -300.0] {
+            let mut region = Region::default();
+
+            *region.rect_mut() = Rect::new(left, 200.0, 150.0, -100.0);
+            regions.push(region);
+        }
+
+        let summary = file.summary(0.01);
+
+        assert_eq!(summary.collision_bounds, None);
+        assert_eq!(summary.camera_bounds, None);
+        assert_eq!(summary.blast_zones.map(|bounds| bounds.left), Some(-200.0));
+        assert!(!summary.to_string().contains("collision bounds"));
+
+        let old = LvdFile {
+            data: Versioned::new(Lvd::empty(1).unwrap()),
+        }
+        .summary(0.01);
+
+        assert_eq!(old.version, 1);
+        assert!(!old.sections.contains_key(&SectionKind::AreaHints));
+        assert_eq!(old.sections[&SectionKind::Collisions], 0);
+    }
+}
@@ -0,0 +1,350 @@
+//! Enforcement of left/right symmetry.
+//!
+//! This module contains the [`LvdFile::mirror_enforce`] method, which replaces one half of a
+//! stage with the mirror image of the other, and the [`Side`] type naming each half.
+
+use crate::{
+    array::Array,
+    objects::{collision::attribute::AttributeFlags, Collision, LvdObject, Point, Region},
+    shape::Rect,
+    transform::Transform,
+    vector::{Vector2, Vector3},
+    version::{Version, Versioned},
+    LvdFile,
+};
+
+/// A half of a stage on either side of a vertical axis.
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum Side {
+    /// The half with lesser x-coordinates.
+    Left,
+
+    /// The half with greater x-coordinates.
+    Right,
+}
+
+impl Side {
+    /// Returns `true` if the x-coordinate lies strictly within the half.
+    fn contains(self, x: f32, axis_x: f32) -> bool {
+        match self {
+            Self::Left => x < axis_x,
+            Self::Right => x > axis_x,
+        }
+    }
+
+    /// Returns the opposite half.
+    fn opposite(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+/// The position of an object relative to the axis of symmetry.
+#[derive(Clone, Copy, PartialEq)]
+enum Placement {
+    /// The object lies entirely within the kept half.
+    Kept,
+
+    /// The object lies entirely within the replaced half.
+    Replaced,
+
+    /// The object touches or crosses the axis.
+    Crossing,
+}
+
+impl Placement {
+    /// Returns the placement of an object with the given x-coordinates.
+    fn of(xs: impl IntoIterator<Item = f32>, axis_x: f32, side: Side) -> Self {
+        let mut xs = xs.into_iter().peekable();
+
+        if xs.peek().is_none() {
+            return Self::Crossing;
+        }
+
+        let (mut kept, mut replaced) = (true, true);
+
+        for x in xs {
+            kept &= side.contains(x, axis_x);
+            replaced &= side.opposite().contains(x, axis_x);
+        }
+
+        match (kept, replaced) {
+            (true, _) => Self::Kept,
+            (_, true) => Self::Replaced,
+            _ => Self::Crossing,
+        }
+    }
+}
+
+impl LvdFile {
+    /// Replaces one half of the stage with the mirror image of the other half
+    /// across the vertical line `x = axis_x`, keeping the half given by `side`.
+    ///
+    /// Collisions and their cliffs, start and restart positions, and camera and death regions
+    /// are affected. Objects entirely within the replaced half are removed, and objects entirely
+    /// within the kept half gain a mirrored copy appended to their section. Objects touching or
+    /// crossing the axis are made symmetric in place: the replaced edge of a region is moved to
+    /// mirror the kept edge, each vertex of a collision within the replaced half is moved to
+    /// mirror the nearest one within the kept half, or onto the axis if nearer, and the cliffs
+    /// of a collision within the replaced half are replaced by mirrored copies of the others.
+    ///
+    /// Mirrored collisions have their normals, cliff directions, and left and right edge
+    /// attributes flipped. Other objects are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{symmetry::Side, LvdFile};
+    ///
+    /// let mut file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    ///
+    /// file.mirror_enforce(0.0, Side::Left);
+    /// ```
+    pub fn mirror_enforce(&mut self, axis_x: f32, side: Side) {
+        let mirror = Transform::from_translation(Vector3::new(-axis_x, 0.0, 0.0))
+            .then(&Transform::from_scale(Vector3::new(-1.0, 1.0, 1.0)))
+            .then(&Transform::from_translation(Vector3::new(axis_x, 0.0, 0.0)));
+
+        mirror_objects(
+            self.data.collisions_mut(),
+            &mirror,
+            |collision| Placement::of(collision.vertices().iter().map(Vector2::x), axis_x, side),
+            |collision| symmetrize_collision(collision, axis_x, side),
+        );
+
+        let mirror_points = |points: Option<&mut Array<Point>>| {
+            mirror_objects(
+                points,
+                &mirror,
+                |point| Placement::of([point.pos().x()], axis_x, side),
+                |_| {},
+            )
+        };
+
+        mirror_points(self.data.start_positions_mut());
+        mirror_points(self.data.restart_positions_mut());
+
+        let mirror_regions = |regions: Option<&mut Array<Region>>| {
+            mirror_objects(
+                regions,
+                &mirror,
+                |region| {
+                    let Rect::V1 { left, right, .. } = region.rect();
+
+                    Placement::of([left, right], axis_x, side)
+                },
+                |region| {
+                    let Rect::V1 { left, right, .. } = region.rect_mut();
+
+                    match side {
+                        Side::Left => *right = 2.0 * axis_x - *left,
+                        Side::Right => *left = 2.0 * axis_x - *right,
+                    }
+                },
+            )
+        };
+
+        mirror_regions(self.data.camera_regions_mut());
+        mirror_regions(self.data.death_regions_mut());
+        mirror_regions(self.data.shrinked_camera_regions_mut());
+        mirror_regions(self.data.shrinked_death_regions_mut());
+    }
+}
+
+/// Removes the objects of a section within the replaced half, appends a mirrored copy of the
+/// objects within the kept half, and makes the objects crossing the axis symmetric.
+fn mirror_objects<T: LvdObject + Version + Clone + Mirror>(
+    section: Option<&mut Array<T>>,
+    mirror: &Transform,
+    placement: impl Fn(&T) -> Placement,
+    symmetrize: impl Fn(&mut T),
+) {
+    let Some(section) = section else {
+        return;
+    };
+    let mut mirrored = Vec::new();
+
+    section
+        .elements_mut()
+        .retain(|object| placement(object) != Placement::Replaced);
+
+    for object in section.iter_mut() {
+        match placement(object) {
+            Placement::Kept => {
+                let mut copy = object.clone();
+
+                copy.transform(mirror);
+                copy.flip_direction();
+                mirrored.push(copy);
+            }
+            Placement::Crossing => symmetrize(object),
+            Placement::Replaced => unreachable!(),
+        }
+    }
+
+    for object in mirrored {
+        section.push(object);
+    }
+}
+
+/// A trait for flipping the left/right data of an object which a transform does not.
+trait Mirror {
+    /// Flips the left/right data of the object after mirroring its positions.
+    fn flip_direction(&mut self) {}
+}
+
+impl Mirror for Point {}
+
+impl Mirror for Region {}
+
+impl Mirror for Collision {
+    fn flip_direction(&mut self) {
+        for cliff in self.cliffs_mut().iter_mut() {
+            *cliff.lr_mut() = -cliff.lr();
+        }
+
+        if let Some(attributes) = self.attributes_mut() {
+            for attribute in attributes.iter_mut() {
+                flip_flags(attribute.flags_mut());
+            }
+        }
+    }
+}
+
+/// Swaps the left and right variants of each edge attribute.
+fn flip_flags(flags: &mut AttributeFlags) {
+    let old = *flags;
+
+    flags.set_left(old.right());
+    flags.set_right(old.left());
+    flags.set_hang_l(old.hang_r());
+    flags.set_hang_r(old.hang_l());
+    flags.set_ignore_link_from_left(old.ignore_link_from_right());
+    flags.set_ignore_link_from_right(old.ignore_link_from_left());
+    flags.set_virtual_hit_line_left(old.virtual_hit_line_right());
+    flags.set_virtual_hit_line_right(old.virtual_hit_line_left());
+}
+
+/// Moves each vertex of a collision within the replaced half to mirror the nearest one within
+/// the kept half, or onto the axis if nearer, recalculating the affected normals, and replaces
+/// the cliffs within the replaced half with mirrored copies of those within the kept half.
+fn symmetrize_collision(collision: &mut Collision, axis_x: f32, side: Side) {
+    let mirror = |v: Vector2| Vector2::new(2.0 * axis_x - v.x(), v.y());
+    let vertices = collision.vertices().iter().copied().collect::<Vec<_>>();
+    let targets = vertices
+        .iter()
+        .filter(|v| side.contains(v.x(), axis_x))
+        .map(|&v| mirror(v))
+        .collect::<Vec<_>>();
+    let mut moved = vec![false; vertices.len()];
+
+    for (index, vertex) in collision.vertices_mut().iter_mut().enumerate() {
+        if !side.opposite().contains(vertex.x(), axis_x) {
+            continue;
+        }
+
+        let on_axis = Vector2::new(axis_x, vertex.y());
+
+        *vertex = targets
+            .iter()
+            .copied()
+            .chain([on_axis])
+            .min_by(|a, b| (*a - *vertex).length().total_cmp(&(*b - *vertex).length()))
+            .unwrap();
+        moved[index] = true;
+    }
+
+    let vertices = collision.vertices().iter().copied().collect::<Vec<_>>();
+
+    for (edge, normal) in collision.normals_mut().iter_mut().enumerate() {
+        let (Some(&start), Some(&end)) = (vertices.get(edge), vertices.get(edge + 1)) else {
+            break;
+        };
+
+        if !moved[edge] && !moved[edge + 1] {
+            continue;
+        }
+
+        if let Some(direction) = (end - start).normalize() {
+            let perp = direction.perp();
+
+            *normal = if perp.dot(*normal) >= 0.0 {
+                perp
+            } else {
+                -perp
+            };
+        }
+    }
+
+    let edge_midpoints = vertices
+        .windows(2)
+        .map(|pair| (pair[0] + pair[1]) * 0.5)
+        .collect::<Vec<_>>();
+    let mut mirrored = Vec::new();
+
+    for cliff in collision.cliffs().iter() {
+        if !side.contains(cliff.pos().x(), axis_x) {
+            continue;
+        }
+
+        let mut copy = cliff.clone();
+
+        *copy.pos_mut() = mirror(cliff.pos());
+        *copy.lr_mut() = -cliff.lr();
+
+        if let Some(line_index) = copy.line_index_mut() {
+            if let Some(&midpoint) = edge_midpoints.get(*line_index as usize) {
+                let target = mirror(midpoint);
+
+                if let Some((edge, _)) =
+                    edge_midpoints.iter().enumerate().min_by(|(_, a), (_, b)| {
+                        (**a - target).length().total_cmp(&(**b - target).length())
+                    })
+                {
+                    *line_index = edge as u32;
+                }
+            }
+        }
+
+        mirrored.push(copy);
+    }
+
+    let cliffs = collision.cliffs_mut().elements_mut();
+
+    cliffs.retain(|cliff| !side.opposite().contains(cliff.pos().x(), axis_x));
+    cliffs.extend(mirrored.into_iter().map(Versioned::new));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placement_of_coordinates() {
+        assert!(Placement::of([-2.0, -1.0], 0.0, Side::Left) == Placement::Kept);
+        assert!(Placement::of([1.0, 2.0], 0.0, Side::Left) == Placement::Replaced);
+        assert!(Placement::of([-1.0, 1.0], 0.0, Side::Left) == Placement::Crossing);
+        assert!(Placement::of([0.0], 0.0, Side::Right) == Placement::Crossing);
+    }
+
+    #[test]
+    fn crossing_collision_is_symmetric() {
+        let mut collision =
+            Collision::new_soft_platform(Vector2::new(-10.0, 5.0), Vector2::new(9.5, 5.25));
+
+        symmetrize_collision(&mut collision, 0.0, Side::Left);
+
+        let vertices = collision.vertices().iter().copied().collect::<Vec<_>>();
+
+        assert_eq!(
+            vertices,
+            [Vector2::new(-10.0, 5.0), Vector2::new(10.0, 5.0)]
+        );
+        assert_eq!(
+            collision.normals().iter().next(),
+            Some(&Vector2::new(0.0, 1.0))
+        );
+    }
+}
@@ -10,6 +10,13 @@ use thiserror::Error;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+#[cfg(feature = "schemars")]
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{InstanceType, Schema, SchemaObject, StringValidation},
+    JsonSchema,
+};
+
 use crate::version::Version;
 
 /// An identifier for matching and filtering LVD objects.
@@ -85,6 +92,56 @@ impl Tag {
 
     /// The maximum supported number value.
     const NUMBER_MAX: u32 = 10000;
+
+    /// Returns the number of the tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::tag::Tag;
+    ///
+    /// let tag = Tag::try_from("IPP0012").unwrap();
+    /// assert_eq!(tag.number(), 12);
+    /// ```
+    pub const fn number(&self) -> u32 {
+        (self.0 & Self::NUMBER_MASK) % Self::NUMBER_MAX
+    }
+
+    /// Returns a copy of the tag with the same letters and the given number,
+    /// or `None` if the number exceeds the maximum supported value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::tag::Tag;
+    ///
+    /// let tag = Tag::try_from("IPP0012").unwrap();
+    /// assert_eq!(tag.with_number(13).unwrap().to_string(), "IPP0013");
+    /// assert!(tag.with_number(10000).is_none());
+    /// ```
+    pub const fn with_number(&self, number: u32) -> Option<Self> {
+        if number >= Self::NUMBER_MAX {
+            return None;
+        }
+
+        Some(Self((self.0 & !Self::NUMBER_MASK) | number))
+    }
+
+    /// Returns `true` if the string representation of the tag begins with the given prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::tag::Tag;
+    ///
+    /// let tag = Tag::try_from("IPP0012").unwrap();
+    /// assert!(tag.starts_with("IPP"));
+    /// assert!(tag.starts_with("IPP001"));
+    /// assert!(!tag.starts_with("FSP"));
+    /// ```
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.to_string().starts_with(prefix)
+    }
 }
 
 impl FromStr for Tag {
@@ -166,7 +223,7 @@ impl fmt::Display for Tag {
                 0 => '_',
                 c => ((c >> Self::LETTER_SHIFT[i]) as u8 + Self::LETTER_CHAR_MIN - 1) as char,
             });
-        let number = (self.0 & Self::NUMBER_MASK) % Self::NUMBER_MAX;
+        let number = self.number();
 
         write!(f, "{}{}{}{:04}", letters[0], letters[1], letters[2], number)
     }
@@ -194,6 +251,25 @@ impl<'de> Deserialize<'de> for Tag {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl JsonSchema for Tag {
+    fn schema_name() -> String {
+        "Tag".to_string()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            string: Some(Box::new(StringValidation {
+                pattern: Some("^[A-Z_]{3}[0-9]{4}$".to_string()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
 impl Version for Tag {
     fn version(&self) -> u8 {
         1
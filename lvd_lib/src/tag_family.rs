@@ -0,0 +1,253 @@
+//! Queries of the shapes sharing a family of tags.
+//!
+//! This module contains the [`LvdFile::shapes_with_tag_prefix`] method, which gathers the
+//! [`GeneralShape2`], [`GeneralShape3`], [`ItemPopup`], and [`FsItem`] objects whose tags begin
+//! with the same letters, such as every `IPP` tag, since stage logic looks shapes up by the family
+//! of their tag rather than by the section they are in.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    array::Array,
+    objects::{FsItem, GeneralShape2, GeneralShape3, ItemPopup, LvdObject},
+    tag::Tag,
+    version::Version,
+    LvdFile, ObjectRef, SectionKind,
+};
+
+/// The shapes of an [`LvdFile`] whose tags begin with the same prefix, grouped by section.
+///
+/// Each shape is paired with its index in its section, and the shapes of each section are
+/// in file order.
+#[derive(Debug, Clone, Default)]
+pub struct TaggedShapes<'a> {
+    /// The matching objects of the `general_shapes2` section.
+    pub general_shapes2: Vec<(usize, &'a GeneralShape2)>,
+
+    /// The matching objects of the `general_shapes3` section.
+    pub general_shapes3: Vec<(usize, &'a GeneralShape3)>,
+
+    /// The matching objects of the `item_popups` section.
+    pub item_popups: Vec<(usize, &'a ItemPopup)>,
+
+    /// The matching objects of the `fs_items` section.
+    pub fs_items: Vec<(usize, &'a FsItem)>,
+}
+
+impl TaggedShapes<'_> {
+    /// Returns the number of matching objects in every section.
+    pub fn len(&self) -> usize {
+        self.general_shapes2.len()
+            + self.general_shapes3.len()
+            + self.item_popups.len()
+            + self.fs_items.len()
+    }
+
+    /// Returns `true` if no object matched.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the positions of the matching objects, grouped by section in file order.
+    pub fn objects(&self) -> Vec<ObjectRef> {
+        let general_shapes2 = indices(&self.general_shapes2, SectionKind::GeneralShapes2);
+        let general_shapes3 = indices(&self.general_shapes3, SectionKind::GeneralShapes3);
+        let item_popups = indices(&self.item_popups, SectionKind::ItemPopups);
+        let fs_items = indices(&self.fs_items, SectionKind::FsItems);
+
+        general_shapes2
+            .chain(general_shapes3)
+            .chain(item_popups)
+            .chain(fs_items)
+            .collect()
+    }
+
+    /// Returns the positions of the matching objects by their full tag, in tag order.
+    ///
+    /// Objects sharing a tag across sections, such as an [`ItemPopup`] and the
+    /// [`GeneralShape2`] it is paired with, are listed under the same tag.
+    pub fn by_tag(&self) -> BTreeMap<Tag, Vec<ObjectRef>> {
+        let mut tags = BTreeMap::<Tag, Vec<ObjectRef>>::new();
+        let mut insert = |section, index, object: &dyn LvdObject| {
+            if let Some(&tag) = object.tag() {
+                tags.entry(tag)
+                    .or_default()
+                    .push(ObjectRef::new(section, index));
+            }
+        };
+
+        for &(index, object) in &self.general_shapes2 {
+            insert(SectionKind::GeneralShapes2, index, object);
+        }
+
+        for &(index, object) in &self.general_shapes3 {
+            insert(SectionKind::GeneralShapes3, index, object);
+        }
+
+        for &(index, object) in &self.item_popups {
+            insert(SectionKind::ItemPopups, index, object);
+        }
+
+        for &(index, object) in &self.fs_items {
+            insert(SectionKind::FsItems, index, object);
+        }
+
+        tags
+    }
+}
+
+impl LvdFile {
+    /// Returns the general shapes, item popups, and Final Smash items whose tags begin with the
+    /// given prefix, such as `"IPP"`.
+    ///
+    /// Sections the version of the file does not support are left empty.
+    /// An empty prefix matches every tagged shape.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{
+    ///     objects::{GeneralShape2, ItemPopup, LvdObject},
+    ///     tag::Tag,
+    ///     version::Versioned,
+    ///     Lvd, LvdFile,
+    /// };
+    ///
+    /// let mut data = Lvd::empty(13).unwrap();
+    /// let mut popup = ItemPopup::default();
+    /// let mut shape = GeneralShape2::default();
+    ///
+    /// *popup.tag_mut().unwrap() = Tag::try_from("IPP0001").unwrap();
+    /// *shape.tag_mut().unwrap() = Tag::try_from("GEN0001").unwrap();
+    /// data.item_popups_mut().unwrap().push(popup);
+    /// data.general_shapes2_mut().unwrap().push(shape);
+    ///
+    /// let file = LvdFile { data: Versioned::new(data) };
+    /// let shapes = file.shapes_with_tag_prefix("IPP");
+    ///
+    /// assert_eq!(shapes.len(), 1);
+    /// assert_eq!(shapes.item_popups[0].0, 0);
+    /// ```
+    pub fn shapes_with_tag_prefix(&self, prefix: &str) -> TaggedShapes<'_> {
+        TaggedShapes {
+            general_shapes2: matching(self.data.general_shapes2(), prefix),
+            general_shapes3: matching(self.data.general_shapes3(), prefix),
+            item_popups: matching(self.data.item_popups(), prefix),
+            fs_items: matching(self.data.fs_items(), prefix),
+        }
+    }
+}
+
+/// Returns the objects of a section whose tags begin with the given prefix,
+/// paired with their indices.
+fn matching<'a, T: LvdObject + Version>(
+    section: Option<&'a Array<T>>,
+    prefix: &str,
+) -> Vec<(usize, &'a T)> {
+    section
+        .into_iter()
+        .flat_map(Array::iter)
+        .enumerate()
+        .filter(|(_, object)| object.tag().is_some_and(|tag| tag.starts_with(prefix)))
+        .collect()
+}
+
+/// Returns the positions of the given objects of a section.
+fn indices<T>(
+    objects: &[(usize, T)],
+    section: SectionKind,
+) -> impl Iterator<Item = ObjectRef> + '_ {
+    objects
+        .iter()
+        .map(move |&(index, _)| ObjectRef::new(section, index))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures::file_with_collisions, version::Versioned, Lvd};
+
+    use super::*;
+
+    fn tagged<T: LvdObject + Default>(tag: &str) -> T {
+        let mut object = T::default();
+
+        *object.tag_mut().unwrap() = Tag::try_from(tag).unwrap();
+        object
+    }
+
+    #[test]
+    fn shapes_grouped_by_tag_family() {
+        let mut file = file_with_collisions([]);
+        let data = &mut file.data;
+
+        data.item_popups_mut()
+            .unwrap()
+            .push(tagged::<ItemPopup>("IPP0002"));
+        data.item_popups_mut()
+            .unwrap()
+            .push(tagged::<ItemPopup>("POP0001"));
+        data.general_shapes2_mut()
+            .unwrap()
+            .push(tagged::<GeneralShape2>("GEN0001"));
+        data.general_shapes2_mut()
+            .unwrap()
+            .push(tagged::<GeneralShape2>("IPP0001"));
+        data.general_shapes3_mut()
+            .unwrap()
+            .push(tagged::<GeneralShape3>("IPP0002"));
+        data.fs_items_mut()
+            .unwrap()
+            .push(tagged::<FsItem>("IPQ0001"));
+
+        let shapes = file.shapes_with_tag_prefix("IPP");
+
+        assert_eq!(shapes.len(), 3);
+        assert_eq!(
+            shapes.objects(),
+            [
+                ObjectRef::new(SectionKind::GeneralShapes2, 1),
+                ObjectRef::new(SectionKind::GeneralShapes3, 0),
+                ObjectRef::new(SectionKind::ItemPopups, 0),
+            ]
+        );
+        assert_eq!(
+            shapes.by_tag().into_iter().collect::<Vec<_>>(),
+            [
+                (
+                    Tag::try_from("IPP0001").unwrap(),
+                    vec![ObjectRef::new(SectionKind::GeneralShapes2, 1)]
+                ),
+                (
+                    Tag::try_from("IPP0002").unwrap(),
+                    vec![
+                        ObjectRef::new(SectionKind::GeneralShapes3, 0),
+                        ObjectRef::new(SectionKind::ItemPopups, 0),
+                    ]
+                ),
+            ]
+        );
+        assert_eq!(file.shapes_with_tag_prefix("").len(), 6);
+        assert!(file.shapes_with_tag_prefix("XYZ").is_empty());
+    }
+
+    #[test]
+    fn prefixes_match_whole_letters() {
+        let mut file = file_with_collisions([]);
+
+        file.data
+            .item_popups_mut()
+            .unwrap()
+            .push(tagged::<ItemPopup>("IPP0012"));
+
+        assert_eq!(file.shapes_with_tag_prefix("IPP001").len(), 1);
+        assert!(file.shapes_with_tag_prefix("ipp").is_empty());
+        assert!(file.shapes_with_tag_prefix("IPP00120").is_empty());
+
+        let old = LvdFile {
+            data: Versioned::new(Lvd::empty(1).unwrap()),
+        };
+
+        assert!(old.data.item_popups().is_none());
+        assert!(old.shapes_with_tag_prefix("").is_empty());
+    }
+}
@@ -0,0 +1,359 @@
+//! Generators of LVD data for testing.
+//!
+//! This module is enabled by the `test-utils` feature. It contains the [`LvdFile::sample_v13`] method
+//! and [`proptest`](mod@proptest) strategies for random objects, so transformations can be tested for preserving
+//! serializability without relying on files from the game.
+
+use std::str::FromStr;
+
+use proptest::{collection, prelude::*, sample};
+
+use crate::{
+    array::Array,
+    id::Id,
+    objects::{
+        base::Base,
+        collision::{
+            attribute::{AttributeFlags, MaterialType},
+            CollisionAttribute, CollisionCliff, CollisionFlags, CollisionSpiritsFloor,
+        },
+        *,
+    },
+    raw::UnknownSection,
+    shape::{Path, Rect, Shape2, Shape3, ShapeArray2, ShapeArrayElement2},
+    string::{FixedString32, FixedString56, FixedString64},
+    tag::Tag,
+    vector::{Vector2, Vector3},
+    version::{Version, Versioned},
+    Lvd, LvdFile,
+};
+
+impl LvdFile {
+    /// Returns a small version 13 file with one object of the latest version in every section.
+    ///
+    /// The contents of the file are the same on every call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    ///
+    /// use lvd_lib::LvdFile;
+    ///
+    /// let file = LvdFile::sample_v13();
+    /// let mut writer = Cursor::new(Vec::new());
+    ///
+    /// file.write(&mut writer).unwrap();
+    /// ```
+    pub fn sample_v13() -> Self {
+        let rect = Rect::V1 {
+            left: -100.0,
+            right: 100.0,
+            top: 100.0,
+            bottom: -50.0,
+        };
+        let shape2 = Shape2::Circle {
+            pos_x: 0.0,
+            pos_y: 20.0,
+            radius: 10.0,
+            path: Versioned::new(path(&[])),
+        };
+        let shape3 = Shape3::Sphere {
+            pos_x: 0.0,
+            pos_y: 20.0,
+            pos_z: 0.0,
+            radius: 10.0,
+        };
+        let shapes = ShapeArray2::V1 {
+            shapes: Versioned::new(Array::new(vec![ShapeArrayElement2(Versioned::new(
+                shape2.clone(),
+            ))])),
+        };
+        let cam_limit = FsCamLimit::V1 {
+            base: base("FsCamLimit"),
+            path: Versioned::new(path(&[(-100.0, 0.0), (100.0, 0.0)])),
+        };
+
+        Self {
+            data: Versioned::new(Lvd::V13 {
+                collisions: array(platform(
+                    "COL_Floor",
+                    &[(-50.0, 0.0), (50.0, 0.0), (50.0, -10.0), (-50.0, -10.0)],
+                )),
+                start_positions: array(Point::V2 {
+                    base: base("START_00"),
+                    pos: Versioned::new(Vector2::new(-20.0, 0.0)),
+                }),
+                restart_positions: array(Point::V2 {
+                    base: base("RESTART_00"),
+                    pos: Versioned::new(Vector2::new(0.0, 40.0)),
+                }),
+                camera_regions: array(Region::V2 {
+                    base: base("CAMERA_00"),
+                    rect: Versioned::new(rect),
+                }),
+                death_regions: array(Region::V2 {
+                    base: base("DEATH_00"),
+                    rect: Versioned::new(Rect::V1 {
+                        left: -200.0,
+                        right: 200.0,
+                        top: 200.0,
+                        bottom: -100.0,
+                    }),
+                }),
+                enemy_generators: array(EnemyGenerator::V3 {
+                    base: base("EnemyGenerator"),
+                    appear_shapes: Versioned::new(shapes.clone()),
+                    trigger_shapes: Versioned::new(shapes.clone()),
+                    unk1: Versioned::new(shapes.clone()),
+                    tag: tag("ENE0001"),
+                    appear_tags: Versioned::new(Array::new(
+                        vec![Tag::from_str("APP0001").unwrap()],
+                    )),
+                    unk2: Versioned::new(Array::default()),
+                    trigger_tags: Versioned::new(Array::new(vec![
+                        Tag::from_str("TRG0001").unwrap()
+                    ])),
+                }),
+                fs_items: array(FsItem::V1 {
+                    base: base("FsItem"),
+                    shape: Versioned::new(shape2.clone()),
+                    tag: tag("ITM0001"),
+                }),
+                fs_unknown: array(FsUnknown::V2 {
+                    base: base("FsUnknown"),
+                    unk1: Versioned::new(rect),
+                    unk2: Versioned::new(cam_limit.clone()),
+                    unk3: 0,
+                }),
+                fs_area_cams: array(FsAreaCam::V1 {
+                    region: Versioned::new(Region::V2 {
+                        base: base("FsAreaCam"),
+                        rect: Versioned::new(rect),
+                    }),
+                    unk: 0,
+                }),
+                fs_area_locks: array(FsAreaLock::V2 {
+                    base: base("FsAreaLock"),
+                    camera_region: Versioned::new(rect),
+                    trigger_region: Versioned::new(rect),
+                    unk1: 0,
+                    unk2: Versioned::new(Vector2::default()),
+                }),
+                fs_cam_limits: array(cam_limit),
+                damage_shapes: array(DamageShape::V1 {
+                    base: base("DamageShape"),
+                    shape: Versioned::new(shape3.clone()),
+                    is_damager: false,
+                    id: 1,
+                }),
+                item_popups: array(ItemPopup::V1 {
+                    base: base("ItemPopup"),
+                    tag: tag("POP0001"),
+                    shapes: Versioned::new(shapes),
+                }),
+                ptrainer_ranges: array(PTrainerRange::V4 {
+                    base: base("PTrainerRange"),
+                    range_min: Versioned::new(Vector3::new(-50.0, 0.0, 0.0)),
+                    range_max: Versioned::new(Vector3::new(50.0, 0.0, 0.0)),
+                    trainers: Versioned::new(Array::new(vec![Vector3::new(0.0, 0.0, -20.0)])),
+                    parent_model_name: Versioned::new(FixedString64::new()),
+                    parent_joint_name: Versioned::new(FixedString64::new()),
+                }),
+                ptrainer_floating_floors: array(PTrainerFloatingFloor::V1 {
+                    base: base("PTrainerFloatingFloor"),
+                    pos: Versioned::new(Vector3::new(0.0, 30.0, 0.0)),
+                }),
+                general_shapes2: array(GeneralShape2::V1 {
+                    base: base("GeneralShape2"),
+                    tag: tag("GEN0001"),
+                    shape: Versioned::new(shape2.clone()),
+                }),
+                general_shapes3: array(GeneralShape3::V1 {
+                    base: base("GeneralShape3"),
+                    tag: tag("GEN0002"),
+                    shape: Versioned::new(shape3.clone()),
+                }),
+                area_lights: array(AreaLight::V2 {
+                    base: base("AreaLight"),
+                    shape: Versioned::new(shape2),
+                    unk1: Versioned::new(FixedString32::new()),
+                    unk2: Versioned::new(FixedString32::new()),
+                }),
+                fs_start_points: array(FsStartPoint::V1 {
+                    base: base("FsStartPoint"),
+                    pos: Versioned::new(Vector2::new(20.0, 0.0)),
+                    id: Versioned::new(Id(1)),
+                }),
+                area_hints: array(AreaHint::V3 {
+                    base: base("AreaHint"),
+                    shape: Versioned::new(shape3.clone()),
+                    unk1: 0,
+                    unk2: 0,
+                    unk3: 0,
+                    unk4: 0,
+                    unk5: 0,
+                    unk6: 0,
+                    unk7: 0,
+                }),
+                split_areas: array(SplitArea::V1 {
+                    base: base("SplitArea"),
+                    shape: Versioned::new(shape3),
+                }),
+                shrinked_camera_regions: array(Region::V2 {
+                    base: base("CAMERA_00"),
+                    rect: Versioned::new(rect),
+                }),
+                shrinked_death_regions: array(Region::V2 {
+                    base: base("DEATH_00"),
+                    rect: Versioned::new(rect),
+                }),
+                unknown_sections: UnknownSection::default(),
+            }),
+        }
+    }
+}
+
+/// Returns a strategy generating two-dimensional vectors within a stage-sized range.
+pub fn vector2() -> impl Strategy<Value = Vector2> {
+    (-500.0f32..500.0, -500.0f32..500.0).prop_map(|(x, y)| Vector2::new(x, y))
+}
+
+/// Returns a strategy generating collisions of the latest version.
+///
+/// Each collision is an open chain of between one and sixteen edges with consistent normals,
+/// and random materials and attributes for each edge.
+pub fn collision() -> impl Strategy<Value = Collision> {
+    (collection::vec(vector2(), 2..=17), any::<bool>())
+        .prop_flat_map(|(vertices, throughable)| {
+            let edge_count = vertices.len() - 1;
+            let attributes = collection::vec(
+                (sample::select(MaterialType::ALL), any::<u32>()),
+                edge_count,
+            );
+
+            (Just(vertices), Just(throughable), attributes)
+        })
+        .prop_map(|(vertices, throughable, attributes)| {
+            let mut collision = platform("COL_Random", &[]);
+
+            collision.flags_mut().set_throughable(throughable);
+            *collision.normals_mut() = vertices.windows(2).map(|e| normal(&e[0], &e[1])).collect();
+            *collision.vertices_mut() = vertices.into_iter().collect();
+            *collision.attributes_mut().unwrap() = attributes
+                .into_iter()
+                .map(|(material, flags)| CollisionAttribute::V1 {
+                    material,
+                    flags: AttributeFlags::from_bytes(u64::from(flags).to_le_bytes()),
+                })
+                .collect();
+
+            collision
+        })
+}
+
+/// Returns a strategy generating version 13 files containing the objects of
+/// [`LvdFile::sample_v13`] and a random collection of collisions.
+pub fn lvd_file() -> impl Strategy<Value = LvdFile> {
+    collection::vec(collision(), 0..8).prop_map(|collisions| {
+        let mut file = LvdFile::sample_v13();
+
+        *file.data.collisions_mut().unwrap() = collisions.into_iter().collect();
+
+        file
+    })
+}
+
+/// Returns a closed collision with the given name and corner positions,
+/// with edges ordered clockwise starting from the top edge.
+fn platform(name: &str, corners: &[(f32, f32)]) -> Collision {
+    let vertices = corners
+        .iter()
+        .chain(corners.first())
+        .map(|&(x, y)| Vector2::new(x, y))
+        .collect::<Vec<_>>();
+    let normals = vertices
+        .windows(2)
+        .map(|edge| normal(&edge[0], &edge[1]))
+        .collect::<Array<_>>();
+    let attributes = (0..normals.len())
+        .map(|_| CollisionAttribute::V1 {
+            material: MaterialType::Rock,
+            flags: AttributeFlags::new(),
+        })
+        .collect::<Array<_>>();
+    let cliffs = vertices
+        .first()
+        .map(|pos| CollisionCliff::V3 {
+            base: Versioned::new(Base::default()),
+            pos: Versioned::new(*pos),
+            lr: -1.0,
+            line_index: 0,
+        })
+        .into_iter()
+        .collect();
+    let spirits_floors = if normals.is_empty() {
+        Array::default()
+    } else {
+        Array::new(vec![CollisionSpiritsFloor::V2 {
+            base: Versioned::new(Base::default()),
+            line_index: 0,
+            line_group: Versioned::new(FixedString64::from_str("SpiritsFloor").unwrap()),
+            unk1: 1.0,
+            unk2: 1.0,
+            unk3: 1.0,
+            unk4: 1.0,
+            unk5: 0.0,
+            unk6: 0.0,
+        }])
+    };
+
+    Collision::V4 {
+        base: base(name),
+        flags: CollisionFlags::new(),
+        vertices: Versioned::new(vertices.into_iter().collect()),
+        normals: Versioned::new(normals),
+        cliffs: Versioned::new(cliffs),
+        attributes: Versioned::new(attributes),
+        spirits_floors: Versioned::new(spirits_floors),
+    }
+}
+
+/// Returns the unit normal vector of the edge between the given vertices,
+/// pointing to the left of the edge's direction.
+fn normal(start: &Vector2, end: &Vector2) -> Vector2 {
+    let (dx, dy) = (end.x() - start.x(), end.y() - start.y());
+    let length = dx.hypot(dy);
+
+    if length > 0.0 {
+        Vector2::new(-dy / length, dx / length)
+    } else {
+        Vector2::new(0.0, 1.0)
+    }
+}
+
+/// Returns the common data of an object with the given name.
+fn base(name: &str) -> Versioned<Base> {
+    let mut base = Base::default();
+
+    *base.meta_info_mut().name_mut() = FixedString56::from_str(name).unwrap();
+
+    Versioned::new(base)
+}
+
+/// Returns an identifier for matching and filtering like objects from the given string.
+fn tag(tag: &str) -> Versioned<Tag> {
+    Versioned::new(Tag::from_str(tag).unwrap())
+}
+
+/// Returns a path through the given points.
+fn path(points: &[(f32, f32)]) -> Path {
+    Path::V1 {
+        points: Versioned::new(points.iter().map(|&(x, y)| Vector2::new(x, y)).collect()),
+    }
+}
+
+/// Returns a section containing only the given object.
+fn array<T: Version>(object: T) -> Versioned<Array<T>> {
+    Versioned::new(Array::new(vec![object]))
+}
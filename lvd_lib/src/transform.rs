@@ -0,0 +1,695 @@
+//! Coordinate-space conversion of LVD objects.
+//!
+//! This module contains the [`Transform`] type, the [`CoordinateMap`] trait for mappings applied
+//! to objects, the [`LvdFile::transform_joint`] method for baking a model joint's transform into
+//! the objects bound to that joint, the [`UnitScale`] type and [`LvdFile::scale_from_game`] and
+//! [`LvdFile::scale_to_game`] methods for converting between in-game units and those of other
+//! programs, and the `transform` methods of the shape types.
+
+use crate::{
+    shape::{Path, Rect, Shape2, Shape3, ShapeArray2},
+    vector::{Vector2, Vector3},
+    LvdFile,
+};
+
+/// An affine transformation in three-dimensional space, such as the world transform of a model joint.
+///
+/// Two-dimensional positions are treated as lying on the plane where z is zero,
+/// and are projected back onto that plane after transforming.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    /// The rows of the matrix, which transforms column vectors.
+    rows: [[f32; 4]; 4],
+}
+
+impl Transform {
+    /// The transformation which leaves every position unchanged.
+    pub const IDENTITY: Self = Self {
+        rows: [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ],
+    };
+
+    /// Creates a new `Transform` from the rows of a matrix which transforms column vectors.
+    ///
+    /// The translation is stored in the last column. The last row is ignored.
+    pub const fn from_rows(rows: [[f32; 4]; 4]) -> Self {
+        Self { rows }
+    }
+
+    /// Creates a new `Transform` from the columns of a matrix which transforms column vectors,
+    /// the layout used by most model formats and math libraries.
+    ///
+    /// The translation is stored in the last column. The last row is ignored.
+    pub const fn from_columns(columns: [[f32; 4]; 4]) -> Self {
+        let mut rows = [[0.0; 4]; 4];
+        let mut i = 0;
+
+        while i < 4 {
+            let mut j = 0;
+
+            while j < 4 {
+                rows[i][j] = columns[j][i];
+                j += 1;
+            }
+
+            i += 1;
+        }
+
+        Self { rows }
+    }
+
+    /// Returns the columns of the matrix, the layout accepted by [`Transform::from_columns`].
+    pub const fn to_columns(&self) -> [[f32; 4]; 4] {
+        let mut columns = [[0.0; 4]; 4];
+        let mut i = 0;
+
+        while i < 4 {
+            let mut j = 0;
+
+            while j < 4 {
+                columns[j][i] = self.rows[i][j];
+                j += 1;
+            }
+
+            i += 1;
+        }
+
+        columns
+    }
+
+    /// Creates a new `Transform` which moves positions by the given offset.
+    pub const fn from_translation(translation: Vector3) -> Self {
+        let mut transform = Self::IDENTITY;
+
+        transform.rows[0][3] = translation.x();
+        transform.rows[1][3] = translation.y();
+        transform.rows[2][3] = translation.z();
+
+        transform
+    }
+
+    /// Creates a new `Transform` which scales positions along each axis.
+    pub const fn from_scale(scale: Vector3) -> Self {
+        let mut transform = Self::IDENTITY;
+
+        transform.rows[0][0] = scale.x();
+        transform.rows[1][1] = scale.y();
+        transform.rows[2][2] = scale.z();
+
+        transform
+    }
+
+    /// Creates a new `Transform` which rotates positions counterclockwise
+    /// about the z-axis by the given angle in radians.
+    pub fn from_rotation_z(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut transform = Self::IDENTITY;
+
+        transform.rows[0][0] = cos;
+        transform.rows[0][1] = -sin;
+        transform.rows[1][0] = sin;
+        transform.rows[1][1] = cos;
+
+        transform
+    }
+
+    /// Returns the transformation which applies `self` followed by `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{transform::Transform, vector::{Vector2, Vector3}};
+    ///
+    /// let transform = Transform::from_scale(Vector3::new(2.0, 2.0, 1.0))
+    ///     .then(&Transform::from_translation(Vector3::new(10.0, 0.0, 0.0)));
+    ///
+    /// assert_eq!(transform.transform_point2(Vector2::new(1.0, 1.0)), Vector2::new(12.0, 2.0));
+    /// ```
+    pub fn then(&self, other: &Self) -> Self {
+        let mut rows = [[0.0; 4]; 4];
+
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..4).map(|k| other.rows[i][k] * self.rows[k][j]).sum();
+            }
+        }
+
+        Self { rows }
+    }
+
+    /// Returns the transformation which undoes `self`, or `None` if `self` collapses
+    /// positions onto a plane, line, or point.
+    ///
+    /// This can be used to move objects authored in world space back into a joint's space.
+    pub fn inverse(&self) -> Option<Self> {
+        let m = &self.rows;
+        let cofactor = |r0: usize, r1: usize, c0: usize, c1: usize| {
+            m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+        };
+        // The transposed cofactor matrix of the linear part.
+        let adjugate = [
+            [
+                cofactor(1, 2, 1, 2),
+                -cofactor(0, 2, 1, 2),
+                cofactor(0, 1, 1, 2),
+            ],
+            [
+                -cofactor(1, 2, 0, 2),
+                cofactor(0, 2, 0, 2),
+                -cofactor(0, 1, 0, 2),
+            ],
+            [
+                cofactor(1, 2, 0, 1),
+                -cofactor(0, 2, 0, 1),
+                cofactor(0, 1, 0, 1),
+            ],
+        ];
+        let determinant = self.determinant3();
+
+        if determinant.abs() < f32::EPSILON {
+            return None;
+        }
+
+        let mut inverse = Self::IDENTITY;
+
+        for (row, adjugate_row) in inverse.rows.iter_mut().zip(adjugate) {
+            for (value, adjugate_value) in row.iter_mut().zip(adjugate_row) {
+                *value = adjugate_value / determinant;
+            }
+        }
+
+        for i in 0..3 {
+            inverse.rows[i][3] = -(0..3).map(|k| inverse.rows[i][k] * m[k][3]).sum::<f32>();
+        }
+
+        Some(inverse)
+    }
+
+    /// Returns the result of transforming a three-dimensional position.
+    pub fn transform_point3(&self, point: Vector3) -> Vector3 {
+        let [x, y, z] = self.apply([point.x(), point.y(), point.z()], 1.0);
+
+        Vector3::new(x, y, z)
+    }
+
+    /// Returns the result of transforming a three-dimensional direction, ignoring the translation.
+    pub fn transform_vector3(&self, vector: Vector3) -> Vector3 {
+        let [x, y, z] = self.apply([vector.x(), vector.y(), vector.z()], 0.0);
+
+        Vector3::new(x, y, z)
+    }
+
+    /// Returns the result of transforming a two-dimensional position.
+    pub fn transform_point2(&self, point: Vector2) -> Vector2 {
+        let [x, y, _] = self.apply([point.x(), point.y(), 0.0], 1.0);
+
+        Vector2::new(x, y)
+    }
+
+    /// Returns the result of transforming a two-dimensional direction, ignoring the translation.
+    pub fn transform_vector2(&self, vector: Vector2) -> Vector2 {
+        let [x, y, _] = self.apply([vector.x(), vector.y(), 0.0], 0.0);
+
+        Vector2::new(x, y)
+    }
+
+    /// Returns the result of transforming a two-dimensional surface normal,
+    /// which remains perpendicular to the transformed surface and has a length of one.
+    ///
+    /// The normal is returned unchanged if the transformation collapses the plane.
+    pub fn transform_normal2(&self, normal: Vector2) -> Vector2 {
+        let m = &self.rows;
+        let determinant = m[0][0] * m[1][1] - m[0][1] * m[1][0];
+
+        if determinant.abs() < f32::EPSILON {
+            return normal;
+        }
+
+        // The inverse transpose of the linear part, scaled by the determinant.
+        let transformed = Vector2::new(
+            (m[1][1] * normal.x() - m[1][0] * normal.y()) * determinant.signum(),
+            (m[0][0] * normal.y() - m[0][1] * normal.x()) * determinant.signum(),
+        );
+
+        transformed.normalize().unwrap_or(normal)
+    }
+
+    /// Returns the factor by which the transformation scales lengths on the plane
+    /// where z is zero, averaged over every direction.
+    fn scale2(&self) -> f32 {
+        let m = &self.rows;
+
+        (m[0][0] * m[1][1] - m[0][1] * m[1][0]).abs().sqrt()
+    }
+
+    /// Returns the factor by which the transformation scales lengths, averaged over every direction.
+    fn scale3(&self) -> f32 {
+        self.determinant3().abs().cbrt()
+    }
+
+    /// Returns the determinant of the linear part of the transformation.
+    fn determinant3(&self) -> f32 {
+        let m = &self.rows;
+
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    fn apply(&self, v: [f32; 3], w: f32) -> [f32; 3] {
+        let row = |i: usize| {
+            let r = &self.rows[i];
+
+            r[0] * v[0] + r[1] * v[1] + r[2] * v[2] + r[3] * w
+        };
+
+        [row(0), row(1), row(2)]
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// The scale between in-game units and the units of another program, such as the meters used
+/// by a modelling program, given as the number of the other units in one in-game unit.
+///
+/// The scale must not be zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitScale(pub f32);
+
+impl UnitScale {
+    /// The scale of programs using in-game units.
+    pub const GAME: Self = Self(1.0);
+
+    /// Returns the transformation converting in-game units to the other units.
+    pub const fn from_game(self) -> Transform {
+        Transform::from_scale(Vector3::new(self.0, self.0, self.0))
+    }
+
+    /// Returns the transformation converting the other units to in-game units.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::{transform::UnitScale, vector::Vector2};
+    ///
+    /// let scale = UnitScale(0.1);
+    ///
+    /// assert_eq!(scale.to_game().transform_point2(Vector2::new(1.0, 2.0)), Vector2::new(10.0, 20.0));
+    /// ```
+    pub fn to_game(self) -> Transform {
+        let factor = 1.0 / self.0;
+
+        Transform::from_scale(Vector3::new(factor, factor, factor))
+    }
+}
+
+impl Default for UnitScale {
+    fn default() -> Self {
+        Self::GAME
+    }
+}
+
+impl LvdFile {
+    /// Converts the positions and sizes of every object from in-game units to the units of
+    /// the scale, such as before exporting the file alongside a model in those units.
+    ///
+    /// As with [`LvdObject::transform`](crate::objects::LvdObject::transform), objects exclusive
+    /// to Smash Run are left unchanged.
+    pub fn scale_from_game(&mut self, scale: UnitScale) {
+        let transform = scale.from_game();
+
+        self.data
+            .for_each_object_mut(|object| object.transform(&transform));
+    }
+
+    /// Converts the positions and sizes of every object from the units of the scale to in-game
+    /// units, undoing [`LvdFile::scale_from_game`].
+    ///
+    /// As with [`LvdObject::transform`](crate::objects::LvdObject::transform), objects exclusive
+    /// to Smash Run are left unchanged.
+    pub fn scale_to_game(&mut self, scale: UnitScale) {
+        let transform = scale.to_game();
+
+        self.data
+            .for_each_object_mut(|object| object.transform(&transform));
+    }
+
+    /// Applies the transform to every object bound to the joint with the given name.
+    ///
+    /// This bakes objects authored relative to a model joint into the world space of the
+    /// stage model, or moves world space objects into the joint's space when given the
+    /// [`inverse`](Transform::inverse) of the joint's transform. The binding of each object
+    /// is left unchanged. The number of transformed objects is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{transform::Transform, vector::Vector3, LvdFile};
+    ///
+    /// let mut file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    /// let transform = Transform::from_translation(Vector3::new(0.0, 20.0, 0.0));
+    /// let count = file.transform_joint("platform_joint", &transform);
+    /// ```
+    pub fn transform_joint(&mut self, joint_name: &str, transform: &Transform) -> usize {
+        let mut count = 0;
+
+        self.data.for_each_object_mut(|object| {
+            let is_bound = object
+                .base()
+                .and_then(|base| base.joint_name())
+                .and_then(|name| name.to_str().ok())
+                .is_some_and(|name| !name.is_empty() && name == joint_name);
+
+            if is_bound {
+                object.transform(transform);
+                count += 1;
+            }
+        });
+
+        count
+    }
+}
+
+/// A mapping of the positions, directions, and sizes of objects,
+/// applied with [`LvdObject::transform`](crate::objects::LvdObject::transform)
+/// and the `transform` methods of the shape types.
+///
+/// This is implemented by [`Transform`] for affine transformations.
+pub trait CoordinateMap {
+    /// Returns the result of mapping a two-dimensional position.
+    fn map_point2(&self, point: Vector2) -> Vector2;
+
+    /// Returns the result of mapping a three-dimensional position.
+    fn map_point3(&self, point: Vector3) -> Vector3;
+
+    /// Returns the result of mapping a three-dimensional direction.
+    fn map_vector3(&self, vector: Vector3) -> Vector3;
+
+    /// Returns the result of mapping a two-dimensional surface normal.
+    fn map_normal2(&self, normal: Vector2) -> Vector2;
+
+    /// Returns the result of mapping a length on the plane where z is zero, such as a radius.
+    fn map_length2(&self, length: f32) -> f32;
+
+    /// Returns the result of mapping a three-dimensional length, such as a radius.
+    fn map_length3(&self, length: f32) -> f32;
+}
+
+impl CoordinateMap for Transform {
+    fn map_point2(&self, point: Vector2) -> Vector2 {
+        self.transform_point2(point)
+    }
+
+    fn map_point3(&self, point: Vector3) -> Vector3 {
+        self.transform_point3(point)
+    }
+
+    fn map_vector3(&self, vector: Vector3) -> Vector3 {
+        self.transform_vector3(vector)
+    }
+
+    fn map_normal2(&self, normal: Vector2) -> Vector2 {
+        self.transform_normal2(normal)
+    }
+
+    fn map_length2(&self, length: f32) -> f32 {
+        length * self.scale2()
+    }
+
+    fn map_length3(&self, length: f32) -> f32 {
+        length * self.scale3()
+    }
+}
+
+impl Rect {
+    /// Replaces the rectangle with the bounds of its transformed corners.
+    pub fn transform(&mut self, transform: &dyn CoordinateMap) {
+        let Self::V1 {
+            left,
+            right,
+            top,
+            bottom,
+        } = self;
+        let corners = [
+            (*left, *top),
+            (*right, *top),
+            (*left, *bottom),
+            (*right, *bottom),
+        ]
+        .map(|(x, y)| transform.map_point2(Vector2::new(x, y)));
+
+        *left = corners.iter().map(Vector2::x).fold(f32::INFINITY, f32::min);
+        *right = corners
+            .iter()
+            .map(Vector2::x)
+            .fold(f32::NEG_INFINITY, f32::max);
+        *top = corners
+            .iter()
+            .map(Vector2::y)
+            .fold(f32::NEG_INFINITY, f32::max);
+        *bottom = corners.iter().map(Vector2::y).fold(f32::INFINITY, f32::min);
+    }
+}
+
+impl Path {
+    /// Transforms every point of the path.
+    pub fn transform(&mut self, transform: &dyn CoordinateMap) {
+        match self {
+            Self::V1 { points } => {
+                for point in points.iter_mut() {
+                    *point = transform.map_point2(*point);
+                }
+            }
+        }
+    }
+}
+
+impl Shape2 {
+    /// Transforms the shape.
+    ///
+    /// Rectangles are replaced with the bounds of their transformed corners,
+    /// and the radius of circles is scaled by the average scale of the transform.
+    /// Shapes of unknown types are left unchanged.
+    pub fn transform(&mut self, transform: &dyn CoordinateMap) {
+        let pos = |x: &mut f32, y: &mut f32| {
+            let point = transform.map_point2(Vector2::new(*x, *y));
+
+            *x = point.x();
+            *y = point.y();
+        };
+
+        match self {
+            Self::Point { pos_x, pos_y, path } => {
+                pos(pos_x, pos_y);
+                path.transform(transform);
+            }
+            Self::Circle {
+                pos_x,
+                pos_y,
+                radius,
+                path,
+            } => {
+                pos(pos_x, pos_y);
+                *radius = transform.map_length2(*radius);
+                path.transform(transform);
+            }
+            Self::Rect {
+                left,
+                right,
+                bottom,
+                top,
+                path,
+            } => {
+                let mut rect = Rect::V1 {
+                    left: *left,
+                    right: *right,
+                    top: *top,
+                    bottom: *bottom,
+                };
+
+                rect.transform(transform);
+
+                let Rect::V1 {
+                    left: new_left,
+                    right: new_right,
+                    top: new_top,
+                    bottom: new_bottom,
+                } = rect;
+
+                (*left, *right, *top, *bottom) = (new_left, new_right, new_top, new_bottom);
+                path.transform(transform);
+            }
+            Self::Path { path } => path.transform(transform),
+            Self::Unknown { .. } => {}
+        }
+    }
+}
+
+impl ShapeArray2 {
+    /// Transforms every shape in the collection.
+    pub fn transform(&mut self, transform: &dyn CoordinateMap) {
+        match self {
+            Self::V1 { shapes } => {
+                for element in shapes.iter_mut() {
+                    element.0.transform(transform);
+                }
+            }
+        }
+    }
+}
+
+impl Shape3 {
+    /// Transforms the shape.
+    ///
+    /// Boxes are replaced with the bounds of their transformed corners,
+    /// and the radius of spheres and capsules is scaled by the average scale of the transform.
+    /// Shapes of unknown types are left unchanged.
+    pub fn transform(&mut self, transform: &dyn CoordinateMap) {
+        let pos = |x: &mut f32, y: &mut f32, z: &mut f32| {
+            let point = transform.map_point3(Vector3::new(*x, *y, *z));
+
+            (*x, *y, *z) = (point.x(), point.y(), point.z());
+        };
+
+        match self {
+            Self::Box {
+                left,
+                right,
+                bottom,
+                top,
+                back,
+                front,
+            } => {
+                let mut corners = Vec::with_capacity(8);
+
+                for x in [*left, *right] {
+                    for y in [*bottom, *top] {
+                        for z in [*back, *front] {
+                            corners.push(transform.map_point3(Vector3::new(x, y, z)));
+                        }
+                    }
+                }
+
+                let min =
+                    |f: fn(&Vector3) -> f32| corners.iter().map(f).fold(f32::INFINITY, f32::min);
+                let max = |f: fn(&Vector3) -> f32| {
+                    corners.iter().map(f).fold(f32::NEG_INFINITY, f32::max)
+                };
+
+                (*left, *right) = (min(Vector3::x), max(Vector3::x));
+                (*bottom, *top) = (min(Vector3::y), max(Vector3::y));
+                (*back, *front) = (min(Vector3::z), max(Vector3::z));
+            }
+            Self::Sphere {
+                pos_x,
+                pos_y,
+                pos_z,
+                radius,
+            } => {
+                pos(pos_x, pos_y, pos_z);
+                *radius = transform.map_length3(*radius);
+            }
+            Self::Capsule {
+                pos_x,
+                pos_y,
+                pos_z,
+                vec_x,
+                vec_y,
+                vec_z,
+                radius,
+            } => {
+                let vec = transform.map_vector3(Vector3::new(*vec_x, *vec_y, *vec_z));
+
+                pos(pos_x, pos_y, pos_z);
+                (*vec_x, *vec_y, *vec_z) = (vec.x(), vec.y(), vec.z());
+                *radius = transform.map_length3(*radius);
+            }
+            Self::Point {
+                pos_x,
+                pos_y,
+                pos_z,
+            } => pos(pos_x, pos_y, pos_z),
+            Self::Unknown { .. } => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: Vector2, b: Vector2) {
+        assert!((a - b).length() < 1e-4, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn inverse_undoes_transform() {
+        let transform = Transform::from_scale(Vector3::new(2.0, 3.0, 1.0))
+            .then(&Transform::from_rotation_z(0.5))
+            .then(&Transform::from_translation(Vector3::new(5.0, -2.0, 1.0)));
+        let inverse = transform.inverse().unwrap();
+        let point = Vector2::new(1.5, -4.0);
+
+        assert_near(
+            inverse.transform_point2(transform.transform_point2(point)),
+            point,
+        );
+        assert!(Transform::from_scale(Vector3::new(1.0, 0.0, 1.0))
+            .inverse()
+            .is_none());
+    }
+
+    #[test]
+    fn normal_stays_perpendicular() {
+        let transform = Transform::from_scale(Vector3::new(4.0, 1.0, 1.0));
+        let edge = Vector2::new(1.0, 1.0);
+        let normal = edge.perp().normalize().unwrap();
+        let transformed = transform.transform_normal2(normal);
+
+        assert!(transform.transform_vector2(edge).dot(transformed).abs() < 1e-5);
+        assert!(transformed.dot(normal) > 0.0);
+
+        let mirror = Transform::from_scale(Vector3::new(-1.0, 1.0, 1.0));
+
+        assert_near(
+            mirror.transform_normal2(Vector2::new(1.0, 0.0)),
+            Vector2::new(-1.0, 0.0),
+        );
+    }
+    #[test]
+    fn unit_scale_round_trips() {
+        let mut data = crate::Lvd::empty(13).unwrap();
+
+        data.collisions_mut()
+            .unwrap()
+            .push(crate::objects::Collision::new_soft_platform(
+                Vector2::new(-80.0, 0.0),
+                Vector2::new(80.0, 10.0),
+            ));
+
+        let mut file = LvdFile {
+            data: crate::version::Versioned::new(data),
+        };
+        let vertices = |file: &LvdFile| {
+            let collision = file.data.collisions().unwrap().iter().next().unwrap();
+
+            collision.vertices().iter().copied().collect::<Vec<_>>()
+        };
+        let scale = UnitScale(0.01);
+
+        file.scale_from_game(scale);
+        assert_near(vertices(&file)[1], Vector2::new(0.8, 0.1));
+
+        file.scale_to_game(scale);
+        assert_near(vertices(&file)[0], Vector2::new(-80.0, 0.0));
+        assert_near(vertices(&file)[1], Vector2::new(80.0, 10.0));
+    }
+}
@@ -0,0 +1,389 @@
+//! Validation of LVD files.
+//!
+//! This module contains the [`LvdFile::validate`] and [`LvdFile::validate_with`] methods,
+//! the [`ValidationOptions`] type configuring the thresholds of the checks, and the [`Issue`] type
+//! describing a problem found in a file.
+
+use std::fmt;
+
+use crate::{
+    limits::CoordinateLimits,
+    objects::{collision::attribute::MaterialType, Collision},
+    shape::{Shape2, Shape3},
+    vector::Vector2,
+    LvdFile, ObjectRef, SectionKind,
+};
+
+/// The severity of a validation issue.
+#[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    /// The file can be written, but may not behave as expected in game.
+    Warning,
+
+    /// The file is unlikely to be loaded correctly by the game.
+    Error,
+}
+
+/// The thresholds of the checks made by [`LvdFile::validate_with`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationOptions {
+    /// The range outside of which positions are reported, or `None` to skip the check.
+    pub coordinate_limits: Option<CoordinateLimits>,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self {
+            coordinate_limits: Some(CoordinateLimits::STABLE),
+        }
+    }
+}
+
+/// A problem found when validating an LVD file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    /// The object the problem was found in.
+    pub object: ObjectRef,
+
+    /// The description of the problem.
+    pub kind: IssueKind,
+}
+
+impl Issue {
+    /// Returns the severity of the issue.
+    pub fn severity(&self) -> Severity {
+        self.kind.severity()
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "object {} in section `{}`: {}",
+            self.object.index,
+            self.object.section.name(),
+            self.kind
+        )
+    }
+}
+
+/// The kinds of problems found when validating an LVD file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IssueKind {
+    /// An edge of a collision uses a material not known to this library.
+    UnknownMaterial {
+        /// The index of the edge.
+        edge: usize,
+
+        /// The numeric value of the material.
+        value: u32,
+    },
+
+    /// A two-dimensional shape uses a shape type not known to this library.
+    UnknownShape2(u32),
+
+    /// A three-dimensional shape uses a shape type not known to this library.
+    UnknownShape3(u32),
+
+    /// A vertex of a collision is at the same position as the previous vertex.
+    DuplicateVertex(usize),
+
+    /// An edge of a collision has a length of zero without the `length0` attribute.
+    ZeroLengthEdge(usize),
+
+    /// Two non-adjacent edges of a collision intersect.
+    SelfIntersection {
+        /// The index of the first edge.
+        first: usize,
+
+        /// The index of the second edge.
+        second: usize,
+    },
+
+    /// The positions, normals, or sizes of an object contain the given number of NaN or
+    /// infinite values, which can crash the game.
+    ///
+    /// Only the values checked by [`LvdFile::non_finite_objects`] are counted.
+    NonFinite(usize),
+
+    /// The positions of an object contain the given number of coordinates beyond the
+    /// [`CoordinateLimits`] of the validation, which can cause physics glitches.
+    OutOfRange(usize),
+
+    /// A problem reported by a [`SectionProcessor`](crate::process::SectionProcessor).
+    Custom {
+        /// The name of the processor.
+        processor: String,
+
+        /// The severity of the problem.
+        severity: Severity,
+
+        /// The description of the problem.
+        message: String,
+    },
+}
+
+impl IssueKind {
+    /// Returns the severity of the kind of issue.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::UnknownMaterial { .. }
+            | Self::UnknownShape2(_)
+            | Self::UnknownShape3(_)
+            | Self::DuplicateVertex(_)
+            | Self::ZeroLengthEdge(_)
+            | Self::OutOfRange(_) => Severity::Warning,
+            Self::SelfIntersection { .. } | Self::NonFinite(_) => Severity::Error,
+            Self::Custom { severity, .. } => *severity,
+        }
+    }
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownMaterial { edge, value } => {
+                write!(f, "edge {edge} uses unknown material {value}")
+            }
+            Self::UnknownShape2(ty) => write!(f, "unknown two-dimensional shape type {ty}"),
+            Self::UnknownShape3(ty) => write!(f, "unknown three-dimensional shape type {ty}"),
+            Self::DuplicateVertex(vertex) => {
+                write!(f, "vertex {vertex} duplicates the previous vertex")
+            }
+            Self::ZeroLengthEdge(edge) => {
+                write!(
+                    f,
+                    "edge {edge} has zero length but is not flagged `length0`"
+                )
+            }
+            Self::SelfIntersection { first, second } => {
+                write!(f, "edges {first} and {second} intersect")
+            }
+            Self::NonFinite(count) => write!(f, "contains {count} NaN or infinite values"),
+            Self::OutOfRange(count) => {
+                write!(f, "contains {count} coordinates beyond the stable range")
+            }
+            Self::Custom {
+                processor, message, ..
+            } => write!(f, "{processor}: {message}"),
+        }
+    }
+}
+
+impl LvdFile {
+    /// Returns the problems found in every object of the file, with the default
+    /// [`ValidationOptions`].
+    ///
+    /// Values not known to this library, such as materials and shape types
+    /// introduced by later game versions, are preserved when reading and writing,
+    /// and are reported here instead. The outline of each collision is checked
+    /// for duplicated vertices, unflagged zero-length edges, and self-intersections,
+    /// and every object is checked for NaN and infinite values and for positions beyond
+    /// [`CoordinateLimits::STABLE`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::LvdFile;
+    ///
+    /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    ///
+    /// for issue in file.validate() {
+    ///     println!("{issue}");
+    /// }
+    /// ```
+    pub fn validate(&self) -> Vec<Issue> {
+        self.validate_with(&ValidationOptions::default())
+    }
+
+    /// Returns the problems found in every object of the file, with the given thresholds.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{
+    ///     limits::CoordinateLimits,
+    ///     validation::ValidationOptions,
+    ///     LvdFile,
+    /// };
+    ///
+    /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    /// let options = ValidationOptions {
+    ///     coordinate_limits: Some(CoordinateLimits::uniform(500.0)),
+    /// };
+    ///
+    /// for issue in file.validate_with(&options) {
+    ///     println!("{issue}");
+    /// }
+    /// ```
+    pub fn validate_with(&self, options: &ValidationOptions) -> Vec<Issue> {
+        let mut issues = Vec::new();
+
+        for &section in SectionKind::ALL {
+            for index in 0..self.data.section_len(section).unwrap_or_default() {
+                let object = ObjectRef::new(section, index);
+                let lvd_object = self.data.object(section, index).unwrap();
+
+                for shape in lvd_object.shapes2() {
+                    if let Shape2::Unknown { ty, .. } = shape {
+                        issues.push(Issue {
+                            object,
+                            kind: IssueKind::UnknownShape2(*ty),
+                        });
+                    }
+                }
+
+                for shape in lvd_object.shapes3() {
+                    if let Shape3::Unknown { ty, .. } = shape {
+                        issues.push(Issue {
+                            object,
+                            kind: IssueKind::UnknownShape3(*ty),
+                        });
+                    }
+                }
+            }
+        }
+
+        issues.extend(
+            self.non_finite_objects()
+                .into_iter()
+                .map(|(object, count)| Issue {
+                    object,
+                    kind: IssueKind::NonFinite(count),
+                }),
+        );
+
+        if let Some(limits) = options.coordinate_limits {
+            issues.extend(
+                self.out_of_range_objects(limits)
+                    .into_iter()
+                    .map(|(object, count)| Issue {
+                        object,
+                        kind: IssueKind::OutOfRange(count),
+                    }),
+            );
+        }
+
+        if let Some(collisions) = self.data.collisions() {
+            for (index, collision) in collisions.iter().enumerate() {
+                let object = ObjectRef::new(SectionKind::Collisions, index);
+
+                issues.extend(
+                    collision_issues(collision)
+                        .into_iter()
+                        .map(|kind| Issue { object, kind }),
+                );
+            }
+        }
+
+        issues
+    }
+}
+
+/// Returns the problems found in the materials and geometry of a collision.
+fn collision_issues(collision: &Collision) -> Vec<IssueKind> {
+    let mut issues = Vec::new();
+    let vertices = collision.vertices().iter().copied().collect::<Vec<_>>();
+    let attributes = collision
+        .attributes()
+        .map(|a| a.iter().collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    for (edge, attribute) in attributes.iter().enumerate() {
+        if let MaterialType::Unknown(value) = attribute.material() {
+            issues.push(IssueKind::UnknownMaterial { edge, value });
+        }
+    }
+
+    for (vertex, pair) in vertices.windows(2).enumerate() {
+        if pair[0] == pair[1] {
+            issues.push(IssueKind::DuplicateVertex(vertex + 1));
+        }
+    }
+
+    let edges = vertices
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .collect::<Vec<_>>();
+    let is_degenerate =
+        |(start, end): (Vector2, Vector2)| (end - start).length() < Collision::ZERO_LENGTH;
+
+    for (edge, &(start, end)) in edges.iter().enumerate() {
+        let is_length0 = attributes
+            .get(edge)
+            .is_some_and(|attribute| attribute.flags().length0());
+
+        if is_degenerate((start, end)) && !is_length0 {
+            issues.push(IssueKind::ZeroLengthEdge(edge));
+        }
+    }
+
+    let is_closed = edges.len() > 2 && vertices.first() == vertices.last();
+
+    for first in 0..edges.len() {
+        for second in first + 2..edges.len() {
+            if (is_closed && first == 0 && second == edges.len() - 1)
+                || is_degenerate(edges[first])
+                || is_degenerate(edges[second])
+            {
+                continue;
+            }
+
+            if segments_intersect(edges[first], edges[second]) {
+                issues.push(IssueKind::SelfIntersection { first, second });
+            }
+        }
+    }
+
+    issues
+}
+
+/// Returns `true` if the two line segments share at least one point.
+fn segments_intersect((a, b): (Vector2, Vector2), (c, d): (Vector2, Vector2)) -> bool {
+    let cross = |o: Vector2, p: Vector2, q: Vector2| (p - o).perp().dot(q - o);
+    let on_segment = |p: Vector2, q: Vector2, r: Vector2| {
+        r.x() >= p.x().min(q.x())
+            && r.x() <= p.x().max(q.x())
+            && r.y() >= p.y().min(q.y())
+            && r.y() <= p.y().max(q.y())
+    };
+    let d1 = cross(c, d, a);
+    let d2 = cross(c, d, b);
+    let d3 = cross(a, b, c);
+    let d4 = cross(a, b, d);
+
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+    {
+        return true;
+    }
+
+    (d1 == 0.0 && on_segment(c, d, a))
+        || (d2 == 0.0 && on_segment(c, d, b))
+        || (d3 == 0.0 && on_segment(a, b, c))
+        || (d4 == 0.0 && on_segment(a, b, d))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segments_intersect_crossing_and_touching() {
+        let v = Vector2::new;
+
+        assert!(segments_intersect(
+            (v(0.0, 0.0), v(2.0, 2.0)),
+            (v(0.0, 2.0), v(2.0, 0.0))
+        ));
+        assert!(segments_intersect(
+            (v(0.0, 0.0), v(2.0, 0.0)),
+            (v(1.0, 0.0), v(1.0, 1.0))
+        ));
+        assert!(!segments_intersect(
+            (v(0.0, 0.0), v(2.0, 0.0)),
+            (v(0.0, 1.0), v(2.0, 1.0))
+        ));
+    }
+}
@@ -0,0 +1,388 @@
+//! Comparison of LVD files against the files shipped with the game.
+//!
+//! This module contains the [`VanillaDatabase`] type, a compact record of the checksum and
+//! object counts of each vanilla LVD file, and the [`Comparison`] type describing how far a
+//! file has diverged from its vanilla counterpart. The database bundled with this library is
+//! read from `data/vanilla.txt` and can be regenerated from extracted game files with
+//! [`VanillaEntry::new`] and [`VanillaDatabase::to_text`].
+
+use std::{fmt, path::Path};
+
+use thiserror::Error;
+
+use crate::{hash::fnv1a, LvdFile, SectionKind};
+
+/// The text of the database bundled with this library.
+const BUNDLED: &str = include_str!("../data/vanilla.txt");
+
+/// The record of a single vanilla LVD file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VanillaEntry {
+    /// The path of the file relative to the root of the extracted game files,
+    /// with `/` separators.
+    pub path: String,
+
+    /// The checksum of the file's bytes, as returned by [`fnv1a`].
+    pub checksum: u64,
+
+    /// The number of objects in each non-empty section of the file, in file order.
+    pub section_counts: Vec<(SectionKind, usize)>,
+}
+
+impl VanillaEntry {
+    /// Creates a new `VanillaEntry` from a file's path, bytes, and parsed data.
+    pub fn new(path: impl Into<String>, bytes: &[u8], file: &LvdFile) -> Self {
+        Self {
+            path: path.into(),
+            checksum: fnv1a(bytes),
+            section_counts: section_counts(file),
+        }
+    }
+
+    /// Returns the file name of the entry's path, such as `battlefield_00.lvd`.
+    pub fn file_name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or_default()
+    }
+}
+
+/// A record of the vanilla LVD files of the game.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct VanillaDatabase {
+    /// The records of each file.
+    pub entries: Vec<VanillaEntry>,
+}
+
+impl VanillaDatabase {
+    /// Returns the database bundled with this library.
+    ///
+    /// The bundled database is empty until it has been generated from extracted game files,
+    /// in which case every file compares as [`Comparison::Unknown`].
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED).expect("bundled vanilla database should be valid")
+    }
+
+    /// Parses a database written by [`VanillaDatabase::to_text`].
+    ///
+    /// Each line holds the hexadecimal checksum of a file, its path, and a comma-separated list
+    /// of the object count of each non-empty section, such as `collisions=12`, separated by
+    /// tabs so paths may contain spaces. Blank lines and lines starting with `#` are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first line which could not be parsed.
+    pub fn parse(text: &str) -> Result<Self, VanillaDatabaseError> {
+        let entries = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(index, line)| {
+                parse_entry(line).ok_or(VanillaDatabaseError::InvalidLine(index + 1))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { entries })
+    }
+
+    /// Returns the text form of the database read by [`VanillaDatabase::parse`].
+    pub fn to_text(&self) -> String {
+        let mut text = BUNDLED
+            .lines()
+            .take_while(|line| line.starts_with('#'))
+            .map(|line| format!("{line}\n"))
+            .collect::<String>();
+
+        for entry in &self.entries {
+            let counts = entry
+                .section_counts
+                .iter()
+                .map(|(kind, count)| format!("{}={count}", kind.name()))
+                .collect::<Vec<_>>()
+                .join(",");
+
+            text.push_str(&format!(
+                "{:016x}\t{}\t{counts}\n",
+                entry.checksum, entry.path
+            ));
+        }
+
+        text
+    }
+
+    /// Returns how the file with the given name, bytes, and parsed data compares to the
+    /// vanilla files.
+    ///
+    /// A file whose checksum matches a vanilla file is unmodified. Otherwise, it is compared
+    /// with the vanilla file of the same file name, and is lightly modified if every section
+    /// holds the same number of objects, or structurally divergent if not.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs;
+    ///
+    /// use lvd_lib::{vanilla::VanillaDatabase, LvdFile};
+    ///
+    /// let bytes = fs::read("battlefield_00.lvd").unwrap();
+    /// let file = LvdFile::read(&mut std::io::Cursor::new(&bytes)).unwrap();
+    ///
+    /// println!("{}", VanillaDatabase::bundled().compare("battlefield_00.lvd", &bytes, &file));
+    /// ```
+    pub fn compare(&self, file_name: &str, bytes: &[u8], file: &LvdFile) -> Comparison {
+        let checksum = fnv1a(bytes);
+
+        if let Some(entry) = self.entries.iter().find(|e| e.checksum == checksum) {
+            return Comparison::Unmodified {
+                path: entry.path.clone(),
+            };
+        }
+
+        let file_name = Path::new(file_name)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(file_name);
+        let Some(entry) = self.entries.iter().find(|e| e.file_name() == file_name) else {
+            return Comparison::Unknown;
+        };
+        let counts = section_counts(file);
+        let count = |counts: &[(SectionKind, usize)], kind: SectionKind| {
+            counts
+                .iter()
+                .find(|(k, _)| *k == kind)
+                .map_or(0, |(_, count)| *count)
+        };
+        let differences = SectionKind::ALL
+            .iter()
+            .map(|&kind| {
+                (
+                    kind,
+                    count(&entry.section_counts, kind),
+                    count(&counts, kind),
+                )
+            })
+            .filter(|(_, vanilla, modified)| vanilla != modified)
+            .collect::<Vec<_>>();
+
+        if differences.is_empty() {
+            Comparison::LightlyModified {
+                path: entry.path.clone(),
+            }
+        } else {
+            Comparison::Divergent {
+                path: entry.path.clone(),
+                differences,
+            }
+        }
+    }
+}
+
+/// How an LVD file compares to the vanilla files, as returned by [`VanillaDatabase::compare`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Comparison {
+    /// The file is identical to a vanilla file.
+    Unmodified {
+        /// The path of the vanilla file.
+        path: String,
+    },
+
+    /// The file differs from the vanilla file of the same name, but every section holds the
+    /// same number of objects.
+    LightlyModified {
+        /// The path of the vanilla file.
+        path: String,
+    },
+
+    /// The file holds a different number of objects than the vanilla file of the same name
+    /// in at least one section.
+    Divergent {
+        /// The path of the vanilla file.
+        path: String,
+
+        /// Each section with a different number of objects, with the number of objects in the
+        /// vanilla file and in the compared file.
+        differences: Vec<(SectionKind, usize, usize)>,
+    },
+
+    /// The file is not identical to any vanilla file, and no vanilla file has the same name.
+    Unknown,
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unmodified { path } => write!(f, "unmodified: identical to {path}"),
+            Self::LightlyModified { path } => {
+                write!(f, "lightly modified: same object counts as {path}")
+            }
+            Self::Divergent { path, differences } => {
+                write!(f, "structurally divergent from {path}:")?;
+
+                for (kind, vanilla, modified) in differences {
+                    write!(f, "\n  {}: {vanilla} -> {modified}", kind.name())?;
+                }
+
+                Ok(())
+            }
+            Self::Unknown => write!(f, "unknown: no vanilla file with the same name"),
+        }
+    }
+}
+
+/// The error type used when parsing a [`VanillaDatabase`].
+#[derive(Debug, PartialEq, Error)]
+pub enum VanillaDatabaseError {
+    /// A line does not hold a checksum, path, and section counts.
+    #[error("line {0}: expected a checksum, path, and section counts")]
+    InvalidLine(usize),
+}
+
+/// Returns the number of objects in each non-empty section of the file, in file order.
+fn section_counts(file: &LvdFile) -> Vec<(SectionKind, usize)> {
+    file.data
+        .sections()
+        .filter_map(|(kind, _)| Some((kind, file.data.section_len(kind)?)))
+        .filter(|(_, count)| *count > 0)
+        .collect()
+}
+
+/// Parses a line of the text form of a database.
+fn parse_entry(line: &str) -> Option<VanillaEntry> {
+    let mut fields = line.split('\t');
+    let checksum = u64::from_str_radix(fields.next()?, 16).ok()?;
+    let path = fields.next()?.to_string();
+    let section_counts = match fields.next() {
+        Some(counts) if !counts.is_empty() => counts
+            .split(',')
+            .map(|count| {
+                let (name, count) = count.split_once('=')?;
+
+                Some((SectionKind::from_name(name)?, count.parse().ok()?))
+            })
+            .collect::<Option<_>>()?,
+        _ => Vec::new(),
+    };
+
+    if fields.next().is_some() {
+        return None;
+    }
+
+    Some(VanillaEntry {
+        path,
+        checksum,
+        section_counts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        fixtures::{file_with_collisions, platform_file},
+        objects::Collision,
+        vector::Vector2,
+    };
+
+    const PATH: &str = "stage/battlefield/normal/param/battlefield_00.lvd";
+
+    #[test]
+    fn compare_against_database() {
+        let mut file = platform_file();
+        let database = VanillaDatabase {
+            entries: vec![VanillaEntry::new(PATH, &file.to_bytes().unwrap(), &file)],
+        };
+
+        assert_eq!(
+            VanillaDatabase::parse(&database.to_text()).unwrap(),
+            database
+        );
+        assert_eq!(
+            database.compare("battlefield_00.lvd", &file.to_bytes().unwrap(), &file),
+            Comparison::Unmodified {
+                path: PATH.to_string()
+            }
+        );
+
+        file.data.collisions_mut().unwrap().elements_mut().clear();
+
+        let bytes = file.to_bytes().unwrap();
+
+        assert_eq!(
+            database.compare("mods/battlefield_00.lvd", &bytes, &file),
+            Comparison::Divergent {
+                path: PATH.to_string(),
+                differences: vec![(SectionKind::Collisions, 1, 0)],
+            }
+        );
+        assert_eq!(
+            database.compare("battlefield_01.lvd", &bytes, &file),
+            Comparison::Unknown
+        );
+        assert!(VanillaDatabase::bundled().entries.is_empty());
+    }
+
+    #[test]
+    fn paths_with_spaces_round_trip() {
+        let file = platform_file();
+        let database = VanillaDatabase {
+            entries: vec![VanillaEntry::new(
+                "stage/my stage/normal/param/my stage_00.lvd",
+                &file.to_bytes().unwrap(),
+                &file,
+            )],
+        };
+
+        assert_eq!(
+            VanillaDatabase::parse(&database.to_text()).unwrap(),
+            database
+        );
+    }
+
+    #[test]
+    fn compare_lightly_modified() {
+        let file = platform_file();
+        let database = VanillaDatabase {
+            entries: vec![VanillaEntry::new(PATH, &file.to_bytes().unwrap(), &file)],
+        };
+        let moved = file_with_collisions([Collision::new_soft_platform(
+            Vector2::new(-20.0, 5.0),
+            Vector2::new(20.0, 5.0),
+        )]);
+
+        assert_eq!(
+            database.compare("battlefield_00.lvd", &moved.to_bytes().unwrap(), &moved),
+            Comparison::LightlyModified {
+                path: PATH.to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_rejects_malformed_lines() {
+        let text =
+            "# comment\n\n0000000000000001\ta.lvd\tcollisions=2\n0000000000000002\tb c.lvd\n";
+        let database = VanillaDatabase::parse(text).unwrap();
+
+        assert_eq!(database.entries.len(), 2);
+        assert_eq!(
+            database.entries[0].section_counts,
+            [(SectionKind::Collisions, 2)]
+        );
+        assert_eq!(database.entries[1].path, "b c.lvd");
+        assert!(database.entries[1].section_counts.is_empty());
+
+        for (text, line) in [
+            ("xyz\ta.lvd\tcollisions=2", 1),
+            ("# comment\n0000000000000001", 2),
+            ("0000000000000001\ta.lvd\twidgets=2", 1),
+            ("0000000000000001\ta.lvd\tcollisions=two", 1),
+            ("0000000000000001\ta.lvd\tcollisions=2\textra", 1),
+            ("0000000000000001 a.lvd collisions=2", 1),
+        ] {
+            assert_eq!(
+                VanillaDatabase::parse(text),
+                Err(VanillaDatabaseError::InvalidLine(line)),
+                "{text:?}"
+            );
+        }
+    }
+}
@@ -2,17 +2,26 @@
 //!
 //! This module contains the [`Vector2`] and [`Vector3`] types.
 
+use std::{
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+};
+
 use binrw::binrw;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
 use crate::version::Version;
 
 /// A two-dimensional vector type.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Vector2 {
     /// The first version of the `Vector2` type.
@@ -34,10 +43,100 @@ impl Version for Vector2 {
     }
 }
 
+impl Vector2 {
+    /// Creates a new `Vector2` from the given components.
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self::V1 { x, y }
+    }
+
+    /// Returns the component along the x-axis.
+    pub const fn x(&self) -> f32 {
+        match self {
+            Self::V1 { x, .. } => *x,
+        }
+    }
+
+    /// Returns the component along the y-axis.
+    pub const fn y(&self) -> f32 {
+        match self {
+            Self::V1 { y, .. } => *y,
+        }
+    }
+
+    /// Returns the dot product of `self` and `other`.
+    pub fn dot(self, other: Self) -> f32 {
+        self.x() * other.x() + self.y() * other.y()
+    }
+
+    /// Returns the length of the vector.
+    pub fn length(self) -> f32 {
+        self.x().hypot(self.y())
+    }
+
+    /// Returns the vector scaled to a length of one, or `None` if the vector has a length of zero.
+    pub fn normalize(self) -> Option<Self> {
+        let length = self.length();
+
+        (length > 0.0).then(|| self * length.recip())
+    }
+
+    /// Returns the vector rotated by 90 degrees counterclockwise.
+    ///
+    /// For the direction of an edge, this is the normal vector facing the left of the edge.
+    pub fn perp(self) -> Self {
+        Self::new(-self.y(), self.x())
+    }
+}
+
+impl fmt::Display for Vector2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x(), self.y())
+    }
+}
+
+impl Default for Vector2 {
+    fn default() -> Self {
+        Self::new(0.0, 0.0)
+    }
+}
+
+impl Add for Vector2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x() + rhs.x(), self.y() + rhs.y())
+    }
+}
+
+impl Sub for Vector2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x() - rhs.x(), self.y() - rhs.y())
+    }
+}
+
+impl Mul<f32> for Vector2 {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self::new(self.x() * rhs, self.y() * rhs)
+    }
+}
+
+impl Neg for Vector2 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x(), -self.y())
+    }
+}
+
 /// A three-dimensional vector type.
 #[binrw]
 #[br(import(version: u8))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Vector3 {
     /// The first version of the `Vector3` type.
@@ -61,3 +160,43 @@ impl Version for Vector3 {
         }
     }
 }
+
+impl Vector3 {
+    /// Creates a new `Vector3` from the given components.
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+        Self::V1 { x, y, z }
+    }
+
+    /// Returns the component along the x-axis.
+    pub const fn x(&self) -> f32 {
+        match self {
+            Self::V1 { x, .. } => *x,
+        }
+    }
+
+    /// Returns the component along the y-axis.
+    pub const fn y(&self) -> f32 {
+        match self {
+            Self::V1 { y, .. } => *y,
+        }
+    }
+
+    /// Returns the component along the z-axis.
+    pub const fn z(&self) -> f32 {
+        match self {
+            Self::V1 { z, .. } => *z,
+        }
+    }
+}
+
+impl fmt::Display for Vector3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x(), self.y(), self.z())
+    }
+}
+
+impl Default for Vector3 {
+    fn default() -> Self {
+        Self::new(0.0, 0.0, 0.0)
+    }
+}
@@ -0,0 +1,334 @@
+//! Verification that LVD files survive a round trip through another form unchanged.
+//!
+//! This module contains the [`RoundTripReport`] type, which compares the bytes of an LVD file with
+//! the bytes written after converting it to another form and back, such as YAML. Besides the
+//! offset of the first differing byte, the report lists the size of every section in both files
+//! and the field differences of the first differing object, found by parsing both files and
+//! comparing them with [`ChangeSet::diff`].
+
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::{
+    annotate::Annotation,
+    change::{Change, ChangeError, ChangeSet},
+    LvdFile, ObjectRef, SectionKind,
+};
+
+/// The sizes of a section before and after a round trip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionStats {
+    /// The section.
+    pub section: SectionKind,
+
+    /// The number of objects in the section of the original file.
+    pub original_count: usize,
+
+    /// The number of objects in the section of the written file.
+    pub written_count: usize,
+
+    /// The number of bytes in the section of the original file.
+    pub original_len: u64,
+
+    /// The number of bytes in the section of the written file.
+    pub written_len: u64,
+
+    /// Whether the bytes of the section are the same in both files.
+    pub identical: bool,
+}
+
+/// The first difference between the original and written bytes of a file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    /// The position of the first differing byte in the original file.
+    pub offset: u64,
+
+    /// The section containing the differing byte, or `None` if it lies in the header
+    /// or trailing bytes of the file.
+    pub section: Option<SectionKind>,
+
+    /// The object containing the differing byte, or `None` if it lies outside of every object,
+    /// such as in the count of a section.
+    pub object: Option<ObjectRef>,
+
+    /// The changes turning the diverging object, or the diverging section if the byte lies
+    /// outside of every object, into the written one.
+    ///
+    /// The changes are empty if both files parse to the same data, such as when a boolean is
+    /// stored as a byte other than zero or one.
+    pub changes: Vec<Change>,
+}
+
+/// The result of comparing the original bytes of an LVD file with the bytes written
+/// after a round trip.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundTripReport {
+    /// The number of bytes in the original file.
+    pub original_len: u64,
+
+    /// The number of bytes in the written file.
+    pub written_len: u64,
+
+    /// The sizes of each section in either file, in file order.
+    pub sections: Vec<SectionStats>,
+
+    /// The first difference between the files, or `None` if they are identical.
+    pub divergence: Option<Divergence>,
+}
+
+impl RoundTripReport {
+    /// Compares the original bytes of an LVD file with the bytes written after a round trip,
+    /// parsing both to find the section and object where they first diverge.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either file cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{verify::RoundTripReport, LvdFile};
+    ///
+    /// let original = std::fs::read("battlefield_00.lvd").unwrap();
+    /// let written = LvdFile::from_bytes(&original).unwrap().to_bytes().unwrap();
+    /// let report = RoundTripReport::compare(&original, &written).unwrap();
+    ///
+    /// if let Some(divergence) = report.divergence {
+    ///     println!("{divergence}");
+    /// }
+    /// ```
+    pub fn compare(original: &[u8], written: &[u8]) -> Result<Self, VerifyError> {
+        let original_file = LvdFile::from_bytes(original).map_err(VerifyError::Original)?;
+        let written_file = LvdFile::from_bytes(written).map_err(VerifyError::Written)?;
+        let original_annotations = original_file.annotations();
+        let written_annotations = written_file.annotations();
+
+        let sections = SectionKind::ALL
+            .iter()
+            .filter_map(|&section| {
+                let original_range = section_range(&original_annotations, section);
+                let written_range = section_range(&written_annotations, section);
+
+                if original_range.is_none() && written_range.is_none() {
+                    return None;
+                }
+
+                let original_bytes = original_range.and_then(|range| original.get(range));
+                let written_bytes = written_range.and_then(|range| written.get(range));
+
+                Some(SectionStats {
+                    section,
+                    original_count: original_file.data.section_len(section).unwrap_or_default(),
+                    written_count: written_file.data.section_len(section).unwrap_or_default(),
+                    original_len: original_bytes.map_or(0, |bytes| bytes.len() as u64),
+                    written_len: written_bytes.map_or(0, |bytes| bytes.len() as u64),
+                    identical: original_bytes == written_bytes,
+                })
+            })
+            .collect();
+
+        let divergence = match first_difference(original, written) {
+            Some(offset) => {
+                let section = original_annotations
+                    .iter()
+                    .filter(|annotation| annotation.depth == 0 && contains(annotation, offset))
+                    .find_map(|annotation| SectionKind::from_name(&annotation.label));
+                let object = original_annotations
+                    .iter()
+                    .filter(|annotation| contains(annotation, offset))
+                    .find_map(|annotation| annotation.object);
+                let changes = ChangeSet::diff(&original_file, &written_file)?
+                    .changes
+                    .into_iter()
+                    .filter(|change| match object {
+                        Some(object) => change.object() == object,
+                        None => section == Some(change.object().section),
+                    })
+                    .collect();
+
+                Some(Divergence {
+                    offset: offset as u64,
+                    section,
+                    object,
+                    changes,
+                })
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            original_len: original.len() as u64,
+            written_len: written.len() as u64,
+            sections,
+            divergence,
+        })
+    }
+
+    /// Returns `true` if the written bytes are identical to the original bytes.
+    pub fn is_identical(&self) -> bool {
+        self.divergence.is_none()
+    }
+}
+
+impl fmt::Display for RoundTripReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "size: {} -> {} bytes",
+            self.original_len, self.written_len
+        )?;
+
+        for section in &self.sections {
+            writeln!(
+                f,
+                "{} {}: {} -> {} objects, {} -> {} bytes",
+                if section.identical { "SAME" } else { "DIFF" },
+                section.section.name(),
+                section.original_count,
+                section.written_count,
+                section.original_len,
+                section.written_len
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the files diverge at offset {:#010x}", self.offset)?;
+
+        match (self.object, self.section) {
+            (Some(object), _) => write!(
+                f,
+                " in object {} in section `{}`",
+                object.index,
+                object.section.name()
+            )?,
+            (None, Some(section)) => write!(f, " in section `{}`", section.name())?,
+            (None, None) => (),
+        }
+
+        for change in &self.changes {
+            match change {
+                Change::SetField { path, old, new, .. } => write!(f, "\n  {path}: {old} -> {new}")?,
+                Change::AddObject { index, .. } => write!(f, "\n  object {index} added")?,
+                Change::RemoveObject { index, .. } => write!(f, "\n  object {index} removed")?,
+                Change::ReplaceObject { .. } => {
+                    write!(f, "\n  object replaced with another version")?
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the byte range of a section, as annotated by [`LvdFile::annotations`].
+fn section_range(
+    annotations: &[Annotation],
+    section: SectionKind,
+) -> Option<std::ops::Range<usize>> {
+    annotations
+        .iter()
+        .find(|annotation| annotation.depth == 0 && annotation.label == section.name())
+        .map(|annotation| annotation.offset as usize..(annotation.offset + annotation.len) as usize)
+}
+
+/// Returns `true` if the annotated range contains the byte at the given position.
+fn contains(annotation: &Annotation, offset: usize) -> bool {
+    (annotation.offset..annotation.offset + annotation.len).contains(&(offset as u64))
+}
+
+/// Returns the position of the first byte which differs between the buffers,
+/// or the length of the shorter buffer if it is a prefix of the longer one.
+fn first_difference(original: &[u8], written: &[u8]) -> Option<usize> {
+    original
+        .iter()
+        .zip(written)
+        .position(|(a, b)| a != b)
+        .or_else(|| (original.len() != written.len()).then(|| original.len().min(written.len())))
+}
+
+/// The error type used when comparing the bytes of a round trip.
+#[derive(Debug, Error)]
+pub enum VerifyError {
+    /// The original file could not be parsed.
+    #[error("failed to parse the original file: {0}")]
+    Original(binrw::Error),
+
+    /// The written file could not be parsed.
+    #[error("failed to parse the written file: {0}")]
+    Written(binrw::Error),
+
+    /// The parsed files could not be compared.
+    #[error(transparent)]
+    Diff(#[from] ChangeError),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures::platform_file, vector::Vector2, version::Versioned};
+
+    use super::*;
+
+    #[test]
+    fn identical_round_trip() {
+        let bytes = platform_file().to_bytes().unwrap();
+        let report = RoundTripReport::compare(&bytes, &bytes).unwrap();
+
+        assert!(report.is_identical());
+        assert!(report.sections.iter().all(|section| section.identical));
+    }
+
+    #[test]
+    fn divergent_object() {
+        let original = platform_file();
+        let mut written = original.clone();
+
+        written.data.collisions_mut().unwrap().elements_mut()[0]
+            .vertices_mut()
+            .elements_mut()[1] = Versioned::new(Vector2::new(20.0, 0.0));
+
+        let report =
+            RoundTripReport::compare(&original.to_bytes().unwrap(), &written.to_bytes().unwrap())
+                .unwrap();
+        let divergence = report.divergence.unwrap();
+        let collisions = report
+            .sections
+            .iter()
+            .find(|section| section.section == SectionKind::Collisions)
+            .unwrap();
+
+        assert!(!collisions.identical);
+        assert_eq!(collisions.original_len, collisions.written_len);
+        assert_eq!(
+            divergence.object,
+            Some(ObjectRef::new(SectionKind::Collisions, 0))
+        );
+        assert!(divergence
+            .to_string()
+            .ends_with("in object 0 in section `collisions`\n  vertices[1].x: 10.0 -> 20.0"));
+    }
+
+    #[test]
+    fn divergence_outside_objects() {
+        let original = platform_file().to_bytes().unwrap();
+        let mut written = original.clone();
+
+        written.push(0xFF);
+
+        let report = RoundTripReport::compare(&original, &written).unwrap();
+        let divergence = report.divergence.unwrap();
+
+        assert_eq!(report.written_len, report.original_len + 1);
+        assert_eq!(divergence.offset, report.original_len);
+        assert_eq!((divergence.section, divergence.object), (None, None));
+        assert!(matches!(
+            RoundTripReport::compare(&original[..8], &written),
+            Err(VerifyError::Original(_))
+        ));
+    }
+}
@@ -1,15 +1,24 @@
 //! Types and traits for working with versioned types.
 
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
 use binrw::{binrw, BinRead, BinWrite};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
 /// The wrapper type for a versioned, non-primitive type.
 #[binrw]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
 #[cfg_attr(feature = "serde", serde(transparent))]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Versioned<T: Version> {
     /// The version number of the wrapped value.
     #[br(temp)]
@@ -21,6 +30,47 @@ pub struct Versioned<T: Version> {
     pub inner: T,
 }
 
+impl<T: Version> Versioned<T> {
+    /// Creates a new `Versioned` from the given value.
+    pub const fn new(inner: T) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T: Upgrade> Versioned<T> {
+    /// Converts the wrapped value into the latest version of its type,
+    /// as described by [`Upgrade::into_latest`].
+    pub fn into_latest(self) -> Self {
+        Self::new(self.inner.into_latest())
+    }
+}
+
+impl<T: Version> From<T> for Versioned<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Version> Deref for Versioned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T: Version> DerefMut for Versioned<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<T: Version + fmt::Display> fmt::Display for Versioned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
 /// A trait for determining a type's version.
 pub trait Version
 where
@@ -31,3 +81,81 @@ where
     /// Returns the version number from `self`.
     fn version(&self) -> u8;
 }
+
+/// A trait for converting the older versions of a type into its latest version.
+///
+/// # Examples
+///
+/// ```
+/// use lvd_lib::{
+///     objects::{base::Base, LvdObject, Point},
+///     vector::Vector2,
+///     version::{Upgrade, Version, Versioned},
+/// };
+///
+/// let point = Point::V1 {
+///     meta_info: Versioned::new(Default::default()),
+///     pos: Versioned::new(Vector2::new(0.0, 10.0)),
+/// };
+/// let point = point.into_latest();
+///
+/// assert_eq!(point.version(), 2);
+/// assert_eq!(point.base().map(Base::version), Some(4));
+/// assert_eq!(point.pos(), Vector2::new(0.0, 10.0));
+/// ```
+pub trait Upgrade: Version + Default {
+    /// The version number of the latest version of the type.
+    const LATEST_VERSION: u8;
+
+    /// Converts `self` into the latest version of its type, along with every versioned value
+    /// it contains.
+    ///
+    /// Fields added by later versions are given the values used by the type's default value.
+    fn into_latest(self) -> Self;
+
+    /// Converts `self` into the latest version of its type in place.
+    fn upgrade(&mut self) {
+        *self = std::mem::take(self).into_latest();
+    }
+
+    /// Returns `true` if `self` is the latest version of its type.
+    ///
+    /// The versioned values it contains may still be older versions.
+    fn is_latest(&self) -> bool {
+        self.version() == Self::LATEST_VERSION
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        array::Array,
+        objects::{base::Base, collision::flags::CollisionFlags, Collision, LvdObject},
+        vector::Vector2,
+    };
+
+    use super::*;
+
+    #[test]
+    fn collision_into_latest() {
+        let collision = Collision::V2 {
+            base: Versioned::new(Base::V1 {
+                meta_info: Versioned::new(Default::default()),
+                dynamic_name: Versioned::new(Default::default()),
+            }),
+            flags: CollisionFlags::new(),
+            vertices: Versioned::new(Array::new(vec![
+                Vector2::new(-10.0, 0.0),
+                Vector2::new(10.0, 0.0),
+            ])),
+            normals: Versioned::new(Array::new(vec![Vector2::new(0.0, 1.0)])),
+            cliffs: Versioned::new(Array::default()),
+        };
+        let collision = collision.into_latest();
+
+        assert!(collision.is_latest());
+        assert_eq!(collision.base().map(Base::version), Some(4));
+        assert_eq!(collision.attributes().map(Array::len), Some(1));
+        assert_eq!(collision.spirits_floors().map(Array::len), Some(0));
+    }
+}
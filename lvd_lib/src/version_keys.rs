@@ -0,0 +1,274 @@
+//! An alternate serialized form of LVD files with explicit version keys.
+//!
+//! By default, each versioned type is serialized as an enum whose variant names the version,
+//! such as a YAML `!V4` tag or a JSON `{"V4": {...}}` object. In the alternate form, the variant
+//! is replaced with a `version` field alongside the other fields:
+//!
+//! ```yaml
+//! base:
+//!   version: 4
+//!   meta_info:
+//!     version: 1
+//!     name: COL_Floor
+//! ```
+//!
+//! When reading the alternate form with [`LvdFile::from_version_keyed_value`], fields missing
+//! from an object are filled in from the object's default value, and sections missing from the
+//! file are left empty. Both forms may be mixed within a single file.
+//!
+//! This module contains the [`to_version_keys`] and [`from_version_keys`] functions for converting
+//! between the two forms, the [`normalize_version_keyed_value`] function, and the [`LvdFile::to_version_keyed_value`] and
+//! [`LvdFile::from_version_keyed_value`] methods.
+
+use serde_json::{Map, Value};
+
+use crate::{
+    objects::collision::{CollisionAttribute, CollisionCliff, CollisionSpiritsFloor},
+    LvdFile, SectionKind,
+};
+
+/// The name of the field storing the version of an object in the alternate form.
+const VERSION_KEY: &str = "version";
+
+impl LvdFile {
+    /// Returns the serialized form of the file with explicit version keys.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::LvdFile;
+    ///
+    /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    /// let value = file.to_version_keyed_value();
+    ///
+    /// assert_eq!(value["version"], 13);
+    /// ```
+    pub fn to_version_keyed_value(&self) -> Value {
+        let mut value = serde_json::to_value(self).unwrap();
+
+        to_version_keys(&mut value);
+
+        value
+    }
+
+    /// Deserializes a file from a serialized form with explicit version keys,
+    /// filling in missing fields and sections with their default values.
+    ///
+    /// Objects serialized with variant names instead of version keys are also accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value does not describe a valid LVD file,
+    /// such as when the file's version key is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lvd_lib::LvdFile;
+    /// use serde_json::json;
+    ///
+    /// let value = json!({
+    ///     "version": 13,
+    ///     "start_positions": {
+    ///         "version": 1,
+    ///         "elements": [{ "version": 2, "pos": { "version": 1, "x": 0.0, "y": 10.0 } }],
+    ///     },
+    /// });
+    /// let file = LvdFile::from_version_keyed_value(value).unwrap();
+    ///
+    /// assert_eq!(file.data.start_positions().unwrap().len(), 1);
+    /// assert_eq!(file.data.collisions().unwrap().len(), 0);
+    /// ```
+    pub fn from_version_keyed_value(mut value: Value) -> Result<Self, serde_json::Error> {
+        normalize_version_keyed_value(&mut value);
+
+        serde_json::from_value(value)
+    }
+}
+
+/// Converts the serialized form of an LVD file with explicit version keys into the default
+/// serialized form, filling in missing fields and sections with their default values.
+///
+/// This is the conversion performed by [`LvdFile::from_version_keyed_value`] before
+/// deserializing, exposed for callers which deserialize the value themselves.
+pub fn normalize_version_keyed_value(value: &mut Value) {
+    to_version_keys(value);
+    fill_lvd_defaults(value);
+    from_version_keys(value);
+}
+
+/// Replaces every single-field object naming a version, such as `{"V4": {...}}`,
+/// with the inner object and a `version` field.
+///
+/// Variant names written as YAML tags, such as `{"!V4": {...}}`, are also replaced.
+pub fn to_version_keys(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            let version = match map.iter().next() {
+                Some((key, Value::Object(_))) if map.len() == 1 => parse_variant(key),
+                _ => None,
+            };
+
+            if let Some(version) = version {
+                let (_, inner) = map.iter_mut().next().unwrap();
+                let Value::Object(inner) = inner.take() else {
+                    unreachable!()
+                };
+                let mut keyed = Map::with_capacity(inner.len() + 1);
+
+                keyed.insert(VERSION_KEY.to_string(), Value::from(version));
+                keyed.extend(inner);
+                *map = keyed;
+            }
+
+            map.values_mut().for_each(to_version_keys);
+        }
+        Value::Array(elements) => elements.iter_mut().for_each(to_version_keys),
+        _ => {}
+    }
+}
+
+/// Replaces every object with a numeric `version` field with a single-field object
+/// naming the version, such as `{"V4": {...}}`, containing the remaining fields.
+pub fn from_version_keys(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.values_mut().for_each(from_version_keys);
+
+            if let Some(version) = map.get(VERSION_KEY).and_then(Value::as_u64) {
+                let mut inner = std::mem::take(map);
+
+                inner.remove(VERSION_KEY);
+                map.insert(format!("V{version}"), Value::Object(inner));
+            }
+        }
+        Value::Array(elements) => elements.iter_mut().for_each(from_version_keys),
+        _ => {}
+    }
+}
+
+/// Returns the version named by a variant, such as `V4` or `!V4`.
+fn parse_variant(key: &str) -> Option<u8> {
+    let digits = key.strip_prefix('!').unwrap_or(key).strip_prefix('V')?;
+
+    if digits.bytes().all(|b| b.is_ascii_digit()) {
+        digits.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Fills in the missing sections of a version-keyed LVD file and the missing fields of its objects.
+fn fill_lvd_defaults(value: &mut Value) {
+    let Some(lvd) = value.as_object_mut() else {
+        return;
+    };
+    let Some(version) = lvd.get(VERSION_KEY).and_then(Value::as_u64) else {
+        return;
+    };
+
+    for &kind in SectionKind::ALL {
+        if !u8::try_from(version).is_ok_and(|version| kind.is_supported_by(version)) {
+            continue;
+        }
+
+        let section = lvd.entry(kind.name()).or_insert_with(|| {
+            serde_json::json!({
+                VERSION_KEY: 1,
+                "elements": [],
+            })
+        });
+
+        let mut template = kind.default_object_value();
+
+        to_version_keys(&mut template);
+        fill_element_defaults(section, &template);
+    }
+}
+
+/// Inserts the fields of `template` missing from `value`, recursing into fields.
+///
+/// Objects are only filled in if they have the same version as the template and no fields
+/// unknown to it, since the fields of other versions and of other enum variants, such as
+/// another shape type, cannot be inferred from the template.
+fn fill_defaults(value: &mut Value, template: &Value) {
+    let (Value::Object(map), Value::Object(template)) = (value, template) else {
+        return;
+    };
+
+    if map.get(VERSION_KEY) != template.get(VERSION_KEY)
+        || map.keys().any(|key| !template.contains_key(key))
+    {
+        return;
+    }
+
+    for (key, default) in template {
+        match map.get_mut(key) {
+            Some(field) => {
+                fill_defaults(field, default);
+
+                if let Some(template) = element_template(key) {
+                    fill_element_defaults(field, &template);
+                }
+            }
+            None => {
+                map.insert(key.clone(), default.clone());
+            }
+        }
+    }
+}
+
+/// Fills in the missing fields of every element of a version-keyed array.
+fn fill_element_defaults(array: &mut Value, template: &Value) {
+    if let Some(elements) = array.get_mut("elements").and_then(Value::as_array_mut) {
+        elements
+            .iter_mut()
+            .for_each(|element| fill_defaults(element, template));
+    }
+}
+
+/// Returns the version-keyed default value of the elements of an array field of an object,
+/// for arrays whose elements have more than one field.
+fn element_template(field: &str) -> Option<Value> {
+    let mut template = match field {
+        "cliffs" => serde_json::to_value(CollisionCliff::default()),
+        "attributes" => serde_json::to_value(CollisionAttribute::default()),
+        "spirits_floors" => serde_json::to_value(CollisionSpiritsFloor::default()),
+        _ => return None,
+    }
+    .unwrap();
+
+    to_version_keys(&mut template);
+
+    Some(template)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn version_keys_round_trip() {
+        let tagged = json!({ "V2": { "pos": { "V1": { "x": 1.0, "y": 2.0 } } } });
+        let mut value = tagged.clone();
+
+        to_version_keys(&mut value);
+        assert_eq!(
+            value,
+            json!({ "version": 2, "pos": { "version": 1, "x": 1.0, "y": 2.0 } })
+        );
+
+        from_version_keys(&mut value);
+        assert_eq!(value, tagged);
+    }
+
+    #[test]
+    fn yaml_tags_are_accepted() {
+        let mut value = json!({ "!V1": { "x": 1.0 } });
+
+        to_version_keys(&mut value);
+        assert_eq!(value, json!({ "version": 1, "x": 1.0 }));
+    }
+}
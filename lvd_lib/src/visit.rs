@@ -0,0 +1,309 @@
+//! Traversal of every object in an LVD file with typed callbacks.
+//!
+//! This module contains the [`LvdVisitor`] and [`LvdVisitorMut`] traits, which have a callback
+//! for each object type, and the [`LvdFile::visit`], [`LvdFile::visit_mut`], and
+//! [`LvdFile::for_each_point2_mut`] methods for walking a file with them.
+
+use std::cell::RefCell;
+
+use crate::{
+    objects::*,
+    transform::CoordinateMap,
+    vector::{Vector2, Vector3},
+    LvdFile, ObjectRef,
+};
+
+/// Generates the [`LvdVisitor`] and [`LvdVisitorMut`] traits and the [`Visit`] implementations
+/// given each object type and the names of its callbacks.
+macro_rules! visitors {
+    ($($ty:ident: $visit:ident, $visit_mut:ident;)+) => {
+        /// A trait for inspecting each object of an LVD file with [`LvdFile::visit`].
+        ///
+        /// Every callback defaults to [`visit_object`](LvdVisitor::visit_object),
+        /// so an implementation only needs to override the callbacks for the types it inspects.
+        ///
+        /// # Examples
+        ///
+        /// ```no_run
+        /// use lvd_lib::{objects::Collision, visit::LvdVisitor, LvdFile, ObjectRef};
+        ///
+        /// #[derive(Default)]
+        /// struct VertexCount(usize);
+        ///
+        /// impl LvdVisitor for VertexCount {
+        ///     fn visit_collision(&mut self, _object: ObjectRef, collision: &Collision) {
+        ///         self.0 += collision.vertices().len();
+        ///     }
+        /// }
+        ///
+        /// let file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+        /// let mut count = VertexCount::default();
+        ///
+        /// file.visit(&mut count);
+        /// ```
+        pub trait LvdVisitor {
+            /// Called for each object without an overridden callback for its type.
+            fn visit_object(&mut self, _object: ObjectRef, _value: &dyn LvdObject) {}
+
+            $(
+                #[doc = concat!("Called for each [`", stringify!($ty), "`].")]
+                fn $visit(&mut self, object: ObjectRef, value: &$ty) {
+                    self.visit_object(object, value);
+                }
+            )+
+        }
+
+        /// A trait for modifying each object of an LVD file with [`LvdFile::visit_mut`].
+        ///
+        /// Every callback defaults to [`visit_object_mut`](LvdVisitorMut::visit_object_mut),
+        /// so an implementation only needs to override the callbacks for the types it modifies.
+        pub trait LvdVisitorMut {
+            /// Called for each object without an overridden callback for its type.
+            fn visit_object_mut(&mut self, _object: ObjectRef, _value: &mut dyn LvdObject) {}
+
+            $(
+                #[doc = concat!("Called for each [`", stringify!($ty), "`].")]
+                fn $visit_mut(&mut self, object: ObjectRef, value: &mut $ty) {
+                    self.visit_object_mut(object, value);
+                }
+            )+
+        }
+
+        $(
+            impl Visit for $ty {
+                fn accept<V: LvdVisitor + ?Sized>(&self, object: ObjectRef, visitor: &mut V) {
+                    visitor.$visit(object, self);
+                }
+
+                fn accept_mut<V: LvdVisitorMut + ?Sized>(&mut self, object: ObjectRef, visitor: &mut V) {
+                    visitor.$visit_mut(object, self);
+                }
+            }
+        )+
+    };
+}
+
+visitors! {
+    Collision: visit_collision, visit_collision_mut;
+    Point: visit_point, visit_point_mut;
+    Region: visit_region, visit_region_mut;
+    EnemyGenerator: visit_enemy_generator, visit_enemy_generator_mut;
+    FsItem: visit_fs_item, visit_fs_item_mut;
+    FsUnknown: visit_fs_unknown, visit_fs_unknown_mut;
+    FsAreaCam: visit_fs_area_cam, visit_fs_area_cam_mut;
+    FsAreaLock: visit_fs_area_lock, visit_fs_area_lock_mut;
+    FsCamLimit: visit_fs_cam_limit, visit_fs_cam_limit_mut;
+    DamageShape: visit_damage_shape, visit_damage_shape_mut;
+    ItemPopup: visit_item_popup, visit_item_popup_mut;
+    PTrainerRange: visit_ptrainer_range, visit_ptrainer_range_mut;
+    PTrainerFloatingFloor: visit_ptrainer_floating_floor, visit_ptrainer_floating_floor_mut;
+    GeneralShape2: visit_general_shape2, visit_general_shape2_mut;
+    GeneralShape3: visit_general_shape3, visit_general_shape3_mut;
+    AreaLight: visit_area_light, visit_area_light_mut;
+    FsStartPoint: visit_fs_start_point, visit_fs_start_point_mut;
+    AreaHint: visit_area_hint, visit_area_hint_mut;
+    SplitArea: visit_split_area, visit_split_area_mut;
+}
+
+/// A trait for dispatching an object to the visitor callback for its type.
+pub(crate) trait Visit {
+    /// Calls the visitor's callback for the object's type.
+    fn accept<V: LvdVisitor + ?Sized>(&self, object: ObjectRef, visitor: &mut V);
+
+    /// Calls the mutable visitor's callback for the object's type.
+    fn accept_mut<V: LvdVisitorMut + ?Sized>(&mut self, object: ObjectRef, visitor: &mut V);
+}
+
+/// A coordinate mapping which passes each two-dimensional position to a closure,
+/// leaving every other value unchanged.
+struct Point2Hook<F>(RefCell<F>);
+
+impl<F: FnMut(&mut Vector2)> CoordinateMap for Point2Hook<F> {
+    fn map_point2(&self, mut point: Vector2) -> Vector2 {
+        (self.0.borrow_mut())(&mut point);
+
+        point
+    }
+
+    fn map_point3(&self, point: Vector3) -> Vector3 {
+        point
+    }
+
+    fn map_vector3(&self, vector: Vector3) -> Vector3 {
+        vector
+    }
+
+    fn map_normal2(&self, normal: Vector2) -> Vector2 {
+        normal
+    }
+
+    fn map_length2(&self, length: f32) -> f32 {
+        length
+    }
+
+    fn map_length3(&self, length: f32) -> f32 {
+        length
+    }
+}
+
+impl LvdFile {
+    /// Calls the visitor's callback for each top-level object in every section, in file order.
+    pub fn visit<V: LvdVisitor + ?Sized>(&self, visitor: &mut V) {
+        self.data.visit(visitor);
+    }
+
+    /// Calls the mutable visitor's callback for each top-level object in every section,
+    /// in file order.
+    pub fn visit_mut<V: LvdVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.data.visit_mut(visitor);
+    }
+
+    /// Calls a closure on each two-dimensional position in the file, such as collision
+    /// vertices, cliff and point positions, and the corners of shapes, allowing it to move them.
+    ///
+    /// Normals, three-dimensional positions, and lengths such as radii are not passed to the
+    /// closure. As with [`LvdObject::transform`], objects exclusive to Smash Run are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::{vector::Vector2, LvdFile};
+    ///
+    /// let mut file = LvdFile::from_file("battlefield_00.lvd").unwrap();
+    ///
+    /// file.for_each_point2_mut(|point| *point = Vector2::new(point.x().round(), point.y().round()));
+    /// ```
+    pub fn for_each_point2_mut<F: FnMut(&mut Vector2)>(&mut self, f: F) {
+        let hook = Point2Hook(RefCell::new(f));
+
+        self.data
+            .for_each_object_mut(|object| object.transform(&hook));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures::file_with_collisions, SectionKind};
+
+    use super::*;
+
+    /// Records the objects passed to the overridden callback and to the default one.
+    #[derive(Default)]
+    struct Recorder {
+        typed: Vec<ObjectRef>,
+        others: Vec<ObjectRef>,
+    }
+
+    impl LvdVisitor for Recorder {
+        fn visit_object(&mut self, object: ObjectRef, _value: &dyn LvdObject) {
+            self.others.push(object);
+        }
+
+        fn visit_collision(&mut self, object: ObjectRef, _collision: &Collision) {
+            self.typed.push(object);
+        }
+    }
+
+    impl LvdVisitorMut for Recorder {
+        fn visit_object_mut(&mut self, object: ObjectRef, _value: &mut dyn LvdObject) {
+            self.others.push(object);
+        }
+
+        fn visit_point_mut(&mut self, object: ObjectRef, point: &mut Point) {
+            *point.pos_mut() = Vector2::new(1.0, 2.0);
+            self.typed.push(object);
+        }
+    }
+
+    fn file() -> LvdFile {
+        let mut file = file_with_collisions([
+            Collision::new_soft_platform(Vector2::new(-10.0, 0.0), Vector2::new(10.0, 0.0)),
+            Collision::new_soft_platform(Vector2::new(20.0, 5.0), Vector2::new(30.0, 5.0)),
+        ]);
+        let data = &mut file.data;
+
+        data.item_popups_mut().unwrap().push(ItemPopup::default());
+        data.death_regions_mut().unwrap().push(Region::default());
+        data.start_positions_mut().unwrap().push(Point::default());
+        data.start_positions_mut().unwrap().push(Point::default());
+
+        file
+    }
+
+    #[test]
+    fn visit_every_object_in_file_order() {
+        let file = file();
+        let mut recorder = Recorder::default();
+
+        file.visit(&mut recorder);
+
+        assert_eq!(
+            recorder.typed,
+            [
+                ObjectRef::new(SectionKind::Collisions, 0),
+                ObjectRef::new(SectionKind::Collisions, 1),
+            ]
+        );
+        assert_eq!(
+            recorder.others,
+            [
+                ObjectRef::new(SectionKind::StartPositions, 0),
+                ObjectRef::new(SectionKind::StartPositions, 1),
+                ObjectRef::new(SectionKind::DeathRegions, 0),
+                ObjectRef::new(SectionKind::ItemPopups, 0),
+            ],
+            "overridden callbacks replace `visit_object`"
+        );
+
+        let total = SectionKind::ALL
+            .iter()
+            .map(|&section| file.data.section_len(section).unwrap_or_default())
+            .sum::<usize>();
+
+        assert_eq!(recorder.typed.len() + recorder.others.len(), total);
+    }
+
+    #[test]
+    fn visit_mut_modifies_objects() {
+        let mut file = file();
+        let mut recorder = Recorder::default();
+
+        file.visit_mut(&mut recorder);
+
+        assert_eq!(recorder.typed.len(), 2);
+        assert_eq!(recorder.others.len(), 4);
+        assert!(file
+            .data
+            .start_positions()
+            .unwrap()
+            .iter()
+            .all(|point| point.pos() == Vector2::new(1.0, 2.0)));
+    }
+
+    #[test]
+    fn for_each_point2_skips_normals() {
+        let mut file = file_with_collisions([Collision::new_soft_platform(
+            Vector2::new(-10.0, 0.0),
+            Vector2::new(10.0, 0.0),
+        )]);
+        let mut count = 0;
+
+        file.for_each_point2_mut(|point| {
+            count += 1;
+            *point = Vector2::new(point.x() * 2.0, point.y() + 1.0);
+        });
+
+        let collision = &file.data.collisions().unwrap().elements()[0];
+
+        // Each end of the platform has a vertex and a cliff.
+        assert_eq!(count, 4);
+        assert_eq!(
+            collision.vertices().iter().copied().collect::<Vec<_>>(),
+            [Vector2::new(-20.0, 1.0), Vector2::new(20.0, 1.0)]
+        );
+        assert_eq!(
+            collision.normals().iter().copied().collect::<Vec<_>>(),
+            [Vector2::new(0.0, 1.0)]
+        );
+    }
+}
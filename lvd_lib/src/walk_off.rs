@@ -0,0 +1,256 @@
+//! Detection and trimming of walk-offs.
+//!
+//! This module contains the [`LvdFile::walk_offs`] method, which finds the floors of solid
+//! collisions reaching the blast zone, and the [`LvdFile::trim_walk_offs`] method, which cuts
+//! them off inside the blast zone with a ledge at each new end, as when converting a casual
+//! stage to a competitive variant.
+
+use std::fmt;
+
+use crate::{array::Array, shape::Rect, symmetry::Side, LvdFile, ObjectRef, SectionKind};
+
+/// A floor edge of a solid collision reaching the blast zone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WalkOff {
+    /// The collision containing the edge.
+    pub object: ObjectRef,
+
+    /// The index of the edge in the collision.
+    pub edge: usize,
+
+    /// The side of the blast zone the edge reaches.
+    pub side: Side,
+}
+
+impl fmt::Display for WalkOff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let side = match self.side {
+            Side::Left => "left",
+            Side::Right => "right",
+        };
+
+        write!(
+            f,
+            "object {} in section `{}`: floor edge {} reaches the {side} blast zone",
+            self.object.index,
+            self.object.section.name(),
+            self.edge
+        )
+    }
+}
+
+impl LvdFile {
+    /// Returns each floor edge of a solid collision extending beyond `margin` inside the left
+    /// or right edge of the blast zone, which is the union of the death regions.
+    ///
+    /// An edge extending beyond both edges is returned once for each side. No edges are
+    /// returned if the file has no death regions.
+    pub fn walk_offs(&self, margin: f32) -> Vec<WalkOff> {
+        let Some((left, right)) = self.trim_bounds(margin) else {
+            return Vec::new();
+        };
+        let mut walk_offs = Vec::new();
+
+        for (index, collision) in self
+            .data
+            .collisions()
+            .into_iter()
+            .flat_map(Array::iter)
+            .enumerate()
+        {
+            if collision.flags().throughable() {
+                continue;
+            }
+
+            let vertices = collision.vertices().elements();
+
+            for edge in (0..collision.edge_count()).filter(|&e| collision.is_floor_edge(e)) {
+                let xs = vertices[edge..].iter().take(2).map(|v| v.x());
+                let sides = [
+                    (Side::Left, xs.clone().any(|x| x < left)),
+                    (Side::Right, xs.clone().any(|x| x > right)),
+                ];
+
+                walk_offs.extend(sides.into_iter().filter(|(_, reaches)| *reaches).map(
+                    |(side, _)| WalkOff {
+                        object: ObjectRef::new(SectionKind::Collisions, index),
+                        edge,
+                        side,
+                    },
+                ));
+            }
+        }
+
+        walk_offs
+    }
+
+    /// Cuts off the parts of each collision with a walk-off beyond `margin` inside the left and
+    /// right edges of the blast zone, as described by [`Collision::trim_x`], returning the number
+    /// of collisions which changed.
+    ///
+    /// Each trimmed floor gets a ledge at its new end, and closed collisions are capped with a
+    /// wall where they are cut, so the stage can no longer be walked off.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lvd_lib::LvdFile;
+    ///
+    /// let mut file = LvdFile::from_file("fox_00.lvd").unwrap();
+    ///
+    /// file.trim_walk_offs(20.0);
+    /// assert!(file.walk_offs(20.0).is_empty());
+    /// ```
+    ///
+    /// [`Collision::trim_x`]: crate::objects::Collision::trim_x
+    pub fn trim_walk_offs(&mut self, margin: f32) -> usize {
+        let Some((left, right)) = self.trim_bounds(margin) else {
+            return 0;
+        };
+        let mut indices = self
+            .walk_offs(margin)
+            .into_iter()
+            .map(|walk_off| walk_off.object.index)
+            .collect::<Vec<_>>();
+
+        indices.dedup();
+
+        let Some(collisions) = self.data.collisions_mut() else {
+            return 0;
+        };
+
+        indices
+            .into_iter()
+            .filter(|&index| {
+                collisions
+                    .iter_mut()
+                    .nth(index)
+                    .is_some_and(|collision| collision.trim_x(left, right))
+            })
+            .count()
+    }
+
+    /// Returns the x-coordinates `margin` inside the left and right edges of the blast zone.
+    fn trim_bounds(&self, margin: f32) -> Option<(f32, f32)> {
+        self.data
+            .death_regions()
+            .into_iter()
+            .flat_map(Array::iter)
+            .map(|region| {
+                let Rect::V1 { left, right, .. } = region.rect();
+
+                (left, right)
+            })
+            .reduce(|(l1, r1), (l2, r2)| (l1.min(l2), r1.max(r2)))
+            .map(|(left, right)| (left + margin, right - margin))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        fixtures::file_with_collisions,
+        objects::{Collision, Region},
+        vector::Vector2,
+    };
+
+    fn death_region(left: f32, right: f32) -> Region {
+        let mut region = Region::default();
+
+        *region.rect_mut() = Rect::V1 {
+            left,
+            right,
+            bottom: -200.0,
+            top: 200.0,
+        };
+        region
+    }
+
+    #[test]
+    fn trim_walk_offs_caps_closed_collision() {
+        let mut collision = Collision::default();
+
+        // A clockwise box whose top floor runs past both blast zones.
+        *collision.vertices_mut() = [
+            (-300.0, 0.0),
+            (300.0, 0.0),
+            (300.0, -50.0),
+            (-300.0, -50.0),
+            (-300.0, 0.0),
+        ]
+        .into_iter()
+        .map(|(x, y)| Vector2::new(x, y))
+        .collect();
+        *collision.normals_mut() = [(0.0, 1.0), (1.0, 0.0), (0.0, -1.0), (-1.0, 0.0)]
+            .into_iter()
+            .map(|(x, y)| Vector2::new(x, y))
+            .collect();
+
+        let mut file = file_with_collisions([collision]);
+
+        file.data
+            .death_regions_mut()
+            .unwrap()
+            .push(death_region(-250.0, 250.0));
+
+        assert_eq!(file.walk_offs(10.0).len(), 2);
+        assert_eq!(file.trim_walk_offs(10.0), 1);
+        assert!(file.walk_offs(10.0).is_empty());
+
+        let collision = file.data.collisions().unwrap().iter().next().unwrap();
+        let vertices = collision.vertices().iter().copied().collect::<Vec<_>>();
+
+        assert!(collision.is_closed());
+        assert_eq!(
+            vertices[..4],
+            [
+                Vector2::new(-240.0, 0.0),
+                Vector2::new(240.0, 0.0),
+                Vector2::new(240.0, -50.0),
+                Vector2::new(-240.0, -50.0),
+            ]
+        );
+        assert_eq!(
+            collision.normals().iter().nth(1),
+            Some(&Vector2::new(1.0, 0.0))
+        );
+        assert_eq!(
+            collision.normals().iter().last(),
+            Some(&Vector2::new(-1.0, 0.0))
+        );
+        assert_eq!(
+            collision
+                .cliffs()
+                .iter()
+                .map(|cliff| (cliff.pos(), cliff.lr(), cliff.line_index()))
+                .collect::<Vec<_>>(),
+            [
+                (Vector2::new(-240.0, 0.0), -1.0, Some(0)),
+                (Vector2::new(240.0, 0.0), 1.0, Some(0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn walk_offs_skip_platforms_and_stages_without_blast_zones() {
+        let platform =
+            Collision::new_soft_platform(Vector2::new(-300.0, 0.0), Vector2::new(300.0, 0.0));
+        let mut file = file_with_collisions([platform]);
+
+        assert!(file.walk_offs(0.0).is_empty(), "no death regions");
+        assert_eq!(file.trim_walk_offs(0.0), 0);
+
+        file.data
+            .death_regions_mut()
+            .unwrap()
+            .push(death_region(-250.0, 250.0));
+
+        assert!(
+            file.walk_offs(0.0).is_empty(),
+            "throughable platforms are not walk-offs"
+        );
+        assert_eq!(file.trim_walk_offs(0.0), 0);
+    }
+}
@@ -0,0 +1,178 @@
+#![cfg(feature = "test-utils")]
+
+use std::io::Cursor;
+
+use lvd_lib::{
+    model::{LvdData, ModelVersions},
+    objects::LvdObject,
+    test_utils,
+    version::Version,
+    visit::LvdVisitor,
+    LvdFile, ObjectRef, SectionKind,
+};
+use proptest::prelude::*;
+
+fn to_bytes(file: &LvdFile) -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::new());
+
+    file.write(&mut writer).unwrap();
+    writer.into_inner()
+}
+
+#[test]
+fn sample_v13_round_trip() {
+    let bytes = to_bytes(&LvdFile::sample_v13());
+    let file = LvdFile::read(&mut Cursor::new(&bytes)).unwrap();
+
+    assert_eq!(to_bytes(&file), bytes);
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn sample_v13_mmap_round_trip() {
+    let path = std::env::temp_dir().join(format!("lvd_lib_mmap_{}.lvd", std::process::id()));
+    let bytes = to_bytes(&LvdFile::sample_v13());
+
+    std::fs::write(&path, &bytes).unwrap();
+
+    let file = unsafe { LvdFile::read_be_mmap(&path) };
+
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(to_bytes(&file.unwrap()), bytes);
+}
+
+#[test]
+fn sample_v13_read_from_offset() {
+    let bytes = to_bytes(&LvdFile::sample_v13());
+    let mut archive = vec![0xFF; 16];
+
+    archive.extend_from_slice(&bytes);
+
+    let mut reader = Cursor::new(&archive);
+
+    reader.set_position(16);
+
+    let file = LvdFile::read_from(&mut reader).unwrap();
+
+    assert_eq!(to_bytes(&file), bytes);
+}
+
+#[test]
+fn sample_v13_read_from_range() {
+    let bytes = to_bytes(&LvdFile::sample_v13());
+    let mut archive = vec![0xFF; 16];
+
+    archive.extend_from_slice(&bytes);
+    archive.extend_from_slice(&[0xEE; 32]);
+
+    let file = LvdFile::read_from_range(Cursor::new(&archive), 16, bytes.len() as u64).unwrap();
+
+    assert!(file.data.unknown_sections().is_empty());
+    assert_eq!(to_bytes(&file), bytes);
+    assert!(
+        LvdFile::read_from_range(Cursor::new(&archive), 16, bytes.len() as u64 / 2).is_err(),
+        "data extending beyond the range is rejected"
+    );
+}
+
+#[test]
+fn sample_v13_file_round_trip() {
+    let path = std::env::temp_dir().join(format!("lvd_lib_file_{}.lvd", std::process::id()));
+    let bytes = to_bytes(&LvdFile::sample_v13());
+
+    LvdFile::sample_v13().write_to_file(&path).unwrap();
+
+    let file = LvdFile::from_file(&path);
+
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(to_bytes(&file.unwrap()), bytes);
+}
+
+#[test]
+fn sample_v13_write_error_names_section() {
+    let mut buffer = [0; 64];
+    let error = LvdFile::sample_v13()
+        .write(&mut Cursor::new(&mut buffer[..]))
+        .unwrap_err();
+
+    assert!(error.to_string().contains("section `collisions`"));
+}
+
+#[test]
+fn sample_v13_sections_in_file_order() {
+    let file = LvdFile::sample_v13();
+    let kinds = file
+        .data
+        .sections()
+        .map(|(kind, _)| kind)
+        .collect::<Vec<_>>();
+    let supported = SectionKind::ALL
+        .iter()
+        .copied()
+        .filter(|kind| kind.is_supported_by(file.data.version()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(kinds, supported);
+}
+
+#[test]
+fn sample_v13_model_round_trip() {
+    let file = LvdFile::sample_v13();
+    let versions = ModelVersions::of(&file.data);
+    let copy = LvdData::from(file.clone()).into_file(&versions).unwrap();
+
+    assert_eq!(to_bytes(&copy), to_bytes(&file));
+}
+
+#[test]
+fn sample_v13_visits_every_object() {
+    struct Visited(Vec<ObjectRef>);
+
+    impl LvdVisitor for Visited {
+        fn visit_object(&mut self, object: ObjectRef, _value: &dyn LvdObject) {
+            self.0.push(object);
+        }
+    }
+
+    let file = LvdFile::sample_v13();
+    let mut visited = Visited(Vec::new());
+
+    file.visit(&mut visited);
+
+    let expected = SectionKind::ALL
+        .iter()
+        .flat_map(|&kind| {
+            (0..file.data.section_len(kind).unwrap_or_default())
+                .map(move |index| ObjectRef::new(kind, index))
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(visited.0, expected);
+}
+
+proptest! {
+    #[test]
+    fn binary_round_trip(file in test_utils::lvd_file()) {
+        let bytes = to_bytes(&file);
+        let file = LvdFile::read(&mut Cursor::new(&bytes)).unwrap();
+
+        prop_assert_eq!(to_bytes(&file), bytes);
+    }
+
+    #[test]
+    fn model_round_trip(file in test_utils::lvd_file()) {
+        let versions = ModelVersions::of(&file.data);
+        let copy = LvdData::from(file.clone()).into_file(&versions).unwrap();
+
+        prop_assert_eq!(to_bytes(&copy), to_bytes(&file));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip(file in test_utils::lvd_file()) {
+        let json = serde_json::to_string(&file).unwrap();
+        let copy = serde_json::from_str::<LvdFile>(&json).unwrap();
+
+        prop_assert_eq!(to_bytes(&copy), to_bytes(&file));
+    }
+}
@@ -0,0 +1,364 @@
+//! An interactive terminal browser for inspecting and editing LVD files.
+
+use std::{error::Error, io};
+
+use lvd_lib::{finite::NonFinitePolicy, LvdFile};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    widgets::{Block, List, ListState, Paragraph},
+    DefaultTerminal, Frame,
+};
+use serde_json::{Number, Value};
+
+/// The message shown when quitting with unsaved changes.
+const UNSAVED_MESSAGE: &str = "Unsaved changes. Press q again to quit.";
+
+/// A step from a node to one of its children.
+#[derive(Clone)]
+enum Key {
+    Field(String),
+    Index(usize),
+}
+
+/// The state of the browser.
+struct Browser {
+    /// The path of the file being browsed.
+    path: String,
+
+    /// The serialized form of the file.
+    root: Value,
+
+    /// The steps from the root to the current node, with the selection to restore when returning.
+    keys: Vec<(Key, usize)>,
+
+    /// The selection within the current node.
+    list: ListState,
+
+    /// The text of the value being edited, if any.
+    input: Option<String>,
+
+    /// The message shown in the status line.
+    message: String,
+
+    /// Determines if there are changes which have not been saved.
+    dirty: bool,
+
+    /// Determines if the browser should exit.
+    quit: bool,
+}
+
+/// Opens the LVD file at the given path in an interactive terminal browser.
+pub fn browse(input_path: String) -> Result<(), Box<dyn Error>> {
+    let lvd = LvdFile::from_file(&input_path)?;
+    let mut browser = Browser {
+        path: input_path,
+        root: serde_json::to_value(&lvd)?,
+        keys: Vec::new(),
+        list: ListState::default().with_selected(Some(0)),
+        input: None,
+        message: String::new(),
+        dirty: false,
+        quit: false,
+    };
+    let mut terminal = ratatui::init();
+    let result = browser.run(&mut terminal);
+
+    ratatui::restore();
+
+    Ok(result?)
+}
+
+impl Browser {
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    match self.input {
+                        Some(_) => self.handle_input_key(key.code),
+                        None => self.handle_key(key.code),
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_key(&mut self, code: KeyCode) {
+        let len = self.children().len();
+        let selected = self.list.selected().unwrap_or_default();
+        let confirming_quit = self.message == UNSAVED_MESSAGE;
+
+        self.message.clear();
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                if self.dirty && !confirming_quit {
+                    self.message = UNSAVED_MESSAGE.to_string();
+                } else {
+                    self.quit = true;
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.list.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down | KeyCode::Char('j') => self
+                .list
+                .select(Some((selected + 1).min(len.saturating_sub(1)))),
+            KeyCode::Right | KeyCode::Enter | KeyCode::Char('l') => self.enter(selected),
+            KeyCode::Left | KeyCode::Backspace | KeyCode::Char('h') => self.leave(),
+            KeyCode::Char('e') => self.start_editing(selected),
+            KeyCode::Char('s') => self.save(),
+            _ => {}
+        }
+    }
+
+    fn handle_input_key(&mut self, code: KeyCode) {
+        let Some(input) = &mut self.input else {
+            return;
+        };
+
+        match code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.input = None,
+            KeyCode::Enter => {
+                let input = self.input.take().unwrap();
+
+                self.finish_editing(&input);
+            }
+            _ => {}
+        }
+    }
+
+    /// Descends into the child at the given index, or edits it if it is a scalar value.
+    fn enter(&mut self, selected: usize) {
+        let Some((key, value)) = self.children().into_iter().nth(selected) else {
+            return;
+        };
+
+        if value.is_object() || value.is_array() {
+            self.keys.push((key, selected));
+            self.list.select(Some(0));
+        } else {
+            self.start_editing(selected);
+        }
+    }
+
+    /// Returns to the parent of the current node.
+    fn leave(&mut self) {
+        if let Some((_, selected)) = self.keys.pop() {
+            self.list.select(Some(selected));
+        }
+    }
+
+    fn start_editing(&mut self, selected: usize) {
+        match self.children().into_iter().nth(selected) {
+            Some((_, Value::String(s))) => self.input = Some(s.clone()),
+            Some((_, value @ (Value::Number(_) | Value::Bool(_)))) => {
+                self.input = Some(value.to_string())
+            }
+            Some(_) => self.message = "Only scalar values can be edited.".to_string(),
+            None => {}
+        }
+    }
+
+    fn finish_editing(&mut self, input: &str) {
+        let selected = self.list.selected().unwrap_or_default();
+        let Some((key, _)) = self.children().into_iter().nth(selected) else {
+            return;
+        };
+        let Some(value) = child_mut(self.node_mut(), &key) else {
+            return;
+        };
+        let new_value = match value {
+            Value::String(_) => Some(Value::String(input.to_string())),
+            Value::Bool(_) => input.parse().ok().map(Value::Bool),
+            Value::Number(n) if n.is_f64() => input
+                .parse()
+                .ok()
+                .and_then(Number::from_f64)
+                .map(Value::Number),
+            Value::Number(_) => input.parse::<i64>().ok().map(Value::from),
+            _ => None,
+        };
+
+        match new_value {
+            Some(new_value) => {
+                *value = new_value;
+                self.dirty = true;
+            }
+            None => self.message = format!("`{input}` is not a valid value for this field."),
+        }
+    }
+
+    fn save(&mut self) {
+        let result = serde_json::from_value::<LvdFile>(self.root.clone())
+            .map_err(|error| error.to_string())
+            .and_then(|lvd| {
+                lvd.write_to_file_checked(&self.path, NonFinitePolicy::Reject)
+                    .map_err(|error| error.to_string())
+            });
+
+        match result {
+            Ok(()) => {
+                self.dirty = false;
+                self.message = format!("Saved {}.", self.path);
+            }
+            Err(error) => self.message = format!("Failed to save: {error}"),
+        }
+    }
+
+    /// Returns the current node.
+    fn node(&self) -> &Value {
+        self.keys.iter().fold(unwrap(&self.root), |node, (key, _)| {
+            child(node, key).map(unwrap).unwrap_or(node)
+        })
+    }
+
+    /// Returns the current node mutably.
+    fn node_mut(&mut self) -> &mut Value {
+        let mut node = unwrap_mut(&mut self.root);
+
+        for (key, _) in &self.keys {
+            node = unwrap_mut(child_mut(node, key).unwrap());
+        }
+
+        node
+    }
+
+    /// Returns the children of the current node with their keys.
+    fn children(&self) -> Vec<(Key, &Value)> {
+        match self.node() {
+            Value::Object(map) => map
+                .iter()
+                .map(|(k, v)| (Key::Field(k.clone()), v))
+                .collect(),
+            Value::Array(elements) => elements
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (Key::Index(i), v))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let breadcrumb = self
+            .keys
+            .iter()
+            .map(|(key, _)| match key {
+                Key::Field(name) => name.clone(),
+                Key::Index(i) => format!("[{i}]"),
+            })
+            .collect::<Vec<_>>()
+            .join(" / ");
+        let modified = if self.dirty { " [modified]" } else { "" };
+        let items = self
+            .children()
+            .into_iter()
+            .map(|(key, value)| match key {
+                Key::Field(name) => format!("{name}: {}", preview(value)),
+                Key::Index(i) => format!("[{i}] {}", preview(value)),
+            })
+            .collect::<Vec<_>>();
+        let list = List::new(items)
+            .block(Block::bordered().title(format!(" {breadcrumb} ")))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let status = match &self.input {
+            Some(input) => format!("Edit: {input}_  (Enter to apply, Esc to cancel)"),
+            None if !self.message.is_empty() => self.message.clone(),
+            None => "↑↓ select  → open  ← back  e edit  s save  q quit".to_string(),
+        };
+
+        frame.render_widget(Paragraph::new(format!("{}{modified}", self.path)), header);
+        frame.render_stateful_widget(list, body, &mut self.list);
+        frame.render_widget(Paragraph::new(status), footer);
+    }
+}
+
+/// Returns `true` if the key of a single-key object only wraps the value for versioning
+/// or array storage, and can be skipped when browsing.
+fn is_wrapper_key(key: &str) -> bool {
+    key == "elements"
+        || key
+            .strip_prefix('V')
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Returns the value inside any version or array wrappers.
+pub fn unwrap(mut value: &Value) -> &Value {
+    while let Value::Object(map) = value {
+        match map.iter().next() {
+            Some((key, inner)) if map.len() == 1 && is_wrapper_key(key) => value = inner,
+            _ => break,
+        }
+    }
+
+    value
+}
+
+/// Returns the value inside any version or array wrappers mutably.
+fn unwrap_mut(value: &mut Value) -> &mut Value {
+    let is_wrapper = match value {
+        Value::Object(map) => map.len() == 1 && map.keys().all(|key| is_wrapper_key(key)),
+        _ => false,
+    };
+
+    if is_wrapper {
+        let inner = value.as_object_mut().unwrap().values_mut().next().unwrap();
+
+        unwrap_mut(inner)
+    } else {
+        value
+    }
+}
+
+fn child<'a>(value: &'a Value, key: &Key) -> Option<&'a Value> {
+    match key {
+        Key::Field(name) => value.get(name),
+        Key::Index(i) => value.get(i),
+    }
+}
+
+fn child_mut<'a>(value: &'a mut Value, key: &Key) -> Option<&'a mut Value> {
+    match key {
+        Key::Field(name) => value.get_mut(name),
+        Key::Index(i) => value.get_mut(i),
+    }
+}
+
+/// Returns the name of an object, if it has one.
+fn name(value: &Value) -> Option<&str> {
+    let value = unwrap(value);
+
+    value
+        .get("name")
+        .and_then(Value::as_str)
+        .or_else(|| value.get("meta_info").and_then(name))
+        .or_else(|| value.get("base").and_then(name))
+        .or_else(|| value.get("region").and_then(name))
+}
+
+/// Returns a short, single-line summary of a value.
+fn preview(value: &Value) -> String {
+    match unwrap(value) {
+        Value::Object(map) => match name(value) {
+            Some(name) => name.to_string(),
+            None => format!("{{{} fields}}", map.len()),
+        },
+        Value::Array(elements) => format!("[{} elements]", elements.len()),
+        Value::String(s) => format!("{s:?}"),
+        value => value.to_string(),
+    }
+}
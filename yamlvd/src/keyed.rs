@@ -0,0 +1,233 @@
+//! The sections of an LVD file's YAML model form as mappings keyed by object name.
+//!
+//! Written with `--keyed`, each section is a mapping from the name of each object to the object
+//! instead of a sequence, so edits to different objects never touch neighbouring lines and merge
+//! without conflicts. Each object records its position in the section under an `order` key.
+//! Objects with an empty or repeated name are keyed by `#` followed by their position instead,
+//! and keep their name in their `base`.
+
+use lvd_lib::SectionKind;
+use serde_yaml::{Mapping, Value};
+
+/// The key holding the position of an object in its section.
+const ORDER_KEY: &str = "order";
+
+/// The prefix of the keys of objects which cannot be keyed by their name.
+const POSITION_PREFIX: char = '#';
+
+/// Converts every section of an LVD file's YAML model form to a mapping keyed by object name.
+pub fn to_keyed(value: &mut Value) {
+    let Some(sections) = value.as_mapping_mut() else {
+        return;
+    };
+
+    for (key, section) in sections.iter_mut() {
+        if key.as_str().and_then(SectionKind::from_name).is_none() {
+            continue;
+        }
+
+        let Value::Sequence(objects) = section else {
+            continue;
+        };
+
+        if !objects.iter().all(Value::is_mapping) {
+            continue;
+        }
+
+        let names = objects
+            .iter()
+            .map(|object| object_name(object).map(str::to_string))
+            .collect::<Vec<_>>();
+        let mut keyed = Mapping::new();
+
+        for (index, (object, name)) in std::mem::take(objects).into_iter().zip(&names).enumerate() {
+            let Value::Mapping(mut object) = object else {
+                continue;
+            };
+            let key = match name {
+                Some(name)
+                    if !name.is_empty()
+                        && !name.starts_with(POSITION_PREFIX)
+                        && names.iter().filter(|n| n.as_ref() == Some(name)).count() == 1 =>
+                {
+                    if let Some(Value::Mapping(base)) = object.get_mut("base") {
+                        base.remove("name");
+                    }
+
+                    name.clone()
+                }
+                _ => format!("{POSITION_PREFIX}{index}"),
+            };
+            let mut entry = Mapping::new();
+
+            entry.insert(ORDER_KEY.into(), index.into());
+            entry.extend(object);
+            keyed.insert(key.into(), Value::Mapping(entry));
+        }
+
+        *section = Value::Mapping(keyed);
+    }
+}
+
+/// Returns `true` if any section of an LVD file's YAML model form is keyed by object name.
+pub fn is_keyed(value: &Value) -> bool {
+    value.as_mapping().is_some_and(|sections| {
+        sections.iter().any(|(key, section)| {
+            key.as_str().and_then(SectionKind::from_name).is_some() && section.is_mapping()
+        })
+    })
+}
+
+/// Converts every section of an LVD file's YAML model form keyed by object name back to a
+/// sequence, ordered by the `order` of each object.
+///
+/// Objects without an `order` are placed after the others, in the order they are written.
+/// Objects without a name take the name they are keyed by.
+///
+/// # Errors
+///
+/// Returns an error if a key is not a string, an object is not a mapping,
+/// or an `order` is not a non-negative integer.
+pub fn from_keyed(value: &mut Value) -> Result<(), String> {
+    let Some(sections) = value.as_mapping_mut() else {
+        return Ok(());
+    };
+
+    for (section_key, section) in sections.iter_mut() {
+        let Some(section_name) = section_key
+            .as_str()
+            .filter(|key| SectionKind::from_name(key).is_some())
+        else {
+            continue;
+        };
+
+        let Value::Mapping(keyed) = section else {
+            continue;
+        };
+
+        let mut objects = Vec::new();
+
+        for (key, object) in std::mem::take(keyed) {
+            let Some(key) = key.as_str() else {
+                return Err(format!(
+                    "the keys of section `{section_name}` must be object names"
+                ));
+            };
+            let Value::Mapping(mut object) = object else {
+                return Err(format!("{section_name}.{key}: expected an object"));
+            };
+            let order = match object.remove(ORDER_KEY) {
+                Some(order) => Some(order.as_u64().ok_or_else(|| {
+                    format!("{section_name}.{key}.{ORDER_KEY}: expected a non-negative integer")
+                })?),
+                None => None,
+            };
+
+            if let Some(Value::Mapping(base)) = object.get_mut("base") {
+                if !base.contains_key("name") {
+                    base.insert("name".into(), key.into());
+                }
+            }
+
+            objects.push((order, Value::Mapping(object)));
+        }
+
+        objects.sort_by_key(|(order, _)| order.unwrap_or(u64::MAX));
+        *section = Value::Sequence(objects.into_iter().map(|(_, object)| object).collect());
+    }
+
+    Ok(())
+}
+
+/// Returns the name of an object in an LVD file's YAML model form.
+fn object_name(object: &Value) -> Option<&str> {
+    object.get("base")?.get("name")?.as_str()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIST: &str = "\
+endian: big
+start_positions:
+- base:
+    name: spawn
+  pos: {x: 1.0, y: 2.0}
+- base:
+    name: ''
+  pos: {x: 3.0, y: 4.0}
+- base:
+    name: ''
+  pos: {x: 5.0, y: 6.0}
+";
+
+    #[test]
+    fn keyed_round_trip() {
+        let list: Value = serde_yaml::from_str(LIST).unwrap();
+        let mut value = list.clone();
+
+        to_keyed(&mut value);
+
+        assert!(is_keyed(&value));
+        assert_eq!(
+            serde_yaml::to_string(&value["start_positions"]).unwrap(),
+            "\
+spawn:
+  order: 0
+  base: {}
+  pos:
+    x: 1.0
+    y: 2.0
+'#1':
+  order: 1
+  base:
+    name: ''
+  pos:
+    x: 3.0
+    y: 4.0
+'#2':
+  order: 2
+  base:
+    name: ''
+  pos:
+    x: 5.0
+    y: 6.0
+"
+        );
+
+        from_keyed(&mut value).unwrap();
+
+        assert!(!is_keyed(&value));
+        assert_eq!(value, list);
+    }
+
+    #[test]
+    fn keyed_order() {
+        let mut value: Value = serde_yaml::from_str(
+            "\
+start_positions:
+  extra:
+    base: {}
+  second:
+    order: 1
+    base: {}
+  first:
+    order: 0
+    base: {}
+",
+        )
+        .unwrap();
+
+        from_keyed(&mut value).unwrap();
+
+        let names = value["start_positions"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(object_name)
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, [Some("first"), Some("second"), Some("extra")]);
+    }
+}
@@ -1,62 +1,1908 @@
+mod browse;
+mod keyed;
+mod model;
+mod provenance;
+mod report;
+#[cfg(feature = "script")]
+mod script;
+mod sections;
+mod split;
+mod style;
+mod survey;
+#[cfg(feature = "vanilla")]
+mod vanilla;
+mod yaml_error;
+
 use std::{
+    error::Error,
     fs,
+    io::{self, Cursor, Read, Write},
     path::{Path, PathBuf},
+    process::ExitCode,
+    sync::mpsc,
+    time::Duration,
 };
 
-use clap::Parser;
-use lvd_lib::LvdFile;
+use clap::{Parser, Subcommand};
+use lvd_lib::{
+    animation::JointAnimation,
+    autofit::{Margins, RegionMargins},
+    finite::NonFinitePolicy,
+    game::UnsupportedSections,
+    legality::Rule,
+    limits::CoordinateLimits,
+    model::ModelVersions,
+    objects::collision::attribute::MaterialType,
+    patch::Patch,
+    stage,
+    transform::UnitScale,
+    validation::{Severity, ValidationOptions},
+    verify::RoundTripReport,
+    LvdFile, SectionKind,
+};
+use notify_debouncer_mini::{new_debouncer, notify::RecursiveMode};
+use report::ErrorFormat;
+use style::YamlStyle;
+use tracing_subscriber::EnvFilter;
 
 /// Convert LVD files to and from YAML
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    /// The input LVD or YAML file path
-    input: String,
+    #[command(subcommand)]
+    command: Option<Command>,
 
-    /// The output LVD or YAML file path
+    /// The input LVD or YAML file path, or `-` to read from the standard input
+    #[arg(required = true)]
+    input: Option<String>,
+
+    /// The output LVD or YAML file path, or `-` to write to the standard output
     output: Option<String>,
+
+    /// The text format to write LVD files to: `yaml` or `json` [default: yaml]
+    #[arg(long, value_parser = parse_format, conflicts_with_all = ["provenance", "split"])]
+    format: Option<TextFormat>,
+
+    /// The root of a mod folder to write the converted LVD file into
+    #[arg(long, requires = "stage", conflicts_with = "output")]
+    mod_root: Option<String>,
+
+    /// The internal name of the stage the LVD file belongs to, such as `battlefield`
+    #[arg(long, requires = "mod_root")]
+    stage: Option<String>,
+
+    /// Write the YAML file in the binary format's raw form, with the version of every object,
+    /// instead of the flattened model
+    #[arg(long)]
+    raw: bool,
+
+    /// Write each object's version as a `version` field instead of a YAML tag,
+    /// which implies `--raw`
+    #[arg(long)]
+    version_keys: bool,
+
+    /// Write only the given comma-separated sections to YAML, such as `collisions,start_positions`
+    #[arg(long, value_delimiter = ',', value_parser = sections::parse_section)]
+    only: Vec<SectionKind>,
+
+    /// Omit the given comma-separated sections when writing YAML
+    #[arg(long, value_delimiter = ',', value_parser = sections::parse_section)]
+    exclude: Vec<SectionKind>,
+
+    /// Write each section of the YAML file as a mapping keyed by object name instead of a list,
+    /// so edits to different objects merge without conflicts
+    #[arg(long, conflicts_with_all = ["raw", "version_keys", "split", "provenance"])]
+    keyed: bool,
+
+    /// Write a comment above each object in the YAML file with its byte offset and version
+    /// in the LVD file
+    #[arg(long)]
+    provenance: bool,
+
+    /// Read an LVD file of a file format version newer than this tool knows as the latest known
+    /// version, keeping the bytes after its sections as unknown sections. Converting the YAML
+    /// file back writes a file of the latest known version, which the game will not read
+    #[arg(long)]
+    read_newer_version: bool,
+
+    /// The LVD file to take the sections missing from the input YAML file from
+    #[arg(long)]
+    base: Option<String>,
+
+    /// The LVD file format version to write, such as `11` for Smash 4, instead of the version
+    /// recorded in the input YAML file
+    #[arg(long)]
+    lvd_version: Option<u8>,
+
+    /// How to write NaN and infinite values from the input YAML file:
+    /// `reject`, `clamp-to-zero`, or `allow` [default: reject]
+    #[arg(long, value_parser = parse_non_finite_policy)]
+    non_finite: Option<NonFinitePolicy>,
+
+    /// Move each collision cliff onto the end of its edge and face it away from the edge
+    /// before writing the LVD file, removing cliffs whose edge does not exist
+    #[arg(long)]
+    fix_cliffs: bool,
+
+    /// Move each coordinate further from the origin than the given distance, 10000 if omitted,
+    /// onto it before writing the LVD file, since distant positions cause physics glitches
+    #[arg(long, value_name = "LIMIT", num_args = 0..=1, default_missing_value = "10000")]
+    clamp_coordinates: Option<f32>,
+
+    /// The number of units in the YAML file per in-game unit, such as `0.1` for a model
+    /// exported at a tenth of the game's scale
+    #[arg(long, value_parser = parse_scale)]
+    scale: Option<UnitScale>,
+
+    /// Write the YAML file as a directory holding one file per object and a manifest
+    /// listing them, instead of a single file
+    #[arg(long, conflicts_with = "provenance")]
+    split: bool,
+
+    /// The number of spaces each nested level of the YAML file is indented by, from 2 to 8
+    /// [default: 2]
+    #[arg(long, value_parser = parse_indent)]
+    indent: Option<usize>,
+
+    /// The longest line a vector or rectangle is written inline on in the YAML file
+    /// [default: 100]
+    #[arg(long)]
+    width: Option<usize>,
+
+    /// Write vectors and rectangles in the YAML file with one line per component
+    /// instead of inline as `{x: 1.0, y: 2.0}`
+    #[arg(long)]
+    block_style: bool,
+
+    /// How to write errors and warnings: `text`, or `json` for one JSON object per line
+    /// [default: text]
+    #[arg(long, global = true, value_parser = parse_error_format)]
+    error_format: Option<ErrorFormat>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Write a JSON Schema describing the YAML form of an LVD file
+    Schema {
+        /// Describe the raw form written with `--raw` instead of the flattened model
+        #[arg(long)]
+        raw: bool,
+
+        /// The LVD file format version to restrict the raw schema to
+        #[arg(long, requires = "raw")]
+        version: Option<u8>,
+
+        /// The output JSON file path, or standard output if omitted
+        output: Option<String>,
+    },
+
+    /// Apply a partial YAML patch to an LVD file
+    Patch {
+        /// The input LVD file path
+        input: String,
+
+        /// The YAML patch file path
+        patch: String,
+
+        /// The output LVD file path, or the input file path if omitted
+        output: Option<String>,
+
+        /// How to write NaN and infinite values from the patch:
+        /// `reject`, `clamp-to-zero`, or `allow` [default: reject]
+        #[arg(long, value_parser = parse_non_finite_policy)]
+        non_finite: Option<NonFinitePolicy>,
+    },
+
+    /// Report problems found in an LVD file, such as values unknown to this tool
+    Validate {
+        /// The input LVD file path
+        input: String,
+
+        /// The distance from the origin along each axis beyond which coordinates are reported,
+        /// or `inf` to skip the check
+        #[arg(long, default_value_t = CoordinateLimits::STABLE.x)]
+        coordinate_limit: f32,
+    },
+
+    /// Convert a YAML file to an LVD file every time the YAML file changes
+    Watch {
+        /// The input YAML file path
+        input: String,
+
+        /// The output LVD file path, or the input file path with the LVD extension if omitted
+        output: Option<String>,
+
+        /// How to write NaN and infinite values from the input YAML file:
+        /// `reject`, `clamp-to-zero`, or `allow` [default: reject]
+        #[arg(long, value_parser = parse_non_finite_policy)]
+        non_finite: Option<NonFinitePolicy>,
+    },
+
+    /// Copy every LVD file of a stage to another stage slot, renaming the names they contain
+    CloneStage {
+        /// The internal name of the stage to copy from, such as `battlefield`
+        #[arg(long)]
+        from: String,
+
+        /// The internal name of the stage to copy to
+        #[arg(long)]
+        to: String,
+
+        /// The root of the mod folder or extracted game files containing the stage
+        dir: String,
+    },
+
+    /// Summarize an LVD file's sections, bounds, legality, and content hash
+    Info {
+        /// The input LVD file path
+        input: String,
+
+        /// Write the summary as JSON, for use by other programs
+        #[arg(long)]
+        json: bool,
+
+        /// The distance within which mirrored positions are considered equal
+        #[arg(long, default_value_t = 0.01)]
+        tolerance: f32,
+    },
+
+    /// Count the collision edges of an LVD file using each material and attribute,
+    /// or list the edges using a material
+    Materials {
+        /// The input LVD file path
+        input: String,
+
+        /// The material to list the edges of, such as `damage1`
+        #[arg(long, value_parser = parse_material)]
+        material: Option<MaterialType>,
+    },
+
+    /// List the model and joint names referenced by the objects of an LVD file,
+    /// or the joint names missing from a list of bone names
+    Bindings {
+        /// The input LVD file path
+        input: String,
+
+        /// A text file listing the stage model's bone names, one per line
+        #[arg(long)]
+        bones: Option<String>,
+    },
+
+    /// Write the vertices of every dynamic collision of an LVD file on each frame
+    /// of an animation of the stage model's joints as JSON
+    Animate {
+        /// The input LVD file path
+        input: String,
+
+        /// The YAML or JSON file mapping each joint's name to its transform on every frame
+        animation: String,
+
+        /// The output JSON file path, or the standard output if omitted
+        output: Option<String>,
+    },
+
+    /// Check that an LVD file converts to YAML and back to the same bytes, listing the size
+    /// of each section and the differing fields of the first object which changed
+    Verify {
+        /// The input LVD file path
+        input: String,
+
+        /// Convert through the raw form instead of the flattened model
+        #[arg(long)]
+        raw: bool,
+    },
+
+    /// Check an LVD file against common competitive legality rules
+    CheckLegality {
+        /// The input LVD file path
+        input: String,
+
+        /// The comma-separated rules to check, or every rule if omitted
+        #[arg(long, value_delimiter = ',', value_parser = parse_rule)]
+        rules: Vec<Rule>,
+
+        /// The distance within which mirrored positions are considered equal
+        #[arg(long, default_value_t = 0.01)]
+        tolerance: f32,
+    },
+
+    /// Report the floors of an LVD file reaching the blast zone, optionally trimming them
+    WalkOffs {
+        /// The input LVD file path
+        input: String,
+
+        /// The distance inside the blast zone at which floors are considered walk-offs
+        /// and trimmed
+        #[arg(long, default_value_t = 0.0)]
+        margin: f32,
+
+        /// Cut off each walk-off at the margin, adding a ledge at each new end
+        #[arg(long)]
+        trim: bool,
+
+        /// The output LVD file path when trimming, or the input file path if omitted
+        #[arg(requires = "trim")]
+        output: Option<String>,
+    },
+
+    /// Replace the camera and death regions of an LVD file with regions fitted around its collisions
+    AutofitRegions {
+        /// The input LVD file path
+        input: String,
+
+        /// The margins of the camera region, given as one distance or as comma-separated
+        /// `left,right,top,bottom` distances
+        #[arg(long, value_parser = parse_margins)]
+        camera: Margins,
+
+        /// The margins of the death region, in the same form as `--camera`
+        #[arg(long, value_parser = parse_margins)]
+        death: Margins,
+
+        /// The margins of the shrunken camera region, or those of the camera region if omitted
+        #[arg(long, value_parser = parse_margins)]
+        shrinked_camera: Option<Margins>,
+
+        /// The margins of the shrunken death region, or those of the death region if omitted
+        #[arg(long, value_parser = parse_margins)]
+        shrinked_death: Option<Margins>,
+
+        /// The output LVD file path, or the input file path if omitted
+        output: Option<String>,
+    },
+
+    /// Scale an LVD file so its collisions span a given width, moving every object with them,
+    /// and fit new camera and death regions around the result
+    Rescale {
+        /// The input LVD file path
+        input: String,
+
+        /// The width the collisions should span
+        #[arg(long, value_parser = parse_distance)]
+        target_width: f32,
+
+        /// The margins of the camera region, given as one distance or as comma-separated
+        /// `left,right,top,bottom` distances [default: 90,90,100,50]
+        #[arg(long, value_parser = parse_margins)]
+        camera: Option<Margins>,
+
+        /// The margins of the death region, in the same form as `--camera`
+        /// [default: 160,160,150,120]
+        #[arg(long, value_parser = parse_margins)]
+        death: Option<Margins>,
+
+        /// The margins of the shrunken camera region [default: 70,70,80,40]
+        #[arg(long, value_parser = parse_margins)]
+        shrinked_camera: Option<Margins>,
+
+        /// The margins of the shrunken death region [default: 130,130,120,100]
+        #[arg(long, value_parser = parse_margins)]
+        shrinked_death: Option<Margins>,
+
+        /// The output LVD file path, or the input file path if omitted
+        output: Option<String>,
+    },
+
+    /// Replace the start and restart positions of an LVD file with points spread along its main floor
+    AutoSpawns {
+        /// The input LVD file path
+        input: String,
+
+        /// The number of points to place
+        #[arg(long, default_value_t = 4)]
+        count: u8,
+
+        /// The output LVD file path, or the input file path if omitted
+        output: Option<String>,
+    },
+
+    /// Add an item popup to an LVD file with paths hovering above every floor
+    FloorItemPopup {
+        /// The input LVD file path
+        input: String,
+
+        /// The height of the paths above the floors
+        #[arg(long, default_value_t = 10.0)]
+        height: f32,
+
+        /// The distance within which floors are merged and points are simplified away
+        #[arg(long, default_value_t = 1.0)]
+        tolerance: f32,
+
+        /// The output LVD file path, or the input file path if omitted
+        output: Option<String>,
+    },
+
+    /// Print a hex dump of an LVD file interleaved with the fields each range of bytes decodes to
+    Annotate {
+        /// The input LVD file path
+        input: String,
+    },
+
+    /// Tabulate the values of a field across every LVD file in a directory and its subdirectories
+    Survey {
+        /// The directory to search for LVD files
+        dir: String,
+
+        /// The field to tabulate, given as an object type and a dotted path, such as `AreaHint.unk1`
+        #[arg(long, value_parser = survey::parse_field)]
+        field: survey::FieldPath,
+
+        /// The number of objects to list for each value
+        #[arg(long, default_value_t = 5)]
+        max_sources: usize,
+    },
+
+    /// Write the name and position of each object in a section of an LVD file as CSV
+    ExportCsv {
+        /// The input LVD file path
+        input: String,
+
+        /// The section to export, such as `start_positions`
+        #[arg(long, value_parser = sections::parse_csv_section)]
+        section: SectionKind,
+
+        /// The number of units in the CSV file per in-game unit
+        #[arg(long, value_parser = parse_scale)]
+        scale: Option<UnitScale>,
+
+        /// The output CSV file path, or standard output if omitted
+        output: Option<String>,
+    },
+
+    /// Replace the objects in a section of an LVD file with the rows of a CSV file
+    ImportCsv {
+        /// The input LVD file path
+        input: String,
+
+        /// The CSV file path
+        csv: String,
+
+        /// The section to import, such as `start_positions`
+        #[arg(long, value_parser = sections::parse_csv_section)]
+        section: SectionKind,
+
+        /// The number of units in the CSV file per in-game unit
+        #[arg(long, value_parser = parse_scale)]
+        scale: Option<UnitScale>,
+
+        /// The output LVD file path, or the input file path if omitted
+        output: Option<String>,
+
+        /// How to write NaN and infinite values from the CSV file:
+        /// `reject`, `clamp-to-zero`, or `allow` [default: reject]
+        #[arg(long, value_parser = parse_non_finite_policy)]
+        non_finite: Option<NonFinitePolicy>,
+    },
+
+    /// Report whether an LVD file is unmodified, lightly modified, or structurally divergent
+    /// from the vanilla file of the same name
+    #[cfg(feature = "vanilla")]
+    CompareVanilla {
+        /// The input LVD file path
+        input: String,
+
+        /// The vanilla database file path, or the database bundled with this tool if omitted
+        #[arg(long)]
+        database: Option<String>,
+    },
+
+    /// Write a database of the vanilla LVD files in a directory of extracted game files
+    /// for use with `compare-vanilla`
+    #[cfg(feature = "vanilla")]
+    VanillaDb {
+        /// The root of the extracted game files
+        dir: String,
+
+        /// The output database file path, or standard output if omitted
+        output: Option<String>,
+    },
+
+    /// Browse and edit an LVD file in an interactive terminal interface
+    Browse {
+        /// The input LVD file path
+        input: String,
+    },
+
+    /// Draw a preview image of an LVD file as PNG
+    #[cfg(feature = "render")]
+    Render {
+        /// The input LVD file path
+        input: String,
+
+        /// The comma-separated sections to draw, such as `collisions,start_positions`
+        /// [default: every section which can be drawn]
+        #[arg(long, value_delimiter = ',', value_parser = sections::parse_render_section)]
+        layers: Vec<SectionKind>,
+
+        /// The width of the image in pixels
+        #[arg(long, default_value_t = 1280)]
+        width: u32,
+
+        /// The height of the image in pixels
+        #[arg(long, default_value_t = 720)]
+        height: u32,
+
+        /// The output PNG file path, or the input file path with a `.png` extension if omitted
+        output: Option<String>,
+    },
+
+    /// Edit an LVD file with a Rhai script, which sees the file as the `lvd` variable
+    #[cfg(feature = "script")]
+    Script {
+        /// The input LVD file path
+        input: String,
+
+        /// The Rhai script file path
+        script: String,
+
+        /// The output LVD file path, or the input file path if omitted
+        output: Option<String>,
+    },
+}
+
+/// The form of an LVD file written to YAML.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum YamlForm {
+    /// The flattened model, omitting the version of each object.
+    Model,
+
+    /// The binary format's raw form, with each object's version as a YAML tag.
+    Raw,
+
+    /// The binary format's raw form, with each object's version as a `version` field.
+    VersionKeys,
+}
+
+/// The text format an LVD file is written to.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+enum TextFormat {
+    /// YAML, which may contain comments and tags.
+    #[default]
+    Yaml,
+
+    /// JSON, for tools such as `jq`.
+    Json,
+}
+
+impl TextFormat {
+    /// Returns the extension of files written in the format.
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Yaml => "yaml",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// The path given in place of a file path to read from the standard input
+/// or write to the standard output.
+const STDIO_PATH: &str = "-";
+
+/// Reads the file at the given path, or the standard input if the path is `-`.
+fn read_input(path: &Path) -> io::Result<Vec<u8>> {
+    if path == Path::new(STDIO_PATH) {
+        let mut bytes = Vec::new();
+
+        io::stdin().read_to_end(&mut bytes)?;
+
+        Ok(bytes)
+    } else {
+        fs::read(path)
+    }
+}
+
+/// Writes the file at the given path, or the standard output if the path is `-`,
+/// reporting an error if it cannot be written.
+fn write_output<P: AsRef<Path>>(path: P, contents: &[u8]) {
+    let path = path.as_ref();
+    let result = if path == Path::new(STDIO_PATH) {
+        io::stdout().write_all(contents)
+    } else {
+        fs::write(path, contents)
+    };
+
+    if let Err(error) = result {
+        report::error!("{}: {error}", path.display());
+    }
 }
 
-fn read_data_write_yaml<P: AsRef<Path> + ToString>(input_path: P, output_path: Option<String>) {
+/// How an LVD file is written as text.
+#[derive(Clone, Copy)]
+struct TextOutput {
+    /// The form of the LVD file.
+    form: YamlForm,
+
+    /// The format of the text.
+    format: TextFormat,
+
+    /// Whether to write a comment above each object with its byte offset and version.
+    provenance: bool,
+
+    /// Whether to write a directory holding one file per object.
+    split: bool,
+
+    /// Whether to write each section of the model form as a mapping keyed by object name.
+    keyed: bool,
+
+    /// The layout of the YAML text.
+    style: YamlStyle,
+}
+
+fn read_data_write_yaml<P: AsRef<Path> + ToString>(
+    input_path: P,
+    bytes: Vec<u8>,
+    output_path: Option<String>,
+    text: TextOutput,
+    scale: Option<UnitScale>,
+    read_newer_version: bool,
+    keep: impl Fn(SectionKind) -> bool,
+) {
+    let TextOutput {
+        form,
+        format,
+        provenance,
+        split,
+        keyed,
+        style,
+    } = text;
+
+    if LvdFile::is_little_endian_lvd(&bytes) {
+        return report::error!("little-endian LVD files are not supported");
+    }
+
+    let latest = ModelVersions::LATEST_LVD_VERSION;
+
+    if let Some(version) = LvdFile::version_of(&bytes).filter(|&version| version > latest) {
+        if !read_newer_version {
+            return report::error!(
+                "{}: version {version} is newer than the latest known version {latest}; \
+                 pass --read-newer-version to read it as version {latest}",
+                input_path.to_string()
+            );
+        }
+
+        report::warning!(
+            "{}: version {version} is newer than the latest known version {latest}; \
+             reading it as version {latest} and keeping the remaining bytes as unknown sections, \
+             so converting it back writes a version {latest} file the game will not read",
+            input_path.to_string()
+        );
+    }
+
+    match LvdFile::from_bytes_as_latest(&bytes) {
+        Ok(mut lvd) => {
+            if let Some(scale) = scale {
+                lvd.scale_from_game(scale);
+            }
+
+            let mut value = match form {
+                YamlForm::Model => match model::to_value(lvd.clone()) {
+                    Ok(value) => value,
+                    Err(error) => {
+                        return report::error!(
+                            "{}: {error}; pass --raw or --version-keys to keep every version",
+                            input_path.to_string()
+                        )
+                    }
+                },
+                YamlForm::Raw => serde_yaml::to_value(&lvd).unwrap(),
+                YamlForm::VersionKeys => {
+                    serde_yaml::to_value(lvd.to_version_keyed_value()).unwrap()
+                }
+            };
+
+            sections::retain_sections(&mut value, keep);
+
+            if keyed {
+                keyed::to_keyed(&mut value);
+            }
+
+            if split {
+                let output_path = output_path
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| input_path.as_ref().with_extension(""));
+
+                if output_path == Path::new(STDIO_PATH) {
+                    return report::error!("--split cannot write to the standard output");
+                }
+
+                if let Err(error) = split::write_split(&output_path, value, &lvd, &style) {
+                    report::error!("{}: {error}", output_path.display());
+                }
+
+                return;
+            }
+
+            let output_path = output_path.map(PathBuf::from).unwrap_or_else(|| {
+                if input_path.to_string() == STDIO_PATH {
+                    PathBuf::from(STDIO_PATH)
+                } else {
+                    PathBuf::from(input_path.to_string() + "." + format.extension())
+                }
+            });
+
+            if format == TextFormat::Json {
+                let json = serde_json::to_string_pretty(&value).unwrap() + "\n";
+
+                return write_output(&output_path, json.as_bytes());
+            }
+
+            let mut yaml = style::to_string(&value, &style);
+
+            if provenance {
+                yaml = provenance::add_comments(
+                    &yaml,
+                    &input_path.to_string(),
+                    &bytes,
+                    &lvd,
+                    form != YamlForm::Model,
+                    style.indent,
+                );
+            }
+
+            write_output(&output_path, yaml.as_bytes());
+        }
+        Err(error) => report::error!("{error:?}"),
+    }
+}
+
+/// Deserializes an LVD file from YAML, taking the sections missing from the YAML file
+/// from the base LVD file if one is given, scaled to the units of the YAML file.
+///
+/// The base file is converted to the same form as the YAML file before the sections are merged.
+fn lvd_from_partial_yaml(
+    yaml: &str,
+    base_path: Option<&str>,
+    scale: Option<UnitScale>,
+) -> Result<LvdFile, Box<dyn Error>> {
+    let Some(base_path) = base_path else {
+        return Ok(yaml_error::lvd_from_yaml(yaml)?);
+    };
+    let mut base = LvdFile::from_file(base_path)?;
+
+    if let Some(scale) = scale {
+        base.scale_from_game(scale);
+    }
+
+    let partial = yaml_error::parse_yaml(yaml)?;
+
+    if model::is_model(&partial) {
+        let mut base = model::to_value(base)?;
+
+        model::merge(&mut base, partial)?;
+
+        return Ok(yaml_error::lvd_from_yaml_value(base)?);
+    }
+
+    let mut base = serde_yaml::to_value(base)?;
+
+    sections::merge_sections(&mut base, partial)?;
+
+    Ok(yaml_error::lvd_from_yaml_value(base)?)
+}
+
+/// How a YAML file is written as an LVD file.
+#[derive(Clone, Copy)]
+struct DataOutput {
+    /// The file format version to write instead of the version recorded in the YAML file.
+    lvd_version: Option<u8>,
+
+    /// How to write NaN and infinite values.
+    non_finite: NonFinitePolicy,
+
+    /// Whether to fix the position and facing direction of every collision cliff.
+    fix_cliffs: bool,
+
+    /// The distance from the origin along each axis to clamp coordinates to, if any.
+    clamp_coordinates: Option<f32>,
+
+    /// Whether to create the directories containing the output file, as for a mod folder.
+    create_directories: bool,
+}
+
+/// Converts a YAML file, or a JSON file since JSON is read as YAML, to an LVD file.
+///
+/// The contents of the YAML file are given as `bytes`, or read from the split directory at the
+/// input path if omitted.
+fn read_yaml_write_data<P: AsRef<Path>>(
+    input_path: P,
+    bytes: Option<Vec<u8>>,
+    output_path: Option<String>,
+    base_path: Option<String>,
+    data: DataOutput,
+    scale: Option<UnitScale>,
+) {
+    let DataOutput {
+        lvd_version,
+        non_finite,
+        fix_cliffs,
+        clamp_coordinates,
+        create_directories,
+    } = data;
+    let yaml = match bytes {
+        Some(bytes) => match String::from_utf8(bytes) {
+            Ok(yaml) => yaml,
+            Err(error) => return report::error!("{error}"),
+        },
+        None => match split::read_split(input_path.as_ref()) {
+            Ok(yaml) => yaml,
+            Err(error) => return report::error!("{error}"),
+        },
+    };
+
+    match lvd_from_partial_yaml(&yaml, base_path.as_deref(), scale) {
+        Ok(mut lvd) => {
+            if let Some(scale) = scale {
+                lvd.scale_to_game(scale);
+            }
+
+            if let Some(version) = lvd_version {
+                lvd = match lvd.to_version(version, UnsupportedSections::Error) {
+                    Ok(lvd) => lvd,
+                    Err(error) => return report::error!("{error}"),
+                };
+            }
+
+            if fix_cliffs {
+                if let Some(collisions) = lvd.data.collisions_mut() {
+                    for collision in collisions.iter_mut() {
+                        collision.fix_cliffs();
+                    }
+                }
+            }
+
+            if let Some(limit) = clamp_coordinates {
+                lvd.clamp_coordinates(CoordinateLimits::uniform(limit));
+            }
+
+            let output_path = output_path.map(PathBuf::from).unwrap_or_else(|| {
+                if input_path.as_ref() == Path::new(STDIO_PATH) {
+                    PathBuf::from(STDIO_PATH)
+                } else {
+                    input_path.as_ref().with_extension("lvd")
+                }
+            });
+            let mut cursor = Cursor::new(Vec::new());
+
+            if let Err(error) = lvd.write_checked(&mut cursor, non_finite) {
+                return report::error!("{error}");
+            }
+
+            if let Some(directory) = output_path.parent().filter(|_| create_directories) {
+                if let Err(error) = fs::create_dir_all(directory) {
+                    return report::error!("{}: {error}", directory.display());
+                }
+            }
+
+            write_output(&output_path, cursor.get_ref());
+        }
+        Err(error) => report::error!("{error}"),
+    }
+}
+
+fn write_schema(raw: bool, version: Option<u8>, output_path: Option<String>) {
+    let schema = match raw {
+        true => match lvd_lib::schema::lvd_file_schema(version) {
+            Some(schema) => schema,
+            None => {
+                return report::error!(
+                    "unknown LVD file format version {}, expected a version from 1 to {}",
+                    version.unwrap_or_default(),
+                    ModelVersions::LATEST_LVD_VERSION
+                )
+            }
+        },
+        false => lvd_lib::schema::lvd_model_schema(),
+    };
+    let json = serde_json::to_string_pretty(&schema).unwrap();
+
+    match output_path {
+        Some(output_path) => write_output(output_path, json.as_bytes()),
+        None => println!("{json}"),
+    }
+}
+
+fn apply_patch(
+    input_path: String,
+    patch_path: String,
+    output_path: Option<String>,
+    non_finite: NonFinitePolicy,
+) {
+    let yaml = match fs::read_to_string(&patch_path) {
+        Ok(yaml) => yaml,
+        Err(error) => return report::error!("{patch_path}: {error}"),
+    };
+    let patch = match serde_yaml::from_str::<Patch>(&yaml) {
+        Ok(patch) => patch,
+        Err(error) => return report::error!("{error:?}"),
+    };
+
     match LvdFile::from_file(&input_path) {
-        Ok(lvd) => {
+        Ok(mut lvd) => match lvd.apply_patch(&patch) {
+            Ok(()) => {
+                if let Err(error) =
+                    lvd.write_to_file_checked(output_path.unwrap_or(input_path), non_finite)
+                {
+                    report::error!("{error}");
+                }
+            }
+            Err(error) => report::error!("{error}"),
+        },
+        Err(error) => report::error!("{error:?}"),
+    }
+}
+
+#[cfg(feature = "render")]
+fn render(
+    input_path: String,
+    layers: Vec<SectionKind>,
+    width: u32,
+    height: u32,
+    output_path: Option<String>,
+) {
+    let lvd = match LvdFile::from_file(&input_path) {
+        Ok(lvd) => lvd,
+        Err(error) => return report::error!("{error:?}"),
+    };
+    let mut options = lvd_lib::render::RenderOptions {
+        width,
+        height,
+        ..Default::default()
+    };
+
+    if !layers.is_empty() {
+        options.layers = layers;
+    }
+
+    match lvd.render_png(&options) {
+        Ok(png) => {
             let output_path = output_path
                 .map(PathBuf::from)
-                .unwrap_or_else(|| PathBuf::from(input_path.to_string() + ".yaml"));
-            let yaml = serde_yaml::to_string(&lvd).unwrap();
+                .unwrap_or_else(|| Path::new(&input_path).with_extension("png"));
+
+            write_output(output_path, &png);
+        }
+        Err(error) => report::error!("{error}"),
+    }
+}
+
+#[cfg(feature = "script")]
+fn run_script(input_path: String, script_path: String, output_path: Option<String>) {
+    let script = match fs::read_to_string(&script_path) {
+        Ok(script) => script,
+        Err(error) => return report::error!("{script_path}: {error}"),
+    };
+
+    match LvdFile::from_file(&input_path) {
+        Ok(lvd) => match script::run_script(&lvd, &script) {
+            Ok(lvd) => {
+                print_issues(&lvd, &ValidationOptions::default());
+
+                if let Err(error) = lvd.write_to_file_checked(
+                    output_path.unwrap_or(input_path),
+                    NonFinitePolicy::Reject,
+                ) {
+                    report::error!("{error}");
+                }
+            }
+            Err(error) => report::error!("{script_path}: {error}"),
+        },
+        Err(error) => report::error!("{error:?}"),
+    }
+}
+
+fn export_csv(
+    input_path: String,
+    section: SectionKind,
+    scale: Option<UnitScale>,
+    output_path: Option<String>,
+) {
+    let csv = match LvdFile::from_file(&input_path) {
+        Ok(mut lvd) => {
+            if let Some(scale) = scale {
+                lvd.scale_from_game(scale);
+            }
+
+            lvd.data.section_to_csv(section)
+        }
+        Err(error) => return report::error!("{error:?}"),
+    };
+
+    match (csv, output_path) {
+        (Ok(csv), Some(output_path)) => write_output(output_path, csv.as_bytes()),
+        (Ok(csv), None) => print!("{csv}"),
+        (Err(error), _) => report::error!("{error}"),
+    }
+}
+
+fn import_csv(
+    input_path: String,
+    csv_path: String,
+    section: SectionKind,
+    scale: Option<UnitScale>,
+    output_path: Option<String>,
+    non_finite: NonFinitePolicy,
+) {
+    let csv = match fs::read_to_string(&csv_path) {
+        Ok(csv) => csv,
+        Err(error) => return report::error!("{csv_path}: {error}"),
+    };
+
+    match LvdFile::from_file(&input_path) {
+        Ok(mut lvd) => match lvd.data.section_from_csv(section, &csv) {
+            Ok(()) => {
+                if let Some(scale) = scale {
+                    let transform = scale.to_game();
+
+                    for index in 0..lvd.data.section_len(section).unwrap_or_default() {
+                        if let Some(object) = lvd.data.object_mut(section, index) {
+                            object.transform(&transform);
+                        }
+                    }
+                }
+
+                if let Err(error) =
+                    lvd.write_to_file_checked(output_path.unwrap_or(input_path), non_finite)
+                {
+                    report::error!("{error}");
+                }
+            }
+            Err(error) => report::error!("{csv_path}: {error}"),
+        },
+        Err(error) => report::error!("{error:?}"),
+    }
+}
+
+fn print_issues(lvd: &LvdFile, options: &ValidationOptions) {
+    for issue in lvd.validate_with(options) {
+        match issue.severity() {
+            Severity::Warning => report::warning!("{issue}"),
+            Severity::Error => report::error!("{issue}"),
+        }
+    }
+}
+
+fn validate(input_path: String, coordinate_limit: f32) {
+    let options = ValidationOptions {
+        coordinate_limits: Some(CoordinateLimits::uniform(coordinate_limit)),
+    };
+
+    match LvdFile::from_file(&input_path) {
+        Ok(lvd) => print_issues(&lvd, &options),
+        Err(error) => report::error!("{error:?}"),
+    }
+}
+
+fn annotate(input_path: String) {
+    let bytes = match fs::read(&input_path) {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            report::error!("{error}");
+            return;
+        }
+    };
+    let lvd = match LvdFile::read(&mut io::Cursor::new(&bytes)) {
+        Ok(lvd) => lvd,
+        Err(error) => {
+            report::error!("{error:?}");
+            return;
+        }
+    };
+    let annotations = lvd.annotations();
+
+    for (index, annotation) in annotations.iter().enumerate() {
+        let indent = "  ".repeat(annotation.depth);
+        let is_group = annotations
+            .get(index + 1)
+            .is_some_and(|next| next.depth > annotation.depth);
+
+        if is_group {
+            println!(
+                "{:08x}  {indent}{} ({} bytes)",
+                annotation.offset, annotation.label, annotation.len
+            );
+            continue;
+        }
+
+        println!("{:08x}  {indent}{}", annotation.offset, annotation.label);
+
+        let start = (annotation.offset as usize).min(bytes.len());
+        let end = ((annotation.offset + annotation.len) as usize).min(bytes.len());
+
+        for row in bytes[start..end].chunks(16) {
+            let hex = row
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            println!("          {indent}  {hex}");
+        }
+    }
+}
+
+/// Parses a legality rule name given on the command line, such as `no-hazards`.
+fn parse_rule(name: &str) -> Result<Rule, String> {
+    Rule::from_name(name).ok_or_else(|| {
+        let names = Rule::ALL
+            .iter()
+            .map(|rule| rule.name())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("unknown rule `{name}`, expected one of: {names}")
+    })
+}
+
+/// Parses a material given on the command line by its name, alias, or numeric value,
+/// such as `damage1`.
+fn parse_material(name: &str) -> Result<MaterialType, String> {
+    MaterialType::from_name(name)
+        .or_else(|| name.parse::<u32>().ok().map(MaterialType::from))
+        .ok_or_else(|| format!("unknown material `{name}`"))
+}
+
+/// Parses a policy for NaN and infinite values given on the command line, such as `allow`.
+fn parse_non_finite_policy(name: &str) -> Result<NonFinitePolicy, String> {
+    NonFinitePolicy::from_name(name).ok_or_else(|| {
+        let names = NonFinitePolicy::ALL
+            .iter()
+            .map(|policy| policy.name())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("unknown policy `{name}`, expected one of: {names}")
+    })
+}
+
+/// Parses a text format given on the command line, either `yaml` or `json`.
+fn parse_format(name: &str) -> Result<TextFormat, String> {
+    match name {
+        "yaml" => Ok(TextFormat::Yaml),
+        "json" => Ok(TextFormat::Json),
+        _ => Err(format!(
+            "unknown format `{name}`, expected one of: yaml, json"
+        )),
+    }
+}
+
+/// Parses an error format given on the command line, either `text` or `json`.
+fn parse_error_format(name: &str) -> Result<ErrorFormat, String> {
+    match name {
+        "text" => Ok(ErrorFormat::Text),
+        "json" => Ok(ErrorFormat::Json),
+        _ => Err(format!(
+            "unknown format `{name}`, expected one of: text, json"
+        )),
+    }
+}
+
+/// Parses an indentation given on the command line, which must be from 2 to 8 spaces.
+fn parse_indent(indent: &str) -> Result<usize, String> {
+    match indent.trim().parse::<usize>() {
+        Ok(indent) if (2..=8).contains(&indent) => Ok(indent),
+        _ => Err(format!("`{indent}` is not a number from 2 to 8")),
+    }
+}
+
+/// Parses a unit scale given on the command line, which must be a positive number.
+fn parse_scale(scale: &str) -> Result<UnitScale, String> {
+    match scale.trim().parse::<f32>() {
+        Ok(scale) if scale.is_finite() && scale > 0.0 => Ok(UnitScale(scale)),
+        _ => Err(format!("`{scale}` is not a positive number")),
+    }
+}
+
+/// Parses a positive distance given on the command line.
+fn parse_distance(distance: &str) -> Result<f32, String> {
+    match distance.trim().parse::<f32>() {
+        Ok(value) if value.is_finite() && value > 0.0 => Ok(value),
+        _ => Err(format!("`{distance}` is not a positive number")),
+    }
+}
+
+/// Parses region margins given on the command line, either as a single distance such as `50`,
+/// or as `left,right,top,bottom` distances such as `90,90,100,50`.
+fn parse_margins(margins: &str) -> Result<Margins, String> {
+    let distances = margins
+        .split(',')
+        .map(|distance| {
+            distance
+                .trim()
+                .parse::<f32>()
+                .map_err(|_| format!("`{distance}` is not a number"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match distances[..] {
+        [margin] => Ok(Margins::uniform(margin)),
+        [left, right, top, bottom] => Ok(Margins::new(left, right, top, bottom)),
+        _ => Err("expected one distance or four comma-separated distances".to_string()),
+    }
+}
+
+fn autofit_regions(input_path: String, margins: RegionMargins, output_path: Option<String>) {
+    let mut lvd = match LvdFile::from_file(&input_path) {
+        Ok(lvd) => lvd,
+        Err(error) => return report::error!("{error:?}"),
+    };
+
+    if lvd.autofit_regions(&margins).is_none() {
+        return report::error!("{input_path} has no collisions to fit regions around");
+    }
+
+    if let Err(error) =
+        lvd.write_to_file_checked(output_path.unwrap_or(input_path), NonFinitePolicy::Reject)
+    {
+        report::error!("{error}");
+    }
+}
+
+fn rescale(
+    input_path: String,
+    target_width: f32,
+    margins: RegionMargins,
+    output_path: Option<String>,
+) {
+    let mut lvd = match LvdFile::from_file(&input_path) {
+        Ok(lvd) => lvd,
+        Err(error) => return report::error!("{error:?}"),
+    };
+
+    let Some(factor) = lvd.rescale(target_width, &margins) else {
+        return report::error!("{input_path} has no collisions with a width to scale");
+    };
+
+    println!("scaled by {factor}");
+
+    if let Some(bounds) = lvd.collision_bounds() {
+        println!("collisions: {bounds}");
+    }
+
+    for (name, regions) in [
+        ("camera region", lvd.data.camera_regions()),
+        ("death region", lvd.data.death_regions()),
+    ] {
+        if let Some(region) = regions.and_then(|regions| regions.iter().next()) {
+            println!("{name}: {}", region.rect());
+        }
+    }
+
+    if let Err(error) =
+        lvd.write_to_file_checked(output_path.unwrap_or(input_path), NonFinitePolicy::Reject)
+    {
+        report::error!("{error}");
+    }
+}
+
+fn auto_spawns(input_path: String, count: u8, output_path: Option<String>) {
+    let mut lvd = match LvdFile::from_file(&input_path) {
+        Ok(lvd) => lvd,
+        Err(error) => return report::error!("{error:?}"),
+    };
+    let Some(positions) = lvd.auto_spawns(count) else {
+        return report::error!(
+            "{input_path} has no solid collision with a floor to place spawns on"
+        );
+    };
+
+    for pos in positions {
+        println!("({}, {})", pos.x(), pos.y());
+    }
+
+    if let Err(error) =
+        lvd.write_to_file_checked(output_path.unwrap_or(input_path), NonFinitePolicy::Reject)
+    {
+        report::error!("{error}");
+    }
+}
+
+fn floor_item_popup(input_path: String, height: f32, tolerance: f32, output_path: Option<String>) {
+    let mut lvd = match LvdFile::from_file(&input_path) {
+        Ok(lvd) => lvd,
+        Err(error) => return report::error!("{error:?}"),
+    };
+    let item_popup = lvd.floor_item_popup(height, tolerance);
+    let Some(item_popups) = lvd.data.item_popups_mut() else {
+        return report::error!("{input_path} does not support item popups");
+    };
+
+    item_popups.push(item_popup);
+
+    if let Err(error) =
+        lvd.write_to_file_checked(output_path.unwrap_or(input_path), NonFinitePolicy::Reject)
+    {
+        report::error!("{error}");
+    }
+}
+
+fn info(input_path: String, json: bool, tolerance: f32) {
+    let summary = match LvdFile::from_file(&input_path) {
+        Ok(lvd) => lvd.summary(tolerance),
+        Err(error) => return report::error!("{error:?}"),
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&summary).unwrap());
+    } else {
+        print!("{summary}");
+    }
+}
+
+fn materials(input_path: String, material: Option<MaterialType>) {
+    let lvd = match LvdFile::from_file(&input_path) {
+        Ok(lvd) => lvd,
+        Err(error) => return report::error!("{error:?}"),
+    };
+
+    match material {
+        Some(material) => {
+            for edge in lvd.edges_with_material(material) {
+                println!("{edge}");
+            }
+        }
+        None => print!("{}", lvd.material_stats()),
+    }
+}
+
+fn bindings(input_path: String, bones_path: Option<String>) {
+    let lvd = match LvdFile::from_file(&input_path) {
+        Ok(lvd) => lvd,
+        Err(error) => return report::error!("{error:?}"),
+    };
+
+    let bindings = match bones_path {
+        Some(bones_path) => {
+            let bones = match fs::read_to_string(&bones_path) {
+                Ok(bones) => bones,
+                Err(error) => return report::error!("{error}"),
+            };
+            let bone_names = bones
+                .lines()
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .collect::<Vec<_>>();
 
-            fs::write(output_path, yaml).expect("failed to write YAML file");
+            lvd.unresolved_bindings(&bone_names)
         }
-        Err(error) => eprintln!("{error:?}"),
+        None => lvd.bindings(),
+    };
+
+    for binding in bindings {
+        println!("{binding}");
     }
 }
 
-fn read_yaml_write_data<P: AsRef<Path>>(input_path: P, output_path: Option<String>) {
-    let yaml = fs::read_to_string(&input_path).unwrap();
+fn animate(input_path: String, animation_path: String, output_path: Option<String>) {
+    let lvd = match LvdFile::from_file(&input_path) {
+        Ok(lvd) => lvd,
+        Err(error) => return report::error!("{error:?}"),
+    };
+    let animation = match fs::read_to_string(&animation_path) {
+        Ok(animation) => animation,
+        Err(error) => return report::error!("{error}"),
+    };
+    let animation = match serde_yaml::from_str::<JointAnimation>(&animation) {
+        Ok(animation) => animation,
+        Err(error) => return report::error!("{error}"),
+    };
+    let json = serde_json::to_string_pretty(&lvd.animate_collisions(&animation)).unwrap();
+
+    match output_path {
+        Some(output_path) => write_output(output_path, json.as_bytes()),
+        None => println!("{json}"),
+    }
+}
 
-    match serde_yaml::from_str::<LvdFile>(&yaml) {
+fn verify(input_path: String, raw: bool) {
+    let bytes = match fs::read(&input_path) {
+        Ok(bytes) => bytes,
+        Err(error) => return report::error!("{input_path}: {error}"),
+    };
+    let lvd = match LvdFile::from_bytes(&bytes) {
+        Ok(lvd) => lvd,
+        Err(error) => return report::error!("{error:?}"),
+    };
+    let value = match raw {
+        true => serde_yaml::to_value(&lvd).unwrap(),
+        false => match model::to_value(lvd) {
+            Ok(value) => value,
+            Err(error) => {
+                return report::error!("{input_path}: {error}; pass --raw to verify the raw form")
+            }
+        },
+    };
+    let yaml = style::to_string(&value, &YamlStyle::default());
+    let written = match yaml_error::lvd_from_yaml(&yaml) {
+        Ok(lvd) => match lvd.to_bytes() {
+            Ok(written) => written,
+            Err(error) => return report::error!("{error}"),
+        },
+        Err(error) => return report::error!("{error}"),
+    };
+
+    match RoundTripReport::compare(&bytes, &written) {
+        Ok(round_trip) => {
+            print!("{round_trip}");
+
+            if let Some(divergence) = round_trip.divergence {
+                report::error!("{divergence}");
+            }
+        }
+        Err(error) => report::error!("{error}"),
+    }
+}
+
+fn check_legality(input_path: String, rules: Vec<Rule>, tolerance: f32) {
+    let rules = if rules.is_empty() {
+        Rule::ALL.to_vec()
+    } else {
+        rules
+    };
+
+    match LvdFile::from_file(&input_path) {
+        Ok(lvd) => print!("{}", lvd.check_legality(&rules, tolerance)),
+        Err(error) => report::error!("{error:?}"),
+    }
+}
+
+fn walk_offs(input_path: String, margin: f32, trim: bool, output_path: Option<String>) {
+    let mut lvd = match LvdFile::from_file(&input_path) {
+        Ok(lvd) => lvd,
+        Err(error) => return report::error!("{error:?}"),
+    };
+
+    for walk_off in lvd.walk_offs(margin) {
+        println!("{walk_off}");
+    }
+
+    if trim {
+        println!("trimmed {} collisions", lvd.trim_walk_offs(margin));
+
+        if let Err(error) =
+            lvd.write_to_file_checked(output_path.unwrap_or(input_path), NonFinitePolicy::Reject)
+        {
+            report::error!("{error}");
+        }
+    }
+}
+
+fn rebuild(
+    input_path: &Path,
+    output_path: &Path,
+    non_finite: NonFinitePolicy,
+    last_yaml: &mut Option<String>,
+) {
+    let yaml = match fs::read_to_string(input_path) {
+        Ok(yaml) => yaml,
+        Err(error) => return report::error!("{error}"),
+    };
+
+    // Reading the file also produces events, so unchanged contents are skipped.
+    if last_yaml.as_ref() == Some(&yaml) {
+        return;
+    }
+
+    match yaml_error::lvd_from_yaml(&yaml) {
         Ok(lvd) => {
-            let output_path = output_path
-                .map(PathBuf::from)
-                .unwrap_or_else(|| input_path.as_ref().with_extension("lvd"));
+            print_issues(&lvd, &ValidationOptions::default());
 
-            lvd.write_to_file(output_path)
-                .expect("failed to write LVD file");
+            match lvd.write_to_file_checked(output_path, non_finite) {
+                Ok(()) => println!("wrote {}", output_path.display()),
+                Err(error) => report::error!("{error}"),
+            }
         }
-        Err(error) => eprintln!("{error:?}"),
+        Err(error) => report::error!("{error}"),
     }
+
+    *last_yaml = Some(yaml);
 }
 
-fn main() {
-    let args = Args::parse();
+fn watch(input_path: String, output_path: Option<String>, non_finite: NonFinitePolicy) {
+    let input_path = match fs::canonicalize(&input_path) {
+        Ok(path) => path,
+        Err(error) => return report::error!("{input_path}: {error}"),
+    };
+    let output_path = output_path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| input_path.with_extension("lvd"));
+    let (sender, receiver) = mpsc::channel();
+    let mut debouncer = match new_debouncer(Duration::from_millis(200), sender) {
+        Ok(debouncer) => debouncer,
+        Err(error) => return report::error!("failed to create file watcher: {error}"),
+    };
+    let mut last_yaml = None;
+
+    // Editors often save by replacing the file, so the directory is watched instead.
+    // A canonical path to a file always has a parent.
+    let directory = input_path.parent().unwrap();
 
-    match Path::new(&args.input)
-        .extension()
-        .expect("input file extension should exist")
-        .to_str()
-        .unwrap()
+    if let Err(error) = debouncer
+        .watcher()
+        .watch(directory, RecursiveMode::NonRecursive)
     {
-        "yaml" | "yml" => read_yaml_write_data(args.input, args.output),
-        _ => read_data_write_yaml(args.input, args.output),
+        return report::error!("failed to watch {}: {error}", directory.display());
+    }
+
+    rebuild(&input_path, &output_path, non_finite, &mut last_yaml);
+
+    for result in receiver {
+        match result {
+            Ok(events) if events.iter().any(|event| event.path == input_path) => {
+                rebuild(&input_path, &output_path, non_finite, &mut last_yaml)
+            }
+            Ok(_) => {}
+            Err(error) => report::error!("{error}"),
+        }
     }
 }
+
+fn clone_stage(from: String, to: String, dir: String) {
+    let files = match stage::find_lvd_files(&dir, &from) {
+        Ok(files) if !files.is_empty() => files,
+        Ok(_) => return report::error!("no LVD files found for stage `{from}` in {dir}"),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            return report::error!("no LVD files found for stage `{from}` in {dir}")
+        }
+        Err(error) => return report::error!("{error}"),
+    };
+    let mut lvds = Vec::with_capacity(files.len());
+
+    // Every file is renamed and checked before any is written, so a failure leaves the target
+    // slot untouched.
+    for (index, path) in files {
+        let mut lvd = match LvdFile::from_file(&path) {
+            Ok(lvd) => lvd,
+            Err(error) => return report::error!("{}: {error:?}", path.display()),
+        };
+
+        if let Err(error) = lvd.rename_prefix(&from, &to) {
+            return report::error!("{}: {error}", path.display());
+        }
+
+        let mut cursor = Cursor::new(Vec::new());
+
+        if let Err(error) = lvd.write_checked(&mut cursor, NonFinitePolicy::Reject) {
+            return report::error!("{}: {error}", path.display());
+        }
+
+        lvds.push((index, cursor.into_inner()));
+    }
+
+    let stage_dir = Path::new(&dir).join(stage::lvd_directory(&to));
+
+    if let Err(error) = fs::create_dir_all(&stage_dir) {
+        return report::error!("{}: {error}", stage_dir.display());
+    }
+
+    for (index, bytes) in lvds {
+        let output_path = Path::new(&dir).join(stage::lvd_path(&to, index));
+
+        match fs::write(&output_path, bytes) {
+            Ok(()) => println!("wrote {}", output_path.display()),
+            Err(error) => report::error!("{}: {error}", output_path.display()),
+        }
+    }
+}
+
+/// Returns the path of the LVD file for a stage within a mod folder.
+///
+/// The file index is taken from the input file name when it follows the game's naming,
+/// such as `battlefield_01.yaml`, and is otherwise zero.
+fn mod_output_path(input_path: &Path, mod_root: &str, stage: &str) -> PathBuf {
+    let index = input_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stage::lvd_file_index(stage, stem))
+        .unwrap_or_default();
+
+    Path::new(mod_root).join(stage::lvd_path(stage, index))
+}
+
+fn convert(args: Args) {
+    let Args {
+        input,
+        output,
+        format,
+        mod_root,
+        stage,
+        raw,
+        version_keys,
+        only,
+        exclude,
+        keyed,
+        provenance,
+        read_newer_version,
+        base,
+        lvd_version,
+        non_finite,
+        fix_cliffs,
+        clamp_coordinates,
+        scale,
+        split,
+        indent,
+        width,
+        block_style,
+        ..
+    } = args;
+    let input = input.unwrap();
+    let input_path = Path::new(&input);
+
+    // A directory is read as a YAML file split with `--split`.
+    let bytes = match input_path.is_dir() {
+        true => None,
+        false => match read_input(input_path) {
+            Ok(bytes) => Some(bytes),
+            Err(error) => return report::error!("{input}: {error}"),
+        },
+    };
+    let is_yaml = match &bytes {
+        None => true,
+        Some(bytes) if input == STDIO_PATH => {
+            !LvdFile::is_lvd(bytes) && !LvdFile::is_little_endian_lvd(bytes)
+        }
+        Some(_) => matches!(
+            input_path
+                .extension()
+                .and_then(|extension| extension.to_str()),
+            Some("yaml" | "yml" | "json")
+        ),
+    };
+
+    match is_yaml {
+        true if !only.is_empty() || !exclude.is_empty() => {
+            report::error!("--only and --exclude only apply to LVD input files")
+        }
+        true if provenance => report::error!("--provenance only applies to LVD input files"),
+        true if split => report::error!("--split only applies to LVD input files"),
+        true if keyed => report::error!("--keyed only applies to LVD input files"),
+        true if raw => report::error!("--raw only applies to LVD input files"),
+        true if format.is_some() => report::error!("--format only applies to LVD input files"),
+        true if read_newer_version => {
+            report::error!("--read-newer-version only applies to LVD input files")
+        }
+        true if mod_root.is_some() && input == STDIO_PATH => {
+            report::error!("--mod-root cannot name the LVD file of the standard input")
+        }
+        true => {
+            let create_directories = mod_root.is_some();
+            let output = match (mod_root, stage) {
+                (Some(mod_root), Some(stage)) => Some(
+                    mod_output_path(Path::new(&input), &mod_root, &stage)
+                        .to_string_lossy()
+                        .into_owned(),
+                ),
+                _ => output,
+            };
+
+            read_yaml_write_data(
+                input,
+                bytes,
+                output,
+                base,
+                DataOutput {
+                    lvd_version,
+                    non_finite: non_finite.unwrap_or_default(),
+                    fix_cliffs,
+                    clamp_coordinates,
+                    create_directories,
+                },
+                scale,
+            )
+        }
+        false if mod_root.is_some() => {
+            report::error!("--mod-root only applies to YAML input files")
+        }
+        false if base.is_some() => report::error!("--base only applies to YAML input files"),
+        false if lvd_version.is_some() => {
+            report::error!("--lvd-version only applies to YAML input files")
+        }
+        false if non_finite.is_some() => {
+            report::error!("--non-finite only applies to YAML input files")
+        }
+        false if fix_cliffs => report::error!("--fix-cliffs only applies to YAML input files"),
+        false if clamp_coordinates.is_some() => {
+            report::error!("--clamp-coordinates only applies to YAML input files")
+        }
+        false if raw && !version_keys && format == Some(TextFormat::Json) => {
+            report::error!("--raw cannot be written as JSON, use --version-keys instead")
+        }
+        false => read_data_write_yaml(
+            input,
+            bytes.unwrap(),
+            output,
+            TextOutput {
+                form: match (raw, version_keys) {
+                    (_, true) => YamlForm::VersionKeys,
+                    (true, false) => YamlForm::Raw,
+                    (false, false) => YamlForm::Model,
+                },
+                format: format.unwrap_or_default(),
+                provenance,
+                split,
+                keyed,
+                style: YamlStyle {
+                    indent: indent.unwrap_or(YamlStyle::default().indent),
+                    width: width.unwrap_or(YamlStyle::default().width),
+                    flow: !block_style,
+                },
+            },
+            scale,
+            read_newer_version,
+            |kind| (only.is_empty() || only.contains(&kind)) && !exclude.contains(&kind),
+        ),
+    }
+}
+
+fn main() -> ExitCode {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_writer(io::stderr)
+        .init();
+
+    let args = Args::parse();
+
+    report::set_format(args.error_format.unwrap_or_default());
+
+    match args.command {
+        Some(Command::Schema {
+            raw,
+            version,
+            output,
+        }) => write_schema(raw, version, output),
+        Some(Command::Patch {
+            input,
+            patch,
+            output,
+            non_finite,
+        }) => apply_patch(input, patch, output, non_finite.unwrap_or_default()),
+        Some(Command::Validate {
+            input,
+            coordinate_limit,
+        }) => validate(input, coordinate_limit),
+        Some(Command::Watch {
+            input,
+            output,
+            non_finite,
+        }) => watch(input, output, non_finite.unwrap_or_default()),
+        Some(Command::CloneStage { from, to, dir }) => clone_stage(from, to, dir),
+        Some(Command::Info {
+            input,
+            json,
+            tolerance,
+        }) => info(input, json, tolerance),
+        Some(Command::Materials { input, material }) => materials(input, material),
+        Some(Command::Bindings { input, bones }) => bindings(input, bones),
+        Some(Command::Animate {
+            input,
+            animation,
+            output,
+        }) => animate(input, animation, output),
+        Some(Command::Verify { input, raw }) => verify(input, raw),
+        Some(Command::CheckLegality {
+            input,
+            rules,
+            tolerance,
+        }) => check_legality(input, rules, tolerance),
+        Some(Command::WalkOffs {
+            input,
+            margin,
+            trim,
+            output,
+        }) => walk_offs(input, margin, trim, output),
+        Some(Command::AutofitRegions {
+            input,
+            camera,
+            death,
+            shrinked_camera,
+            shrinked_death,
+            output,
+        }) => {
+            let margins = RegionMargins {
+                camera,
+                death,
+                shrinked_camera: shrinked_camera.unwrap_or(camera),
+                shrinked_death: shrinked_death.unwrap_or(death),
+            };
+
+            autofit_regions(input, margins, output)
+        }
+        Some(Command::Rescale {
+            input,
+            target_width,
+            camera,
+            death,
+            shrinked_camera,
+            shrinked_death,
+            output,
+        }) => {
+            let standard = RegionMargins::STANDARD;
+            let margins = RegionMargins {
+                camera: camera.unwrap_or(standard.camera),
+                death: death.unwrap_or(standard.death),
+                shrinked_camera: shrinked_camera.unwrap_or(standard.shrinked_camera),
+                shrinked_death: shrinked_death.unwrap_or(standard.shrinked_death),
+            };
+
+            rescale(input, target_width, margins, output)
+        }
+        Some(Command::AutoSpawns {
+            input,
+            count,
+            output,
+        }) => auto_spawns(input, count, output),
+        Some(Command::FloorItemPopup {
+            input,
+            height,
+            tolerance,
+            output,
+        }) => floor_item_popup(input, height, tolerance, output),
+        Some(Command::Annotate { input }) => annotate(input),
+        Some(Command::Survey {
+            dir,
+            field,
+            max_sources,
+        }) => {
+            if let Err(error) = survey::survey(Path::new(&dir), &field, max_sources) {
+                report::error!("{error}");
+            }
+        }
+        Some(Command::ExportCsv {
+            input,
+            section,
+            scale,
+            output,
+        }) => export_csv(input, section, scale, output),
+        Some(Command::ImportCsv {
+            input,
+            csv,
+            section,
+            scale,
+            output,
+            non_finite,
+        }) => import_csv(
+            input,
+            csv,
+            section,
+            scale,
+            output,
+            non_finite.unwrap_or_default(),
+        ),
+        #[cfg(feature = "vanilla")]
+        Some(Command::CompareVanilla { input, database }) => {
+            if let Err(error) = vanilla::compare(&input, database) {
+                report::error!("{error}");
+            }
+        }
+        #[cfg(feature = "vanilla")]
+        Some(Command::VanillaDb { dir, output }) => {
+            if let Err(error) = vanilla::build_database(Path::new(&dir), output) {
+                report::error!("{error}");
+            }
+        }
+        Some(Command::Browse { input }) => {
+            if let Err(error) = browse::browse(input) {
+                report::error!("{error}");
+            }
+        }
+        #[cfg(feature = "render")]
+        Some(Command::Render {
+            input,
+            layers,
+            width,
+            height,
+            output,
+        }) => render(input, layers, width, height, output),
+        #[cfg(feature = "script")]
+        Some(Command::Script {
+            input,
+            script,
+            output,
+        }) => run_script(input, script, output),
+        None => convert(args),
+    }
+
+    report::exit_code()
+}
@@ -0,0 +1,175 @@
+//! The flattened model form of LVD files in YAML, written by default instead of the raw form.
+//!
+//! The model form is an [`LvdData`] mapping with the byte order of the source file under an
+//! `endian` key and the versions of its types under a `versions` key, so it converts back to the
+//! same versions without the game it came from being remembered. The raw form is written with
+//! `--raw` and has a variant tag such as `!V13` or a `version` field at the top.
+
+use std::error::Error;
+
+use lvd_lib::{
+    model::{LvdData, ModelVersions},
+    LvdFile,
+};
+use serde_yaml::{Mapping, Value};
+
+/// The key holding the byte order of an LVD file's model form.
+const ENDIAN_KEY: &str = "endian";
+
+/// The key holding the [`ModelVersions`] of an LVD file's model form.
+const VERSIONS_KEY: &str = "versions";
+
+/// The byte order of every LVD file which can be read, used by every supported game.
+const BIG_ENDIAN: &str = "big";
+
+/// Returns the model form of an LVD file, with its byte order and the versions of its types
+/// listed first.
+///
+/// # Errors
+///
+/// Returns an error if a type is stored in more than one version throughout the file,
+/// as the model form lists a single version of each type and so would change some objects.
+pub fn to_value(lvd: LvdFile) -> Result<Value, Box<dyn Error>> {
+    let versions = ModelVersions::of_uniform(&lvd.data)
+        .map_err(|error| format!("{error}, which the model form cannot keep"))?;
+    let mut mapping = Mapping::new();
+
+    mapping.insert(ENDIAN_KEY.into(), BIG_ENDIAN.into());
+    mapping.insert(VERSIONS_KEY.into(), serde_yaml::to_value(versions)?);
+
+    if let Value::Mapping(sections) = serde_yaml::to_value(LvdData::from(lvd))? {
+        mapping.extend(sections);
+    }
+
+    Ok(Value::Mapping(mapping))
+}
+
+/// Returns `true` if the value is the model form of an LVD file rather than its raw form.
+pub fn is_model(value: &Value) -> bool {
+    match value {
+        Value::Mapping(mapping) => !mapping.contains_key("version"),
+        _ => false,
+    }
+}
+
+/// Returns an error if the model form of an LVD file names a byte order other than big-endian,
+/// the only one supported.
+pub fn check_endian(value: &Value) -> Result<(), String> {
+    match value.get(ENDIAN_KEY) {
+        None => Ok(()),
+        Some(endian) if endian == BIG_ENDIAN => Ok(()),
+        Some(endian) if endian == "little" => {
+            Err("little-endian LVD files are not supported".to_string())
+        }
+        Some(endian) => Err(format!(
+            "unknown byte order `{}`, expected `{BIG_ENDIAN}`",
+            serde_yaml::to_string(endian).unwrap_or_default().trim_end()
+        )),
+    }
+}
+
+/// Returns the versions listed in the model form of an LVD file,
+/// or the latest versions if they are omitted.
+pub fn versions(value: &Value) -> Result<ModelVersions, serde_yaml::Error> {
+    value
+        .get(VERSIONS_KEY)
+        .map_or(Ok(ModelVersions::latest()), |versions| {
+            serde_yaml::from_value(versions.clone())
+        })
+}
+
+/// Replaces the sections and versions of `base`, the model form of an LVD file, with the ones
+/// present in `partial`, leaving the remaining sections of `base` unchanged.
+///
+/// # Errors
+///
+/// Returns an error if `partial` is not the model form of an LVD file.
+pub fn merge(base: &mut Value, partial: Value) -> Result<(), String> {
+    let (Value::Mapping(sections), Value::Mapping(partial_sections)) = (base, partial) else {
+        return Err("the YAML file does not describe an LVD file".to_string());
+    };
+
+    sections.extend(partial_sections);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use lvd_lib::{
+        objects::{base::MetaInfo, Collision, Point},
+        vector::Vector2,
+        version::Versioned,
+        Lvd,
+    };
+
+    use super::*;
+
+    #[test]
+    fn model_form_hides_versions() {
+        let mut data = Lvd::empty(11).unwrap();
+
+        data.collisions_mut()
+            .unwrap()
+            .push(Collision::new_soft_platform(
+                Vector2::new(-10.0, 0.0),
+                Vector2::new(10.0, 0.0),
+            ));
+
+        let yaml = serde_yaml::to_string(
+            &to_value(LvdFile {
+                data: Versioned::new(data),
+            })
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert!(yaml.starts_with("endian: big\nversions:\n  lvd: 11\n"));
+        assert!(!yaml.contains("!V"));
+        assert!(!yaml.contains("elements"));
+    }
+
+    #[test]
+    fn model_form_rejects_mixed_versions() {
+        let mut data = Lvd::empty(13).unwrap();
+        let old = Point::V1 {
+            meta_info: Versioned::new(MetaInfo::default()),
+            pos: Versioned::new(Vector2::new(0.0, 10.0)),
+        };
+
+        data.start_positions_mut().unwrap().push(Point::default());
+        data.start_positions_mut().unwrap().push(old);
+
+        let error = to_value(LvdFile {
+            data: Versioned::new(data),
+        })
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "`Point` is stored in both version 2 and version 1, which the model form cannot keep"
+        );
+    }
+
+    #[test]
+    fn check_model_endian() {
+        let endian = |yaml| check_endian(&serde_yaml::from_str(yaml).unwrap());
+
+        assert!(endian("collisions: []\n").is_ok());
+        assert!(endian("endian: big\n").is_ok());
+        assert_eq!(
+            endian("endian: little\n").unwrap_err(),
+            "little-endian LVD files are not supported"
+        );
+        assert!(endian("endian: 1\n").is_err());
+    }
+
+    #[test]
+    fn raw_form_is_not_model() {
+        assert!(!is_model(
+            &serde_yaml::from_str("!V13\ncollisions: a\n").unwrap()
+        ));
+        assert!(!is_model(&serde_yaml::from_str("version: 13\n").unwrap()));
+        assert!(is_model(&serde_yaml::from_str("collisions: []\n").unwrap()));
+    }
+}
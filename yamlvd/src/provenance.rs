@@ -0,0 +1,62 @@
+//! Comments recording where each object of an LVD file's YAML form was read from.
+//!
+//! The comments are ignored when converting the YAML file back to an LVD file, so they do not
+//! affect the order or contents of the objects.
+
+use std::collections::HashMap;
+
+use lvd_lib::{LvdFile, ObjectRef, SectionKind};
+
+/// Returns the YAML form of an LVD file with a comment naming the source file at the top,
+/// and a comment above each object with its section, index, byte offset, and version
+/// in the source file.
+///
+/// The objects of a section are nested in an `elements` field indented by `indent` spaces
+/// in the raw form, and listed directly under the section in the model form.
+pub fn add_comments(
+    yaml: &str,
+    source: &str,
+    bytes: &[u8],
+    lvd: &LvdFile,
+    raw: bool,
+    indent: usize,
+) -> String {
+    let offsets = lvd
+        .annotations()
+        .into_iter()
+        .filter_map(|annotation| Some((annotation.object?, annotation.offset)))
+        .collect::<HashMap<ObjectRef, u64>>();
+    let dash = format!("-{}", " ".repeat(indent - 1));
+    let indent = " ".repeat(if raw { indent } else { 0 });
+    let object_prefix = format!("{indent}{dash}");
+    let mut output = format!("# source: {source}\n");
+    let mut section = None;
+    let mut index = 0;
+
+    for line in yaml.lines() {
+        if !line.starts_with([' ', '-']) {
+            section = line.split(':').next().and_then(SectionKind::from_name);
+            index = 0;
+        }
+
+        if let (Some(kind), true) = (section, line.starts_with(&object_prefix)) {
+            let object = ObjectRef::new(kind, index);
+
+            if let Some(&offset) = offsets.get(&object) {
+                let version = bytes.get(offset as usize).copied().unwrap_or_default();
+
+                output.push_str(&format!(
+                    "{indent}# {}[{index}]: offset {offset:#010x}, version {version}\n",
+                    kind.name()
+                ));
+            }
+
+            index += 1;
+        }
+
+        output.push_str(line);
+        output.push('\n');
+    }
+
+    output
+}
@@ -0,0 +1,101 @@
+//! Reporting of errors and warnings to the standard error stream, as text or as JSON lines
+//! for build scripts, and the exit code reflecting whether any errors were reported.
+
+use std::{
+    fmt,
+    process::ExitCode,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
+
+use serde::Serialize;
+
+/// The format errors and warnings are written in.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// One line of text per message, such as `error: collisions[3].base.name: ...`.
+    #[default]
+    Text,
+
+    /// One JSON object per line with the `level` and `message` of the message.
+    Json,
+}
+
+/// The severity of a reported message.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    /// A problem which does not prevent the command from completing.
+    Warning,
+
+    /// A failure of the command.
+    Error,
+}
+
+static FORMAT: OnceLock<ErrorFormat> = OnceLock::new();
+static FAILED: AtomicBool = AtomicBool::new(false);
+
+/// Sets the format of every message reported afterwards.
+pub fn set_format(format: ErrorFormat) {
+    let _ = FORMAT.set(format);
+}
+
+/// Writes a message to the standard error stream, marking the process as failed if it is an error.
+pub fn report(level: Level, message: fmt::Arguments) {
+    if level == Level::Error {
+        FAILED.store(true, Ordering::Relaxed);
+    }
+
+    match FORMAT.get().copied().unwrap_or_default() {
+        ErrorFormat::Text => {
+            let level = match level {
+                Level::Warning => "warning",
+                Level::Error => "error",
+            };
+
+            eprintln!("{level}: {message}");
+        }
+        ErrorFormat::Json => {
+            #[derive(Serialize)]
+            struct Message {
+                level: Level,
+                message: String,
+            }
+
+            let message = Message {
+                level,
+                message: message.to_string(),
+            };
+
+            eprintln!("{}", serde_json::to_string(&message).unwrap());
+        }
+    }
+}
+
+/// Returns [`ExitCode::FAILURE`] if any error was reported, or [`ExitCode::SUCCESS`] otherwise.
+pub fn exit_code() -> ExitCode {
+    if FAILED.load(Ordering::Relaxed) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Reports an error, which makes the process exit with a nonzero code.
+macro_rules! error {
+    ($($arg:tt)*) => {
+        $crate::report::report($crate::report::Level::Error, format_args!($($arg)*))
+    };
+}
+
+/// Reports a warning, which does not affect the exit code.
+macro_rules! warning {
+    ($($arg:tt)*) => {
+        $crate::report::report($crate::report::Level::Warning, format_args!($($arg)*))
+    };
+}
+
+pub(crate) use error;
+pub(crate) use warning;
@@ -0,0 +1,93 @@
+//! Bulk edits of LVD files with Rhai scripts.
+//!
+//! A script sees the file in its version-keyed form as the `lvd` variable, a map with a key
+//! for each section, and edits it in place:
+//!
+//! ```rhai
+//! for i in 0..lvd.collisions.elements.len() {
+//!     for j in 0..lvd.collisions.elements[i].vertices.elements.len() {
+//!         lvd.collisions.elements[i].vertices.elements[j].y += 10.0;
+//!     }
+//! }
+//! ```
+
+use std::error::Error;
+
+use lvd_lib::LvdFile;
+use rhai::{serde, Dynamic, Engine, Scope};
+
+/// Runs a script on an LVD file, returning the file as the script left it.
+pub fn run_script(lvd: &LvdFile, script: &str) -> Result<LvdFile, Box<dyn Error>> {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    scope.push_dynamic("lvd", serde::to_dynamic(lvd.to_version_keyed_value())?);
+    engine.run_with_scope(&mut scope, script)?;
+
+    let value = scope
+        .get_value::<Dynamic>("lvd")
+        .ok_or("the script removed the `lvd` variable")?;
+
+    Ok(LvdFile::from_version_keyed_value(serde::from_dynamic(
+        &value,
+    )?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use lvd_lib::vector::Vector2;
+
+    use super::*;
+
+    #[test]
+    fn script_moves_vertices() {
+        let lvd = LvdFile::sample_v13();
+        let script = "
+            for i in 0..lvd.collisions.elements[0].vertices.elements.len() {
+                lvd.collisions.elements[0].vertices.elements[i].x *= 2;
+                lvd.collisions.elements[0].vertices.elements[i].y += 5.0;
+            }
+
+            lvd.collisions.elements[0].attributes.elements[0].flags.throughable = true;
+        ";
+        let edited = run_script(&lvd, script).unwrap();
+        let collision = edited.data.collisions().unwrap().iter().next().unwrap();
+
+        assert_eq!(
+            collision.vertices().iter().copied().collect::<Vec<_>>(),
+            [
+                (-100.0, 5.0),
+                (100.0, 5.0),
+                (100.0, -5.0),
+                (-100.0, -5.0),
+                (-100.0, 5.0)
+            ]
+            .map(|(x, y)| Vector2::new(x, y))
+        );
+        assert!(collision
+            .edges()
+            .next()
+            .unwrap()
+            .attribute
+            .unwrap()
+            .flags()
+            .throughable());
+        assert!(run_script(&lvd, "lvd.collisions = 1 +").is_err());
+    }
+
+    #[test]
+    fn script_errors() {
+        let lvd = LvdFile::sample_v13();
+
+        assert!(run_script(&lvd, "")
+            .is_ok_and(|edited| edited.to_bytes().unwrap() == lvd.to_bytes().unwrap()));
+        assert!(
+            run_script(&lvd, "lvd.collisions = 1;").is_err(),
+            "sections must keep their form"
+        );
+        assert!(run_script(&lvd, "throw \"stop\";")
+            .unwrap_err()
+            .to_string()
+            .contains("stop"));
+    }
+}
@@ -0,0 +1,145 @@
+//! Selecting and merging the sections of LVD files in their YAML form.
+
+use lvd_lib::SectionKind;
+use serde_yaml::{Mapping, Value};
+
+/// Parses a section name given on the command line, such as `collisions`.
+pub fn parse_section(name: &str) -> Result<SectionKind, String> {
+    SectionKind::from_name(name).ok_or_else(|| {
+        let names = SectionKind::ALL
+            .iter()
+            .map(|kind| kind.name())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("unknown section `{name}`, expected one of: {names}")
+    })
+}
+
+/// Parses the name of a section which can be converted to CSV given on the command line,
+/// such as `start_positions`.
+pub fn parse_csv_section(name: &str) -> Result<SectionKind, String> {
+    SectionKind::from_name(name)
+        .filter(|kind| lvd_lib::csv::SECTIONS.contains(kind))
+        .ok_or_else(|| {
+            let names = lvd_lib::csv::SECTIONS
+                .iter()
+                .map(|kind| kind.name())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!("section `{name}` cannot be converted to CSV, expected one of: {names}")
+        })
+}
+
+/// Parses the name of a section which can be drawn given on the command line,
+/// such as `collisions`.
+#[cfg(feature = "render")]
+pub fn parse_render_section(name: &str) -> Result<SectionKind, String> {
+    SectionKind::from_name(name)
+        .filter(|kind| lvd_lib::render::LAYERS.contains(kind))
+        .ok_or_else(|| {
+            let names = lvd_lib::render::LAYERS
+                .iter()
+                .map(|kind| kind.name())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!("section `{name}` cannot be drawn, expected one of: {names}")
+        })
+}
+
+/// Returns the mapping of section names to sections of an LVD file's YAML form,
+/// written with either a variant tag such as `!V13` or a `version` field.
+fn sections_mut(value: &mut Value) -> Option<&mut Mapping> {
+    match value {
+        Value::Tagged(tagged) => tagged.value.as_mapping_mut(),
+        value => value.as_mapping_mut(),
+    }
+}
+
+/// Returns the file format version of an LVD file's YAML form.
+fn file_version(value: &Value) -> Option<u64> {
+    match value {
+        Value::Tagged(tagged) => tagged.tag.to_string().trim_start_matches("!V").parse().ok(),
+        value => value.get("version")?.as_u64(),
+    }
+}
+
+/// Removes the sections of an LVD file's YAML form for which `keep` returns `false`.
+pub fn retain_sections<F: Fn(SectionKind) -> bool>(value: &mut Value, keep: F) {
+    if let Some(sections) = sections_mut(value) {
+        sections.retain(|key, _| {
+            key.as_str()
+                .and_then(SectionKind::from_name)
+                .is_none_or(&keep)
+        });
+    }
+}
+
+/// Replaces the sections of `base`, an LVD file's YAML form, with the sections present in
+/// `partial`, leaving the remaining sections of `base` unchanged.
+///
+/// # Errors
+///
+/// Returns an error if the two files have different format versions,
+/// or if `partial` contains a section unsupported by the version.
+pub fn merge_sections(base: &mut Value, mut partial: Value) -> Result<(), String> {
+    let base_version = file_version(base);
+
+    if let Some(version) = file_version(&partial).filter(|&version| Some(version) != base_version) {
+        return Err(format!(
+            "the YAML file has version {version} but the base file has version {}",
+            base_version.unwrap_or_default()
+        ));
+    }
+
+    let (Some(sections), Some(partial_sections)) = (sections_mut(base), sections_mut(&mut partial))
+    else {
+        return Err("the YAML file does not describe an LVD file".to_string());
+    };
+
+    for (key, section) in std::mem::take(partial_sections) {
+        if key.as_str() == Some("version") {
+            continue;
+        }
+
+        match sections.get_mut(&key) {
+            Some(base_section) => *base_section = section,
+            None => {
+                return Err(format!(
+                    "section `{}` is not supported by the file's version",
+                    key.as_str().unwrap_or_default()
+                ))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_replaces_only_present_sections() {
+        let mut base = serde_yaml::from_str("!V13\ncollisions: a\nstart_positions: b\n").unwrap();
+        let partial = serde_yaml::from_str("!V13\nstart_positions: c\n").unwrap();
+
+        merge_sections(&mut base, partial).unwrap();
+
+        assert_eq!(
+            base,
+            serde_yaml::from_str::<Value>("!V13\ncollisions: a\nstart_positions: c\n").unwrap()
+        );
+    }
+
+    #[test]
+    fn merge_rejects_mismatched_versions() {
+        let mut base = serde_yaml::from_str("!V13\ncollisions: a\n").unwrap();
+        let partial = serde_yaml::from_str("version: 12\ncollisions: b\n").unwrap();
+
+        assert!(merge_sections(&mut base, partial).is_err());
+    }
+}
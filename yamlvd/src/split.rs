@@ -0,0 +1,270 @@
+//! Splitting the YAML form of LVD files into one file per object and joining them back together.
+
+use std::{
+    error::Error,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use lvd_lib::{LvdFile, SectionKind};
+use serde_yaml::{Mapping, Value};
+
+use crate::style::{self, YamlStyle};
+
+/// The file name of the manifest of a split YAML file.
+pub const MANIFEST_FILE_NAME: &str = "lvd.yaml";
+
+/// Returns the mapping of an object or section's YAML form,
+/// written with either a variant tag such as `!V1` or a `version` field.
+fn mapping_mut(value: &mut Value) -> Option<&mut Mapping> {
+    match value {
+        Value::Tagged(tagged) => tagged.value.as_mapping_mut(),
+        value => value.as_mapping_mut(),
+    }
+}
+
+/// Returns the objects of a section's YAML form,
+/// listed directly in the model form or in an `elements` field in the raw form.
+fn elements_mut(section: &mut Value) -> Option<&mut Vec<Value>> {
+    match section {
+        Value::Sequence(elements) => Some(elements),
+        section => mapping_mut(section)?.get_mut("elements")?.as_sequence_mut(),
+    }
+}
+
+/// Returns the path of an object's file relative to the split directory,
+/// such as `collisions/003_COL_00_Floor01.yaml`.
+fn object_path(kind: SectionKind, index: usize, name: &str) -> String {
+    let name = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+
+    if name.is_empty() {
+        format!("{}/{index:03}.yaml", kind.name())
+    } else {
+        format!("{}/{index:03}_{name}.yaml", kind.name())
+    }
+}
+
+/// Removes the YAML files left in a section's directory by a previous split.
+fn remove_object_files(dir: &Path) -> io::Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(error) => return Err(error),
+    };
+
+    for entry in entries {
+        let path = entry?.path();
+
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "yaml")
+        {
+            fs::remove_file(path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes `value`, the YAML form of `lvd`, to the directory as one file per object,
+/// grouped into a directory per section, along with a manifest listing the files in order.
+///
+/// Files left in the section directories by a previous split are removed.
+pub fn write_split(
+    dir: &Path,
+    mut value: Value,
+    lvd: &LvdFile,
+    style: &YamlStyle,
+) -> io::Result<()> {
+    let sections = mapping_mut(&mut value)
+        .ok_or_else(|| io::Error::other("the YAML form of the LVD file is not a mapping"))?;
+
+    fs::create_dir_all(dir)?;
+
+    for (key, section) in sections.iter_mut() {
+        let Some(kind) = key.as_str().and_then(SectionKind::from_name) else {
+            continue;
+        };
+        let Some(elements) = elements_mut(section) else {
+            continue;
+        };
+        let section_dir = dir.join(kind.name());
+
+        remove_object_files(&section_dir)?;
+
+        if !elements.is_empty() {
+            fs::create_dir_all(&section_dir)?;
+        }
+
+        for (index, element) in elements.iter_mut().enumerate() {
+            let name = lvd
+                .data
+                .object(kind, index)
+                .and_then(|object| object.name().to_str().ok())
+                .unwrap_or_default();
+            let path = object_path(kind, index, name);
+            fs::write(dir.join(&path), style::to_string(element, style))?;
+
+            *element = Value::String(path);
+        }
+    }
+
+    fs::write(
+        dir.join(MANIFEST_FILE_NAME),
+        style::to_string(&value, style),
+    )
+}
+
+/// Reads the YAML form of an LVD file from a directory written by [`write_split`],
+/// replacing each object file listed in the manifest with its contents.
+///
+/// # Errors
+///
+/// Returns an error naming the file which could not be read or parsed.
+pub fn read_split(dir: &Path) -> Result<String, Box<dyn Error>> {
+    let read_value = |path: PathBuf| -> Result<Value, Box<dyn Error>> {
+        let yaml =
+            fs::read_to_string(&path).map_err(|error| format!("{}: {error}", path.display()))?;
+
+        serde_yaml::from_str(&yaml).map_err(|error| format!("{}: {error}", path.display()).into())
+    };
+    let mut value = read_value(dir.join(MANIFEST_FILE_NAME))?;
+
+    if let Some(sections) = mapping_mut(&mut value) {
+        for (key, section) in sections.iter_mut() {
+            if key.as_str().and_then(SectionKind::from_name).is_none() {
+                continue;
+            }
+
+            for element in elements_mut(section).into_iter().flatten() {
+                if let Value::String(path) = element {
+                    *element = read_value(dir.join(&*path))?;
+                }
+            }
+        }
+    }
+
+    Ok(serde_yaml::to_string(&value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use lvd_lib::objects::LvdObject;
+
+    use super::*;
+
+    /// Returns the sample file with a copy of its collision, so two objects share a name.
+    fn sample_lvd() -> LvdFile {
+        let mut lvd = LvdFile::sample_v13();
+        let collisions = lvd.data.collisions_mut().unwrap();
+        let collision = collisions.iter().next().unwrap().clone();
+
+        collisions.push(collision);
+        lvd
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("yamlvd_{name}_{}", std::process::id()))
+    }
+
+    fn assert_split_round_trip(name: &str, lvd: LvdFile, value: Value) {
+        let dir = temp_dir(name);
+
+        write_split(&dir, value.clone(), &lvd, &YamlStyle::default()).unwrap();
+
+        let object_files_exist = [
+            "collisions/000_COL_Floor.yaml",
+            "collisions/001_COL_Floor.yaml",
+        ]
+        .iter()
+        .all(|path| dir.join(path).is_file());
+        let joined = read_split(&dir);
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(object_files_exist);
+        assert_eq!(
+            serde_yaml::from_str::<Value>(&joined.unwrap()).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn split_and_join() {
+        let lvd = sample_lvd();
+        let value = serde_yaml::to_value(&lvd).unwrap();
+
+        assert_split_round_trip("split", lvd, value);
+    }
+
+    #[test]
+    fn split_and_join_model() {
+        let lvd = sample_lvd();
+        let value = crate::model::to_value(lvd.clone()).unwrap();
+
+        assert_split_round_trip("split_model", lvd, value);
+    }
+
+    #[test]
+    fn object_paths_are_sanitized() {
+        assert_eq!(
+            object_path(SectionKind::Collisions, 7, "COL 00/Floor.01"),
+            "collisions/007_COL_00_Floor_01.yaml"
+        );
+        assert_eq!(
+            object_path(SectionKind::StartPositions, 12, ""),
+            "start_positions/012.yaml"
+        );
+    }
+
+    #[test]
+    fn resplit_removes_stale_files() {
+        let dir = temp_dir("resplit");
+        let mut lvd = sample_lvd();
+
+        write_split(
+            &dir,
+            serde_yaml::to_value(&lvd).unwrap(),
+            &lvd,
+            &YamlStyle::default(),
+        )
+        .unwrap();
+
+        lvd.data.collisions_mut().unwrap().elements_mut().pop();
+        *lvd.data.collisions_mut().unwrap().elements_mut()[0]
+            .meta_info_mut()
+            .name_mut() = "COL_Renamed".try_into().unwrap();
+
+        write_split(
+            &dir,
+            serde_yaml::to_value(&lvd).unwrap(),
+            &lvd,
+            &YamlStyle::default(),
+        )
+        .unwrap();
+
+        let mut files = fs::read_dir(dir.join("collisions"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+
+        files.sort();
+        fs::remove_file(dir.join("collisions/000_COL_Renamed.yaml")).unwrap();
+
+        let missing = read_split(&dir).unwrap_err().to_string();
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files, ["000_COL_Renamed.yaml"]);
+        assert!(missing.contains("000_COL_Renamed.yaml"), "{missing}");
+    }
+}
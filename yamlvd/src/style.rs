@@ -0,0 +1,299 @@
+//! The layout of YAML files written from LVD files.
+//!
+//! serde_yaml always writes nested mappings in block style, which spends a line on every
+//! component of every vector. This module writes the same values with a configurable indentation
+//! and with small mappings of numbers, such as vectors and rectangles, as inline flow mappings.
+//!
+//! ```yaml
+//! vertices:
+//! - {x: -10.0, y: 0.0}
+//! - {x: 10.0, y: 0.0}
+//! ```
+
+use std::fmt::Write;
+
+use serde_yaml::{value::TaggedValue, Mapping, Value};
+
+/// The most entries a mapping of numbers may have to be written in flow style.
+const MAX_FLOW_ENTRIES: usize = 4;
+
+/// How YAML files are laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YamlStyle {
+    /// The number of spaces each nested mapping is indented by, at least two.
+    pub indent: usize,
+
+    /// The longest line a flow mapping is written on, beyond which the mapping is written
+    /// in block style instead.
+    pub width: usize,
+
+    /// Whether mappings of up to four numbers are written in flow style.
+    pub flow: bool,
+}
+
+impl Default for YamlStyle {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            width: 100,
+            flow: true,
+        }
+    }
+}
+
+/// Where a value is written relative to its parent.
+#[derive(Clone, Copy)]
+enum Position {
+    /// At the start of the document.
+    Root,
+
+    /// After the key of a mapping entry.
+    Value,
+
+    /// After the dash of a sequence item.
+    Item,
+}
+
+/// Returns the YAML form of the value laid out with the given style.
+pub fn to_string(value: &Value, style: &YamlStyle) -> String {
+    let mut writer = Writer {
+        style: *style,
+        output: String::new(),
+    };
+
+    writer.value(value, 0, 0, Position::Root);
+    writer.output
+}
+
+struct Writer {
+    style: YamlStyle,
+    output: String,
+}
+
+impl Writer {
+    /// Writes a value at the given position, where `column` is the column its block content
+    /// starts at and `line_len` is the length of the line written so far.
+    fn value(&mut self, value: &Value, column: usize, line_len: usize, position: Position) {
+        if let Some(inline) = self.inline(value, line_len) {
+            let separator = if matches!(position, Position::Value) {
+                " "
+            } else {
+                ""
+            };
+
+            self.output.push_str(separator);
+            self.output.push_str(&inline);
+            self.output.push('\n');
+            return;
+        }
+
+        match value {
+            Value::Mapping(mapping) => self.mapping(mapping, column, position),
+            Value::Sequence(sequence) => self.sequence(sequence, column, position),
+            Value::Tagged(tagged) => self.tagged(tagged, column, line_len, position),
+            _ => unreachable!("scalars are written inline"),
+        }
+    }
+
+    fn mapping(&mut self, mapping: &Mapping, column: usize, position: Position) {
+        let column = match position {
+            Position::Value => {
+                self.output.push('\n');
+                column + self.style.indent
+            }
+            Position::Root | Position::Item => column,
+        };
+
+        for (index, (key, value)) in mapping.iter().enumerate() {
+            // The first entry of a sequence item follows its dash.
+            if index > 0 || !matches!(position, Position::Item) {
+                self.indent(column);
+            }
+
+            let key = scalar(key);
+
+            let _ = write!(self.output, "{key}:");
+            self.value(value, column, column + key.len() + 1, Position::Value);
+        }
+    }
+
+    fn sequence(&mut self, sequence: &[Value], column: usize, position: Position) {
+        // Sequences in mappings start at the column of their key, as written by serde_yaml.
+        let column = match position {
+            Position::Value => {
+                self.output.push('\n');
+                column
+            }
+            Position::Root | Position::Item => column,
+        };
+
+        for (index, item) in sequence.iter().enumerate() {
+            if index > 0 || !matches!(position, Position::Item) {
+                self.indent(column);
+            }
+
+            let dash = format!("-{}", " ".repeat(self.style.indent - 1));
+
+            self.output.push_str(&dash);
+            self.value(
+                item,
+                column + dash.len(),
+                column + dash.len(),
+                Position::Item,
+            );
+        }
+    }
+
+    fn tagged(&mut self, tagged: &TaggedValue, column: usize, line_len: usize, position: Position) {
+        let separator = if matches!(position, Position::Value) {
+            " "
+        } else {
+            ""
+        };
+        let tag = tagged.tag.to_string();
+
+        let _ = write!(self.output, "{separator}{tag}");
+
+        let line_len = line_len + separator.len() + tag.len();
+
+        if let Some(inline) = self.inline(&tagged.value, line_len + 1) {
+            let _ = writeln!(self.output, " {inline}");
+            return;
+        }
+
+        // Block content follows the tag on the next line.
+        match &tagged.value {
+            Value::Mapping(mapping) => {
+                let column = match position {
+                    Position::Value => column + self.style.indent,
+                    Position::Root | Position::Item => column,
+                };
+
+                self.output.push('\n');
+                self.mapping(mapping, column, Position::Root);
+            }
+            Value::Sequence(sequence) => self.sequence(sequence, column, Position::Value),
+            value => self.value(value, column, line_len, Position::Value),
+        }
+    }
+
+    /// Returns the value written on a single line, if it is a scalar, an empty collection,
+    /// or a mapping which fits in flow style on a line already `line_len` long.
+    fn inline(&self, value: &Value, line_len: usize) -> Option<String> {
+        match value {
+            Value::Sequence(sequence) if sequence.is_empty() => Some("[]".to_string()),
+            Value::Mapping(mapping) if mapping.is_empty() => Some("{}".to_string()),
+            Value::Mapping(mapping) if self.style.flow && is_flow_mapping(mapping) => {
+                let entries = mapping
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", scalar(key), scalar(value)))
+                    .collect::<Vec<_>>();
+                let flow = format!("{{{}}}", entries.join(", "));
+
+                (line_len + 1 + flow.len() <= self.style.width).then_some(flow)
+            }
+            Value::Mapping(_) | Value::Sequence(_) | Value::Tagged(_) => None,
+            value => Some(scalar(value)),
+        }
+    }
+
+    fn indent(&mut self, column: usize) {
+        self.output.extend(std::iter::repeat_n(' ', column));
+    }
+}
+
+/// Returns `true` if the mapping is small and holds only numbers, such as a vector or rectangle.
+fn is_flow_mapping(mapping: &Mapping) -> bool {
+    mapping.len() <= MAX_FLOW_ENTRIES
+        && mapping
+            .iter()
+            .all(|(key, value)| key.is_string() && value.is_number())
+}
+
+/// Returns the YAML form of a scalar value on a single line.
+fn scalar(value: &Value) -> String {
+    let yaml = serde_yaml::to_string(value).unwrap();
+    let yaml = yaml.strip_suffix('\n').unwrap_or(&yaml);
+
+    // Strings spanning several lines are written as block scalars,
+    // so they are written as double-quoted JSON strings instead.
+    match value {
+        Value::String(string) if yaml.contains('\n') => serde_json::to_string(string).unwrap(),
+        _ => yaml.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lvd_lib::LvdFile;
+
+    use super::*;
+
+    /// The style of serde_yaml, which writes every mapping in block style.
+    const BLOCK: YamlStyle = YamlStyle {
+        indent: 2,
+        width: usize::MAX,
+        flow: false,
+    };
+
+    fn file() -> LvdFile {
+        LvdFile::sample_v13()
+    }
+
+    #[test]
+    fn block_style_matches_serde_yaml() {
+        for value in [
+            serde_yaml::to_value(file()).unwrap(),
+            crate::model::to_value(file()).unwrap(),
+        ] {
+            assert_eq!(
+                to_string(&value, &BLOCK),
+                serde_yaml::to_string(&value).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn block_style_matches_serde_yaml_tags() {
+        let value = serde_yaml::from_str::<Value>(
+            "a: !t [1, 2]\nb: [!t [1, 2], !u {x: 1}, [3, 4]]\nc: !s [{p: 1, q: 2}]\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            to_string(&value, &BLOCK),
+            serde_yaml::to_string(&value).unwrap()
+        );
+    }
+
+    #[test]
+    fn flow_style_round_trip() {
+        for style in [
+            YamlStyle::default(),
+            YamlStyle {
+                indent: 4,
+                width: 40,
+                flow: true,
+            },
+        ] {
+            for value in [
+                serde_yaml::to_value(file()).unwrap(),
+                crate::model::to_value(file()).unwrap(),
+            ] {
+                let yaml = to_string(&value, &style);
+
+                assert_eq!(serde_yaml::from_str::<Value>(&yaml).unwrap(), value);
+            }
+        }
+    }
+
+    #[test]
+    fn vectors_are_inline() {
+        let yaml = to_string(
+            &crate::model::to_value(file()).unwrap(),
+            &YamlStyle::default(),
+        );
+
+        assert!(yaml.contains("  vertices:\n  - {x: -50.0, y: 0.0}\n  - {x: 50.0, y: 0.0}\n"));
+    }
+}
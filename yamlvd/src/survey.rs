@@ -0,0 +1,165 @@
+//! Tabulating the values of a field across every LVD file in a directory.
+
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use lvd_lib::{LvdFile, SectionKind};
+use serde_json::Value;
+
+use crate::{browse::unwrap, report};
+
+/// The label of the values of objects without the field, such as those of an older version.
+const MISSING: &str = "(missing)";
+
+/// An object containing a surveyed value.
+struct Source {
+    /// The path of the file containing the object.
+    path: PathBuf,
+
+    /// The name of the object.
+    name: String,
+}
+
+/// A field of an object type given on the command line, such as `AreaHint.unk1`.
+#[derive(Clone)]
+pub struct FieldPath {
+    /// The name of the object type, such as `AreaHint`.
+    type_name: String,
+
+    /// The names of the fields leading to the surveyed value, such as `base.dynamic_name`.
+    fields: Vec<String>,
+}
+
+/// Parses a field given on the command line as an object type followed by a dotted path.
+pub fn parse_field(field: &str) -> Result<FieldPath, String> {
+    let mut parts = field.split('.');
+    let type_name = parts.next().unwrap_or_default();
+    let fields = parts.map(str::to_string).collect::<Vec<_>>();
+
+    if !SectionKind::ALL
+        .iter()
+        .any(|kind| kind.type_name() == type_name)
+    {
+        let mut names = Vec::new();
+
+        for kind in SectionKind::ALL {
+            if !names.contains(&kind.type_name()) {
+                names.push(kind.type_name());
+            }
+        }
+
+        return Err(format!(
+            "unknown object type `{type_name}`, expected one of: {}",
+            names.join(", ")
+        ));
+    }
+
+    if fields.is_empty() || fields.iter().any(String::is_empty) {
+        return Err(format!(
+            "expected a field of `{type_name}` such as `{type_name}.base.dynamic_name`"
+        ));
+    }
+
+    Ok(FieldPath {
+        type_name: type_name.to_string(),
+        fields,
+    })
+}
+
+/// Prints how often each value of the field occurs across the LVD files in the directory
+/// and its subdirectories, listing up to `max_sources` objects with each value.
+pub fn survey(dir: &Path, field: &FieldPath, max_sources: usize) -> io::Result<()> {
+    let mut paths = Vec::new();
+
+    find_lvd_files(dir, &mut paths)?;
+    paths.sort();
+
+    let mut values = HashMap::<String, Vec<Source>>::new();
+    let mut file_count = 0;
+
+    for path in paths {
+        let lvd = match LvdFile::from_file(&path) {
+            Ok(lvd) => lvd,
+            Err(error) => {
+                report::warning!("{}: {error}", path.display());
+                continue;
+            }
+        };
+        let data = serde_json::to_value(&lvd.data).map_err(io::Error::other)?;
+        let kinds = SectionKind::ALL
+            .iter()
+            .filter(|kind| kind.type_name() == field.type_name);
+
+        file_count += 1;
+
+        for &kind in kinds {
+            let Some(Value::Array(objects)) = unwrap(&data).get(kind.name()).map(unwrap) else {
+                continue;
+            };
+
+            for (index, object) in objects.iter().enumerate() {
+                let value = field
+                    .fields
+                    .iter()
+                    .try_fold(unwrap(object), |value, name| value.get(name).map(unwrap))
+                    .map_or_else(|| MISSING.to_string(), Value::to_string);
+                let name = lvd
+                    .data
+                    .object(kind, index)
+                    .and_then(|object| object.name().to_string().ok())
+                    .unwrap_or_default();
+
+                values.entry(value).or_default().push(Source {
+                    path: path.clone(),
+                    name,
+                });
+            }
+        }
+    }
+
+    let object_count = values.values().map(Vec::len).sum::<usize>();
+    let mut values = values.into_iter().collect::<Vec<_>>();
+
+    values.sort_by(|(a, a_sources), (b, b_sources)| {
+        b_sources.len().cmp(&a_sources.len()).then_with(|| a.cmp(b))
+    });
+
+    println!(
+        "{}.{}: {} distinct values in {object_count} objects from {file_count} files",
+        field.type_name,
+        field.fields.join("."),
+        values.len(),
+    );
+
+    for (value, sources) in values {
+        println!("{value}: {} objects", sources.len());
+
+        for source in sources.iter().take(max_sources) {
+            println!("  {}: {}", source.path.display(), source.name);
+        }
+
+        if sources.len() > max_sources {
+            println!("  ... and {} more", sources.len() - max_sources);
+        }
+    }
+
+    Ok(())
+}
+
+/// Appends the paths of the LVD files in the directory and its subdirectories.
+pub fn find_lvd_files(dir: &Path, paths: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            find_lvd_files(&path, paths)?;
+        } else if path.extension().is_some_and(|extension| extension == "lvd") {
+            paths.push(path);
+        }
+    }
+
+    Ok(())
+}
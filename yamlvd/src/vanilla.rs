@@ -0,0 +1,75 @@
+//! Building and comparing against the database of vanilla LVD files.
+
+use std::{error::Error, fs, io, path::Path};
+
+use lvd_lib::{
+    vanilla::{VanillaDatabase, VanillaEntry},
+    LvdFile,
+};
+
+use crate::{report, survey::find_lvd_files};
+
+/// Writes a database of every LVD file in the extracted game files in the directory,
+/// or prints it to standard output if no output path is given.
+pub fn build_database(dir: &Path, output_path: Option<String>) -> io::Result<()> {
+    let mut paths = Vec::new();
+
+    find_lvd_files(dir, &mut paths)?;
+    paths.sort();
+
+    let mut database = VanillaDatabase::default();
+
+    for path in paths {
+        let bytes = fs::read(&path)?;
+        let lvd = match LvdFile::read(&mut io::Cursor::new(&bytes)) {
+            Ok(lvd) => lvd,
+            Err(error) => {
+                report::warning!("{}: {error}", path.display());
+                continue;
+            }
+        };
+        let relative_path = path
+            .strip_prefix(dir)
+            .unwrap_or(&path)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        database
+            .entries
+            .push(VanillaEntry::new(relative_path, &bytes, &lvd));
+    }
+
+    match output_path {
+        Some(output_path) => fs::write(output_path, database.to_text()),
+        None => {
+            print!("{}", database.to_text());
+            Ok(())
+        }
+    }
+}
+
+/// Prints how an LVD file compares to the vanilla files in the database at the given path,
+/// or the bundled database if no path is given.
+pub fn compare(input_path: &str, database_path: Option<String>) -> Result<(), Box<dyn Error>> {
+    let database = match database_path {
+        Some(database_path) => VanillaDatabase::parse(&fs::read_to_string(database_path)?)?,
+        None => VanillaDatabase::bundled(),
+    };
+
+    if database.entries.is_empty() {
+        return Err(
+            "the vanilla database is empty; generate one with `yamlvd vanilla-db` \
+                    and pass it with --database"
+                .into(),
+        );
+    }
+
+    let bytes = fs::read(input_path)?;
+    let lvd = LvdFile::read(&mut io::Cursor::new(&bytes))?;
+
+    println!("{}", database.compare(input_path, &bytes, &lvd));
+
+    Ok(())
+}
@@ -0,0 +1,392 @@
+//! Descriptive errors for YAML files which fail to deserialize as LVD files.
+
+use std::{error::Error, fmt};
+
+use lvd_lib::{
+    model::{LvdData, ModelError, ModelVersions},
+    version_keys::normalize_version_keyed_value,
+    LvdFile, SectionKind,
+};
+use serde_path_to_error::{Path, Segment};
+
+use crate::{keyed, model};
+
+/// An error deserializing an LVD file from YAML,
+/// with the location of the offending value and a hint for fixing it.
+#[derive(Debug)]
+pub struct YamlError {
+    /// The path to the offending value, such as `collisions[3].base.meta_info.name`.
+    path: String,
+
+    /// The message of the underlying error.
+    message: String,
+
+    /// The line and column of the offending value, if known.
+    location: Option<(usize, usize)>,
+
+    /// A suggestion for fixing the error, if one applies.
+    hint: Option<String>,
+}
+
+impl YamlError {
+    fn new(path: String, message: String, location: Option<(usize, usize)>) -> Self {
+        Self {
+            path,
+            hint: hint(&message),
+            message,
+            location,
+        }
+    }
+}
+
+impl fmt::Display for YamlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)?;
+        } else {
+            write!(f, "{}: {}", self.path, self.message)?;
+        }
+
+        if let Some((line, column)) = self.location {
+            write!(f, " (line {line}, column {column})")?;
+        }
+
+        if let Some(hint) = &self.hint {
+            write!(f, "\nhint: {hint}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for YamlError {}
+
+impl From<ModelError> for YamlError {
+    fn from(error: ModelError) -> Self {
+        Self::new(String::new(), error.to_string(), None)
+    }
+}
+
+/// Deserializes an LVD file from YAML in its model form, or in its raw form using either
+/// variant tags or explicit version keys.
+pub fn lvd_from_yaml(yaml: &str) -> Result<LvdFile, YamlError> {
+    let value = parse_yaml(yaml)?;
+
+    // Sections keyed by object name are converted to sequences before being deserialized,
+    // so their errors do not include line numbers.
+    if model::is_model(&value) && keyed::is_keyed(&value) {
+        return lvd_from_yaml_value(value);
+    }
+
+    if model::is_model(&value) {
+        let versions = model_versions(&value)?;
+        let data: LvdData = from_yaml_str(yaml)?;
+
+        return Ok(data.into_file(&versions)?);
+    }
+
+    let value = to_json(&value)?;
+
+    if has_version_keys(&value) {
+        from_version_keyed_value(value)
+    } else {
+        from_yaml_str(yaml)
+    }
+}
+
+/// Deserializes a value from YAML text, with the path and location of any offending value.
+fn from_yaml_str<T: serde::de::DeserializeOwned>(yaml: &str) -> Result<T, YamlError> {
+    serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(yaml)).map_err(|error| {
+        let location = error.inner().location().map(|l| (l.line(), l.column()));
+
+        YamlError::new(
+            display_path(error.path()),
+            strip_context(error.inner()),
+            location,
+        )
+    })
+}
+
+/// Returns the versions listed in the model form of an LVD file, after checking its byte order.
+fn model_versions(value: &serde_yaml::Value) -> Result<ModelVersions, YamlError> {
+    model::check_endian(value)
+        .map_err(|error| YamlError::new("endian".to_string(), error, None))?;
+
+    model::versions(value)
+        .map_err(|error| YamlError::new("versions".to_string(), strip_context(&error), None))
+}
+
+/// Parses YAML text without interpreting it as an LVD file.
+pub fn parse_yaml(yaml: &str) -> Result<serde_yaml::Value, YamlError> {
+    serde_yaml::from_str(yaml).map_err(|error| {
+        let location = error.location().map(|l| (l.line(), l.column()));
+
+        YamlError::new(String::new(), strip_context(&error), location)
+    })
+}
+
+/// Deserializes an LVD file from a parsed YAML value in either form, like [`lvd_from_yaml`].
+/// Errors do not include line numbers, since the value has no source text.
+pub fn lvd_from_yaml_value(mut value: serde_yaml::Value) -> Result<LvdFile, YamlError> {
+    if model::is_model(&value) {
+        keyed::from_keyed(&mut value)
+            .map_err(|error| YamlError::new(String::new(), error, None))?;
+
+        let versions = model_versions(&value)?;
+        let data: LvdData = serde_path_to_error::deserialize(value).map_err(|error| {
+            YamlError::new(
+                display_path(error.path()),
+                strip_context(error.inner()),
+                None,
+            )
+        })?;
+
+        return Ok(data.into_file(&versions)?);
+    }
+
+    let json = to_json(&value)?;
+
+    if has_version_keys(&json) {
+        from_version_keyed_value(json)
+    } else {
+        serde_path_to_error::deserialize(value).map_err(|error| {
+            YamlError::new(
+                display_path(error.path()),
+                strip_context(error.inner()),
+                None,
+            )
+        })
+    }
+}
+
+/// Converts a parsed YAML value to JSON, with YAML tags such as `!Circle` converted to
+/// single-field objects such as `{"Circle": ...}` as expected by enum variants.
+fn to_json(value: &serde_yaml::Value) -> Result<serde_json::Value, YamlError> {
+    use serde_yaml::Value;
+
+    Ok(match value {
+        Value::Sequence(elements) => elements
+            .iter()
+            .map(to_json)
+            .collect::<Result<_, _>>()
+            .map(serde_json::Value::Array)?,
+        Value::Mapping(mapping) => {
+            let mut map = serde_json::Map::with_capacity(mapping.len());
+
+            for (key, value) in mapping {
+                let Some(key) = key.as_str() else {
+                    return Err(YamlError::new(
+                        String::new(),
+                        format!("expected a string key, found {key:?}"),
+                        None,
+                    ));
+                };
+
+                map.insert(key.to_string(), to_json(value)?);
+            }
+
+            serde_json::Value::Object(map)
+        }
+        Value::Tagged(tagged) => {
+            let tag = tagged.tag.to_string();
+            let variant = tag.strip_prefix('!').unwrap_or(&tag);
+
+            serde_json::json!({ variant: to_json(&tagged.value)? })
+        }
+        value => serde_json::to_value(value)
+            .map_err(|error| YamlError::new(String::new(), error.to_string(), None))?,
+    })
+}
+
+/// Deserializes an LVD file from a value with explicit version keys.
+fn from_version_keyed_value(mut value: serde_json::Value) -> Result<LvdFile, YamlError> {
+    normalize_version_keyed_value(&mut value);
+
+    serde_path_to_error::deserialize(value).map_err(|error| {
+        YamlError::new(display_path(error.path()), error.inner().to_string(), None)
+    })
+}
+
+/// Returns `true` if any object in the value has a `version` field.
+fn has_version_keys(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.contains_key("version") || map.values().any(has_version_keys)
+        }
+        serde_json::Value::Array(elements) => elements.iter().any(has_version_keys),
+        _ => false,
+    }
+}
+
+/// Returns the message of a YAML error without the path prepended to it or the location
+/// appended to it, since both are displayed separately.
+fn strip_context(error: &serde_yaml::Error) -> String {
+    let mut message = error.to_string();
+
+    if let Some(location) = error.location() {
+        let suffix = format!(" at line {} column {}", location.line(), location.column());
+
+        message = message.replacen(&suffix, "", 1);
+    }
+
+    match message.split_once(": ") {
+        Some((path, rest)) if !path.contains(' ') => rest.to_string(),
+        _ => message,
+    }
+}
+
+/// Returns the path to a value with version variants omitted and array elements
+/// indexed directly, such as `collisions[3].base.meta_info.name`.
+fn display_path(path: &Path) -> String {
+    let mut display = String::new();
+
+    for segment in path.iter() {
+        match segment {
+            Segment::Seq { index } => {
+                if let Some(len) = display.strip_suffix("elements").map(str::len) {
+                    display.truncate(len.saturating_sub(1));
+                }
+
+                display += &format!("[{index}]");
+            }
+            Segment::Map { key } | Segment::Enum { variant: key } => {
+                if is_version_variant(key) {
+                    continue;
+                }
+
+                if !display.is_empty() {
+                    display.push('.');
+                }
+
+                display += key;
+            }
+            Segment::Unknown => {}
+        }
+    }
+
+    display
+}
+
+/// Returns `true` if the name is a version variant, such as `V4` or `!V4`.
+fn is_version_variant(name: &str) -> bool {
+    name.strip_prefix('!')
+        .unwrap_or(name)
+        .strip_prefix('V')
+        .is_some_and(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Returns a suggestion for fixing the most common causes of an error message.
+fn hint(message: &str) -> Option<String> {
+    if let Some(capacity) = message
+        .split_once("exceeds buffer capacity of ")
+        .and_then(|(_, rest)| rest.split(' ').next())
+    {
+        return Some(format!(
+            "shorten the string to at most {capacity} bytes; \
+             non-ASCII characters take more than one byte each"
+        ));
+    }
+
+    if let Some(variant) = quoted_after(message, "unknown variant ") {
+        return Some(if is_version_variant(variant) {
+            format!(
+                "version {variant} is not supported for this value; use one of the listed versions"
+            )
+        } else {
+            format!("`{variant}` is not a valid value here; use one of the listed values")
+        });
+    }
+
+    if quoted_after(message, "unknown material ").is_some() {
+        return Some(
+            "use a material alias, such as `ice` or `grass`, or the material's numeric value"
+                .to_string(),
+        );
+    }
+
+    if let Some(section) = quoted_after(message, "missing field ")
+        .filter(|field| SectionKind::from_name(field).is_some())
+    {
+        return Some(format!(
+            "add the `{section}` section, or take the sections missing from the file \
+             from an existing LVD file with --base"
+        ));
+    }
+
+    if let Some(field) = quoted_after(message, "missing field ") {
+        return Some(format!(
+            "add the `{field}` field, or write the file with version keys (see --version-keys) \
+             to have missing fields filled in with their defaults"
+        ));
+    }
+
+    if let Some(field) = quoted_after(message, "unknown field ") {
+        return Some(format!(
+            "remove the `{field}` field or correct its spelling; \
+             the fields of an object depend on its version"
+        ));
+    }
+
+    if message.contains("did not match any variant") || message.contains("YAML tag") {
+        return Some(
+            "check that the object's version tag, such as `!V1`, \
+             matches the version its fields belong to"
+                .to_string(),
+        );
+    }
+
+    if message.starts_with("invalid type") {
+        return Some(
+            "check the type of the value, such as quoting a name which looks like a number"
+                .to_string(),
+        );
+    }
+
+    None
+}
+
+/// Returns the backtick-quoted word following a prefix in a message.
+fn quoted_after<'a>(message: &'a str, prefix: &str) -> Option<&'a str> {
+    let (_, rest) = message.split_once(prefix)?;
+    let rest = rest.strip_prefix('`')?;
+
+    rest.split_once('`').map(|(word, _)| word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_omits_versions_and_element_keys() {
+        let yaml = "!V13\ncollisions: !V1\n  elements:\n  - !V4\n    base: 5\n";
+        let error = lvd_from_yaml(yaml).unwrap_err();
+
+        assert_eq!(error.path, "collisions[0].base");
+        assert!(error.hint.is_some());
+    }
+
+    #[test]
+    fn model_path_has_line() {
+        let yaml = "versions:\n  lvd: 13\ncollisions:\n- base:\n    name: 5\n  vertices: a\n";
+        let error = lvd_from_yaml(yaml).unwrap_err();
+
+        assert_eq!(error.path, "collisions[0].vertices");
+        assert_eq!(error.location, Some((6, 13)));
+    }
+
+    #[test]
+    fn model_section_unsupported_by_version() {
+        let yaml = "versions:\n  lvd: 1\narea_hints:\n- {}\n";
+        let error = lvd_from_yaml(yaml).unwrap_err();
+
+        assert!(error.message.contains("area_hints"));
+    }
+
+    #[test]
+    fn hint_names_string_capacity() {
+        let hint = hint("nul-terminated string exceeds buffer capacity of 56 bytes");
+
+        assert!(hint.unwrap().contains("at most 56 bytes"));
+    }
+}